@@ -0,0 +1,519 @@
+use crate::app::split_app_name;
+use crate::error::{AdifError, Result};
+use crate::types::{AdifFile, DataType, Field, Record};
+use quick_xml::XmlVersion;
+use quick_xml::escape::{escape, partial_escape, resolve_predefined_entity};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Parse an ADX (XML) format string into an AdifFile
+pub fn parse_adx(input: &str) -> Result<AdifFile> {
+    let mut parser = AdxParser::new(input);
+    parser.parse()
+}
+
+/// Where the parser currently is within the ADX document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Document,
+    Adx,
+    Header,
+    Records,
+    Record,
+}
+
+/// A field element that has been opened but not yet closed
+struct OpenField {
+    element: String,
+    field: Field,
+    /// Text appended to the value when the element closes
+    suffix: String,
+}
+
+/// Internal parser state
+struct AdxParser<'a> {
+    reader: Reader<&'a [u8]>,
+    file: AdifFile,
+    section: Section,
+    record: Record,
+    open_field: Option<OpenField>,
+}
+
+impl<'a> AdxParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            reader: Reader::from_str(input),
+            file: AdifFile::new(),
+            section: Section::Document,
+            record: Record::new(),
+            open_field: None,
+        }
+    }
+
+    fn parse(&mut self) -> Result<AdifFile> {
+        loop {
+            let event = self.reader.read_event().map_err(|e| self.xml_error(e))?;
+            match event {
+                Event::Start(e) => self.start_element(&e, false)?,
+                Event::Empty(e) => self.start_element(&e, true)?,
+                Event::End(e) => {
+                    let name = e.name().as_ref().to_uppercase();
+                    self.end_element(&name)?;
+                }
+                Event::Text(e) => {
+                    let text = e.xml10_content();
+                    self.text(&text)?;
+                }
+                Event::CData(e) => {
+                    let text = e.xml10_content();
+                    self.text(&text)?;
+                }
+                Event::GeneralRef(e) => {
+                    let resolved = match e.resolve_char_ref().map_err(|e| self.xml_error(e))? {
+                        Some(c) => c.to_string(),
+                        None => resolve_predefined_entity(&e)
+                            .ok_or_else(|| self.error(format!("Unknown entity '&{};'", &*e)))?
+                            .to_string(),
+                    };
+                    self.text(&resolved)?;
+                }
+                Event::Comment(e)
+                    if self.section == Section::Header && self.open_field.is_none() =>
+                {
+                    let header = &mut self.file.header;
+                    if !header.preamble.is_empty() {
+                        header.preamble.push('\n');
+                    }
+                    header.preamble.push_str(e.xml10_content().trim());
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if self.section != Section::Document {
+            return Err(AdifError::UnexpectedEof(self.position()));
+        }
+
+        Ok(std::mem::take(&mut self.file))
+    }
+
+    fn start_element(&mut self, e: &BytesStart, empty: bool) -> Result<()> {
+        let name = e.name().as_ref().to_uppercase();
+
+        if self.open_field.is_some() {
+            return Err(self.error(format!("Unexpected element <{}> inside field", name)));
+        }
+
+        let next = match (self.section, name.as_str()) {
+            (Section::Document, "ADX") => Some(Section::Adx),
+            (Section::Document, _) => {
+                return Err(self.error(format!("Expected <ADX> root element, found <{}>", name)));
+            }
+            (Section::Adx, "HEADER") => Some(Section::Header),
+            (Section::Adx, "RECORDS") => Some(Section::Records),
+            (Section::Records, "RECORD") => {
+                self.record = Record::new();
+                Some(Section::Record)
+            }
+            (Section::Header, _) | (Section::Record, _) => None,
+            _ => return Err(self.error(format!("Unexpected element <{}>", name))),
+        };
+
+        match next {
+            Some(section) => {
+                self.section = section;
+                if empty {
+                    self.end_element(&name)?;
+                }
+            }
+            None => {
+                let (field, suffix) = self.field_for_element(&name, e)?;
+                self.open_field = Some(OpenField {
+                    element: name.clone(),
+                    field,
+                    suffix,
+                });
+                if empty {
+                    self.end_element(&name)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn end_element(&mut self, name: &str) -> Result<()> {
+        if let Some(mut open) = self.open_field.take() {
+            if open.element != name {
+                return Err(self.error(format!(
+                    "Mismatched closing tag </{}> for <{}>",
+                    name, open.element
+                )));
+            }
+            open.field.value.push_str(&open.suffix);
+            match self.section {
+                Section::Header => self.file.header.add_field(open.field),
+                _ => self.record.add_field(open.field),
+            }
+            return Ok(());
+        }
+
+        self.section = match (self.section, name) {
+            (Section::Adx, "ADX") => Section::Document,
+            (Section::Header, "HEADER") | (Section::Records, "RECORDS") => Section::Adx,
+            (Section::Record, "RECORD") => {
+                let record = std::mem::take(&mut self.record);
+                if !record.fields.is_empty() {
+                    self.file.records.push(record);
+                }
+                Section::Records
+            }
+            _ => return Err(self.error(format!("Unexpected closing tag </{}>", name))),
+        };
+
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) -> Result<()> {
+        match &mut self.open_field {
+            Some(open) => open.field.value.push_str(text),
+            None if text.trim().is_empty() => {}
+            None => return Err(self.error(format!("Unexpected text '{}'", text.trim()))),
+        }
+        Ok(())
+    }
+
+    /// Build the field for an element inside HEADER or RECORD.
+    ///
+    /// APP elements become `APP_{PROGRAMID}_{FIELDNAME}` fields. USERDEF
+    /// elements in the header become `USERDEFn` fields whose value is the
+    /// field name followed by any enumeration or range, matching the ADI
+    /// representation; in records they take their name from FIELDNAME.
+    /// Returns the field along with any text to append after its content.
+    fn field_for_element(&self, name: &str, e: &BytesStart) -> Result<(Field, String)> {
+        let data_type = match self.attribute(e, "TYPE")? {
+            Some(t) => data_type_from_attribute(&t)
+                .ok_or_else(|| self.error(format!("Invalid TYPE attribute '{}'", t)))?,
+            None => DataType::Unspecified,
+        };
+
+        let mut suffix = String::new();
+        let field = match (name, self.section) {
+            ("APP", _) => {
+                let program = self.required_attribute(e, "PROGRAMID")?;
+                let field_name = self.required_attribute(e, "FIELDNAME")?;
+                Field::with_type(format!("APP_{}_{}", program, field_name), data_type, "")
+            }
+            ("USERDEF", Section::Header) => {
+                let id = self.required_attribute(e, "FIELDID")?;
+                if let Some(options) = self.attribute(e, "ENUM")? {
+                    suffix = format!(",{}", options);
+                } else if let Some(range) = self.attribute(e, "RANGE")? {
+                    suffix = format!(",{}", range);
+                }
+                Field::with_type(format!("USERDEF{}", id), data_type, "")
+            }
+            ("USERDEF", _) => {
+                let field_name = self.required_attribute(e, "FIELDNAME")?;
+                Field::with_type(field_name, data_type, "")
+            }
+            _ => Field::with_type(name, data_type, ""),
+        };
+
+        Ok((field, suffix))
+    }
+
+    fn attribute(&self, e: &BytesStart, key: &str) -> Result<Option<String>> {
+        for attr in e.attributes() {
+            let attr = attr.map_err(|e| self.xml_error(e))?;
+            if attr.key.as_ref().eq_ignore_ascii_case(key) {
+                let value = attr
+                    .normalized_value(XmlVersion::Implicit1_0)
+                    .map_err(|e| self.xml_error(e))?;
+                return Ok(Some(value.into_owned()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn required_attribute(&self, e: &BytesStart, key: &str) -> Result<String> {
+        self.attribute(e, key)?.ok_or_else(|| {
+            let name = e.name().as_ref().to_uppercase();
+            self.error(format!("Missing {} attribute on <{}>", key, name))
+        })
+    }
+
+    fn position(&self) -> usize {
+        self.reader.buffer_position() as usize
+    }
+
+    fn error(&self, message: String) -> AdifError {
+        AdifError::ParseError {
+            position: self.position(),
+            message,
+        }
+    }
+
+    fn xml_error(&self, e: impl std::fmt::Display) -> AdifError {
+        AdifError::ParseError {
+            position: self.reader.error_position() as usize,
+            message: format!("XML error: {}", e),
+        }
+    }
+}
+
+/// Serialize an AdifFile to an ADX (XML) format string
+///
+/// Header `USERDEFn` fields are written as USERDEF elements, and record
+/// fields whose names they define are written as `<USERDEF FIELDNAME=..>`.
+/// `APP_{PROGRAMID}_{FIELDNAME}` fields are written as APP elements.
+pub fn write_adx(file: &AdifFile) -> String {
+    let mut s = String::new();
+    s.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<ADX>\n");
+
+    // Header
+    let header = &file.header;
+    let mut userdef_names = HashSet::new();
+    if !header.fields.is_empty() || !header.preamble.is_empty() {
+        s.push_str("  <HEADER>\n");
+        if !header.preamble.trim().is_empty() {
+            // "--" is not allowed inside an XML comment
+            let comment = header.preamble.trim().replace("--", "- -");
+            let _ = writeln!(s, "    <!-- {} -->", comment);
+        }
+        for field in &header.fields {
+            match field.name.strip_prefix("USERDEF") {
+                Some(id) if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) => {
+                    let (name, extra) = match field.value.split_once(',') {
+                        Some((name, extra)) => (name, Some(extra)),
+                        None => (field.value.as_str(), None),
+                    };
+                    userdef_names.insert(name.to_uppercase());
+
+                    let _ = write!(s, "    <USERDEF FIELDID=\"{}\"", id);
+                    write_type_attribute(&mut s, field.data_type);
+                    if let Some(extra) = extra {
+                        let attr = if extra.contains(':') { "RANGE" } else { "ENUM" };
+                        let _ = write!(s, " {}=\"{}\"", attr, escape(extra));
+                    }
+                    let _ = writeln!(s, ">{}</USERDEF>", partial_escape(name));
+                }
+                _ => write_element(&mut s, "    ", field),
+            }
+        }
+        s.push_str("  </HEADER>\n");
+    }
+
+    // Records
+    s.push_str("  <RECORDS>\n");
+    for record in &file.records {
+        s.push_str("    <RECORD>\n");
+        for field in &record.fields {
+            if userdef_names.contains(field.name.as_str()) {
+                let _ = writeln!(
+                    s,
+                    "      <USERDEF FIELDNAME=\"{}\">{}</USERDEF>",
+                    escape(field.name.as_str()),
+                    partial_escape(field.value.as_str())
+                );
+            } else {
+                write_element(&mut s, "      ", field);
+            }
+        }
+        s.push_str("    </RECORD>\n");
+    }
+    s.push_str("  </RECORDS>\n</ADX>\n");
+
+    s
+}
+
+/// Write a field as an element, using an APP element for application-defined fields
+fn write_element(s: &mut String, indent: &str, field: &Field) {
+    match split_app_name(&field.name) {
+        Some((program, name)) => {
+            let _ = write!(
+                s,
+                "{}<APP PROGRAMID=\"{}\" FIELDNAME=\"{}\"",
+                indent,
+                escape(program),
+                escape(name)
+            );
+            write_type_attribute(s, field.data_type);
+            let _ = writeln!(s, ">{}</APP>", partial_escape(field.value.as_str()));
+        }
+        None => {
+            let _ = writeln!(
+                s,
+                "{}<{}>{}</{}>",
+                indent,
+                field.name,
+                partial_escape(field.value.as_str()),
+                field.name
+            );
+        }
+    }
+}
+
+fn write_type_attribute(s: &mut String, data_type: DataType) {
+    if let Some(t) = data_type.to_char() {
+        let _ = write!(s, " TYPE=\"{}\"", t);
+    }
+}
+
+/// Parse a TYPE attribute, which holds a single data type indicator
+pub(crate) fn data_type_from_attribute(value: &str) -> Option<DataType> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => DataType::from_char(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ADX>
+    <HEADER>
+        <!-- Exported by a test -->
+        <ADIF_VER>3.1.6</ADIF_VER>
+        <PROGRAMID>Test</PROGRAMID>
+        <USERDEF FIELDID="1" TYPE="N">EPC</USERDEF>
+        <USERDEF FIELDID="2" TYPE="E" ENUM="{S,M,L}">SWEATERSIZE</USERDEF>
+    </HEADER>
+    <RECORDS>
+        <RECORD>
+            <QSO_DATE>20240115</QSO_DATE>
+            <TIME_ON>1430</TIME_ON>
+            <CALL>VK9NS</CALL>
+            <NAME_INTL>Jürgen</NAME_INTL>
+            <APP PROGRAMID="MONOLOG" FIELDNAME="Compression" TYPE="s">off</APP>
+            <USERDEF FIELDNAME="EPC">32123</USERDEF>
+        </RECORD>
+        <RECORD>
+            <CALL>ON4UN</CALL>
+            <COMMENT>R&amp;R &lt;73&gt; &#x41;</COMMENT>
+            <NOTES/>
+        </RECORD>
+    </RECORDS>
+</ADX>
+"#;
+
+    #[test]
+    fn test_parse_header() {
+        let result = parse_adx(SAMPLE).unwrap();
+
+        assert_eq!(result.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(result.header.program_id, Some("Test".to_string()));
+        assert_eq!(result.header.preamble, "Exported by a test");
+
+        let epc = result.header.get("USERDEF1").unwrap();
+        assert_eq!(epc.value, "EPC");
+        assert_eq!(epc.data_type, DataType::Number);
+        assert_eq!(
+            result.header.get_value("USERDEF2"),
+            Some("SWEATERSIZE,{S,M,L}")
+        );
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let result = parse_adx(SAMPLE).unwrap();
+
+        assert_eq!(result.records.len(), 2);
+        let record = &result.records[0];
+        assert_eq!(record.call(), Some("VK9NS"));
+        assert_eq!(record.qso_date(), Some("20240115"));
+        assert_eq!(record.get_value("NAME_INTL"), Some("Jürgen"));
+        assert_eq!(record.get_value("EPC"), Some("32123"));
+
+        let app = record.get("APP_MONOLOG_COMPRESSION").unwrap();
+        assert_eq!(app.value, "off");
+        assert_eq!(app.data_type, DataType::String);
+    }
+
+    #[test]
+    fn test_parse_entities_and_empty_elements() {
+        let result = parse_adx(SAMPLE).unwrap();
+        let record = &result.records[1];
+
+        assert_eq!(record.get_value("COMMENT"), Some("R&R <73> A"));
+        assert_eq!(record.get_value("NOTES"), Some(""));
+    }
+
+    #[test]
+    fn test_missing_root_element() {
+        let result = parse_adx("<RECORDS><RECORD><CALL>W1AW</CALL></RECORD></RECORDS>");
+        assert!(matches!(result, Err(AdifError::ParseError { .. })));
+    }
+
+    #[test]
+    fn test_app_requires_attributes() {
+        let input = "<ADX><RECORDS><RECORD><APP PROGRAMID=\"X\">1</APP></RECORD></RECORDS></ADX>";
+        assert!(parse_adx(input).is_err());
+    }
+
+    #[test]
+    fn test_truncated_document() {
+        let input = "<ADX><RECORDS><RECORD><CALL>W1AW</CALL>";
+        assert!(matches!(parse_adx(input), Err(AdifError::UnexpectedEof(_))));
+    }
+
+    #[test]
+    fn test_nested_element_in_field() {
+        let input = "<ADX><RECORDS><RECORD><CALL><X/></CALL></RECORD></RECORDS></ADX>";
+        assert!(parse_adx(input).is_err());
+    }
+
+    #[test]
+    fn test_write_adx_roundtrip() {
+        let original = parse_adx(SAMPLE).unwrap();
+        let written = write_adx(&original);
+        let reparsed = parse_adx(&written).unwrap();
+
+        assert_eq!(reparsed.header.adif_version, original.header.adif_version);
+        assert_eq!(reparsed.header.preamble, original.header.preamble);
+        assert_eq!(reparsed.header.fields, original.header.fields);
+        assert_eq!(reparsed.records.len(), original.records.len());
+        for (a, b) in original.records.iter().zip(reparsed.records.iter()) {
+            assert_eq!(a.fields, b.fields);
+        }
+    }
+
+    #[test]
+    fn test_write_adx_elements() {
+        let original = parse_adx(SAMPLE).unwrap();
+        let written = write_adx(&original);
+
+        assert!(written.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(
+            written.contains(
+                "<USERDEF FIELDID=\"2\" TYPE=\"E\" ENUM=\"{S,M,L}\">SWEATERSIZE</USERDEF>"
+            )
+        );
+        assert!(
+            written.contains(
+                "<APP PROGRAMID=\"MONOLOG\" FIELDNAME=\"COMPRESSION\" TYPE=\"S\">off</APP>"
+            )
+        );
+        assert!(written.contains("<USERDEF FIELDNAME=\"EPC\">32123</USERDEF>"));
+        assert!(written.contains("<NAME_INTL>Jürgen</NAME_INTL>"));
+        assert!(written.contains("<COMMENT>R&amp;R &lt;73&gt; A</COMMENT>"));
+    }
+
+    #[test]
+    fn test_write_adx_from_adi() {
+        let adi = "<CALL:4>W1AW<FREQ:6:N>14.074<EOR>";
+        let file = crate::parse_adi(adi).unwrap();
+        let written = write_adx(&file);
+
+        assert!(!written.contains("<HEADER>"));
+        let reparsed = parse_adx(&written).unwrap();
+        assert_eq!(reparsed.records[0].call(), Some("W1AW"));
+        assert_eq!(reparsed.records[0].freq(), Some("14.074"));
+    }
+}
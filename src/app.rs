@@ -0,0 +1,65 @@
+use crate::types::Record;
+
+impl Record {
+    /// Get an application-defined field `APP_{program}_{field}`, matching
+    /// both parts case-insensitively
+    pub fn get_app(&self, program: &str, field: &str) -> Option<&str> {
+        self.app_fields()
+            .find(|(p, f, _)| p.eq_ignore_ascii_case(program) && f.eq_ignore_ascii_case(field))
+            .map(|(_, _, value)| value)
+    }
+
+    /// Iterate over the application-defined fields as (program, field,
+    /// value), in record order
+    pub fn app_fields(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.fields.iter().filter_map(|f| {
+            let (program, field) = split_app_name(&f.name)?;
+            Some((program, field, f.value.as_str()))
+        })
+    }
+}
+
+/// Split an `APP_{PROGRAMID}_{FIELDNAME}` field name into its program and
+/// field name, or `None` if it isn't application-defined
+pub(crate) fn split_app_name(name: &str) -> Option<(&str, &str)> {
+    let rest = name
+        .get(..4)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("APP_"))
+        .and(name.get(4..))?;
+    rest.split_once('_')
+        .filter(|(program, field)| !program.is_empty() && !field.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_split_app_name() {
+        assert_eq!(split_app_name("APP_N1MM_ID"), Some(("N1MM", "ID")));
+        assert_eq!(split_app_name("app_lotw_rxqsl"), Some(("lotw", "rxqsl")));
+        assert_eq!(
+            split_app_name("APP_EQSL_QTH_NICKNAME"),
+            Some(("EQSL", "QTH_NICKNAME"))
+        );
+        assert_eq!(split_app_name("APP_X"), None);
+        assert_eq!(split_app_name("APP__X"), None);
+        assert_eq!(split_app_name("CALL"), None);
+    }
+
+    #[test]
+    fn test_app_fields() {
+        let file =
+            parse_adi("<APP_N1MM_ID:3>abc<CALL:4>W1AW<app_lotw_2xqsl:1>Y<APP_X:1>1<EOR>").unwrap();
+        let record = &file.records[0];
+        assert_eq!(record.get_app("n1mm", "id"), Some("abc"));
+        assert_eq!(record.get_app("LOTW", "2XQSL"), Some("Y"));
+        assert_eq!(record.get_app("N1MM", "RADIO_NR"), None);
+        assert_eq!(
+            record.app_fields().collect::<Vec<_>>(),
+            [("N1MM", "ID", "abc"), ("LOTW", "2XQSL", "Y")]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::enumeration::enumeration;
+use crate::error::Result;
+use crate::types::Record;
+
+enumeration! {
+    /// An ARRL or RAC section, from the ADIF ARRL Section enumeration
+    pub enum ArrlSection for "ARRL_SECT" {
+        Ab => "AB",
+        Ak => "AK",
+        Al => "AL",
+        Ar => "AR",
+        Az => "AZ",
+        Bc => "BC",
+        Co => "CO",
+        Ct => "CT",
+        De => "DE",
+        Eb => "EB",
+        Ema => "EMA",
+        Eny => "ENY",
+        Epa => "EPA",
+        Ewa => "EWA",
+        Ga => "GA",
+        Gh => "GH",
+        Gta => "GTA",
+        Ia => "IA",
+        Id => "ID",
+        Il => "IL",
+        In => "IN",
+        Ks => "KS",
+        Ky => "KY",
+        La => "LA",
+        Lax => "LAX",
+        Mar => "MAR",
+        Mb => "MB",
+        Mdc => "MDC",
+        Me => "ME",
+        Mi => "MI",
+        Mn => "MN",
+        Mo => "MO",
+        Ms => "MS",
+        Mt => "MT",
+        Nb => "NB",
+        Nc => "NC",
+        Nd => "ND",
+        Ne => "NE",
+        Nfl => "NFL",
+        Nh => "NH",
+        Nl => "NL",
+        Nli => "NLI",
+        Nm => "NM",
+        Nnj => "NNJ",
+        Nny => "NNY",
+        Ns => "NS",
+        Nt => "NT",
+        Ntx => "NTX",
+        Nv => "NV",
+        Oh => "OH",
+        Ok => "OK",
+        On => "ON",
+        One => "ONE",
+        Onn => "ONN",
+        Ons => "ONS",
+        Or => "OR",
+        Org => "ORG",
+        Pac => "PAC",
+        Pe => "PE",
+        Pr => "PR",
+        Qc => "QC",
+        Ri => "RI",
+        Sb => "SB",
+        Sc => "SC",
+        Scv => "SCV",
+        Sd => "SD",
+        Sdg => "SDG",
+        Sf => "SF",
+        Sfl => "SFL",
+        Sjv => "SJV",
+        Sk => "SK",
+        Snj => "SNJ",
+        Stx => "STX",
+        Sv => "SV",
+        Tn => "TN",
+        Ut => "UT",
+        Va => "VA",
+        Vi => "VI",
+        Vt => "VT",
+        Wcf => "WCF",
+        Wi => "WI",
+        Wma => "WMA",
+        Wny => "WNY",
+        Wpa => "WPA",
+        Wtx => "WTX",
+        Wv => "WV",
+        Wwa => "WWA",
+        Wy => "WY",
+    }
+}
+
+impl ArrlSection {
+    /// Get the section's full name
+    pub fn name(self) -> &'static str {
+        match self {
+            ArrlSection::Ab => "Alberta",
+            ArrlSection::Ak => "Alaska",
+            ArrlSection::Al => "Alabama",
+            ArrlSection::Ar => "Arkansas",
+            ArrlSection::Az => "Arizona",
+            ArrlSection::Bc => "British Columbia",
+            ArrlSection::Co => "Colorado",
+            ArrlSection::Ct => "Connecticut",
+            ArrlSection::De => "Delaware",
+            ArrlSection::Eb => "East Bay",
+            ArrlSection::Ema => "Eastern Massachusetts",
+            ArrlSection::Eny => "Eastern New York",
+            ArrlSection::Epa => "Eastern Pennsylvania",
+            ArrlSection::Ewa => "Eastern Washington",
+            ArrlSection::Ga => "Georgia",
+            ArrlSection::Gh => "Golden Horseshoe",
+            ArrlSection::Gta => "Greater Toronto Area",
+            ArrlSection::Ia => "Iowa",
+            ArrlSection::Id => "Idaho",
+            ArrlSection::Il => "Illinois",
+            ArrlSection::In => "Indiana",
+            ArrlSection::Ks => "Kansas",
+            ArrlSection::Ky => "Kentucky",
+            ArrlSection::La => "Louisiana",
+            ArrlSection::Lax => "Los Angeles",
+            ArrlSection::Mar => "Maritime",
+            ArrlSection::Mb => "Manitoba",
+            ArrlSection::Mdc => "Maryland-DC",
+            ArrlSection::Me => "Maine",
+            ArrlSection::Mi => "Michigan",
+            ArrlSection::Mn => "Minnesota",
+            ArrlSection::Mo => "Missouri",
+            ArrlSection::Ms => "Mississippi",
+            ArrlSection::Mt => "Montana",
+            ArrlSection::Nb => "New Brunswick",
+            ArrlSection::Nc => "North Carolina",
+            ArrlSection::Nd => "North Dakota",
+            ArrlSection::Ne => "Nebraska",
+            ArrlSection::Nfl => "Northern Florida",
+            ArrlSection::Nh => "New Hampshire",
+            ArrlSection::Nl => "Newfoundland/Labrador",
+            ArrlSection::Nli => "New York City-Long Island",
+            ArrlSection::Nm => "New Mexico",
+            ArrlSection::Nnj => "Northern New Jersey",
+            ArrlSection::Nny => "Northern New York",
+            ArrlSection::Ns => "Nova Scotia",
+            ArrlSection::Nt => "Northern Territories",
+            ArrlSection::Ntx => "North Texas",
+            ArrlSection::Nv => "Nevada",
+            ArrlSection::Oh => "Ohio",
+            ArrlSection::Ok => "Oklahoma",
+            ArrlSection::On => "Ontario",
+            ArrlSection::One => "Ontario East",
+            ArrlSection::Onn => "Ontario North",
+            ArrlSection::Ons => "Ontario South",
+            ArrlSection::Or => "Oregon",
+            ArrlSection::Org => "Orange",
+            ArrlSection::Pac => "Pacific",
+            ArrlSection::Pe => "Prince Edward Island",
+            ArrlSection::Pr => "Puerto Rico",
+            ArrlSection::Qc => "Quebec",
+            ArrlSection::Ri => "Rhode Island",
+            ArrlSection::Sb => "Santa Barbara",
+            ArrlSection::Sc => "South Carolina",
+            ArrlSection::Scv => "Santa Clara Valley",
+            ArrlSection::Sd => "South Dakota",
+            ArrlSection::Sdg => "San Diego",
+            ArrlSection::Sf => "San Francisco",
+            ArrlSection::Sfl => "Southern Florida",
+            ArrlSection::Sjv => "San Joaquin Valley",
+            ArrlSection::Sk => "Saskatchewan",
+            ArrlSection::Snj => "Southern New Jersey",
+            ArrlSection::Stx => "South Texas",
+            ArrlSection::Sv => "Sacramento Valley",
+            ArrlSection::Tn => "Tennessee",
+            ArrlSection::Ut => "Utah",
+            ArrlSection::Va => "Virginia",
+            ArrlSection::Vi => "US Virgin Islands",
+            ArrlSection::Vt => "Vermont",
+            ArrlSection::Wcf => "West Central Florida",
+            ArrlSection::Wi => "Wisconsin",
+            ArrlSection::Wma => "Western Massachusetts",
+            ArrlSection::Wny => "Western New York",
+            ArrlSection::Wpa => "Western Pennsylvania",
+            ArrlSection::Wtx => "West Texas",
+            ArrlSection::Wv => "West Virginia",
+            ArrlSection::Wwa => "Western Washington",
+            ArrlSection::Wy => "Wyoming",
+        }
+    }
+}
+
+impl Record {
+    /// Get the ARRL section of the contacted station, or `None` if
+    /// ARRL_SECT is missing or empty
+    pub fn arrl_sect(&self) -> Option<Result<ArrlSection>> {
+        self.get_parsed("ARRL_SECT")
+    }
+
+    /// Get the logging station's ARRL section, from MY_ARRL_SECT
+    pub fn my_arrl_sect(&self) -> Option<Result<ArrlSection>> {
+        self.get_parsed("MY_ARRL_SECT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdifError, parse_adi};
+
+    #[test]
+    fn test_arrl_sect() {
+        let file = parse_adi("<ARRL_SECT:3>emA<EOR><ARRL_SECT:2>DX<EOR>").unwrap();
+        let section = file.records[0].arrl_sect().unwrap().unwrap();
+        assert_eq!(section, ArrlSection::Ema);
+        assert_eq!(section.to_string(), "EMA");
+        assert_eq!(section.name(), "Eastern Massachusetts");
+        assert!(matches!(
+            file.records[1].arrl_sect(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "ARRL_SECT"
+        ));
+    }
+}
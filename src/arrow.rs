@@ -0,0 +1,358 @@
+#[cfg(feature = "parquet")]
+use crate::columns::field_columns;
+use crate::columns::{ColumnType, parse_bool, parse_number, record_columns};
+use crate::date::{AdifDate, AdifTime};
+use crate::error::{AdifError, Result};
+use crate::from_field::FromField;
+use crate::types::{AdifFile, Field, Record};
+use arrow_array::builder::{
+    BooleanBuilder, Date32Builder, Float64Builder, StringBuilder, Time32SecondBuilder,
+};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Date64Type, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type,
+    Time32MillisecondType, Time32SecondType, Time64MicrosecondType, Time64NanosecondType,
+    UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_schema::{DataType as ArrowType, Field as ArrowField, Schema, SchemaRef, TimeUnit};
+use std::sync::Arc;
+
+/// Get the Arrow type used for a column
+fn arrow_type(column_type: ColumnType) -> ArrowType {
+    match column_type {
+        ColumnType::Text => ArrowType::Utf8,
+        ColumnType::Date => ArrowType::Date32,
+        ColumnType::Time => ArrowType::Time32(TimeUnit::Second),
+        ColumnType::Number => ArrowType::Float64,
+        ColumnType::Boolean => ArrowType::Boolean,
+    }
+}
+
+/// Column values collected for the next batch
+enum ColumnBuilder {
+    Text(StringBuilder),
+    Date(Date32Builder),
+    Time(Time32SecondBuilder),
+    Number(Float64Builder),
+    Boolean(BooleanBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::Text => ColumnBuilder::Text(StringBuilder::new()),
+            ColumnType::Date => ColumnBuilder::Date(Date32Builder::new()),
+            ColumnType::Time => ColumnBuilder::Time(Time32SecondBuilder::new()),
+            ColumnType::Number => ColumnBuilder::Number(Float64Builder::new()),
+            ColumnType::Boolean => ColumnBuilder::Boolean(BooleanBuilder::new()),
+        }
+    }
+
+    /// Append a value, which must have been checked with
+    /// [`ColumnType::accepts`]
+    fn append(&mut self, value: Option<&str>) {
+        match self {
+            ColumnBuilder::Text(b) => b.append_option(value),
+            ColumnBuilder::Date(b) => b.append_option(
+                value
+                    .and_then(|v| AdifDate::from_field(v).ok())
+                    .map(AdifDate::unix_days),
+            ),
+            ColumnBuilder::Time(b) => b.append_option(
+                value
+                    .and_then(|v| AdifTime::from_field(v).ok())
+                    .map(|t| t.to_duration().as_secs() as i32),
+            ),
+            ColumnBuilder::Number(b) => b.append_option(value.and_then(parse_number)),
+            ColumnBuilder::Boolean(b) => b.append_option(value.and_then(parse_bool)),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            ColumnBuilder::Text(b) => Arc::new(b.finish()),
+            ColumnBuilder::Date(b) => Arc::new(b.finish()),
+            ColumnBuilder::Time(b) => Arc::new(b.finish()),
+            ColumnBuilder::Number(b) => Arc::new(b.finish()),
+            ColumnBuilder::Boolean(b) => Arc::new(b.finish()),
+        }
+    }
+}
+
+/// Builds record batches with a fixed set of typed columns
+pub(crate) struct BatchBuilder {
+    schema: SchemaRef,
+    columns: Vec<(String, ColumnType)>,
+    builders: Vec<ColumnBuilder>,
+    len: usize,
+}
+
+impl BatchBuilder {
+    fn new(columns: Vec<(String, ColumnType)>) -> Self {
+        let schema = Arc::new(Schema::new(
+            columns
+                .iter()
+                .map(|(name, column_type)| ArrowField::new(name, arrow_type(*column_type), true))
+                .collect::<Vec<_>>(),
+        ));
+        let builders = columns
+            .iter()
+            .map(|(_, t)| ColumnBuilder::new(*t))
+            .collect();
+        Self {
+            schema,
+            columns,
+            builders,
+            len: 0,
+        }
+    }
+
+    /// Build one column per field name, typed from the ADIF specification
+    #[cfg(feature = "parquet")]
+    pub(crate) fn for_fields(fields: &[&str]) -> Self {
+        Self::new(field_columns(fields))
+    }
+
+    /// Build the columns for a set of records
+    pub(crate) fn for_records(records: &[Record]) -> Self {
+        Self::new(record_columns(records))
+    }
+
+    #[cfg(feature = "parquet")]
+    pub(crate) fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Get the number of records pushed since the last batch
+    #[cfg(feature = "parquet")]
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Add a record, or fail without adding anything if a value doesn't
+    /// fit its column's type; `index` is used in the error
+    pub(crate) fn push(&mut self, record: &Record, index: usize) -> Result<()> {
+        let values: Vec<Option<&str>> = self
+            .columns
+            .iter()
+            .map(|(name, _)| record.get_value(name).filter(|v| !v.is_empty()))
+            .collect();
+        for ((name, column_type), value) in self.columns.iter().zip(&values) {
+            if let Some(value) = value
+                && !column_type.accepts(value)
+            {
+                return Err(AdifError::Conversion {
+                    record: index,
+                    message: format!("invalid {} '{}'", name, value),
+                });
+            }
+        }
+
+        for (builder, value) in self.builders.iter_mut().zip(values) {
+            builder.append(value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Take the records pushed so far as a batch
+    pub(crate) fn finish(&mut self) -> Result<RecordBatch> {
+        let arrays = self
+            .builders
+            .iter_mut()
+            .map(ColumnBuilder::finish)
+            .collect();
+        self.len = 0;
+        Ok(RecordBatch::try_new(self.schema.clone(), arrays)?)
+    }
+}
+
+impl AdifFile {
+    /// Convert the records to an Arrow record batch
+    ///
+    /// There is one nullable column per field name found in the records,
+    /// in order of first appearance. Dates are `Date32`, times
+    /// `Time32(Second)`, numbers `Float64` and booleans `Boolean`, based on
+    /// each field's type in the ADIF specification; other fields, and
+    /// typed fields with a value that doesn't parse, are `Utf8`. Missing
+    /// and empty values are null.
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        let mut builder = BatchBuilder::for_records(&self.records);
+        for (index, record) in self.records.iter().enumerate() {
+            builder.push(record, index)?;
+        }
+        builder.finish()
+    }
+
+    /// Build a log from an Arrow record batch, one record per row
+    ///
+    /// Column names become field names. String, integer, float, boolean,
+    /// date and time columns are supported and converted back to ADIF
+    /// text; nulls are skipped.
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<AdifFile> {
+        let schema = batch.schema();
+        let mut records = vec![Record::new(); batch.num_rows()];
+        for (field, column) in schema.fields().iter().zip(batch.columns()) {
+            let name = field.name().to_ascii_uppercase();
+            for (row, record) in records.iter_mut().enumerate() {
+                if column.is_null(row) {
+                    continue;
+                }
+                let value = cell(column, row).ok_or_else(|| AdifError::Conversion {
+                    record: row,
+                    message: format!(
+                        "unsupported type {} for column {}",
+                        field.data_type(),
+                        field.name()
+                    ),
+                })?;
+                record.add_field(Field::new(name.as_str(), value));
+            }
+        }
+
+        Ok(AdifFile {
+            records,
+            ..Default::default()
+        })
+    }
+}
+
+/// Convert a non-null cell to ADIF text, if its type is supported
+fn cell(column: &ArrayRef, row: usize) -> Option<String> {
+    let value = match column.data_type() {
+        ArrowType::Utf8 => column.as_string::<i32>().value(row).to_string(),
+        ArrowType::LargeUtf8 => column.as_string::<i64>().value(row).to_string(),
+        ArrowType::Utf8View => column.as_string_view().value(row).to_string(),
+        ArrowType::Boolean => if column.as_boolean().value(row) {
+            "Y"
+        } else {
+            "N"
+        }
+        .to_string(),
+        ArrowType::Int8 => column.as_primitive::<Int8Type>().value(row).to_string(),
+        ArrowType::Int16 => column.as_primitive::<Int16Type>().value(row).to_string(),
+        ArrowType::Int32 => column.as_primitive::<Int32Type>().value(row).to_string(),
+        ArrowType::Int64 => column.as_primitive::<Int64Type>().value(row).to_string(),
+        ArrowType::UInt8 => column.as_primitive::<UInt8Type>().value(row).to_string(),
+        ArrowType::UInt16 => column.as_primitive::<UInt16Type>().value(row).to_string(),
+        ArrowType::UInt32 => column.as_primitive::<UInt32Type>().value(row).to_string(),
+        ArrowType::UInt64 => column.as_primitive::<UInt64Type>().value(row).to_string(),
+        ArrowType::Float32 => column.as_primitive::<Float32Type>().value(row).to_string(),
+        ArrowType::Float64 => column.as_primitive::<Float64Type>().value(row).to_string(),
+        ArrowType::Date32 => format_date(column.as_primitive::<Date32Type>().value(row) as i64),
+        ArrowType::Date64 => {
+            let millis = column.as_primitive::<Date64Type>().value(row);
+            format_date(millis.div_euclid(86_400_000))
+        }
+        ArrowType::Time32(TimeUnit::Second) => {
+            format_time(column.as_primitive::<Time32SecondType>().value(row) as i64)
+        }
+        ArrowType::Time32(TimeUnit::Millisecond) => {
+            format_time(column.as_primitive::<Time32MillisecondType>().value(row) as i64 / 1000)
+        }
+        ArrowType::Time64(TimeUnit::Microsecond) => {
+            format_time(column.as_primitive::<Time64MicrosecondType>().value(row) / 1_000_000)
+        }
+        ArrowType::Time64(TimeUnit::Nanosecond) => {
+            format_time(column.as_primitive::<Time64NanosecondType>().value(row) / 1_000_000_000)
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Format days since 1970-01-01 as a YYYYMMDD date
+fn format_date(days: i64) -> String {
+    // Civil date from days, with the year starting in March
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+/// Format seconds since midnight as an HHMMSS time
+fn format_time(seconds: i64) -> String {
+    let seconds = seconds.rem_euclid(86_400);
+    format!(
+        "{:02}{:02}{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use arrow_array::{Int32Array, StringArray};
+
+    #[test]
+    fn test_to_record_batch() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:4>1423<FREQ:6>14.074<SWL:1>N<EOR>\
+             <CALL:4>K1AB<QSO_DATE:8>19700102<TIME_ON:6>000001<FREQ:2>?<EOR>",
+        )
+        .unwrap();
+        let batch = file.to_record_batch().unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        let schema = batch.schema();
+        let types: Vec<_> = schema.fields().iter().map(|f| f.data_type()).collect();
+        assert_eq!(
+            types,
+            [
+                &ArrowType::Utf8,
+                &ArrowType::Date32,
+                &ArrowType::Time32(TimeUnit::Second),
+                // "?" isn't a number
+                &ArrowType::Utf8,
+                &ArrowType::Boolean,
+            ]
+        );
+        assert_eq!(batch.column(0).as_string::<i32>().value(1), "K1AB");
+        let dates = batch.column(1).as_primitive::<Date32Type>();
+        assert_eq!((dates.value(0), dates.value(1)), (19737, 1));
+        assert_eq!(
+            batch.column(2).as_primitive::<Time32SecondType>().value(1),
+            1
+        );
+        assert!(batch.column(4).is_null(1));
+    }
+
+    #[test]
+    fn test_from_record_batch() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240229<TIME_ON:6>142305<FREQ:6>14.074<SWL:1>Y<EOR>\
+             <CALL:4>K1AB<QSO_DATE:8>18991231<EOR>",
+        )
+        .unwrap();
+        let back = AdifFile::from_record_batch(&file.to_record_batch().unwrap()).unwrap();
+        assert_eq!(back.records[0].fields, file.records[0].fields);
+        assert_eq!(back.records[1].fields, file.records[1].fields);
+
+        let batch = RecordBatch::try_from_iter([
+            (
+                "call",
+                Arc::new(StringArray::from(vec!["W1AW"])) as ArrayRef,
+            ),
+            ("cqz", Arc::new(Int32Array::from(vec![5])) as ArrayRef),
+        ])
+        .unwrap();
+        let record = &AdifFile::from_record_batch(&batch).unwrap().records[0];
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(record.get_value("CQZ"), Some("5"));
+    }
+}
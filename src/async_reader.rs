@@ -0,0 +1,104 @@
+use crate::error::Result;
+use crate::options::ParserOptions;
+use crate::reader::ReaderCore;
+use crate::types::{AdifHeader, Record};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Asynchronous streaming ADI reader for tokio
+///
+/// Behaves like [`RecordReader`](crate::RecordReader) but reads from an
+/// [`AsyncBufRead`], so large uploads can be parsed without blocking or
+/// buffering the whole input.
+pub struct AsyncRecordReader<R> {
+    reader: R,
+    core: ReaderCore,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncRecordReader<R> {
+    /// Create a reader over a buffered async ADI source
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParserOptions::default())
+    }
+
+    /// Create a reader using the given parser options
+    pub fn with_options(reader: R, options: ParserOptions) -> Self {
+        Self {
+            reader,
+            core: ReaderCore::new(options),
+        }
+    }
+
+    /// Get the file header, reading it from the stream if necessary
+    pub async fn header(&mut self) -> Result<&AdifHeader> {
+        while self.core.header().is_none() {
+            self.fill().await?;
+        }
+        Ok(self.core.header().expect("header was read"))
+    }
+
+    /// Read the next record, or `None` at the end of the data
+    pub async fn read_record(&mut self) -> Result<Option<Record>> {
+        loop {
+            if let Some(result) = self.core.next_record() {
+                return result;
+            }
+            self.fill().await?;
+        }
+    }
+
+    /// Pass the buffered input to the core
+    async fn fill(&mut self) -> Result<()> {
+        let n = match self.reader.fill_buf().await {
+            Ok(buf) => self.core.feed(buf)?,
+            Err(e) => {
+                self.core.fail();
+                return Err(e.into());
+            }
+        };
+        self.reader.consume(n);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AdifError;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn test_matches_record_reader() {
+        let input = "Log\n<ADIF_VER:5>3.1.6<EOH>\n\
+                     <call:4>K1AB<FREQ:6:N>14.074<EOR>\n<CALL:4>K2CD<EOR>\n<CALL:4>K3EF";
+        let expected: Vec<Record> = crate::RecordReader::new(input.as_bytes())
+            .map(|r| r.unwrap())
+            .collect();
+        let mut reader = AsyncRecordReader::new(BufReader::with_capacity(4, input.as_bytes()));
+
+        let header = reader.header().await.unwrap();
+        assert_eq!(header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(header.preamble, "Log\n");
+
+        let mut records = Vec::new();
+        while let Some(record) = reader.read_record().await.unwrap() {
+            records.push(record);
+        }
+        assert_eq!(records.len(), expected.len());
+        for (a, b) in records.iter().zip(expected.iter()) {
+            assert_eq!(a.fields, b.fields);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_ends_stream() {
+        let input = "<CALL:5>W1AW1<EOR><CALL:10>W1AW";
+        let mut reader = AsyncRecordReader::new(input.as_bytes());
+
+        assert!(reader.read_record().await.unwrap().is_some());
+        assert!(matches!(
+            reader.read_record().await,
+            Err(AdifError::InvalidFieldLength { expected: 10, .. })
+        ));
+        assert!(reader.read_record().await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::enumeration::enumeration;
+use crate::frequency::Frequency;
+use core::ops::RangeInclusive;
+
+enumeration! {
+    /// An amateur band, from the ADIF Band enumeration
+    ///
+    /// Bands order from the lowest frequency to the highest.
+    pub enum Band for "BAND" {
+        M2190 => "2190m",
+        M630 => "630m",
+        M560 => "560m",
+        M160 => "160m",
+        M80 => "80m",
+        M60 => "60m",
+        M40 => "40m",
+        M30 => "30m",
+        M20 => "20m",
+        M17 => "17m",
+        M15 => "15m",
+        M12 => "12m",
+        M10 => "10m",
+        M8 => "8m",
+        M6 => "6m",
+        M5 => "5m",
+        M4 => "4m",
+        M2 => "2m",
+        M1_25 => "1.25m",
+        Cm70 => "70cm",
+        Cm33 => "33cm",
+        Cm23 => "23cm",
+        Cm13 => "13cm",
+        Cm9 => "9cm",
+        Cm6 => "6cm",
+        Cm3 => "3cm",
+        Cm1_25 => "1.25cm",
+        Mm6 => "6mm",
+        Mm4 => "4mm",
+        Mm2_5 => "2.5mm",
+        Mm2 => "2mm",
+        Mm1 => "1mm",
+        Submm => "submm",
+    }
+}
+
+impl Band {
+    /// Get the band's edges, as given in the ADIF specification
+    pub fn range(self) -> RangeInclusive<Frequency> {
+        let (low, high) = match self {
+            Band::M2190 => (135_700, 137_800),
+            Band::M630 => (472_000, 479_000),
+            Band::M560 => (501_000, 504_000),
+            Band::M160 => (1_800_000, 2_000_000),
+            Band::M80 => (3_500_000, 4_000_000),
+            Band::M60 => (5_060_000, 5_450_000),
+            Band::M40 => (7_000_000, 7_300_000),
+            Band::M30 => (10_100_000, 10_150_000),
+            Band::M20 => (14_000_000, 14_350_000),
+            Band::M17 => (18_068_000, 18_168_000),
+            Band::M15 => (21_000_000, 21_450_000),
+            Band::M12 => (24_890_000, 24_990_000),
+            Band::M10 => (28_000_000, 29_700_000),
+            Band::M8 => (40_000_000, 45_000_000),
+            Band::M6 => (50_000_000, 54_000_000),
+            Band::M5 => (54_000_001, 69_900_000),
+            Band::M4 => (70_000_000, 71_000_000),
+            Band::M2 => (144_000_000, 148_000_000),
+            Band::M1_25 => (222_000_000, 225_000_000),
+            Band::Cm70 => (420_000_000, 450_000_000),
+            Band::Cm33 => (902_000_000, 928_000_000),
+            Band::Cm23 => (1_240_000_000, 1_300_000_000),
+            Band::Cm13 => (2_300_000_000, 2_450_000_000),
+            Band::Cm9 => (3_300_000_000, 3_500_000_000),
+            Band::Cm6 => (5_650_000_000, 5_925_000_000),
+            Band::Cm3 => (10_000_000_000, 10_500_000_000),
+            Band::Cm1_25 => (24_000_000_000, 24_250_000_000),
+            Band::Mm6 => (47_000_000_000, 47_200_000_000),
+            Band::Mm4 => (75_500_000_000, 81_000_000_000),
+            Band::Mm2_5 => (119_980_000_000, 123_000_000_000),
+            Band::Mm2 => (134_000_000_000, 149_000_000_000),
+            Band::Mm1 => (241_000_000_000, 250_000_000_000),
+            Band::Submm => (300_000_000_000, 7_500_000_000_000),
+        };
+        Frequency::from_hz(low)..=Frequency::from_hz(high)
+    }
+
+    /// Check whether a frequency is within the band
+    pub fn contains(self, frequency: Frequency) -> bool {
+        self.range().contains(&frequency)
+    }
+
+    /// Get the band a frequency is in, if any
+    pub fn from_frequency(frequency: Frequency) -> Option<Band> {
+        Band::ALL
+            .iter()
+            .copied()
+            .find(|band| band.contains(frequency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdifError;
+
+    #[test]
+    fn test_band() {
+        assert_eq!("20M".parse::<Band>().unwrap(), Band::M20);
+        assert_eq!(" 1.25cm".parse::<Band>().unwrap(), Band::Cm1_25);
+        assert_eq!(Band::Cm70.to_string(), "70cm");
+        assert!(Band::M160 < Band::M2);
+        assert!(matches!(
+            "11m".parse::<Band>(),
+            Err(AdifError::InvalidField { field, .. }) if field == "BAND"
+        ));
+    }
+
+    #[test]
+    fn test_band_ranges() {
+        let freq = |mhz: &str| mhz.parse::<Frequency>().unwrap();
+        assert_eq!(Band::from_frequency(freq("14.074")), Some(Band::M20));
+        assert_eq!(Band::from_frequency(freq("0.1357")), Some(Band::M2190));
+        assert_eq!(Band::from_frequency(freq("54")), Some(Band::M6));
+        assert_eq!(Band::from_frequency(freq("54.000001")), Some(Band::M5));
+        assert_eq!(Band::from_frequency(freq("10368.1")), Some(Band::Cm3));
+        assert_eq!(Band::from_frequency(freq("27.185")), None);
+        assert!(Band::M40.contains(freq("7.3")));
+        assert!(!Band::M40.contains(freq("7.3001")));
+        assert!(
+            Band::ALL
+                .windows(2)
+                .all(|pair| pair[0].range().end() < pair[1].range().start())
+        );
+    }
+}
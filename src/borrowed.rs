@@ -0,0 +1,185 @@
+use crate::field_name::Interner;
+use crate::types::{AdifHeader, DataType, Field, Record};
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A single ADIF field borrowing its value from the parsed input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldRef<'a> {
+    /// Field name (stored uppercase; only allocated if the input wasn't)
+    pub name: Cow<'a, str>,
+    /// Data type indicator (if specified)
+    pub data_type: DataType,
+    /// Field value
+    pub value: &'a str,
+    /// Byte range of the data specifier and value in the input (empty if
+    /// the field wasn't parsed)
+    pub span: Range<usize>,
+}
+
+impl<'a> FieldRef<'a> {
+    /// Create a new borrowed field, uppercasing the name if needed
+    pub fn new(name: &'a str, data_type: DataType, value: &'a str) -> Self {
+        let name = if name.bytes().any(|b| b.is_ascii_lowercase()) {
+            Cow::Owned(name.to_ascii_uppercase())
+        } else {
+            Cow::Borrowed(name)
+        };
+        Self {
+            name,
+            data_type,
+            value,
+            span: 0..0,
+        }
+    }
+
+    /// Convert to an owned Field
+    pub fn into_owned(self) -> Field {
+        self.into_owned_interned(&mut Interner::new())
+    }
+
+    /// Convert to an owned Field, sharing non-standard names via `interner`
+    pub(crate) fn into_owned_interned(self, interner: &mut Interner) -> Field {
+        Field {
+            name: interner.intern(&self.name),
+            data_type: self.data_type,
+            value: self.value.to_string(),
+            raw: None,
+        }
+    }
+}
+
+/// A single QSO record borrowing from the parsed input
+#[derive(Debug, Clone, Default)]
+pub struct RecordRef<'a> {
+    /// Fields in this record
+    pub fields: Vec<FieldRef<'a>>,
+    /// Byte range in the input from the end of the previous record through
+    /// this record's `<EOR>` and the line ending after it (empty if the
+    /// record wasn't parsed)
+    pub span: Range<usize>,
+}
+
+impl<'a> RecordRef<'a> {
+    /// Create a new empty record
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a field by name (case-insensitive)
+    pub fn get(&self, name: &str) -> Option<&FieldRef<'a>> {
+        self.fields
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Get a field value by name (case-insensitive)
+    pub fn get_value(&self, name: &str) -> Option<&'a str> {
+        self.get(name).map(|f| f.value)
+    }
+
+    /// Convert to an owned Record
+    pub fn into_owned(self) -> Record {
+        self.into_owned_interned(&mut Interner::new())
+    }
+
+    /// Convert to an owned Record, sharing non-standard names via `interner`
+    pub(crate) fn into_owned_interned(self, interner: &mut Interner) -> Record {
+        Record {
+            fields: self
+                .fields
+                .into_iter()
+                .map(|f| f.into_owned_interned(interner))
+                .collect(),
+            terminator: None,
+        }
+    }
+}
+
+/// A complete ADIF file whose records borrow from the parsed input
+#[derive(Debug, Clone, Default)]
+pub struct AdifFileRef<'a> {
+    /// File header (may be empty if no header present)
+    pub header: AdifHeader,
+    /// QSO records
+    pub records: Vec<RecordRef<'a>>,
+}
+
+impl<'a> AdifFileRef<'a> {
+    /// Get the number of records in the file
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Check if the file has no records
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Iterate over records
+    pub fn iter(&self) -> impl Iterator<Item = &RecordRef<'a>> {
+        self.records.iter()
+    }
+
+    /// Convert to an owned AdifFile
+    pub fn into_owned(self) -> crate::AdifFile {
+        let mut interner = Interner::new();
+        crate::AdifFile {
+            header: self.header,
+            records: self
+                .records
+                .into_iter()
+                .map(|r| r.into_owned_interned(&mut interner))
+                .collect(),
+            trailer: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi_borrowed;
+
+    #[test]
+    fn test_borrowed_values() {
+        let input = String::from("<CALL:4>W1AW<band:3>20m<EOR>");
+        let file = parse_adi_borrowed(&input).unwrap();
+
+        let record = &file.records[0];
+        let call = record.get("call").unwrap();
+        assert!(matches!(call.name, Cow::Borrowed("CALL")));
+        assert!(std::ptr::eq(call.value, &input[8..12]));
+
+        // Lowercase names are normalized
+        let band = record.get("BAND").unwrap();
+        assert!(matches!(band.name, Cow::Owned(_)));
+        assert_eq!(band.name, "BAND");
+    }
+
+    #[test]
+    fn test_into_owned_matches_parse_adi() {
+        let input = "Log<ADIF_VER:5>3.1.6<EOH><CALL:4>W1AW<FREQ:6:N>14.074<EOR><CALL:4>K1AB<EOR>";
+        let borrowed = parse_adi_borrowed(input).unwrap();
+        assert_eq!(borrowed.len(), 2);
+        assert_eq!(borrowed.header.adif_version, Some("3.1.6".to_string()));
+
+        let owned = borrowed.into_owned();
+        let parsed = crate::parse_adi(input).unwrap();
+        for (a, b) in owned.records.iter().zip(parsed.records.iter()) {
+            assert_eq!(a.fields, b.fields);
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_name() {
+        let mut record = RecordRef::new();
+        record
+            .fields
+            .push(FieldRef::new("qth_ü", DataType::Unspecified, "x"));
+        assert_eq!(record.fields[0].name, "QTH_ü");
+        assert_eq!(record.get_value("qth_ü"), Some("x"));
+    }
+}
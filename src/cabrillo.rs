@@ -0,0 +1,458 @@
+use crate::error::{AdifError, Result};
+use crate::types::{AdifFile, Field, Record};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Band designators used in place of a frequency for 50 MHz and up,
+/// with each band's edges in MHz
+const VHF_BANDS: &[(&str, f64, f64, &str)] = &[
+    ("6M", 50.0, 54.0, "50"),
+    ("4M", 70.0, 71.0, "70"),
+    ("2M", 144.0, 148.0, "144"),
+    ("1.25M", 222.0, 225.0, "222"),
+    ("70CM", 420.0, 450.0, "432"),
+    ("33CM", 902.0, 928.0, "902"),
+    ("23CM", 1240.0, 1300.0, "1.2G"),
+    ("13CM", 2300.0, 2450.0, "2.3G"),
+    ("9CM", 3300.0, 3500.0, "3.4G"),
+    ("6CM", 5650.0, 5925.0, "5.7G"),
+    ("3CM", 10000.0, 10500.0, "10G"),
+    ("1.25CM", 24000.0, 24250.0, "24G"),
+    ("6MM", 47000.0, 47200.0, "47G"),
+    ("4MM", 75500.0, 81000.0, "75G"),
+    ("2.5MM", 119980.0, 123000.0, "119G"),
+    ("2MM", 134000.0, 149000.0, "142G"),
+    ("1MM", 241000.0, 250000.0, "241G"),
+];
+
+/// Lower band edges in kHz, used when a record only has a band
+const HF_BANDS: &[(&str, u32)] = &[
+    ("160M", 1800),
+    ("80M", 3500),
+    ("60M", 5330),
+    ("40M", 7000),
+    ("30M", 10100),
+    ("20M", 14000),
+    ("17M", 18068),
+    ("15M", 21000),
+    ("12M", 24890),
+    ("10M", 28000),
+];
+
+/// Header settings for a Cabrillo log
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CabrilloOptions {
+    /// Contest name for the `CONTEST:` tag, e.g. `ARRL-DX-CW`
+    pub contest: String,
+    /// Callsign used in the log; also the sent call for records without
+    /// `STATION_CALLSIGN` or `OPERATOR`
+    pub callsign: String,
+    /// Additional header tags (such as `CATEGORY-OPERATOR`), in order
+    pub tags: Vec<(String, String)>,
+    /// Leave the RST out of the sent and received exchanges
+    pub omit_rst: bool,
+}
+
+impl CabrilloOptions {
+    /// Create options for a contest and callsign
+    pub fn new(contest: impl Into<String>, callsign: impl Into<String>) -> Self {
+        Self {
+            contest: contest.into(),
+            callsign: callsign.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a header tag
+    pub fn with_tag(mut self, tag: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((tag.into().to_uppercase(), value.into()));
+        self
+    }
+}
+
+/// Serialize an AdifFile to a Cabrillo 3.0 log
+///
+/// Each record becomes a `QSO:` line built from CALL, QSO_DATE, TIME_ON,
+/// FREQ (or BAND), MODE and the RST_SENT/RST_RCVD, STX/SRX and
+/// STX_STRING/SRX_STRING exchange fields. Fails if a record is missing a
+/// required field.
+pub fn write_cabrillo(file: &AdifFile, options: &CabrilloOptions) -> Result<String> {
+    let mut s = String::new();
+    s.push_str("START-OF-LOG: 3.0\n");
+    if !options.contest.is_empty() {
+        let _ = writeln!(s, "CONTEST: {}", options.contest);
+    }
+    if !options.callsign.is_empty() {
+        let _ = writeln!(s, "CALLSIGN: {}", options.callsign);
+    }
+    for (tag, value) in &options.tags {
+        let _ = writeln!(s, "{}: {}", tag, value);
+    }
+    if !options.tags.iter().any(|(tag, _)| tag == "CREATED-BY") {
+        let _ = writeln!(s, "CREATED-BY: adif_parser {}", env!("CARGO_PKG_VERSION"));
+    }
+
+    for (index, record) in file.records.iter().enumerate() {
+        s.push_str(
+            &qso_line(record, options).map_err(|message| AdifError::Conversion {
+                record: index,
+                message,
+            })?,
+        );
+        s.push('\n');
+    }
+    s.push_str("END-OF-LOG:\n");
+    Ok(s)
+}
+
+/// Build the `QSO:` line for a record
+fn qso_line(record: &Record, options: &CabrilloOptions) -> core::result::Result<String, String> {
+    let required = |name: &str| {
+        record
+            .get_value(name)
+            .filter(|v| !v.trim().is_empty())
+            .map(str::trim)
+            .ok_or_else(|| format!("missing {}", name))
+    };
+
+    let freq = frequency(record)?;
+    let mode = mode(required("MODE")?);
+    let date = required("QSO_DATE")?;
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid QSO_DATE '{}'", date));
+    }
+    let time = required("TIME_ON")?;
+    if time.len() < 4 || !time.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid TIME_ON '{}'", time));
+    }
+    let sent_call = ["STATION_CALLSIGN", "OPERATOR"]
+        .into_iter()
+        .find_map(|name| required(name).ok())
+        .unwrap_or(options.callsign.as_str());
+    if sent_call.is_empty() {
+        return Err("missing STATION_CALLSIGN".to_string());
+    }
+    let call = required("CALL")?;
+
+    let exchange = |rst: &str, number: &str, string: &str| {
+        let mut parts = Vec::new();
+        if !options.omit_rst {
+            parts.extend(record.get_value(rst).map(str::trim));
+        }
+        parts.extend(
+            record
+                .get_value(string)
+                .or_else(|| record.get_value(number))
+                .map(str::trim),
+        );
+        parts.retain(|p| !p.is_empty());
+        parts.join(" ")
+    };
+    let sent = exchange("RST_SENT", "STX", "STX_STRING");
+    let rcvd = exchange("RST_RCVD", "SRX", "SRX_STRING");
+
+    let line = format!(
+        "QSO: {:>5} {} {}-{}-{} {} {:<13} {} {:<13} {}",
+        freq,
+        mode,
+        &date[..4],
+        &date[4..6],
+        &date[6..],
+        &time[..4],
+        sent_call.to_uppercase(),
+        sent,
+        call.to_uppercase(),
+        rcvd,
+    );
+    Ok(line.trim_end().to_string())
+}
+
+/// Get the Cabrillo frequency: kHz below 30 MHz, a band designator above
+fn frequency(record: &Record) -> core::result::Result<String, String> {
+    if let Some(freq) = record.freq().map(str::trim).filter(|f| !f.is_empty()) {
+        let mhz: f64 = freq
+            .parse()
+            .ok()
+            .filter(|mhz: &f64| mhz.is_finite() && *mhz > 0.0)
+            .ok_or_else(|| format!("invalid FREQ '{}'", freq))?;
+        if mhz < 30.0 {
+            // Round to the nearest kHz
+            return Ok(format!("{}", (mhz * 1000.0 + 0.5) as u64));
+        }
+        return VHF_BANDS
+            .iter()
+            .find(|(_, low, high, _)| (*low..=*high).contains(&mhz))
+            .map(|(_, _, _, designator)| designator.to_string())
+            .ok_or_else(|| format!("FREQ '{}' is not in a contest band", freq));
+    }
+
+    let band = record
+        .band()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .ok_or_else(|| "missing FREQ or BAND".to_string())?;
+    HF_BANDS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(band))
+        .map(|(_, khz)| khz.to_string())
+        .or_else(|| {
+            VHF_BANDS
+                .iter()
+                .find(|(name, ..)| name.eq_ignore_ascii_case(band))
+                .map(|(_, _, _, designator)| designator.to_string())
+        })
+        .ok_or_else(|| format!("BAND '{}' is not a contest band", band))
+}
+
+/// Map an ADIF mode to a Cabrillo mode
+fn mode(mode: &str) -> &'static str {
+    match mode.to_ascii_uppercase().as_str() {
+        "CW" => "CW",
+        "SSB" | "AM" | "DIGITALVOICE" => "PH",
+        "FM" => "FM",
+        "RTTY" => "RY",
+        _ => "DG",
+    }
+}
+
+/// Parse a Cabrillo log into an AdifFile
+///
+/// Each `QSO:` line becomes a record with CALL, QSO_DATE, TIME_ON, FREQ
+/// (or BAND for band designators), MODE, STATION_CALLSIGN and the
+/// exchanges, plus CONTEST_ID from the `CONTEST:` tag. The sent and
+/// received exchanges are assumed to have the same number of columns;
+/// a leading RST becomes RST_SENT/RST_RCVD, a numeric remainder STX/SRX
+/// and anything else STX_STRING/SRX_STRING. The header lines are kept
+/// as the preamble. `X-QSO:` and `QTC:` lines are ignored.
+pub fn parse_cabrillo(input: &str) -> Result<AdifFile> {
+    let mut file = AdifFile::new();
+    let mut contest = None;
+    let mut position = 0;
+
+    for line in input.split_inclusive('\n') {
+        let start = position;
+        position += line.len();
+        let line = line.trim();
+        let Some((tag, value)) = line.split_once(':') else {
+            if line.is_empty() {
+                continue;
+            }
+            return Err(AdifError::ParseError {
+                position: start,
+                message: format!("Expected a Cabrillo tag, found '{}'", line),
+            });
+        };
+        let tag = tag.trim().to_ascii_uppercase();
+        let value = value.trim();
+
+        match tag.as_str() {
+            "QSO" => {
+                let record =
+                    parse_qso(value, contest).map_err(|message| AdifError::ParseError {
+                        position: start,
+                        message,
+                    })?;
+                file.records.push(record);
+            }
+            "X-QSO" | "QTC" => {}
+            "END-OF-LOG" => break,
+            _ => {
+                if tag == "CONTEST" && !value.is_empty() {
+                    contest = Some(value);
+                }
+                if file.records.is_empty() {
+                    file.header.preamble.push_str(line);
+                    file.header.preamble.push('\n');
+                }
+            }
+        }
+    }
+    Ok(file)
+}
+
+/// Build a record from the text after `QSO:`
+fn parse_qso(line: &str, contest: Option<&str>) -> core::result::Result<Record, String> {
+    let columns: Vec<&str> = line.split_whitespace().collect();
+    // freq mode date time call-sent exch-sent call-rcvd exch-rcvd [transmitter]
+    let calls_and_exchanges = columns.len().saturating_sub(4);
+    if calls_and_exchanges < 2 {
+        return Err(format!("Incomplete QSO line '{}'", line));
+    }
+    let exchange_len = (calls_and_exchanges - 2) / 2;
+
+    let mut record = Record::new();
+    let (freq, mode, date, time) = (columns[0], columns[1], columns[2], columns[3]);
+    if let Some((band, ..)) = VHF_BANDS.iter().find(|band| band.3 == freq) {
+        record.add_field(Field::new("BAND", band.to_ascii_lowercase()));
+    } else {
+        let khz: u64 = freq
+            .parse()
+            .map_err(|_| format!("Invalid QSO frequency '{}'", freq))?;
+        let mut mhz = format!("{}.{:03}", khz / 1000, khz % 1000);
+        mhz.truncate(mhz.trim_end_matches('0').trim_end_matches('.').len());
+        record.add_field(Field::new("FREQ", mhz));
+    }
+
+    let mode = match mode.to_ascii_uppercase().as_str() {
+        "CW" => Some("CW"),
+        "PH" => Some("SSB"),
+        "FM" => Some("FM"),
+        "RY" => Some("RTTY"),
+        "DG" => None,
+        _ => return Err(format!("Invalid QSO mode '{}'", mode)),
+    };
+    if let Some(mode) = mode {
+        record.add_field(Field::new("MODE", mode));
+    }
+
+    let digits = date.replace('-', "");
+    if digits.len() != 8 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid QSO date '{}'", date));
+    }
+    record.add_field(Field::new("QSO_DATE", digits));
+    if time.len() != 4 || !time.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid QSO time '{}'", time));
+    }
+    record.add_field(Field::new("TIME_ON", time));
+
+    let sent = &columns[4..];
+    let rcvd = &sent[1 + exchange_len..];
+    record.add_field(Field::new("STATION_CALLSIGN", sent[0].to_uppercase()));
+    record.add_field(Field::new("CALL", rcvd[0].to_uppercase()));
+    push_exchange(
+        &mut record,
+        &sent[1..=exchange_len],
+        ["RST_SENT", "STX", "STX_STRING"],
+    );
+    push_exchange(
+        &mut record,
+        &rcvd[1..=exchange_len],
+        ["RST_RCVD", "SRX", "SRX_STRING"],
+    );
+    if let Some(contest) = contest {
+        record.add_field(Field::new("CONTEST_ID", contest));
+    }
+    Ok(record)
+}
+
+/// Add an exchange's RST and number or string fields to a record
+fn push_exchange(record: &mut Record, mut exchange: &[&str], [rst, number, string]: [&str; 3]) {
+    if let [first, rest @ ..] = exchange
+        && !rest.is_empty()
+        && is_rst(first)
+    {
+        record.add_field(Field::new(rst, *first));
+        exchange = rest;
+    }
+    match exchange {
+        [] => {}
+        [value] if value.bytes().all(|b| b.is_ascii_digit()) => {
+            record.add_field(Field::new(number, *value));
+        }
+        _ => record.add_field(Field::new(string, exchange.join(" "))),
+    }
+}
+
+/// Check whether an exchange column looks like a signal report
+fn is_rst(value: &str) -> bool {
+    matches!(value.len(), 2 | 3)
+        && value.bytes().all(|b| b.is_ascii_digit())
+        && (b'1'..=b'5').contains(&value.as_bytes()[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_write_cabrillo() {
+        let file = parse_adi(
+            "<CALL:4>k1ab<QSO_DATE:8>20240115<TIME_ON:6>142305<FREQ:6>14.025\
+             <MODE:2>CW<RST_SENT:3>599<RST_RCVD:3>579<STX:3>001<SRX_STRING:2>MA<EOR>\
+             <CALL:4>W2XY<QSO_DATE:8>20240115<TIME_ON:4>1500<BAND:2>2m\
+             <MODE:3>SSB<STATION_CALLSIGN:4>N1MM<EOR>",
+        )
+        .unwrap();
+        let options = CabrilloOptions::new("ARRL-DX-CW", "W1AW")
+            .with_tag("category-operator", "SINGLE-OP")
+            .with_tag("CREATED-BY", "Test");
+        let log = write_cabrillo(&file, &options).unwrap();
+
+        assert_eq!(
+            log,
+            "START-OF-LOG: 3.0\n\
+             CONTEST: ARRL-DX-CW\n\
+             CALLSIGN: W1AW\n\
+             CATEGORY-OPERATOR: SINGLE-OP\n\
+             CREATED-BY: Test\n\
+             QSO: 14025 CW 2024-01-15 1423 W1AW          599 001 K1AB          579 MA\n\
+             QSO:   144 PH 2024-01-15 1500 N1MM           W2XY\n\
+             END-OF-LOG:\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let file = parse_adi("<CALL:4>W1AW<EOR><CALL:4>K1AB<MODE:2>CW<EOR>").unwrap();
+        let result = write_cabrillo(&file, &CabrilloOptions::new("TEST", "W1AW"));
+        assert!(matches!(
+            result,
+            Err(AdifError::Conversion { record: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_cabrillo() {
+        let log = "START-OF-LOG: 3.0\n\
+                   CONTEST: ARRL-DX-CW\n\
+                   CALLSIGN: W1AW\n\
+                   QSO: 14025 CW 2024-01-15 1423 W1AW          599 001 K1AB          579 MA\n\
+                   X-QSO: 7000 CW 2024-01-15 1424 W1AW 599 002 K1XX 599 CT\n\
+                   QSO:   144 PH 2024-01-15 1500 N1MM           W2XY\n\
+                   QSO:  7010 DG 2024-01-15 1510 W1AW 123 K2AA 456 1\n\
+                   END-OF-LOG:\n";
+        let file = parse_cabrillo(log).unwrap();
+
+        assert_eq!(file.len(), 3);
+        assert!(
+            file.header
+                .preamble
+                .starts_with("START-OF-LOG: 3.0\nCONTEST:")
+        );
+        let first = &file.records[0];
+        assert_eq!(first.freq(), Some("14.025"));
+        assert_eq!(first.qso_date(), Some("20240115"));
+        assert_eq!(first.get_value("STATION_CALLSIGN"), Some("W1AW"));
+        assert_eq!(first.rst_rcvd(), Some("579"));
+        assert_eq!(first.get_value("STX"), Some("001"));
+        assert_eq!(first.get_value("SRX_STRING"), Some("MA"));
+        assert_eq!(first.get_value("CONTEST_ID"), Some("ARRL-DX-CW"));
+        assert_eq!(file.records[1].band(), Some("2m"));
+        assert_eq!(file.records[1].call(), Some("W2XY"));
+        assert_eq!(file.records[2].freq(), Some("7.01"));
+        assert_eq!(file.records[2].mode(), None);
+        assert_eq!(file.records[2].get_value("SRX"), Some("456"));
+
+        // Exporting again reproduces the QSO lines
+        let mut file = file;
+        file.records.truncate(2);
+        let options = CabrilloOptions::new("ARRL-DX-CW", "W1AW");
+        let exported = write_cabrillo(&file, &options).unwrap();
+        assert!(exported.contains(
+            "QSO: 14025 CW 2024-01-15 1423 W1AW          599 001 K1AB          579 MA\n"
+        ));
+    }
+
+    #[test]
+    fn test_parse_cabrillo_errors() {
+        assert!(matches!(
+            parse_cabrillo("START-OF-LOG: 3.0\nQSO: 14025 CW 2024-01-15\n"),
+            Err(AdifError::ParseError { position: 18, .. })
+        ));
+        assert!(parse_cabrillo("QSO: 14025 XX 2024-01-15 1423 W1AW K1AB\n").is_err());
+        assert!(parse_cabrillo("not cabrillo\n").is_err());
+    }
+}
@@ -0,0 +1,451 @@
+use crate::error::{AdifError, Result};
+use crate::field_name::{FieldName, Interner};
+use crate::types::{AdifFile, AdifHeader, DataType, Field, RawField, Record};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Magic bytes at the start of a cache
+const CACHE_MAGIC: &[u8; 8] = b"ADIFCACH";
+
+/// Version of the cache format, bumped on any incompatible change
+const CACHE_VERSION: u64 = 1;
+
+/// Size and modification time of the file a cache was built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    len: u64,
+    modified: u64,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> Result<Stamp> {
+        let metadata = fs::metadata(path)?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos() as u64);
+        Ok(Stamp {
+            len: metadata.len(),
+            modified,
+        })
+    }
+}
+
+impl AdifFile {
+    /// Write the log in a compact binary format for fast reloading
+    ///
+    /// The cache starts with a format version; [`AdifFile::from_cache`]
+    /// rejects caches written by an incompatible version of this library,
+    /// so callers can fall back to parsing the original file. Everything,
+    /// including fidelity-mode source text, is preserved.
+    pub fn to_cache<W: Write>(&self, writer: W) -> Result<()> {
+        self.write_cache(writer, None)
+    }
+
+    /// Read a log written by [`AdifFile::to_cache`]
+    ///
+    /// Returns [`AdifError::UnsupportedFormat`] if the input isn't a cache
+    /// or was written by an incompatible version.
+    pub fn from_cache<R: Read>(reader: R) -> Result<AdifFile> {
+        read_cache(reader).map(|(file, _)| file)
+    }
+
+    /// Read a file from disk (see [`AdifFile::from_path`]), using a cache
+    ///
+    /// If `cache` was built from the file at its current size and
+    /// modification time, the log is loaded from it. Otherwise the file is
+    /// parsed and the cache rewritten; failing to write the cache is not
+    /// an error.
+    pub fn from_path_cached(path: impl AsRef<Path>, cache: impl AsRef<Path>) -> Result<AdifFile> {
+        let (path, cache) = (path.as_ref(), cache.as_ref());
+        let stamp = Stamp::of(path)?;
+        if let Ok(reader) = File::open(cache)
+            && let Ok((file, Some(cached))) = read_cache(reader)
+            && cached == stamp
+        {
+            return Ok(file);
+        }
+
+        let file = AdifFile::from_path(path)?;
+        // The cache is only an optimization, so a read-only location
+        // shouldn't stop the log from loading
+        let _ = File::create(cache)
+            .map_err(AdifError::from)
+            .and_then(|writer| file.write_cache(writer, Some(stamp)));
+        Ok(file)
+    }
+
+    fn write_cache<W: Write>(&self, writer: W, stamp: Option<Stamp>) -> Result<()> {
+        let mut w = CacheWriter {
+            writer: BufWriter::new(writer),
+            names: HashMap::new(),
+        };
+        w.writer.write_all(CACHE_MAGIC)?;
+        w.varint(CACHE_VERSION)?;
+        match stamp {
+            Some(stamp) => {
+                w.varint(1)?;
+                w.varint(stamp.len)?;
+                w.varint(stamp.modified)?;
+            }
+            None => w.varint(0)?,
+        }
+
+        // Field names are written once, then referred to by index
+        let fields = self
+            .header
+            .fields
+            .iter()
+            .chain(self.records.iter().flat_map(|r| &r.fields));
+        let mut names = Vec::new();
+        for field in fields {
+            let next = w.names.len() as u64;
+            w.names.entry(field.name.as_str()).or_insert_with(|| {
+                names.push(field.name.as_str());
+                next
+            });
+        }
+        w.varint(names.len() as u64)?;
+        for name in names {
+            w.string(name)?;
+        }
+
+        let header = &self.header;
+        w.string(&header.preamble)?;
+        for value in [
+            &header.adif_version,
+            &header.program_id,
+            &header.program_version,
+            &header.created_timestamp,
+            &header.terminator,
+        ] {
+            w.optional(value.as_deref())?;
+        }
+        w.fields(&header.fields)?;
+
+        w.varint(self.records.len() as u64)?;
+        for record in &self.records {
+            w.fields(&record.fields)?;
+            w.optional(record.terminator.as_deref())?;
+        }
+        w.optional(self.trailer.as_deref())?;
+        w.writer.flush()?;
+        Ok(())
+    }
+}
+
+struct CacheWriter<'a, W: Write> {
+    writer: BufWriter<W>,
+    names: HashMap<&'a str, u64>,
+}
+
+impl<W: Write> CacheWriter<'_, W> {
+    /// Write an unsigned LEB128 integer
+    fn varint(&mut self, mut n: u64) -> Result<()> {
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                self.writer.write_all(&[byte])?;
+                return Ok(());
+            }
+            self.writer.write_all(&[byte | 0x80])?;
+        }
+    }
+
+    fn string(&mut self, s: &str) -> Result<()> {
+        self.varint(s.len() as u64)?;
+        self.writer.write_all(s.as_bytes())?;
+        Ok(())
+    }
+
+    fn optional(&mut self, s: Option<&str>) -> Result<()> {
+        match s {
+            Some(s) => {
+                self.varint(1)?;
+                self.string(s)
+            }
+            None => self.varint(0),
+        }
+    }
+
+    fn fields(&mut self, fields: &[Field]) -> Result<()> {
+        self.varint(fields.len() as u64)?;
+        for field in fields {
+            let index = self.names[field.name.as_str()];
+            self.varint(index)?;
+            let data_type = field.data_type.to_char().map_or(0, |c| c as u8);
+            self.writer.write_all(&[data_type])?;
+            self.string(&field.value)?;
+            match &field.raw {
+                Some(raw) => {
+                    self.varint(1)?;
+                    self.varint(raw.span.start as u64)?;
+                    self.varint(raw.span.end as u64)?;
+                    self.string(&raw.leading)?;
+                    self.string(&raw.specifier)?;
+                }
+                None => self.varint(0)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_cache<R: Read>(reader: R) -> Result<(AdifFile, Option<Stamp>)> {
+    let mut r = CacheReader {
+        reader: BufReader::new(reader),
+        position: 0,
+        names: Vec::new(),
+    };
+    let mut magic = [0u8; 8];
+    if r.reader.read_exact(&mut magic).is_err() || magic != *CACHE_MAGIC {
+        return Err(AdifError::UnsupportedFormat(
+            "not an ADIF cache".to_string(),
+        ));
+    }
+    r.position = magic.len();
+    let version = r.varint()?;
+    if version != CACHE_VERSION {
+        return Err(AdifError::UnsupportedFormat(format!(
+            "ADIF cache version {}",
+            version
+        )));
+    }
+    let stamp = match r.varint()? {
+        0 => None,
+        _ => Some(Stamp {
+            len: r.varint()?,
+            modified: r.varint()?,
+        }),
+    };
+
+    let mut interner = Interner::new();
+    let count = r.varint()?;
+    for _ in 0..count {
+        let name = r.string()?;
+        r.names.push(interner.intern(&name));
+    }
+
+    let mut header = AdifHeader {
+        preamble: r.string()?,
+        adif_version: r.optional()?,
+        program_id: r.optional()?,
+        program_version: r.optional()?,
+        created_timestamp: r.optional()?,
+        terminator: r.optional()?,
+        ..Default::default()
+    };
+    header.fields = r.fields()?;
+
+    let count = r.varint()?;
+    let mut records = Vec::with_capacity(count.min(1 << 16) as usize);
+    for _ in 0..count {
+        records.push(Record {
+            fields: r.fields()?,
+            terminator: r.optional()?,
+        });
+    }
+    let trailer = r.optional()?;
+    Ok((
+        AdifFile {
+            header,
+            records,
+            trailer,
+        },
+        stamp,
+    ))
+}
+
+struct CacheReader<R: Read> {
+    reader: BufReader<R>,
+    position: usize,
+    names: Vec<FieldName>,
+}
+
+impl<R: Read> CacheReader<R> {
+    fn error(&self, message: &str) -> AdifError {
+        AdifError::ParseError {
+            position: self.position,
+            message: message.to_string(),
+        }
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        let mut byte = [0u8];
+        self.reader
+            .read_exact(&mut byte)
+            .map_err(|_| AdifError::UnexpectedEof(self.position))?;
+        self.position += 1;
+        Ok(byte[0])
+    }
+
+    /// Read an unsigned LEB128 integer
+    fn varint(&mut self) -> Result<u64> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            n |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(self.error("Invalid integer"))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.varint()?;
+        // Don't trust the length for allocation, in case the cache is corrupt
+        let mut bytes = Vec::new();
+        (&mut self.reader).take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(AdifError::UnexpectedEof(self.position + bytes.len()));
+        }
+        let s = String::from_utf8(bytes).map_err(|_| self.error("Invalid UTF-8"))?;
+        self.position += s.len();
+        Ok(s)
+    }
+
+    fn optional(&mut self) -> Result<Option<String>> {
+        match self.varint()? {
+            0 => Ok(None),
+            _ => self.string().map(Some),
+        }
+    }
+
+    fn fields(&mut self) -> Result<Vec<Field>> {
+        let count = self.varint()?;
+        let mut fields = Vec::with_capacity(count.min(1 << 10) as usize);
+        for _ in 0..count {
+            let index = self.varint()? as usize;
+            let name = self
+                .names
+                .get(index)
+                .cloned()
+                .ok_or_else(|| self.error("Invalid field name index"))?;
+            let data_type = match self.byte()? {
+                0 => DataType::Unspecified,
+                c => {
+                    DataType::from_char(c as char).ok_or_else(|| self.error("Invalid data type"))?
+                }
+            };
+            let value = self.string()?;
+            let raw = match self.varint()? {
+                0 => None,
+                _ => Some(Box::new(RawField {
+                    span: self.varint()? as usize..self.varint()? as usize,
+                    leading: self.string()?,
+                    specifier: self.string()?,
+                })),
+            };
+            fields.push(Field {
+                name,
+                data_type,
+                value,
+                raw,
+            });
+        }
+        Ok(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParserOptions, parse_adi, parse_adi_with_options, write_adi};
+    use std::sync::Arc;
+
+    const SAMPLE: &str = "Log\n<ADIF_VER:5>3.1.6<PROGRAMID:4>Test<EOH>\n\
+                          <CALL:4>W1AW<FREQ:6:N>14.074<APP_X_ID:3>abc<EOR>\n\
+                          <call:4>K1AB<APP_X_ID:1>x<EOR>\n";
+
+    #[test]
+    fn test_cache_round_trip() {
+        let file = parse_adi(SAMPLE).unwrap();
+        let mut out = Vec::new();
+        file.to_cache(&mut out).unwrap();
+        assert!(out.starts_with(CACHE_MAGIC));
+
+        let back = AdifFile::from_cache(out.as_slice()).unwrap();
+        assert_eq!(back.header.preamble, "Log\n");
+        assert_eq!(back.header.program_id.as_deref(), Some("Test"));
+        assert_eq!(back.header.fields, file.header.fields);
+        assert_eq!(back.len(), 2);
+        for (a, b) in back.records.iter().zip(&file.records) {
+            assert_eq!(a.fields, b.fields);
+        }
+        assert_eq!(back.records[0].fields[1].data_type, DataType::Number);
+        // Repeated non-standard names share one allocation
+        match (
+            &back.records[0].fields[2].name,
+            &back.records[1].fields[1].name,
+        ) {
+            (FieldName::Other(a), FieldName::Other(b)) => assert!(Arc::ptr_eq(a, b)),
+            _ => panic!("expected non-standard names"),
+        }
+    }
+
+    #[test]
+    fn test_cache_fidelity() {
+        let options = ParserOptions {
+            preserve_raw: true,
+            ..Default::default()
+        };
+        let file = parse_adi_with_options(SAMPLE, &options).unwrap();
+        let mut out = Vec::new();
+        file.to_cache(&mut out).unwrap();
+        let back = AdifFile::from_cache(out.as_slice()).unwrap();
+        assert_eq!(write_adi(&back), SAMPLE);
+    }
+
+    #[test]
+    fn test_cache_invalid() {
+        assert!(matches!(
+            AdifFile::from_cache(&b"<CALL:4>W1AW<EOR>"[..]),
+            Err(AdifError::UnsupportedFormat(_))
+        ));
+
+        let mut out = Vec::new();
+        parse_adi(SAMPLE).unwrap().to_cache(&mut out).unwrap();
+        let mut newer = out.clone();
+        newer[8] = 99;
+        assert!(matches!(
+            AdifFile::from_cache(newer.as_slice()),
+            Err(AdifError::UnsupportedFormat(_))
+        ));
+        out.truncate(out.len() - 10);
+        assert!(matches!(
+            AdifFile::from_cache(out.as_slice()),
+            Err(AdifError::UnexpectedEof(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_path_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.adi");
+        let cache = dir.path().join("log.cache");
+        fs::write(&path, SAMPLE).unwrap();
+
+        let file = AdifFile::from_path_cached(&path, &cache).unwrap();
+        assert_eq!(file.len(), 2);
+        let (cached, stamp) = read_cache(File::open(&cache).unwrap()).unwrap();
+        assert_eq!(cached.len(), 2);
+        assert_eq!(stamp, Some(Stamp::of(&path).unwrap()));
+
+        // A changed source file invalidates the cache
+        fs::write(&path, "<CALL:4>W1AW<EOR>").unwrap();
+        let file = AdifFile::from_path_cached(&path, &cache).unwrap();
+        assert_eq!(file.len(), 1);
+        assert_eq!(
+            AdifFile::from_cache(File::open(&cache).unwrap())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
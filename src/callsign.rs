@@ -0,0 +1,181 @@
+use crate::error::{AdifError, Result};
+use crate::from_field::{FromField, ToField};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+
+/// Portable designators that may follow a call sign after a `/`
+const DESIGNATORS: [&str; 9] = ["A", "AM", "B", "LGT", "M", "MM", "P", "QRP", "QRPP"];
+
+/// A call sign, as in CALL, OPERATOR and STATION_CALLSIGN
+///
+/// A call sign is a base call made of a prefix, one or more digits and a
+/// suffix of letters (`W1AW`, `3DA0RU`, `GB13COL`), optionally preceded
+/// by a prefix for operation away from home (`DL/W1AW`) and followed by a
+/// portable designator (`/P`, `/MM`), a call area digit (`/4`) or a prefix
+/// (`/VE3`). Call signs are kept in upper case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Callsign {
+    call: String,
+    base: Range<usize>,
+}
+
+impl Callsign {
+    /// Get the whole call sign, such as `DL/W1AW/P`
+    pub fn as_str(&self) -> &str {
+        &self.call
+    }
+
+    /// Get the base call, such as `W1AW` in `DL/W1AW/P`
+    pub fn base(&self) -> &str {
+        &self.call[self.base.clone()]
+    }
+
+    /// Get the part before the base call, such as `DL` in `DL/W1AW/P`
+    pub fn prefix(&self) -> Option<&str> {
+        (self.base.start > 0).then(|| &self.call[..self.base.start - 1])
+    }
+
+    /// Get the part after the base call, such as `P` in `DL/W1AW/P`
+    pub fn suffix(&self) -> Option<&str> {
+        self.call.get(self.base.end + 1..)
+    }
+}
+
+/// Check for a base call: a prefix of up to three characters including a
+/// letter, one to four digits, and a suffix of one to five letters
+fn is_base_call(s: &str) -> bool {
+    let rest = s.trim_end_matches(|c: char| c.is_ascii_uppercase());
+    let suffix = s.len() - rest.len();
+    let prefix = rest.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = rest.len() - prefix.len();
+    (1..=5).contains(&suffix)
+        && (1..=4).contains(&digits)
+        && (1..=3).contains(&prefix.len())
+        && prefix.bytes().all(|b| b.is_ascii_alphanumeric())
+        && prefix.bytes().any(|b| b.is_ascii_uppercase())
+}
+
+/// Check for a prefix, such as `DL`, `VE3` or `KH6`
+fn is_prefix(s: &str) -> bool {
+    (1..=4).contains(&s.len())
+        && s.bytes().all(|b| b.is_ascii_alphanumeric())
+        && s.bytes().any(|b| b.is_ascii_uppercase())
+}
+
+/// Check for what may follow a base call: a portable designator, a call
+/// area digit or a prefix
+fn is_suffix(s: &str) -> bool {
+    DESIGNATORS.contains(&s) || (s.len() == 1 && s.as_bytes()[0].is_ascii_digit()) || is_prefix(s)
+}
+
+impl FromStr for Callsign {
+    type Err = AdifError;
+
+    /// Parse a call sign such as `W1AW` or `dl/w1aw/p`
+    fn from_str(s: &str) -> Result<Self> {
+        let call = s.trim().to_ascii_uppercase();
+        let invalid = || AdifError::InvalidField {
+            field: "Callsign".into(),
+            message: format!("'{}' is not a call sign", s.trim()),
+        };
+        let parts: Vec<&str> = call.split('/').collect();
+        if parts.len() > 3 {
+            return Err(invalid());
+        }
+        // The longest part that can be a base call is the base call,
+        // preferring a later one so that `VP2E/W1AW` is W1AW abroad
+        let base = (0..parts.len())
+            .filter(|&i| is_base_call(parts[i]))
+            .max_by_key(|&i| (parts[i].len(), i))
+            .ok_or_else(invalid)?;
+        let valid = match (base, parts.len()) {
+            (0, 1) => true,
+            (0, 2) => is_suffix(parts[1]),
+            (1, 2) => is_prefix(parts[0]),
+            (1, 3) => is_prefix(parts[0]) && is_suffix(parts[2]),
+            _ => false,
+        };
+        if !valid {
+            return Err(invalid());
+        }
+        let start = parts[..base].iter().map(|p| p.len() + 1).sum::<usize>();
+        let base = start..start + parts[base].len();
+        Ok(Callsign { call, base })
+    }
+}
+
+impl fmt::Display for Callsign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.call)
+    }
+}
+
+impl FromField for Callsign {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        match value.parse() {
+            Ok(call) => Ok(call),
+            Err(AdifError::InvalidField { message, .. }) => Err(message),
+            Err(other) => Err(format!("{}", other)),
+        }
+    }
+}
+
+impl ToField for Callsign {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse_callsign() {
+        for good in [
+            "W1AW", "K1A", "3DA0RU", "GB13COL", "4U1UN", "2E0ABC", "9A1A", "A61AB", "N0CALL",
+        ] {
+            assert!(good.parse::<Callsign>().is_ok(), "{}", good);
+        }
+        for bad in [
+            "",
+            "59",
+            "QRZ",
+            "W1",
+            "1ABC",
+            "W1AW1",
+            "W1ABCDEF",
+            "W1AW/",
+            "/W1AW",
+            "W1AW/P/QRP/X",
+            "W1AW/FOOBAR",
+            "DL/K/W1AW",
+            "W1 AW",
+        ] {
+            assert!(bad.parse::<Callsign>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_callsign_parts() {
+        let call: Callsign = " dl/w1aw/p ".parse().unwrap();
+        assert_eq!(call.to_string(), "DL/W1AW/P");
+        assert_eq!(call.base(), "W1AW");
+        assert_eq!(call.prefix(), Some("DL"));
+        assert_eq!(call.suffix(), Some("P"));
+
+        let call: Callsign = "W1AW/VE3".parse().unwrap();
+        assert_eq!((call.base(), call.prefix()), ("W1AW", None));
+        assert_eq!(call.suffix(), Some("VE3"));
+        let call: Callsign = "VP2E/W1AW".parse().unwrap();
+        assert_eq!(call.base(), "W1AW");
+        assert_eq!("W1AW/4".parse::<Callsign>().unwrap().suffix(), Some("4"));
+        assert_eq!("W1AW/MM".parse::<Callsign>().unwrap().base(), "W1AW");
+        assert_eq!("W1AW".parse::<Callsign>().unwrap().suffix(), None);
+    }
+}
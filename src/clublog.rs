@@ -0,0 +1,214 @@
+use crate::profile::{
+    Lint, LintLevel, ProfileExport, check_date, check_time, required, subset, value,
+};
+use crate::types::{AdifFile, Record};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Fields Club Log reads from an upload
+const FIELDS: &[&str] = &[
+    "STATION_CALLSIGN",
+    "OPERATOR",
+    "CALL",
+    "QSO_DATE",
+    "TIME_ON",
+    "BAND",
+    "BAND_RX",
+    "FREQ",
+    "FREQ_RX",
+    "MODE",
+    "SUBMODE",
+    "RST_SENT",
+    "RST_RCVD",
+    "PROP_MODE",
+    "SAT_NAME",
+    "DXCC",
+    "GRIDSQUARE",
+    "VUCC_GRIDS",
+    "MY_GRIDSQUARE",
+    "QSL_SENT",
+    "QSLSDATE",
+    "QSL_RCVD",
+    "QSLRDATE",
+    "LOTW_QSL_RCVD",
+    "CREDIT_GRANTED",
+    "NOTES",
+];
+
+/// Values Club Log understands in QSL received and sent fields
+const QSL_STATUSES: &[&str] = &["Y", "N", "R", "I", "V"];
+
+/// QSL status fields checked by the lint pass
+const QSL_FIELDS: &[&str] = &["QSL_SENT", "QSL_RCVD", "LOTW_QSL_RCVD"];
+
+/// Club Log doesn't accept QSOs from before DXCC began
+const FIRST_DATE: &str = "19451115";
+
+/// Export records for upload to Club Log as a single `clublog.adi`
+pub(crate) fn export(file: &AdifFile) -> ProfileExport {
+    let mut export = ProfileExport::default();
+    for (index, record) in file.records.iter().enumerate() {
+        match check(record) {
+            Ok(()) => export
+                .file_mut("clublog.adi".into(), file)
+                .records
+                .push(subset(record, FIELDS)),
+            Err(reason) => export.reject(index, reason),
+        }
+    }
+    export
+}
+
+/// Check a record for the fields Club Log requires
+fn check(record: &Record) -> core::result::Result<(), String> {
+    required(record, "CALL")?;
+    let date = required(record, "QSO_DATE")?;
+    check_date(date)?;
+    if date < FIRST_DATE {
+        return Err(format!("QSO_DATE '{}' is before 15 November 1945", date));
+    }
+    check_time(required(record, "TIME_ON")?)?;
+    if value(record, "BAND").is_none() && value(record, "FREQ").is_none() {
+        return Err("missing BAND or FREQ".into());
+    }
+    required(record, "MODE")?;
+    Ok(())
+}
+
+/// Find records that Club Log accepts but may misinterpret
+pub(crate) fn warnings(file: &AdifFile) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    let mut warn = |record, message| {
+        lints.push(Lint {
+            record,
+            level: LintLevel::Warning,
+            message,
+        })
+    };
+    let mut seen: Vec<[Option<String>; 5]> = Vec::new();
+    for (index, record) in file.records.iter().enumerate() {
+        if value(record, "STATION_CALLSIGN").is_none() {
+            match value(record, "OPERATOR") {
+                Some(operator) => warn(
+                    index,
+                    format!(
+                        "missing STATION_CALLSIGN; Club Log ignores OPERATOR '{}' and \
+                         assumes the log's callsign",
+                        operator
+                    ),
+                ),
+                None => warn(
+                    index,
+                    "missing STATION_CALLSIGN; Club Log assumes the log's callsign".into(),
+                ),
+            }
+        }
+        for name in QSL_FIELDS {
+            if let Some(status) = value(record, name)
+                && !QSL_STATUSES.iter().any(|s| s.eq_ignore_ascii_case(status))
+            {
+                warn(
+                    index,
+                    format!(
+                        "{} '{}' is not a QSL status Club Log understands",
+                        name, status
+                    ),
+                );
+            }
+        }
+        if let Some(dxcc) = value(record, "DXCC")
+            && !dxcc.bytes().all(|b| b.is_ascii_digit())
+        {
+            warn(
+                index,
+                format!("DXCC '{}' is not an entity number and is ignored", dxcc),
+            );
+        }
+
+        let key = ["CALL", "QSO_DATE", "TIME_ON", "BAND", "MODE"]
+            .map(|name| value(record, name).map(str::to_ascii_uppercase));
+        if key[0].is_some() {
+            match seen.iter().position(|k| *k == key) {
+                Some(first) => warn(
+                    index,
+                    format!("duplicate of record {}; Club Log keeps only one", first),
+                ),
+                None => seen.push(key),
+            }
+        }
+    }
+    lints
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_adi;
+    use crate::profile::{ExportProfile, LintLevel};
+
+    #[test]
+    fn test_export_clublog() {
+        let file = parse_adi(
+            "<STATION_CALLSIGN:4>W1AW<CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:4>1200\
+             <FREQ:6>14.074<MODE:3>FT8<QSL_RCVD:1>Y<APP_FOO:1>x<EOR>\
+             <CALL:4>K2AB<QSO_DATE:8>19450101<TIME_ON:4>1200<BAND:3>20m<MODE:2>CW<EOR>",
+        )
+        .unwrap();
+        let export = ExportProfile::ClubLog.export(&file);
+        assert_eq!(export.files[0].name, "clublog.adi");
+        assert_eq!(
+            export.files[0].file.records[0].to_adi_string(),
+            "<STATION_CALLSIGN:4>W1AW<CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:4>1200\
+             <FREQ:6>14.074<MODE:3>FT8<QSL_RCVD:1>Y<EOR>\n"
+        );
+        assert_eq!(
+            export.rejected[0].reason,
+            "QSO_DATE '19450101' is before 15 November 1945"
+        );
+    }
+
+    #[test]
+    fn test_lint_clublog() {
+        let file = parse_adi(
+            "<STATION_CALLSIGN:4>W1AW<CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:4>1200\
+             <BAND:3>20m<MODE:3>FT8<QSL_RCVD:3>Yes<DXCC:3>USA<EOR>\
+             <OPERATOR:4>N0CA<CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:4>1200\
+             <BAND:3>20M<MODE:3>ft8<EOR>\
+             <STATION_CALLSIGN:4>W1AW<CALL:4>K2AB<QSO_DATE:8>20240601<EOR>",
+        )
+        .unwrap();
+        let lints: Vec<_> = ExportProfile::ClubLog
+            .lint(&file)
+            .into_iter()
+            .map(|l| (l.record, l.level, l.message))
+            .collect();
+        assert_eq!(
+            lints,
+            [
+                (
+                    0,
+                    LintLevel::Warning,
+                    "QSL_RCVD 'Yes' is not a QSL status Club Log understands".into()
+                ),
+                (
+                    0,
+                    LintLevel::Warning,
+                    "DXCC 'USA' is not an entity number and is ignored".into()
+                ),
+                (
+                    1,
+                    LintLevel::Warning,
+                    "missing STATION_CALLSIGN; Club Log ignores OPERATOR 'N0CA' and \
+                     assumes the log's callsign"
+                        .into()
+                ),
+                (
+                    1,
+                    LintLevel::Warning,
+                    "duplicate of record 0; Club Log keeps only one".into()
+                ),
+                (2, LintLevel::Reject, "missing TIME_ON".into()),
+            ]
+        );
+    }
+}
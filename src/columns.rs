@@ -0,0 +1,119 @@
+//! Typed columns shared by the tabular exporters
+
+use crate::date::{AdifDate, AdifTime};
+use crate::field_name::FieldName;
+use crate::from_field::FromField;
+use crate::types::{DataType, Record};
+
+/// Storage type of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColumnType {
+    Text,
+    Date,
+    Time,
+    Number,
+    Boolean,
+}
+
+impl ColumnType {
+    /// Get the column type for a field's specified data type
+    pub(crate) fn of(data_type: DataType) -> Self {
+        match data_type {
+            DataType::Date => ColumnType::Date,
+            DataType::Time => ColumnType::Time,
+            DataType::Number => ColumnType::Number,
+            DataType::Boolean => ColumnType::Boolean,
+            _ => ColumnType::Text,
+        }
+    }
+
+    /// Check that a value can be stored in a column of this type
+    pub(crate) fn accepts(self, value: &str) -> bool {
+        match self {
+            ColumnType::Text => true,
+            ColumnType::Date => AdifDate::from_field(value).is_ok(),
+            ColumnType::Time => AdifTime::from_field(value).is_ok(),
+            ColumnType::Number => parse_number(value).is_some(),
+            ColumnType::Boolean => parse_bool(value).is_some(),
+        }
+    }
+}
+
+/// Get one column per field name, typed from the ADIF specification
+#[cfg(feature = "parquet")]
+pub(crate) fn field_columns(fields: &[&str]) -> Vec<(String, ColumnType)> {
+    fields
+        .iter()
+        .map(|name| {
+            let column_type = ColumnType::of(FieldName::from(*name).data_type());
+            (name.to_ascii_uppercase(), column_type)
+        })
+        .collect()
+}
+
+/// Get the columns for a set of records: one per field name, in order of
+/// first appearance, falling back to text if any value doesn't fit the
+/// field's type
+pub(crate) fn record_columns(records: &[Record]) -> Vec<(String, ColumnType)> {
+    let mut columns: Vec<(String, ColumnType)> = Vec::new();
+    for field in records.iter().flat_map(|r| &r.fields) {
+        if field.value.is_empty() {
+            continue;
+        }
+        let name = field.name.to_ascii_uppercase();
+        let position = match columns.iter().position(|(n, _)| *n == name) {
+            Some(position) => position,
+            None => {
+                let name_type = FieldName::from(name.as_str()).data_type();
+                columns.push((name, ColumnType::of(name_type)));
+                columns.len() - 1
+            }
+        };
+        let column_type = &mut columns[position].1;
+        if !column_type.accepts(&field.value) {
+            *column_type = ColumnType::Text;
+        }
+    }
+    columns
+}
+
+pub(crate) fn parse_number(value: &str) -> Option<f64> {
+    value.trim().parse().ok().filter(|n: &f64| n.is_finite())
+}
+
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "Y" | "y" => Some(true),
+        "N" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_record_columns() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240229<FREQ:6>14.074<TIME_ON:4>2460<EOR>\
+             <APP_X:1>1<QSO_DATE:8>20230229<SWL:1>N<EOR>",
+        )
+        .unwrap();
+        assert_eq!(
+            record_columns(&file.records),
+            [
+                ("CALL".to_string(), ColumnType::Text),
+                // 2023 isn't a leap year
+                ("QSO_DATE".to_string(), ColumnType::Text),
+                ("FREQ".to_string(), ColumnType::Number),
+                ("TIME_ON".to_string(), ColumnType::Text),
+                ("APP_X".to_string(), ColumnType::Text),
+                ("SWL".to_string(), ColumnType::Boolean),
+            ]
+        );
+        // ADIF dates start in 1930
+        assert!(!ColumnType::Date.accepts("19291231"));
+    }
+}
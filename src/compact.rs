@@ -0,0 +1,228 @@
+use crate::borrowed::{FieldRef, RecordRef};
+use crate::types::{DataType, Field, Record};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Location of one field within a [`CompactRecord`]'s buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Slot {
+    /// End of the name; the name starts where the previous value ended
+    name_end: u32,
+    /// End of the value; the value starts where the name ended
+    value_end: u32,
+    data_type: DataType,
+}
+
+/// A memory-efficient QSO record
+///
+/// All names and values live in a single buffer and each field is a pair
+/// of offsets into it, so a record costs two allocations regardless of
+/// its field count. Use this instead of [`Record`] when holding millions
+/// of records in memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactRecord {
+    buffer: String,
+    slots: Vec<Slot>,
+}
+
+impl CompactRecord {
+    /// Create a new empty record
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to the record (the name is uppercased)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the record's text grows beyond 4 GiB.
+    pub fn add_field(&mut self, name: &str, data_type: DataType, value: &str) {
+        if name.bytes().any(|b| b.is_ascii_lowercase()) {
+            self.buffer.push_str(&name.to_ascii_uppercase());
+        } else {
+            self.buffer.push_str(name);
+        }
+        let name_end = offset(self.buffer.len());
+        self.buffer.push_str(value);
+        self.slots.push(Slot {
+            name_end,
+            value_end: offset(self.buffer.len()),
+            data_type,
+        });
+    }
+
+    /// Get the number of fields
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Check if the record has no fields
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Release any spare capacity left over from building the record
+    pub fn shrink_to_fit(&mut self) {
+        self.buffer.shrink_to_fit();
+        self.slots.shrink_to_fit();
+    }
+
+    /// Iterate over the fields, borrowing from the record
+    pub fn iter(&self) -> impl Iterator<Item = FieldRef<'_>> {
+        let mut start = 0;
+        self.slots.iter().map(move |slot| {
+            let (name_end, value_end) = (slot.name_end as usize, slot.value_end as usize);
+            let field = FieldRef {
+                name: Cow::Borrowed(&self.buffer[start..name_end]),
+                data_type: slot.data_type,
+                value: &self.buffer[name_end..value_end],
+                span: 0..0,
+            };
+            start = value_end;
+            field
+        })
+    }
+
+    /// Get a field by name (case-insensitive)
+    pub fn get(&self, name: &str) -> Option<FieldRef<'_>> {
+        self.iter().find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Get a field value by name (case-insensitive)
+    pub fn get_value(&self, name: &str) -> Option<&str> {
+        self.get(name).map(|f| f.value)
+    }
+
+    /// Convert to a regular Record
+    pub fn to_record(&self) -> Record {
+        Record {
+            fields: self.iter().map(FieldRef::into_owned).collect(),
+            terminator: None,
+        }
+    }
+
+    /// Get the call sign of the contacted station
+    pub fn call(&self) -> Option<&str> {
+        self.get_value("CALL")
+    }
+
+    /// Get the QSO date
+    pub fn qso_date(&self) -> Option<&str> {
+        self.get_value("QSO_DATE")
+    }
+
+    /// Get the time on (start time)
+    pub fn time_on(&self) -> Option<&str> {
+        self.get_value("TIME_ON")
+    }
+
+    /// Get the band
+    pub fn band(&self) -> Option<&str> {
+        self.get_value("BAND")
+    }
+
+    /// Get the frequency in MHz
+    pub fn freq(&self) -> Option<&str> {
+        self.get_value("FREQ")
+    }
+
+    /// Get the mode
+    pub fn mode(&self) -> Option<&str> {
+        self.get_value("MODE")
+    }
+
+    /// Get the RST sent
+    pub fn rst_sent(&self) -> Option<&str> {
+        self.get_value("RST_SENT")
+    }
+
+    /// Get the RST received
+    pub fn rst_rcvd(&self) -> Option<&str> {
+        self.get_value("RST_RCVD")
+    }
+}
+
+fn offset(len: usize) -> u32 {
+    u32::try_from(len).expect("compact record exceeds 4 GiB")
+}
+
+impl<'a> FromIterator<FieldRef<'a>> for CompactRecord {
+    fn from_iter<I: IntoIterator<Item = FieldRef<'a>>>(iter: I) -> Self {
+        let mut record = CompactRecord::new();
+        for field in iter {
+            record.add_field(&field.name, field.data_type, field.value);
+        }
+        record.shrink_to_fit();
+        record
+    }
+}
+
+impl From<&Record> for CompactRecord {
+    fn from(record: &Record) -> Self {
+        let mut compact = CompactRecord::new();
+        for Field {
+            name,
+            data_type,
+            value,
+            ..
+        } in &record.fields
+        {
+            compact.add_field(name, *data_type, value);
+        }
+        compact.shrink_to_fit();
+        compact
+    }
+}
+
+impl From<Record> for CompactRecord {
+    fn from(record: Record) -> Self {
+        CompactRecord::from(&record)
+    }
+}
+
+impl From<&RecordRef<'_>> for CompactRecord {
+    fn from(record: &RecordRef<'_>) -> Self {
+        record.fields.iter().cloned().collect()
+    }
+}
+
+impl From<&CompactRecord> for Record {
+    fn from(record: &CompactRecord) -> Self {
+        record.to_record()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi_borrowed;
+
+    #[test]
+    fn test_accessors() {
+        let mut record = CompactRecord::new();
+        record.add_field("call", DataType::Unspecified, "W1AW");
+        record.add_field("FREQ", DataType::Number, "14.074");
+        record.add_field("NOTES", DataType::String, "");
+
+        assert_eq!(record.len(), 3);
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(record.freq(), Some("14.074"));
+        assert_eq!(record.get_value("notes"), Some(""));
+        assert_eq!(record.get("Freq").unwrap().data_type, DataType::Number);
+        assert_eq!(record.get_value("BAND"), None);
+
+        let names: Vec<String> = record.iter().map(|f| f.name.into_owned()).collect();
+        assert_eq!(names, vec!["CALL", "FREQ", "NOTES"]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let file = parse_adi_borrowed("<CALL:4>W1AW<NAME:5>José<MODE:2>CW<EOR>").unwrap();
+        let compact = CompactRecord::from(&file.records[0]);
+
+        let record = compact.to_record();
+        assert_eq!(record.fields, file.records[0].clone().into_owned().fields);
+        assert_eq!(CompactRecord::from(&record), compact);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::enumeration::enumeration;
+use crate::from_field::{FromField, ToField};
+use crate::types::Record;
+use alloc::string::{String, ToString};
+use core::convert::Infallible;
+use core::fmt;
+use core::str::FromStr;
+
+enumeration! {
+    /// A contest, from the ADIF Contest ID enumeration
+    ///
+    /// Holds the IDs of the ADIF 3.1.6 enumeration, including import-only
+    /// ones; see [`ContestId`] for values outside it.
+    pub enum Contest for "CONTEST_ID" {
+        Podxs160mSprint => "070-160M-SPRINT",
+        Podxs3Day => "070-3-DAY",
+        Podxs31Flavors => "070-31-FLAVORS",
+        Podxs40mSprint => "070-40M-SPRINT",
+        Podxs80mSprint => "070-80M-SPRINT",
+        PodxsPskfest => "070-PSKFEST",
+        PodxsStPatsDay => "070-ST-PATS-DAY",
+        PodxsValentineSprint => "070-VALENTINE-SPRINT",
+        Rtty10 => "10-RTTY",
+        TenTenOpenSeason => "1010-OPEN-SEASON",
+        SevenQp => "7QP",
+        AlQsoParty => "AL-QSO-PARTY",
+        AllAsianDxCw => "ALL-ASIAN-DX-CW",
+        AllAsianDxPhone => "ALL-ASIAN-DX-PHONE",
+        AnartsRtty => "ANARTS-RTTY",
+        AnatolianRtty => "ANATOLIAN-RTTY",
+        ApSprint => "AP-SPRINT",
+        ArQsoParty => "AR-QSO-PARTY",
+        AriDx => "ARI-DX",
+        Arrl10 => "ARRL-10",
+        Arrl10Ghz => "ARRL-10-GHZ",
+        Arrl160 => "ARRL-160",
+        Arrl222 => "ARRL-222",
+        ArrlDigi => "ARRL-DIGI",
+        ArrlDxCw => "ARRL-DX-CW",
+        ArrlDxSsb => "ARRL-DX-SSB",
+        ArrlEme => "ARRL-EME",
+        ArrlFd => "ARRL-FD",
+        ArrlRrCw => "ARRL-RR-CW",
+        ArrlRrRtty => "ARRL-RR-RTTY",
+        ArrlRrSsb => "ARRL-RR-SSB",
+        ArrlRtty => "ARRL-RTTY",
+        ArrlScr => "ARRL-SCR",
+        ArrlSsCw => "ARRL-SS-CW",
+        ArrlSsSsb => "ARRL-SS-SSB",
+        ArrlUhfAug => "ARRL-UHF-AUG",
+        ArrlVhfJan => "ARRL-VHF-JAN",
+        ArrlVhfJun => "ARRL-VHF-JUN",
+        ArrlVhfSep => "ARRL-VHF-SEP",
+        AzQsoParty => "AZ-QSO-PARTY",
+        BartgRtty => "BARTG-RTTY",
+        BartgSprint => "BARTG-SPRINT",
+        BataviaFt8 => "BATAVIA-FT8",
+        BcQsoParty => "BC-QSO-PARTY",
+        CaQsoParty => "CA-QSO-PARTY",
+        CisDx => "CIS-DX",
+        CoQsoParty => "CO-QSO-PARTY",
+        Cq160Cw => "CQ-160-CW",
+        Cq160Ssb => "CQ-160-SSB",
+        CqM => "CQ-M",
+        CqVhf => "CQ-VHF",
+        CqWpxCw => "CQ-WPX-CW",
+        CqWpxRtty => "CQ-WPX-RTTY",
+        CqWpxSsb => "CQ-WPX-SSB",
+        CqWwCw => "CQ-WW-CW",
+        CqWwRtty => "CQ-WW-RTTY",
+        CqWwSsb => "CQ-WW-SSB",
+        CtQsoParty => "CT-QSO-PARTY",
+        CvaDxCw => "CVA-DX-CW",
+        CvaDxSsb => "CVA-DX-SSB",
+        CwopsCwOpen => "CWOPS-CW-OPEN",
+        CwopsCwt => "CWOPS-CWT",
+        Darc10 => "DARC-10",
+        DarcCwa => "DARC-CWA",
+        DarcFt4 => "DARC-FT4",
+        DarcTrainee => "DARC-TRAINEE",
+        DarcUkwFieldDay => "DARC-UKW-FIELD-DAY",
+        DarcUkwSpring => "DARC-UKW-SPRING",
+        DarcWaedcCw => "DARC-WAEDC-CW",
+        DarcWaedcRtty => "DARC-WAEDC-RTTY",
+        DarcWaedcSsb => "DARC-WAEDC-SSB",
+        DarcWag => "DARC-WAG",
+        DarcXmas => "DARC-XMAS",
+        DeQsoParty => "DE-QSO-PARTY",
+        DlDxRtty => "DL-DX-RTTY",
+        DmcRtty => "DMC-RTTY",
+        EaCncw => "EA-CNCW",
+        EaDme => "EA-DME",
+        EaMajestadCw => "EA-MAJESTAD-CW",
+        EaMajestadSsb => "EA-MAJESTAD-SSB",
+        EaPsk63 => "EA-PSK63",
+        EaRtty => "EA-RTTY",
+        EaSmreCw => "EA-SMRE-CW",
+        EaSmreSsb => "EA-SMRE-SSB",
+        EaVhfAtlantic => "EA-VHF-ATLANTIC",
+        EaVhfCom => "EA-VHF-COM",
+        EaVhfCostaSol => "EA-VHF-COSTA-SOL",
+        EaVhfEa => "EA-VHF-EA",
+        EaVhfEa1rcs => "EA-VHF-EA1RCS",
+        EaVhfQsl => "EA-VHF-QSL",
+        EaVhfSadurni => "EA-VHF-SADURNI",
+        EaWwRtty => "EA-WW-RTTY",
+        EpcPsk63 => "EPC-PSK63",
+        EuSprint => "EU Sprint",
+        EuHf => "EU-HF",
+        EuPskDx => "EU-PSK-DX",
+        Eucw160m => "EUCW160M",
+        FallSprint => "FALL SPRINT",
+        FlQsoParty => "FL-QSO-PARTY",
+        GaQsoParty => "GA-QSO-PARTY",
+        HaDx => "HA-DX",
+        Helvetia => "HELVETIA",
+        HiQsoParty => "HI-QSO-PARTY",
+        Holyland => "HOLYLAND",
+        IaQsoParty => "IA-QSO-PARTY",
+        IaruFieldDay => "IARU-FIELD-DAY",
+        IaruHf => "IARU-HF",
+        IcwcMst => "ICWC-MST",
+        IdQsoParty => "ID-QSO-PARTY",
+        IlQsoParty => "IL QSO Party",
+        InQsoParty => "IN-QSO-PARTY",
+        JartsWwRtty => "JARTS-WW-RTTY",
+        JidxCw => "JIDX-CW",
+        JidxSsb => "JIDX-SSB",
+        JtDxRtty => "JT-DX-RTTY",
+        K1usnSso => "K1USN-SSO",
+        K1usnSst => "K1USN-SST",
+        KsQsoParty => "KS-QSO-PARTY",
+        KyQsoParty => "KY-QSO-PARTY",
+        LaQsoParty => "LA-QSO-PARTY",
+        LdcRtty => "LDC-RTTY",
+        LzDx => "LZ DX",
+        MarQsoParty => "MAR-QSO-PARTY",
+        MdQsoParty => "MD-QSO-PARTY",
+        MeQsoParty => "ME-QSO-PARTY",
+        MiQsoParty => "MI-QSO-PARTY",
+        MidatlanticQsoParty => "MIDATLANTIC-QSO-PARTY",
+        MnQsoParty => "MN-QSO-PARTY",
+        MoQsoParty => "MO-QSO-PARTY",
+        MsQsoParty => "MS-QSO-PARTY",
+        MtQsoParty => "MT-QSO-PARTY",
+        NaSprintCw => "NA-SPRINT-CW",
+        NaSprintRtty => "NA-SPRINT-RTTY",
+        NaSprintSsb => "NA-SPRINT-SSB",
+        NaqpCw => "NAQP-CW",
+        NaqpRtty => "NAQP-RTTY",
+        NaqpSsb => "NAQP-SSB",
+        NcQsoParty => "NC-QSO-PARTY",
+        NdQsoParty => "ND-QSO-PARTY",
+        NeQsoParty => "NE-QSO-PARTY",
+        Neqp => "NEQP",
+        NhQsoParty => "NH-QSO-PARTY",
+        NjQsoParty => "NJ-QSO-PARTY",
+        NmQsoParty => "NM-QSO-PARTY",
+        NrauBalticCw => "NRAU-BALTIC-CW",
+        NrauBalticSsb => "NRAU-BALTIC-SSB",
+        NvQsoParty => "NV-QSO-PARTY",
+        NyQsoParty => "NY-QSO-PARTY",
+        OceaniaDxCw => "OCEANIA-DX-CW",
+        OceaniaDxSsb => "OCEANIA-DX-SSB",
+        OhQsoParty => "OH-QSO-PARTY",
+        OkDxRtty => "OK-DX-RTTY",
+        OkOmDx => "OK-OM-DX",
+        OkQsoParty => "OK-QSO-PARTY",
+        OmissQsoParty => "OMISS-QSO-PARTY",
+        OnQsoParty => "ON-QSO-PARTY",
+        OrQsoParty => "OR-QSO-PARTY",
+        PaQsoParty => "PA-QSO-PARTY",
+        Pacc => "PACC",
+        PskDeathmatch => "PSK-DEATHMATCH",
+        QcQsoParty => "QC-QSO-PARTY",
+        Rac => "RAC",
+        RacCanadaDay => "RAC-CANADA-DAY",
+        RacCanadaWinter => "RAC-CANADA-WINTER",
+        Rdac => "RDAC",
+        Rdxc => "RDXC",
+        Ref160m => "REF-160M",
+        RefCw => "REF-CW",
+        RefSsb => "REF-SSB",
+        RepPortugalDayHf => "REP-PORTUGAL-DAY-HF",
+        RiQsoParty => "RI-QSO-PARTY",
+        Rsgb160 => "RSGB-160",
+        Rsgb2128Cw => "RSGB-21/28-CW",
+        Rsgb2128Ssb => "RSGB-21/28-SSB",
+        Rsgb80mCc => "RSGB-80M-CC",
+        RsgbAfsCw => "RSGB-AFS-CW",
+        RsgbAfsSsb => "RSGB-AFS-SSB",
+        RsgbClubCalls => "RSGB-CLUB-CALLS",
+        RsgbCommonwealth => "RSGB-COMMONWEALTH",
+        RsgbIota => "RSGB-IOTA",
+        RsgbLowPower => "RSGB-LOW-POWER",
+        RsgbNfd => "RSGB-NFD",
+        RsgbRopoco => "RSGB-ROPOCO",
+        RsgbSsbFd => "RSGB-SSB-FD",
+        RussianRtty => "RUSSIAN-RTTY",
+        SacCw => "SAC-CW",
+        SacSsb => "SAC-SSB",
+        SartgRtty => "SARTG-RTTY",
+        ScQsoParty => "SC-QSO-PARTY",
+        SccRtty => "SCC-RTTY",
+        SdQsoParty => "SD-QSO-PARTY",
+        SmpAug => "SMP-AUG",
+        SmpMay => "SMP-MAY",
+        SpDxRtty => "SP-DX-RTTY",
+        SparWinterFd => "SPAR-WINTER-FD",
+        SpDx => "SPDXContest",
+        SpringSprint => "SPRING SPRINT",
+        SrMarathon => "SR-MARATHON",
+        StewPerry => "STEW-PERRY",
+        SummerSprint => "SUMMER SPRINT",
+        TaraGridDip => "TARA-GRID-DIP",
+        TaraRtty => "TARA-RTTY",
+        TaraRumble => "TARA-RUMBLE",
+        TaraSkirmish => "TARA-SKIRMISH",
+        TenRtty => "TEN-RTTY",
+        TmcRtty => "TMC-RTTY",
+        TnQsoParty => "TN-QSO-PARTY",
+        TxQsoParty => "TX-QSO-PARTY",
+        UbaDxCw => "UBA-DX-CW",
+        UbaDxSsb => "UBA-DX-SSB",
+        UkDxBpsk63 => "UK-DX-BPSK63",
+        UkDxRtty => "UK-DX-RTTY",
+        UkrChampRtty => "UKR-CHAMP-RTTY",
+        UkrainianDx => "UKRAINIAN DX",
+        Uksmg6mMarathon => "UKSMG-6M-MARATHON",
+        UksmgSummerEs => "UKSMG-SUMMER-ES",
+        UsCountiesQso => "US-COUNTIES-QSO",
+        UtQsoParty => "UT-QSO-PARTY",
+        VaQsoParty => "VA-QSO-PARTY",
+        VenezIndDay => "VENEZ-IND-DAY",
+        VoltaRtty => "VOLTA-RTTY",
+        VtQsoParty => "VT-QSO-PARTY",
+        WaQsoParty => "WA-QSO-PARTY",
+        Wfd => "WFD",
+        WiQsoParty => "WI-QSO-PARTY",
+        WiaHarryAngel => "WIA-HARRY ANGEL",
+        WiaJmmfd => "WIA-JMMFD",
+        WiaOcdx => "WIA-OCDX",
+        WiaRemembrance => "WIA-REMEMBRANCE",
+        WiaRossHull => "WIA-ROSS HULL",
+        WiaTransTasman => "WIA-TRANS TASMAN",
+        WiaVhfUhfFd => "WIA-VHF/UHF FD",
+        WiaVkShires => "WIA-VK SHIRES",
+        WinterSprint => "WINTER SPRINT",
+        WvQsoParty => "WV-QSO-PARTY",
+        WwDigi => "WW-DIGI",
+        WyQsoParty => "WY-QSO-PARTY",
+        XeIntlRtty => "XE-INTL-RTTY",
+        XeQsoParty => "XE-QSO-PARTY",
+        XeRtty => "XE-RTTY",
+        Yudxc => "YUDXC",
+    }
+}
+
+/// The value of a CONTEST_ID field: a known [`Contest`], or any other ID
+///
+/// CONTEST_ID is a String field whose values are only suggested by the
+/// enumeration, so every value parses; unknown IDs keep their text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContestId {
+    /// A contest in the enumeration
+    Known(Contest),
+    /// Any other contest ID, trimmed
+    Other(String),
+}
+
+impl ContestId {
+    /// Get the known contest, if any
+    pub fn contest(&self) -> Option<Contest> {
+        match self {
+            ContestId::Known(contest) => Some(*contest),
+            ContestId::Other(_) => None,
+        }
+    }
+
+    /// Get the ID as written in ADIF
+    pub fn as_str(&self) -> &str {
+        match self {
+            ContestId::Known(contest) => contest.as_str(),
+            ContestId::Other(id) => id,
+        }
+    }
+}
+
+impl From<Contest> for ContestId {
+    fn from(contest: Contest) -> Self {
+        ContestId::Known(contest)
+    }
+}
+
+impl FromStr for ContestId {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Infallible> {
+        Ok(Contest::from_field(s)
+            .map_or_else(|_| ContestId::Other(s.trim().to_string()), ContestId::Known))
+    }
+}
+
+impl FromField for ContestId {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        let Ok(id) = value.parse();
+        Ok(id)
+    }
+}
+
+impl ToField for ContestId {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for ContestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Record {
+    /// Get the contest ID, or `None` if CONTEST_ID is missing or empty
+    pub fn contest_id(&self) -> Option<ContestId> {
+        self.get_parsed("CONTEST_ID").and_then(|id| id.ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_contest_id() {
+        let file =
+            parse_adi("<CONTEST_ID:8>cq-ww-cw<EOR><CONTEST_ID:10> MY-PARTY <EOR><CALL:4>W1AW<EOR>")
+                .unwrap();
+        let id = file.records[0].contest_id().unwrap();
+        assert_eq!(id, ContestId::Known(Contest::CqWwCw));
+        assert_eq!(id.to_string(), "CQ-WW-CW");
+        let other = file.records[1].contest_id().unwrap();
+        assert_eq!(other, ContestId::Other("MY-PARTY".into()));
+        assert_eq!(other.contest(), None);
+        assert!(file.records[2].contest_id().is_none());
+        assert_eq!(Contest::SevenQp.as_str(), "7QP");
+        assert_eq!(ContestId::from(Contest::Arrl10).as_str(), "ARRL-10");
+        assert_eq!(
+            "il qso party".parse::<Contest>().unwrap(),
+            Contest::IlQsoParty
+        );
+        assert_eq!(Contest::Rsgb2128Cw.as_str(), "RSGB-21/28-CW");
+    }
+}
@@ -0,0 +1,59 @@
+use crate::enumeration::enumeration;
+use crate::error::Result;
+use crate::types::Record;
+
+enumeration! {
+    /// A continent, from the ADIF Continent enumeration
+    pub enum Continent for "CONT" {
+        Na => "NA",
+        Sa => "SA",
+        Eu => "EU",
+        Af => "AF",
+        Oc => "OC",
+        As => "AS",
+        An => "AN",
+    }
+}
+
+impl Continent {
+    /// Get the continent's name
+    pub fn name(self) -> &'static str {
+        match self {
+            Continent::Na => "North America",
+            Continent::Sa => "South America",
+            Continent::Eu => "Europe",
+            Continent::Af => "Africa",
+            Continent::Oc => "Oceania",
+            Continent::As => "Asia",
+            Continent::An => "Antarctica",
+        }
+    }
+}
+
+impl Record {
+    /// Get the continent of the contacted station, or `None` if CONT is
+    /// missing or empty
+    pub fn cont(&self) -> Option<Result<Continent>> {
+        self.get_parsed("CONT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdifError, parse_adi};
+
+    #[test]
+    fn test_cont() {
+        let file = parse_adi("<CONT:2>eu<EOR><CONT:2>XX<EOR><CALL:4>W1AW<EOR>").unwrap();
+        let cont = file.records[0].cont().unwrap().unwrap();
+        assert_eq!(cont, Continent::Eu);
+        assert_eq!(cont.to_string(), "EU");
+        assert_eq!(cont.name(), "Europe");
+        assert!(matches!(
+            file.records[1].cont(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "CONT"
+        ));
+        assert!(file.records[2].cont().is_none());
+    }
+}
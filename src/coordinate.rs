@@ -0,0 +1,209 @@
+use crate::error::{AdifError, Result};
+use crate::from_field::{FromField, ToField};
+use crate::types::Record;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+/// A latitude or longitude, from the ADIF Location type (`XDDD MM.MMM`)
+///
+/// Held as signed decimal degrees: north and east are positive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinate {
+    degrees: f64,
+    latitude: bool,
+}
+
+impl Coordinate {
+    /// Create a latitude, or `None` if it is outside -90 to 90 degrees
+    pub fn latitude(degrees: f64) -> Option<Self> {
+        (-90.0..=90.0).contains(&degrees).then_some(Coordinate {
+            degrees,
+            latitude: true,
+        })
+    }
+
+    /// Create a longitude, or `None` if it is outside -180 to 180 degrees
+    pub fn longitude(degrees: f64) -> Option<Self> {
+        (-180.0..=180.0).contains(&degrees).then_some(Coordinate {
+            degrees,
+            latitude: false,
+        })
+    }
+
+    /// Get the signed decimal degrees
+    pub fn degrees(self) -> f64 {
+        self.degrees
+    }
+
+    /// Check whether this is a latitude (N or S) rather than a longitude
+    pub fn is_latitude(self) -> bool {
+        self.latitude
+    }
+}
+
+impl FromStr for Coordinate {
+    type Err = AdifError;
+
+    /// Parse a location such as `N040 30.000`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let invalid = || AdifError::InvalidField {
+            field: "Location".into(),
+            message: format!("'{}' is not a location (XDDD MM.MMM)", s),
+        };
+        let (sign, latitude) = match s.bytes().next().map(|b| b.to_ascii_uppercase()) {
+            Some(b'N') => (1.0, true),
+            Some(b'S') => (-1.0, true),
+            Some(b'E') => (1.0, false),
+            Some(b'W') => (-1.0, false),
+            _ => return Err(invalid()),
+        };
+        let (degrees, minutes) = s
+            .get(1..)
+            .and_then(|s| s.split_once(' '))
+            .ok_or_else(invalid)?;
+        if degrees.len() != 3 || !degrees.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let degrees: f64 = degrees.parse().map_err(|_| invalid())?;
+        let minutes: f64 = minutes.parse().map_err(|_| invalid())?;
+        if !(0.0..60.0).contains(&minutes) {
+            return Err(invalid());
+        }
+        let degrees = sign * (degrees + minutes / 60.0);
+        if latitude {
+            Coordinate::latitude(degrees)
+        } else {
+            Coordinate::longitude(degrees)
+        }
+        .ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hemisphere = match (self.latitude, self.degrees < 0.0) {
+            (true, false) => 'N',
+            (true, true) => 'S',
+            (false, false) => 'E',
+            (false, true) => 'W',
+        };
+        // Round to thousandths of a minute before splitting off the degrees
+        let thousandths = (self.degrees.abs() * 60_000.0 + 0.5) as u64;
+        write!(
+            f,
+            "{}{:03} {:02}.{:03}",
+            hemisphere,
+            thousandths / 60_000,
+            thousandths % 60_000 / 1000,
+            thousandths % 1000
+        )
+    }
+}
+
+impl FromField for Coordinate {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        match value.parse() {
+            Ok(coordinate) => Ok(coordinate),
+            Err(AdifError::InvalidField { message, .. }) => Err(message),
+            Err(other) => Err(format!("{}", other)),
+        }
+    }
+}
+
+impl ToField for Coordinate {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Record {
+    /// Get the contacted station's latitude, or `None` if LAT is missing
+    /// or empty
+    pub fn lat(&self) -> Option<Result<Coordinate>> {
+        self.coordinate("LAT", true)
+    }
+
+    /// Get the contacted station's longitude
+    pub fn lon(&self) -> Option<Result<Coordinate>> {
+        self.coordinate("LON", false)
+    }
+
+    /// Get the logging station's latitude
+    pub fn my_lat(&self) -> Option<Result<Coordinate>> {
+        self.coordinate("MY_LAT", true)
+    }
+
+    /// Get the logging station's longitude
+    pub fn my_lon(&self) -> Option<Result<Coordinate>> {
+        self.coordinate("MY_LON", false)
+    }
+
+    /// Get a location field, checking it is a latitude or a longitude
+    pub(crate) fn coordinate(&self, name: &str, latitude: bool) -> Option<Result<Coordinate>> {
+        Some(self.get_parsed(name)?.and_then(|c: Coordinate| {
+            if c.latitude == latitude {
+                Ok(c)
+            } else {
+                Err(AdifError::InvalidField {
+                    field: name.into(),
+                    message: format!(
+                        "'{}' is not a {}",
+                        c,
+                        if latitude { "latitude" } else { "longitude" }
+                    ),
+                })
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse_coordinate() {
+        let degrees = |s: &str| s.parse::<Coordinate>().ok().map(Coordinate::degrees);
+        assert_eq!(degrees("N040 30.000"), Some(40.5));
+        assert_eq!(degrees("w073 15.000"), Some(-73.25));
+        assert_eq!(degrees("N40 30.000"), None);
+        assert_eq!(degrees("E181 00.000"), None);
+        assert_eq!(degrees("N091 00.000"), None);
+        assert_eq!(degrees("S000 60.000"), None);
+    }
+
+    #[test]
+    fn test_display_coordinate() {
+        let coordinate: Coordinate = "s033 51.900".parse().unwrap();
+        assert_eq!(coordinate.to_string(), "S033 51.900");
+        assert_eq!(
+            Coordinate::longitude(-0.99999999).unwrap().to_string(),
+            "W001 00.000"
+        );
+        assert_eq!(
+            Coordinate::longitude(151.2093).unwrap().to_string(),
+            "E151 12.558"
+        );
+        assert!(Coordinate::latitude(90.5).is_none());
+    }
+
+    #[test]
+    fn test_lat_lon() {
+        let file =
+            parse_adi("<LAT:11>N041 42.850<LON:11>W072 43.667<MY_LAT:11>E010 00.000<EOR>").unwrap();
+        let record = &file.records[0];
+        assert!((record.lat().unwrap().unwrap().degrees() - 41.714).abs() < 0.001);
+        assert!(!record.lon().unwrap().unwrap().is_latitude());
+        assert!(matches!(
+            record.my_lat(),
+            Some(Err(AdifError::InvalidField { field, message }))
+                if field == "MY_LAT" && message == "'E010 00.000' is not a latitude"
+        ));
+        assert!(record.my_lon().is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::error::{AdifError, Result};
+use crate::types::{AdifFile, Field, Record};
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Options controlling how CSV is converted to ADIF
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Column separator
+    pub delimiter: u8,
+    /// Maps CSV column headers (case-insensitive) to ADIF field names. Map
+    /// a column to an empty string to ignore it.
+    pub columns: HashMap<String, String>,
+    /// Use unmapped column headers as field names (uppercased, with spaces
+    /// and dashes turned into underscores) instead of ignoring them
+    pub keep_unmapped: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            columns: HashMap::new(),
+            keep_unmapped: true,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Map a CSV column to an ADIF field name
+    pub fn with_column(mut self, column: impl Into<String>, field: impl Into<String>) -> Self {
+        self.columns
+            .insert(column.into().to_uppercase(), field.into());
+        self
+    }
+
+    /// Get the ADIF field name for a column header, if it is imported
+    fn field_name(&self, column: &str) -> Option<String> {
+        let column = column.trim();
+        let key = column.to_uppercase();
+        let name = match self.columns.get(&key) {
+            Some(name) => name.to_uppercase(),
+            None if self.keep_unmapped => key.replace([' ', '-'], "_"),
+            None => return None,
+        };
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+/// Parse CSV data whose first row names the columns into an AdifFile
+pub fn parse_csv(input: &str) -> Result<AdifFile> {
+    parse_csv_with_options(input.as_bytes(), &CsvOptions::default())
+}
+
+/// Parse CSV data using the given options
+///
+/// Each row becomes a record; empty cells are skipped. Fails if a column
+/// would produce a name that can't be written as an ADI field.
+pub fn parse_csv_with_options<R: Read>(input: R, options: &CsvOptions) -> Result<AdifFile> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(input);
+
+    let mut names = Vec::new();
+    for column in reader.headers()? {
+        let name = options.field_name(column);
+        if let Some(name) = &name
+            && (name.contains([':', '<', '>', ',']) || name.contains(char::is_whitespace))
+        {
+            return Err(AdifError::InvalidDataSpecifier {
+                position: 0,
+                message: format!("Column '{}' is not a valid field name", column),
+            });
+        }
+        names.push(name);
+    }
+
+    let mut file = AdifFile::new();
+    for row in reader.records() {
+        let row = row?;
+        let mut record = Record::new();
+        for (name, value) in names.iter().zip(row.iter()) {
+            if let Some(name) = name
+                && !value.is_empty()
+            {
+                record.add_field(Field::new(name.as_str(), value));
+            }
+        }
+        if !record.fields.is_empty() {
+            file.records.push(record);
+        }
+    }
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let input = "Call,qso_date,Time On,Band,Notes\n\
+                     W1AW,20240115,1423,20m,\n\
+                     K1AB,20240115,1425,40m,\"big, signal\"\n";
+        let file = parse_csv(input).unwrap();
+
+        assert_eq!(file.len(), 2);
+        assert_eq!(file.records[0].call(), Some("W1AW"));
+        assert_eq!(file.records[0].time_on(), Some("1423"));
+        assert_eq!(file.records[0].get("NOTES"), None);
+        assert_eq!(file.records[1].get_value("NOTES"), Some("big, signal"));
+    }
+
+    #[test]
+    fn test_column_mapping() {
+        let input = "Callsign;Freq (MHz);Comment;Row\nW1AW;14.074;hi;1\n";
+        let options = CsvOptions {
+            delimiter: b';',
+            keep_unmapped: false,
+            ..Default::default()
+        }
+        .with_column("callsign", "CALL")
+        .with_column("Freq (MHz)", "freq")
+        .with_column("Row", "");
+        let file = parse_csv_with_options(input.as_bytes(), &options).unwrap();
+
+        let record = &file.records[0];
+        assert_eq!(record.fields.len(), 2);
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(record.freq(), Some("14.074"));
+    }
+
+    #[test]
+    fn test_invalid_column_name() {
+        let result = parse_csv("Freq:MHz\n14.074\n");
+        assert!(matches!(
+            result,
+            Err(AdifError::InvalidDataSpecifier { .. })
+        ));
+    }
+}
@@ -0,0 +1,454 @@
+use crate::error::{AdifError, Result};
+use crate::from_field::{FromField, ToField};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+use core::time::Duration;
+
+/// A value of the ADIF Date type: a Gregorian date from 1930 on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AdifDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl AdifDate {
+    /// Create a date, checking that it exists and is from 1930 on
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self> {
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let month_days = match month {
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            1..=12 => 31,
+            _ => 0,
+        };
+        if !(1930..=9999).contains(&year) || day == 0 || day > month_days {
+            return Err(invalid(
+                "Date",
+                format!("{:04}-{:02}-{:02} is not a valid date", year, month, day),
+            ));
+        }
+        Ok(AdifDate { year, month, day })
+    }
+
+    /// Get the year
+    pub fn year(self) -> u16 {
+        self.year
+    }
+
+    /// Get the month, from 1
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Get the day of the month, from 1
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Get the number of days since 1970-01-01, negative before
+    pub fn unix_days(self) -> i32 {
+        days(
+            i32::from(self.year),
+            u32::from(self.month),
+            u32::from(self.day),
+        )
+    }
+
+    /// Get the moment a time on this date (UTC) was
+    #[cfg(feature = "std")]
+    pub fn to_system_time(self, time: AdifTime) -> std::time::SystemTime {
+        let seconds = i64::from(self.unix_days()) * 86_400 + time.to_duration().as_secs() as i64;
+        let offset = Duration::from_secs(seconds.unsigned_abs());
+        if seconds >= 0 {
+            std::time::UNIX_EPOCH + offset
+        } else {
+            std::time::UNIX_EPOCH - offset
+        }
+    }
+
+    /// Get the UTC date and time (to the second) of a moment, such as
+    /// `SystemTime::now()`
+    #[cfg(feature = "std")]
+    pub fn from_system_time(moment: std::time::SystemTime) -> Result<(AdifDate, AdifTime)> {
+        let seconds = match moment.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after) => after.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs_f64().ceil() as i64),
+        };
+        let (year, month, day) = civil(seconds.div_euclid(86_400) as i32);
+        let second = seconds.rem_euclid(86_400) as u32;
+        let date = AdifDate::new(u16::try_from(year).unwrap_or(0), month as u8, day as u8)?;
+        let time = AdifTime::new(
+            (second / 3600) as u8,
+            (second / 60 % 60) as u8,
+            (second % 60) as u8,
+        )?;
+        Ok((date, time))
+    }
+}
+
+impl FromStr for AdifDate {
+    type Err = AdifError;
+
+    /// Parse a date written as YYYYMMDD
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.len() != 8 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("Date", format!("'{}' is not a date (YYYYMMDD)", s)));
+        }
+        let number = |range: core::ops::Range<usize>| s[range].parse::<u16>().unwrap_or(0);
+        AdifDate::new(number(0..4), number(4..6) as u8, number(6..8) as u8)
+    }
+}
+
+impl fmt::Display for AdifDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}{:02}{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A value of the ADIF Time type: HHMM or HHMMSS
+///
+/// Times remember whether they had seconds, so they display as written,
+/// but compare by value: `1200` equals `120000`.
+#[derive(Debug, Clone, Copy)]
+pub struct AdifTime {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    has_seconds: bool,
+}
+
+impl AdifTime {
+    /// Create a time with seconds, displayed as HHMMSS
+    pub fn new(hour: u8, minute: u8, second: u8) -> Result<Self> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(invalid(
+                "Time",
+                format!(
+                    "{:02}:{:02}:{:02} is not a valid time",
+                    hour, minute, second
+                ),
+            ));
+        }
+        Ok(AdifTime {
+            hour,
+            minute,
+            second,
+            has_seconds: true,
+        })
+    }
+
+    /// Create a time without seconds, displayed as HHMM
+    pub fn from_hm(hour: u8, minute: u8) -> Result<Self> {
+        let time = AdifTime::new(hour, minute, 0)?;
+        Ok(AdifTime {
+            has_seconds: false,
+            ..time
+        })
+    }
+
+    /// Get the hour
+    pub fn hour(self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute
+    pub fn minute(self) -> u8 {
+        self.minute
+    }
+
+    /// Get the second (0 for HHMM times)
+    pub fn second(self) -> u8 {
+        self.second
+    }
+
+    /// Check whether the time was given with seconds
+    pub fn has_seconds(self) -> bool {
+        self.has_seconds
+    }
+
+    /// Get the time since midnight
+    pub fn to_duration(self) -> Duration {
+        Duration::from_secs(
+            u64::from(self.hour) * 3600 + u64::from(self.minute) * 60 + u64::from(self.second),
+        )
+    }
+
+    fn key(self) -> (u8, u8, u8) {
+        (self.hour, self.minute, self.second)
+    }
+}
+
+impl PartialEq for AdifTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for AdifTime {}
+
+impl Hash for AdifTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for AdifTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AdifTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl FromStr for AdifTime {
+    type Err = AdifError;
+
+    /// Parse a time written as HHMM or HHMMSS
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if !matches!(s.len(), 4 | 6) || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid(
+                "Time",
+                format!("'{}' is not a time (HHMM or HHMMSS)", s),
+            ));
+        }
+        let number = |range: core::ops::Range<usize>| s[range].parse::<u8>().unwrap_or(0);
+        if s.len() == 4 {
+            AdifTime::from_hm(number(0..2), number(2..4))
+        } else {
+            AdifTime::new(number(0..2), number(2..4), number(4..6))
+        }
+    }
+}
+
+impl fmt::Display for AdifTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}{:02}", self.hour, self.minute)?;
+        if self.has_seconds {
+            write!(f, "{:02}", self.second)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromField for AdifDate {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        value.parse().map_err(message)
+    }
+}
+
+impl ToField for AdifDate {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FromField for AdifTime {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        value.parse().map_err(message)
+    }
+}
+
+impl ToField for AdifTime {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<AdifDate> for chrono::NaiveDate {
+    fn from(date: AdifDate) -> Self {
+        chrono::NaiveDate::from_ymd_opt(
+            i32::from(date.year),
+            u32::from(date.month),
+            u32::from(date.day),
+        )
+        .expect("AdifDate is a valid date")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for AdifDate {
+    type Error = AdifError;
+
+    fn try_from(date: chrono::NaiveDate) -> Result<Self> {
+        use chrono::Datelike;
+        let year = u16::try_from(date.year()).unwrap_or(0);
+        AdifDate::new(year, date.month() as u8, date.day() as u8)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<AdifTime> for chrono::NaiveTime {
+    fn from(time: AdifTime) -> Self {
+        chrono::NaiveTime::from_hms_opt(
+            u32::from(time.hour),
+            u32::from(time.minute),
+            u32::from(time.second),
+        )
+        .expect("AdifTime is a valid time")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveTime> for AdifTime {
+    /// Convert a time, dropping fractions of a second
+    fn from(time: chrono::NaiveTime) -> Self {
+        use chrono::Timelike;
+        AdifTime {
+            hour: time.hour() as u8,
+            minute: time.minute() as u8,
+            // Leap seconds are represented as the 59th second
+            second: time.second().min(59) as u8,
+            has_seconds: true,
+        }
+    }
+}
+
+/// Get the days since 1970-01-01 for a valid date
+pub(crate) fn days(year: i32, month: u32, day: u32) -> i32 {
+    // Days from civil date, with the year starting in March
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400) as u32;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era as i32 - 719_468
+}
+
+/// Get the (year, month, day) of a number of days since 1970-01-01
+#[cfg(feature = "std")]
+fn civil(days: i32) -> (i32, u32, u32) {
+    // The inverse of `days`, again with the year starting in March
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097) as u32;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era as i32 + era * 400 + i32::from(month <= 2);
+    (year, month, day)
+}
+
+fn invalid(field: &str, message: String) -> AdifError {
+    AdifError::InvalidField {
+        field: field.into(),
+        message,
+    }
+}
+
+/// Get the message of an error from parsing a date or time
+fn message(error: AdifError) -> String {
+    match error {
+        AdifError::InvalidField { message, .. } => message,
+        other => format!("{}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_adif_date() {
+        let date: AdifDate = "20240229".parse().unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+        assert_eq!(date.to_string(), "20240229");
+        assert_eq!(date.unix_days(), 19_782);
+        assert!(date < "20240301".parse().unwrap());
+        assert!("20230229".parse::<AdifDate>().is_err());
+        assert!("19291231".parse::<AdifDate>().is_err());
+        assert!("2024-02-29".parse::<AdifDate>().is_err());
+    }
+
+    #[test]
+    fn test_adif_time() {
+        let time: AdifTime = "1423".parse().unwrap();
+        assert_eq!(time.to_string(), "1423");
+        assert_eq!(time, "142300".parse().unwrap());
+        assert!(time < "142301".parse().unwrap());
+        assert_eq!(time.to_duration(), Duration::from_secs(14 * 3600 + 23 * 60));
+        assert_eq!(AdifTime::new(1, 2, 3).unwrap().to_string(), "010203");
+        assert!("2400".parse::<AdifTime>().is_err());
+        assert!("14235".parse::<AdifTime>().is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_system_time() {
+        let date: AdifDate = "19690101".parse().unwrap();
+        let time = AdifTime::from_hm(0, 1).unwrap();
+        assert_eq!(
+            std::time::UNIX_EPOCH
+                .duration_since(date.to_system_time(time))
+                .unwrap(),
+            Duration::from_secs(365 * 86_400 - 60)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_system_time() {
+        for (date, time) in [
+            ("20240229", "235959"),
+            ("19700101", "000000"),
+            ("19691231", "120000"),
+        ] {
+            let date: AdifDate = date.parse().unwrap();
+            let time: AdifTime = time.parse().unwrap();
+            let moment = date.to_system_time(time);
+            assert_eq!(AdifDate::from_system_time(moment).unwrap(), (date, time));
+        }
+        let moment = std::time::UNIX_EPOCH - Duration::from_millis(1500);
+        let (date, time) = AdifDate::from_system_time(moment).unwrap();
+        assert_eq!(
+            (date.to_string(), time.to_string()),
+            ("19691231".into(), "235958".into())
+        );
+    }
+
+    #[test]
+    fn test_get_as_date() {
+        let file = parse_adi("<QSO_DATE:8>19290101<TIME_ON:4>0930<EOR>").unwrap();
+        let record = &file.records[0];
+        assert!(matches!(
+            record.get_as::<AdifDate>("QSO_DATE"),
+            Err(AdifError::InvalidField { field, message })
+                if field == "QSO_DATE" && message == "1929-01-01 is not a valid date"
+        ));
+        assert_eq!(
+            record.get_as::<AdifTime>("TIME_ON").unwrap(),
+            AdifTime::from_hm(9, 30).unwrap()
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_conversions() {
+        let date: AdifDate = "20240115".parse().unwrap();
+        let naive = chrono::NaiveDate::from(date);
+        assert_eq!(AdifDate::try_from(naive).unwrap(), date);
+        let time: AdifTime = "142359".parse().unwrap();
+        assert_eq!(AdifTime::from(chrono::NaiveTime::from(time)), time);
+    }
+}
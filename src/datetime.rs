@@ -0,0 +1,57 @@
+use crate::error::Result;
+use crate::types::Record;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+impl Record {
+    /// Get QSO_DATE as a date, or `None` if it is missing
+    pub fn qso_date_parsed(&self) -> Option<Result<NaiveDate>> {
+        self.get_parsed("QSO_DATE")
+    }
+
+    /// Get TIME_ON as a time, or `None` if it is missing
+    ///
+    /// Four-digit times (HHMM) have zero seconds.
+    pub fn time_on_parsed(&self) -> Option<Result<NaiveTime>> {
+        self.get_parsed("TIME_ON")
+    }
+
+    /// Get the start of the QSO (UTC) from QSO_DATE and TIME_ON, or `None`
+    /// if either is missing
+    pub fn qso_datetime(&self) -> Option<Result<NaiveDateTime>> {
+        let date = self.qso_date_parsed()?;
+        let time = self.time_on_parsed()?;
+        Some(date.and_then(|date| Ok(date.and_time(time?))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AdifError, parse_adi};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_qso_datetime() {
+        let file = parse_adi(
+            "<QSO_DATE:8>20240115<TIME_ON:4>1423<EOR>\
+             <QSO_DATE:8>20240115<TIME_ON:6>142359<EOR>\
+             <QSO_DATE:8>20240115<EOR>\
+             <QSO_DATE:8>20240115<TIME_ON:5>14235<EOR>",
+        )
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        assert_eq!(
+            file.records[0].qso_datetime().unwrap().unwrap(),
+            date.and_hms_opt(14, 23, 0).unwrap()
+        );
+        assert_eq!(
+            file.records[1].qso_datetime().unwrap().unwrap(),
+            date.and_hms_opt(14, 23, 59).unwrap()
+        );
+        assert!(file.records[2].qso_datetime().is_none());
+        assert_eq!(file.records[2].qso_date_parsed().unwrap().unwrap(), date);
+        assert!(matches!(
+            file.records[3].qso_datetime(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "TIME_ON"
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::error::{AdifError, Result};
+use crate::parser::parse_adi;
+use crate::types::AdifFile;
+use alloc::string::ToString;
+
+/// ADIF serialization formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Tag-based ADI format
+    Adi,
+    /// XML-based ADX format
+    Adx,
+}
+
+/// UTF-8 byte order mark, which some Windows tools write
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Guess the format of ADIF data from its content
+///
+/// Returns `None` if the data doesn't look like ADI or ADX.
+pub fn detect_format(input: &[u8]) -> Option<Format> {
+    let input = input.strip_prefix(UTF8_BOM).unwrap_or(input);
+    let start = input.iter().position(|b| !b.is_ascii_whitespace())?;
+    let trimmed = &input[start..];
+
+    if starts_with_ignore_case(trimmed, b"<?xml") || starts_with_ignore_case(trimmed, b"<ADX") {
+        return Some(Format::Adx);
+    }
+
+    // Look for something shaped like an ADI data specifier or marker tag
+    let mut rest = input;
+    while let Some(i) = rest.iter().position(|&b| b == b'<') {
+        rest = &rest[i + 1..];
+        if looks_like_adi_tag(rest) {
+            return Some(Format::Adi);
+        }
+    }
+
+    None
+}
+
+/// Parse ADIF data in whichever format it appears to be
+///
+/// Returns the parsed file along with the detected format.
+pub fn parse_auto(input: &str) -> Result<(AdifFile, Format)> {
+    match detect_format(input.as_bytes()) {
+        Some(Format::Adi) => Ok((parse_adi(input)?, Format::Adi)),
+        Some(Format::Adx) => parse_adx(input).map(|file| (file, Format::Adx)),
+        None => Err(AdifError::UnsupportedFormat(
+            "input is neither ADI nor ADX".to_string(),
+        )),
+    }
+}
+
+#[cfg(feature = "adx")]
+fn parse_adx(input: &str) -> Result<AdifFile> {
+    let input = input.strip_prefix('\u{FEFF}').unwrap_or(input);
+    crate::adx::parse_adx(input)
+}
+
+#[cfg(not(feature = "adx"))]
+fn parse_adx(_input: &str) -> Result<AdifFile> {
+    Err(AdifError::UnsupportedFormat(
+        "ADX input requires the adx feature".to_string(),
+    ))
+}
+
+/// Check for `NAME:digits` or `EOH>`/`EOR>` following a '<'
+fn looks_like_adi_tag(after_bracket: &[u8]) -> bool {
+    let name_len = after_bracket
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+        .count();
+    if name_len == 0 {
+        return false;
+    }
+
+    let name = &after_bracket[..name_len];
+    match after_bracket.get(name_len) {
+        Some(b':') => after_bracket
+            .get(name_len + 1)
+            .is_some_and(|b| b.is_ascii_digit()),
+        Some(b'>') => name.eq_ignore_ascii_case(b"EOH") || name.eq_ignore_ascii_case(b"EOR"),
+        _ => false,
+    }
+}
+
+fn starts_with_ignore_case(haystack: &[u8], prefix: &[u8]) -> bool {
+    haystack.len() >= prefix.len() && haystack[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_adi() {
+        assert_eq!(detect_format(b"<CALL:4>W1AW<EOR>"), Some(Format::Adi));
+        assert_eq!(
+            detect_format(b"Exported <today>\n<ADIF_VER:5>3.1.6<eoh>"),
+            Some(Format::Adi)
+        );
+        assert_eq!(detect_format(b"Header\n<EOH>\n"), Some(Format::Adi));
+    }
+
+    #[test]
+    fn test_detect_adx() {
+        assert_eq!(
+            detect_format(b"\xEF\xBB\xBF  <?xml version=\"1.0\"?><ADX></ADX>"),
+            Some(Format::Adx)
+        );
+        assert_eq!(detect_format(b"<adx><RECORDS/></adx>"), Some(Format::Adx));
+    }
+
+    #[test]
+    fn test_detect_unsupported() {
+        assert_eq!(detect_format(b""), None);
+        assert_eq!(detect_format(b"CALL,QSO_DATE\nW1AW,20240115\n"), None);
+        assert_eq!(detect_format(b"<html><body>73</body></html>"), None);
+    }
+
+    #[test]
+    fn test_parse_auto_adi() {
+        let (file, format) = parse_auto("<CALL:4>W1AW<EOR>").unwrap();
+        assert_eq!(format, Format::Adi);
+        assert_eq!(file.records[0].call(), Some("W1AW"));
+    }
+
+    #[cfg(feature = "adx")]
+    #[test]
+    fn test_parse_auto_adx() {
+        let input = "<?xml version=\"1.0\"?><ADX><RECORDS><RECORD><CALL>W1AW</CALL></RECORD></RECORDS></ADX>";
+        let (file, format) = parse_auto(input).unwrap();
+        assert_eq!(format, Format::Adx);
+        assert_eq!(file.records[0].call(), Some("W1AW"));
+    }
+
+    #[test]
+    fn test_parse_auto_unsupported() {
+        assert!(matches!(
+            parse_auto("QSO: 14025 CW 2024-01-15 1430 W1AW"),
+            Err(AdifError::UnsupportedFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,397 @@
+use crate::error::{AdifError, Result};
+use crate::types::{AdifFile, Field, Record};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// EDI `PBand` values and the matching ADIF bands
+const BANDS: &[(&str, &str)] = &[
+    ("50 MHz", "6m"),
+    ("70 MHz", "4m"),
+    ("144 MHz", "2m"),
+    ("432 MHz", "70cm"),
+    ("1,3 GHz", "23cm"),
+    ("2,3 GHz", "13cm"),
+    ("3,4 GHz", "9cm"),
+    ("5,7 GHz", "6cm"),
+    ("10 GHz", "3cm"),
+    ("24 GHz", "1.25cm"),
+    ("47 GHz", "6mm"),
+    ("76 GHz", "4mm"),
+    ("120 GHz", "2.5mm"),
+    ("144 GHz", "2mm"),
+    ("248 GHz", "1mm"),
+];
+
+/// EDI mode codes (index) and the matching ADIF modes; mixed-mode codes
+/// use the sent mode
+const MODES: &[&str] = &[
+    "", "SSB", "CW", "SSB", "CW", "AM", "FM", "RTTY", "SSTV", "ATV",
+];
+
+/// Field holding the EDI QSO points, which have no ADIF equivalent
+const POINTS_FIELD: &str = "APP_EDI_POINTS";
+
+/// Header settings for an EDI log
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EdiOptions {
+    /// Contest name for the `TName` tag
+    pub contest: String,
+    /// Station callsign for the `PCall` tag; defaults to the first
+    /// record's STATION_CALLSIGN
+    pub callsign: String,
+    /// Station QTH locator for the `PWWLo` tag; defaults to the first
+    /// record's MY_GRIDSQUARE
+    pub locator: String,
+    /// Additional header tags (such as `PSect` or `PAdr1`), in order
+    pub tags: Vec<(String, String)>,
+}
+
+impl EdiOptions {
+    /// Create options for a contest and callsign
+    pub fn new(contest: impl Into<String>, callsign: impl Into<String>) -> Self {
+        Self {
+            contest: contest.into(),
+            callsign: callsign.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a header tag
+    pub fn with_tag(mut self, tag: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((tag.into(), value.into()));
+        self
+    }
+}
+
+/// Parse an EDI (REG1TEST) log into an AdifFile
+///
+/// Each QSO record becomes a record with QSO_DATE, TIME_ON, CALL, MODE,
+/// RST_SENT, STX, RST_RCVD, SRX, SRX_STRING (the received exchange),
+/// GRIDSQUARE and the QSO points as `APP_EDI_POINTS`. The `PCall`,
+/// `PWWLo`, `PBand` and `TName` header tags are copied to each record as
+/// STATION_CALLSIGN, MY_GRIDSQUARE, BAND and CONTEST_ID. The header
+/// sections are kept as the preamble.
+pub fn parse_edi(input: &str) -> Result<AdifFile> {
+    let mut file = AdifFile::new();
+    let mut shared = Vec::new();
+    let mut in_records = false;
+    let mut has_records = false;
+    let mut position = 0;
+
+    for line in input.split_inclusive('\n') {
+        let start = position;
+        position += line.len();
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let section = line.trim_matches(['[', ']']);
+            let name = section.split(';').next().unwrap_or_default();
+            if name.eq_ignore_ascii_case("END") {
+                break;
+            }
+            in_records = name.eq_ignore_ascii_case("QSORecords");
+            has_records |= in_records;
+            if !in_records {
+                file.header.preamble.push_str(line);
+                file.header.preamble.push('\n');
+            }
+            continue;
+        }
+
+        if in_records {
+            let record = parse_qso(line, &shared).map_err(|message| AdifError::ParseError {
+                position: start,
+                message,
+            })?;
+            file.records.push(record);
+            continue;
+        }
+
+        file.header.preamble.push_str(line);
+        file.header.preamble.push('\n');
+        let Some((tag, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        let field = match tag.trim() {
+            "PCall" => Field::new("STATION_CALLSIGN", value.to_uppercase()),
+            "PWWLo" => Field::new("MY_GRIDSQUARE", value),
+            "TName" => Field::new("CONTEST_ID", value),
+            "PBand" => match BANDS
+                .iter()
+                .find(|(edi, _)| edi.eq_ignore_ascii_case(value))
+            {
+                Some((_, band)) => Field::new("BAND", *band),
+                None => continue,
+            },
+            _ => continue,
+        };
+        shared.push(field);
+    }
+
+    if !has_records {
+        return Err(AdifError::ParseError {
+            position: 0,
+            message: "Missing [QSORecords] section".to_string(),
+        });
+    }
+    Ok(file)
+}
+
+/// Build a record from an EDI QSO line
+fn parse_qso(line: &str, shared: &[Field]) -> core::result::Result<Record, String> {
+    let columns: Vec<&str> = line.split(';').map(str::trim).collect();
+    // date;time;call;mode;rst-s;nr-s;rst-r;nr-r;exch-r;wwl-r;points;new-exch;new-wwl;new-dxcc;dupe
+    if columns.len() < 11 {
+        return Err(format!("Incomplete QSO record '{}'", line));
+    }
+
+    let date = columns[0];
+    if date.len() != 6 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid QSO date '{}'", date));
+    }
+    let century = if date < "50" { "20" } else { "19" };
+    let time = columns[1];
+    if time.len() != 4 || !time.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid QSO time '{}'", time));
+    }
+    if columns[2].is_empty() {
+        return Err("Missing QSO call".to_string());
+    }
+    let mode = match columns[3] {
+        "" => "",
+        code => code
+            .parse::<usize>()
+            .ok()
+            .and_then(|code| MODES.get(code).copied())
+            .ok_or_else(|| format!("Invalid QSO mode '{}'", code))?,
+    };
+
+    let mut record = Record::new();
+    record.add_field(Field::new("QSO_DATE", format!("{}{}", century, date)));
+    record.add_field(Field::new("TIME_ON", time));
+    record.add_field(Field::new("CALL", columns[2].to_uppercase()));
+    let optional = [
+        ("MODE", mode),
+        ("RST_SENT", columns[4]),
+        ("STX", columns[5]),
+        ("RST_RCVD", columns[6]),
+        ("SRX", columns[7]),
+        ("SRX_STRING", columns[8]),
+        ("GRIDSQUARE", columns[9]),
+        (POINTS_FIELD, columns[10]),
+    ];
+    for (name, value) in optional {
+        if !value.is_empty() {
+            record.add_field(Field::new(name, value));
+        }
+    }
+    for field in shared {
+        record.add_field(field.clone());
+    }
+    Ok(record)
+}
+
+/// Serialize an AdifFile to an EDI (REG1TEST) log
+///
+/// Records need QSO_DATE, TIME_ON and CALL. QSO points come from
+/// `APP_EDI_POINTS`, or else DISTANCE. `PBand` and `TDate` are taken
+/// from the records.
+pub fn write_edi(file: &AdifFile, options: &EdiOptions) -> Result<String> {
+    let mut lines = Vec::with_capacity(file.records.len());
+    for (index, record) in file.records.iter().enumerate() {
+        let line = qso_line(record).map_err(|message| AdifError::Conversion {
+            record: index,
+            message,
+        })?;
+        lines.push(line);
+    }
+
+    let first = file.records.first();
+    let from_record = |name: &str| {
+        first
+            .and_then(|r| r.get_value(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+    let callsign = match options.callsign.as_str() {
+        "" => from_record("STATION_CALLSIGN"),
+        callsign => callsign.to_string(),
+    };
+    let locator = match options.locator.as_str() {
+        "" => from_record("MY_GRIDSQUARE"),
+        locator => locator.to_string(),
+    };
+    let band = first
+        .and_then(Record::band)
+        .and_then(|band| BANDS.iter().find(|(_, b)| b.eq_ignore_ascii_case(band)))
+        .map(|(edi, _)| *edi)
+        .unwrap_or_default();
+    let mut dates: Vec<&str> = file.records.iter().filter_map(Record::qso_date).collect();
+    dates.sort_unstable();
+
+    let mut s = String::new();
+    s.push_str("[REG1TEST;1]\n");
+    let _ = writeln!(s, "TName={}", options.contest);
+    let _ = writeln!(
+        s,
+        "TDate={};{}",
+        dates.first().unwrap_or(&""),
+        dates.last().unwrap_or(&"")
+    );
+    let _ = writeln!(s, "PCall={}", callsign);
+    let _ = writeln!(s, "PWWLo={}", locator);
+    let _ = writeln!(s, "PBand={}", band);
+    for (tag, value) in &options.tags {
+        let _ = writeln!(s, "{}={}", tag, value);
+    }
+    let _ = writeln!(s, "[QSORecords;{}]", lines.len());
+    for line in lines {
+        s.push_str(&line);
+        s.push('\n');
+    }
+    s.push_str("[END; adif_parser]\n");
+    Ok(s)
+}
+
+/// Build the EDI QSO line for a record
+fn qso_line(record: &Record) -> core::result::Result<String, String> {
+    let value = |name: &str| record.get_value(name).map(str::trim).unwrap_or_default();
+
+    let date = value("QSO_DATE");
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid QSO_DATE '{}'", date));
+    }
+    let time = value("TIME_ON");
+    if time.len() < 4 || !time.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid TIME_ON '{}'", time));
+    }
+    let call = value("CALL");
+    if call.is_empty() {
+        return Err("missing CALL".to_string());
+    }
+    let mode = value("MODE");
+    let mode = match MODES.iter().position(|m| m.eq_ignore_ascii_case(mode)) {
+        Some(code) if !mode.is_empty() => code.to_string(),
+        _ => String::new(),
+    };
+    let number = |name: &str| match value(name) {
+        n if !n.is_empty() && n.len() < 3 && n.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{:0>3}", n)
+        }
+        n => n.to_string(),
+    };
+    let points = match value(POINTS_FIELD) {
+        "" => value("DISTANCE")
+            .parse::<f64>()
+            .map(|km| format!("{}", (km + 0.5) as u64))
+            .unwrap_or_default(),
+        points => points.to_string(),
+    };
+    let locator = value("GRIDSQUARE");
+
+    Ok(format!(
+        "{};{};{};{};{};{};{};{};{};{};{};;;;",
+        &date[2..],
+        &time[..4],
+        call.to_uppercase(),
+        mode,
+        value("RST_SENT"),
+        number("STX"),
+        value("RST_RCVD"),
+        number("SRX"),
+        value("SRX_STRING"),
+        &locator[..locator.len().min(6)].to_uppercase(),
+        points,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "[REG1TEST;1]\n\
+                       TName=IARU Region 1 VHF\n\
+                       TDate=20240907;20240908\n\
+                       PCall=OK1ABC\n\
+                       PWWLo=JN79FX\n\
+                       PBand=144 MHz\n\
+                       [Remarks]\n\
+                       Portable\n\
+                       [QSORecords;2]\n\
+                       240907;1423;OK2XYZ;2;599;001;579;005;;JN89AB;123;;N;;\n\
+                       240907;1501;dl1aaa;1;59;002;59;017;BY;JO60LA;;;;;\n\
+                       [END; Test]\n";
+
+    #[test]
+    fn test_parse_edi() {
+        let file = parse_edi(LOG).unwrap();
+
+        assert_eq!(file.len(), 2);
+        assert!(file.header.preamble.contains("[Remarks]\nPortable\n"));
+        let first = &file.records[0];
+        assert_eq!(first.qso_date(), Some("20240907"));
+        assert_eq!(first.time_on(), Some("1423"));
+        assert_eq!(first.mode(), Some("CW"));
+        assert_eq!(first.get_value("STX"), Some("001"));
+        assert_eq!(first.get_value("GRIDSQUARE"), Some("JN89AB"));
+        assert_eq!(first.get_value("APP_EDI_POINTS"), Some("123"));
+        assert_eq!(first.get_value("STATION_CALLSIGN"), Some("OK1ABC"));
+        assert_eq!(first.get_value("MY_GRIDSQUARE"), Some("JN79FX"));
+        assert_eq!(first.band(), Some("2m"));
+        assert_eq!(first.get_value("CONTEST_ID"), Some("IARU Region 1 VHF"));
+        assert_eq!(file.records[1].call(), Some("DL1AAA"));
+        assert_eq!(file.records[1].get_value("SRX_STRING"), Some("BY"));
+        assert_eq!(file.records[1].get("APP_EDI_POINTS"), None);
+    }
+
+    #[test]
+    fn test_write_edi() {
+        let file = parse_edi(LOG).unwrap();
+        let options = EdiOptions::new("IARU Region 1 VHF", "").with_tag("PSect", "SINGLE");
+        let edi = write_edi(&file, &options).unwrap();
+
+        assert_eq!(
+            edi,
+            "[REG1TEST;1]\n\
+             TName=IARU Region 1 VHF\n\
+             TDate=20240907;20240907\n\
+             PCall=OK1ABC\n\
+             PWWLo=JN79FX\n\
+             PBand=144 MHz\n\
+             PSect=SINGLE\n\
+             [QSORecords;2]\n\
+             240907;1423;OK2XYZ;2;599;001;579;005;;JN89AB;123;;;;\n\
+             240907;1501;DL1AAA;1;59;002;59;017;BY;JO60LA;;;;;\n\
+             [END; adif_parser]\n"
+        );
+        assert_eq!(
+            parse_edi(&edi).unwrap().records[1].fields,
+            file.records[1].fields
+        );
+    }
+
+    #[test]
+    fn test_edi_errors() {
+        assert!(matches!(
+            parse_edi("[REG1TEST;1]\n[QSORecords;1]\n2409;1423;OK2XYZ;;;;;;;;\n"),
+            Err(AdifError::ParseError { position: 28, .. })
+        ));
+        assert!(parse_edi("TName=Test\n").is_err());
+
+        let mut file = AdifFile::new();
+        file.records.push(Record::new());
+        assert!(matches!(
+            write_edi(&file, &EdiOptions::default()),
+            Err(AdifError::Conversion { record: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Character encoding of ADI byte input
+///
+/// ADI field lengths count bytes in the file's own encoding, so values are
+/// decoded individually after the data specifiers have been parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// UTF-8; invalid byte sequences are an error
+    #[default]
+    Utf8,
+    /// UTF-8; invalid byte sequences are replaced with U+FFFD
+    Utf8Lossy,
+    /// ISO-8859-1 (Latin-1)
+    Latin1,
+    /// Windows-1252, the usual encoding of logs from Windows loggers
+    Windows1252,
+    /// UTF-8 where valid, otherwise Windows-1252, decided per value
+    Auto,
+}
+
+/// Characters for Windows-1252 bytes 0x80-0x9F. Unassigned bytes map to
+/// the matching C1 control character, as Latin-1 does.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+impl Encoding {
+    /// Decode bytes to a string.
+    ///
+    /// Fails only for strict UTF-8, returning the offset of the first
+    /// invalid byte.
+    pub fn decode(self, bytes: Vec<u8>) -> Result<String, usize> {
+        match self {
+            Encoding::Utf8 => String::from_utf8(bytes).map_err(|e| e.utf8_error().valid_up_to()),
+            Encoding::Utf8Lossy => Ok(match String::from_utf8(bytes) {
+                Ok(s) => s,
+                Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+            }),
+            Encoding::Latin1 => Ok(bytes.iter().map(|&b| char::from(b)).collect()),
+            Encoding::Windows1252 => Ok(decode_windows_1252(&bytes)),
+            Encoding::Auto => Ok(match String::from_utf8(bytes) {
+                Ok(s) => s,
+                Err(e) => decode_windows_1252(e.as_bytes()),
+            }),
+        }
+    }
+}
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+            _ => char::from(b),
+        })
+        .collect()
+}
+
+/// Quote and escape a string for JSON output written without serde_json
+pub(crate) fn json_string(value: &str) -> String {
+    let mut s = String::with_capacity(value.len() + 2);
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(s, "\\u{:04x}", c as u32);
+            }
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latin1() {
+        let bytes = b"M\xfcller \x80".to_vec();
+        assert_eq!(Encoding::Latin1.decode(bytes).unwrap(), "Müller \u{80}");
+    }
+
+    #[test]
+    fn test_windows_1252() {
+        let bytes = b"Fran\xe7ois \x80 \x93hi\x94".to_vec();
+        assert_eq!(
+            Encoding::Windows1252.decode(bytes).unwrap(),
+            "François € \u{201C}hi\u{201D}"
+        );
+    }
+
+    #[test]
+    fn test_auto() {
+        assert_eq!(
+            Encoding::Auto.decode("José".as_bytes().to_vec()).unwrap(),
+            "José"
+        );
+        assert_eq!(Encoding::Auto.decode(b"Jos\xe9".to_vec()).unwrap(), "José");
+    }
+
+    #[test]
+    fn test_strict_utf8_error_offset() {
+        assert_eq!(Encoding::Utf8.decode(b"ab\xffc".to_vec()), Err(2));
+    }
+}
@@ -0,0 +1,75 @@
+//! Definition of enums for the enumerations in the ADIF specification
+
+/// Define an enum for an ADIF enumeration, with `as_str`, `ALL`, `Display`,
+/// `ToField` and case-insensitive `FromStr` and `FromField`
+///
+/// Parse errors are [`AdifError::InvalidField`](crate::AdifError) for the
+/// named field.
+macro_rules! enumeration {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident for $field:literal {
+            $($variant:ident => $text:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[non_exhaustive]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $text, "`")]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// All values, in the order of the specification
+            pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            /// Get the value as written in ADIF
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $($name::$variant => $text,)*
+                }
+            }
+        }
+
+        impl crate::from_field::FromField for $name {
+            fn from_field(value: &str) -> core::result::Result<Self, alloc::string::String> {
+                let value = value.trim();
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|v| v.as_str().eq_ignore_ascii_case(value))
+                    .ok_or_else(|| alloc::format!("unknown value '{}'", value))
+            }
+        }
+
+        impl crate::from_field::ToField for $name {
+            fn to_field(&self) -> alloc::string::String {
+                alloc::string::String::from(self.as_str())
+            }
+        }
+
+        impl core::str::FromStr for $name {
+            type Err = crate::error::AdifError;
+
+            fn from_str(s: &str) -> crate::error::Result<Self> {
+                <Self as crate::from_field::FromField>::from_field(s).map_err(|message| {
+                    crate::error::AdifError::InvalidField {
+                        field: $field.into(),
+                        message,
+                    }
+                })
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+pub(crate) use enumeration;
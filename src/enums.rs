@@ -0,0 +1,171 @@
+//! Enums for the smaller ADIF enumerations
+
+use crate::enumeration::enumeration;
+use crate::error::Result;
+use crate::types::Record;
+
+enumeration! {
+    /// A signal path, from the ADIF Antenna Path enumeration
+    pub enum AntPath for "ANT_PATH" {
+        Grayline => "G",
+        Other => "O",
+        ShortPath => "S",
+        LongPath => "L",
+    }
+}
+
+enumeration! {
+    /// Whether a QSO was completed, from the ADIF QSO Complete enumeration
+    pub enum QsoComplete for "QSO_COMPLETE" {
+        Yes => "Y",
+        No => "N",
+        NotHeard => "NIL",
+        Uncertain => "?",
+    }
+}
+
+enumeration! {
+    /// A means of confirming a QSO, from the ADIF QSL Medium enumeration
+    /// used in CREDIT_SUBMITTED and CREDIT_GRANTED
+    pub enum QslMedium for "CREDIT_GRANTED" {
+        Card => "CARD",
+        Eqsl => "EQSL",
+        Lotw => "LOTW",
+    }
+}
+
+enumeration! {
+    /// A kind of Morse key, from the ADIF Morse Key Type enumeration
+    pub enum MorseKeyType for "MORSE_KEY_TYPE" {
+        StraightKey => "SK",
+        Sideswiper => "SS",
+        Bug => "BUG",
+        FullyAutomatic => "FAB",
+        SinglePaddle => "SP",
+        DualPaddle => "DP",
+        Computer => "CPU",
+    }
+}
+
+enumeration! {
+    /// A special region of a DXCC entity, from the ADIF Region enumeration
+    pub enum Region for "REGION" {
+        NoRegion => "NONE",
+        ItuVienna => "IV",
+        AfricanItaly => "AI",
+        Sicily => "SY",
+        BearIsland => "BI",
+        ShetlandIslands => "SI",
+        Kosovo => "KO",
+        EuropeanTurkey => "ET",
+    }
+}
+
+enumeration! {
+    /// Whether a QSO was uploaded to an online service, from the ADIF QSO
+    /// Upload Status enumeration
+    pub enum UploadStatus for "CLUBLOG_QSO_UPLOAD_STATUS" {
+        Yes => "Y",
+        No => "N",
+        Modified => "M",
+    }
+}
+
+enumeration! {
+    /// Whether a QSO was downloaded from an online service, from the ADIF
+    /// QSO Download Status enumeration
+    pub enum DownloadStatus for "QRZCOM_QSO_DOWNLOAD_STATUS" {
+        Yes => "Y",
+        No => "N",
+        Ignore => "I",
+    }
+}
+
+impl Record {
+    /// Get the signal path, or `None` if ANT_PATH is missing or empty
+    pub fn ant_path(&self) -> Option<Result<AntPath>> {
+        self.get_parsed("ANT_PATH")
+    }
+
+    /// Get whether the QSO was completed
+    pub fn qso_complete(&self) -> Option<Result<QsoComplete>> {
+        self.get_parsed("QSO_COMPLETE")
+    }
+
+    /// Get the contacted operator's Morse key
+    pub fn morse_key_type(&self) -> Option<Result<MorseKeyType>> {
+        self.get_parsed("MORSE_KEY_TYPE")
+    }
+
+    /// Get the logging operator's Morse key
+    pub fn my_morse_key_type(&self) -> Option<Result<MorseKeyType>> {
+        self.get_parsed("MY_MORSE_KEY_TYPE")
+    }
+
+    /// Get the special region of the contacted station's entity
+    pub fn region(&self) -> Option<Result<Region>> {
+        self.get_parsed("REGION")
+    }
+
+    /// Get whether the QSO was uploaded to Club Log
+    pub fn clublog_qso_upload_status(&self) -> Option<Result<UploadStatus>> {
+        self.get_parsed("CLUBLOG_QSO_UPLOAD_STATUS")
+    }
+
+    /// Get whether the QSO was uploaded to HRDLog.net
+    pub fn hrdlog_qso_upload_status(&self) -> Option<Result<UploadStatus>> {
+        self.get_parsed("HRDLOG_QSO_UPLOAD_STATUS")
+    }
+
+    /// Get whether the QSO was uploaded to QRZ.com
+    pub fn qrzcom_qso_upload_status(&self) -> Option<Result<UploadStatus>> {
+        self.get_parsed("QRZCOM_QSO_UPLOAD_STATUS")
+    }
+
+    /// Get whether the QSO was downloaded from QRZ.com
+    pub fn qrzcom_qso_download_status(&self) -> Option<Result<DownloadStatus>> {
+        self.get_parsed("QRZCOM_QSO_DOWNLOAD_STATUS")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdifError, parse_adi};
+
+    #[test]
+    fn test_small_enumerations() {
+        let file = parse_adi(
+            "<ANT_PATH:1>l<QSO_COMPLETE:3>NIL<MY_MORSE_KEY_TYPE:3>bug<REGION:2>KO\
+             <CLUBLOG_QSO_UPLOAD_STATUS:1>M<QRZCOM_QSO_DOWNLOAD_STATUS:1>Y\
+             <MORSE_KEY_TYPE:6>cootie<EOR>",
+        )
+        .unwrap();
+        let record = &file.records[0];
+        assert_eq!(record.ant_path().unwrap().unwrap(), AntPath::LongPath);
+        assert_eq!(
+            record.qso_complete().unwrap().unwrap(),
+            QsoComplete::NotHeard
+        );
+        assert_eq!(
+            record.my_morse_key_type().unwrap().unwrap(),
+            MorseKeyType::Bug
+        );
+        assert_eq!(record.region().unwrap().unwrap(), Region::Kosovo);
+        assert_eq!(
+            record.clublog_qso_upload_status().unwrap().unwrap(),
+            UploadStatus::Modified
+        );
+        assert_eq!(
+            record.qrzcom_qso_download_status().unwrap().unwrap(),
+            DownloadStatus::Yes
+        );
+        assert!(matches!(
+            record.morse_key_type(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "MORSE_KEY_TYPE"
+        ));
+        assert!(record.hrdlog_qso_upload_status().is_none());
+        assert_eq!("lotw".parse::<QslMedium>().unwrap(), QslMedium::Lotw);
+        assert_eq!(QsoComplete::Uncertain.to_string(), "?");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::profile::{ProfileExport, check_date, check_time, required, subset};
+use crate::types::{AdifFile, Record};
+use alloc::format;
+use alloc::string::String;
+
+/// Fields eQSL reads from an upload
+const FIELDS: &[&str] = &[
+    "CALL",
+    "QSO_DATE",
+    "TIME_ON",
+    "BAND",
+    "BAND_RX",
+    "FREQ",
+    "FREQ_RX",
+    "MODE",
+    "SUBMODE",
+    "RST_SENT",
+    "PROP_MODE",
+    "SAT_NAME",
+    "SAT_MODE",
+    "QSLMSG",
+    "APP_EQSL_QTH_NICKNAME",
+];
+
+/// Export records for upload to eQSL as a single `eqsl.adi`
+pub(crate) fn export(file: &AdifFile) -> ProfileExport {
+    let mut export = ProfileExport::default();
+    for (index, record) in file.records.iter().enumerate() {
+        match check(record) {
+            Ok(()) => export
+                .file_mut("eqsl.adi".into(), file)
+                .records
+                .push(subset(record, FIELDS)),
+            Err(reason) => export.reject(index, reason),
+        }
+    }
+    export
+}
+
+/// Check a record for the fields eQSL requires
+fn check(record: &Record) -> core::result::Result<(), String> {
+    let call = required(record, "CALL")?;
+    if call.contains(char::is_whitespace) {
+        return Err(format!("invalid CALL '{}'", call));
+    }
+    check_date(required(record, "QSO_DATE")?)?;
+    check_time(required(record, "TIME_ON")?)?;
+    required(record, "BAND")?;
+    required(record, "MODE")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_adi;
+    use crate::profile::ExportProfile;
+
+    #[test]
+    fn test_export_eqsl() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240601<TIME_ON:6>120000<BAND:3>20m<MODE:3>FT8\
+             <RST_SENT:3>-10<NAME:5>Hiram<QSL_SENT:1>Y<QSLMSG:3>TNX<EOR>\
+             <CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:3>120<BAND:3>20m<MODE:2>CW<EOR>\
+             <CALL:4>K2AB<QSO_DATE:8>20240601<TIME_ON:4>1200<MODE:2>CW<EOR>",
+        )
+        .unwrap();
+        let export = ExportProfile::Eqsl.export(&file);
+        assert_eq!(export.files.len(), 1);
+        assert_eq!(export.files[0].name, "eqsl.adi");
+        assert_eq!(
+            export.files[0].file.records[0].to_adi_string(),
+            "<CALL:4>W1AW<QSO_DATE:8>20240601<TIME_ON:6>120000<BAND:3>20m<MODE:3>FT8\
+             <RST_SENT:3>-10<QSLMSG:3>TNX<EOR>\n"
+        );
+        let reasons: Vec<_> = export.rejected.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            reasons,
+            ["record 1: invalid TIME_ON '120'", "record 2: missing BAND"]
+        );
+    }
+}
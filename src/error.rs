@@ -1,11 +1,54 @@
+use crate::options::Limit;
+use alloc::string::String;
 use thiserror::Error;
 
 /// Errors that can occur during ADIF parsing
 #[derive(Error, Debug)]
 pub enum AdifError {
+    #[cfg(feature = "std")]
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[cfg(feature = "csv")]
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[cfg(feature = "json")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[cfg(feature = "adx")]
+    #[error("Invalid ADX: {} schema violation(s)", .0.len())]
+    Schema(Vec<crate::schema::SchemaViolation>),
+
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[cfg(feature = "polars")]
+    #[error("Polars error: {0}")]
+    Polars(#[from] polars::error::PolarsError),
+
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[cfg(feature = "template")]
+    #[error("Template error: {0}")]
+    Template(#[from] minijinja::Error),
+
+    #[cfg(feature = "xlsx")]
+    #[error("XLSX error: {0}")]
+    Xlsx(#[from] rust_xlsxwriter::XlsxError),
+
+    #[cfg(feature = "yaml")]
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml_ng::Error),
+
     #[error("Invalid data specifier at position {position}: {message}")]
     InvalidDataSpecifier { position: usize, message: String },
 
@@ -24,6 +67,34 @@ pub enum AdifError {
 
     #[error("Parse error at position {position}: {message}")]
     ParseError { position: usize, message: String },
+
+    #[error("Unsupported format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("Missing field {0}")]
+    MissingField(String),
+
+    #[error("Invalid {field}: {message}")]
+    InvalidField { field: String, message: String },
+
+    #[error("Unknown field {name} at position {position}")]
+    UnknownField { position: usize, name: String },
+
+    #[error("International text in field {name} at position {position}, which ADI doesn't allow")]
+    IntlText { position: usize, name: String },
+
+    #[error("Duplicate field {name} at position {position}")]
+    DuplicateField { position: usize, name: String },
+
+    #[error("Cannot convert record {record}: {message}")]
+    Conversion { record: usize, message: String },
+
+    #[error("Limit exceeded at position {position}: {limit} is more than {max}")]
+    LimitExceeded {
+        position: usize,
+        limit: Limit,
+        max: usize,
+    },
 }
 
-pub type Result<T> = std::result::Result<T, AdifError>;
+pub type Result<T> = core::result::Result<T, AdifError>;
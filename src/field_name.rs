@@ -0,0 +1,495 @@
+use crate::types::DataType;
+use crate::validate::FieldSpec;
+use alloc::borrow::{Borrow, Cow};
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// Upper bound on the length of a known field name
+const MAX_KNOWN_LEN: usize = 32;
+
+macro_rules! field_names {
+    ($($variant:ident => $name:literal,)*) => {
+        /// An ADIF field name
+        ///
+        /// Fields defined by the ADIF specification are plain enum variants,
+        /// so storing and matching them needs no allocation. Any other name
+        /// (`APP_`, `USERDEFn`, user-defined fields, or names that weren't
+        /// uppercased) is kept in [`FieldName::Other`], shared through an
+        /// [`Interner`] where possible.
+        ///
+        /// Names compare and hash as their text, so `Other("CALL")` equals
+        /// [`FieldName::Call`].
+        #[derive(Debug, Clone)]
+        #[non_exhaustive]
+        pub enum FieldName {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*
+            /// Any name not defined by the specification
+            Other(Arc<str>),
+        }
+
+        /// Names of all fields defined by the specification, in order
+        #[cfg(any(test, feature = "sqlite"))]
+        pub(crate) const KNOWN_NAMES: &[&str] = &[$($name,)*];
+
+        impl FieldName {
+            /// Get the name as text
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(FieldName::$variant => $name,)*
+                    FieldName::Other(name) => name,
+                }
+            }
+
+            /// Look up a field defined by the ADIF specification (exact case)
+            fn from_upper(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(FieldName::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+field_names! {
+    AIndex => "A_INDEX",
+    Address => "ADDRESS",
+    AddressIntl => "ADDRESS_INTL",
+    AdifVer => "ADIF_VER",
+    Age => "AGE",
+    Altitude => "ALTITUDE",
+    AntAz => "ANT_AZ",
+    AntEl => "ANT_EL",
+    AntPath => "ANT_PATH",
+    ArrlSect => "ARRL_SECT",
+    Award => "AWARD",
+    AwardGranted => "AWARD_GRANTED",
+    AwardSubmitted => "AWARD_SUBMITTED",
+    Band => "BAND",
+    BandRx => "BAND_RX",
+    Call => "CALL",
+    Check => "CHECK",
+    Class => "CLASS",
+    ClublogQsoUploadDate => "CLUBLOG_QSO_UPLOAD_DATE",
+    ClublogQsoUploadStatus => "CLUBLOG_QSO_UPLOAD_STATUS",
+    Cnty => "CNTY",
+    CntyAlt => "CNTY_ALT",
+    Comment => "COMMENT",
+    CommentIntl => "COMMENT_INTL",
+    Cont => "CONT",
+    ContactedOp => "CONTACTED_OP",
+    ContestId => "CONTEST_ID",
+    Country => "COUNTRY",
+    CountryIntl => "COUNTRY_INTL",
+    Cqz => "CQZ",
+    CreatedTimestamp => "CREATED_TIMESTAMP",
+    CreditGranted => "CREDIT_GRANTED",
+    CreditSubmitted => "CREDIT_SUBMITTED",
+    DarcDok => "DARC_DOK",
+    DclQslrdate => "DCL_QSLRDATE",
+    DclQslsdate => "DCL_QSLSDATE",
+    DclQslRcvd => "DCL_QSL_RCVD",
+    DclQslSent => "DCL_QSL_SENT",
+    Distance => "DISTANCE",
+    Dxcc => "DXCC",
+    Email => "EMAIL",
+    EqCall => "EQ_CALL",
+    EqslAg => "EQSL_AG",
+    EqslQslrdate => "EQSL_QSLRDATE",
+    EqslQslsdate => "EQSL_QSLSDATE",
+    EqslQslRcvd => "EQSL_QSL_RCVD",
+    EqslQslSent => "EQSL_QSL_SENT",
+    Fists => "FISTS",
+    FistsCc => "FISTS_CC",
+    ForceInit => "FORCE_INIT",
+    Freq => "FREQ",
+    FreqRx => "FREQ_RX",
+    Gridsquare => "GRIDSQUARE",
+    GridsquareExt => "GRIDSQUARE_EXT",
+    GuestOp => "GUEST_OP",
+    HamlogeuQsoUploadDate => "HAMLOGEU_QSO_UPLOAD_DATE",
+    HamlogeuQsoUploadStatus => "HAMLOGEU_QSO_UPLOAD_STATUS",
+    HamqthQsoUploadDate => "HAMQTH_QSO_UPLOAD_DATE",
+    HamqthQsoUploadStatus => "HAMQTH_QSO_UPLOAD_STATUS",
+    HrdlogQsoUploadDate => "HRDLOG_QSO_UPLOAD_DATE",
+    HrdlogQsoUploadStatus => "HRDLOG_QSO_UPLOAD_STATUS",
+    Iota => "IOTA",
+    IotaIslandId => "IOTA_ISLAND_ID",
+    Ituz => "ITUZ",
+    KIndex => "K_INDEX",
+    Lat => "LAT",
+    Lon => "LON",
+    LotwQslrdate => "LOTW_QSLRDATE",
+    LotwQslsdate => "LOTW_QSLSDATE",
+    LotwQslRcvd => "LOTW_QSL_RCVD",
+    LotwQslSent => "LOTW_QSL_SENT",
+    MaxBursts => "MAX_BURSTS",
+    Mode => "MODE",
+    MorseKeyInfo => "MORSE_KEY_INFO",
+    MorseKeyType => "MORSE_KEY_TYPE",
+    MsShower => "MS_SHOWER",
+    MyAltitude => "MY_ALTITUDE",
+    MyAntenna => "MY_ANTENNA",
+    MyAntennaIntl => "MY_ANTENNA_INTL",
+    MyArrlSect => "MY_ARRL_SECT",
+    MyCity => "MY_CITY",
+    MyCityIntl => "MY_CITY_INTL",
+    MyCnty => "MY_CNTY",
+    MyCntyAlt => "MY_CNTY_ALT",
+    MyCountry => "MY_COUNTRY",
+    MyCountryIntl => "MY_COUNTRY_INTL",
+    MyCqZone => "MY_CQ_ZONE",
+    MyDarcDok => "MY_DARC_DOK",
+    MyDxcc => "MY_DXCC",
+    MyFists => "MY_FISTS",
+    MyGridsquare => "MY_GRIDSQUARE",
+    MyGridsquareExt => "MY_GRIDSQUARE_EXT",
+    MyIota => "MY_IOTA",
+    MyIotaIslandId => "MY_IOTA_ISLAND_ID",
+    MyItuZone => "MY_ITU_ZONE",
+    MyLat => "MY_LAT",
+    MyLon => "MY_LON",
+    MyMorseKeyInfo => "MY_MORSE_KEY_INFO",
+    MyMorseKeyType => "MY_MORSE_KEY_TYPE",
+    MyName => "MY_NAME",
+    MyNameIntl => "MY_NAME_INTL",
+    MyPostalCode => "MY_POSTAL_CODE",
+    MyPostalCodeIntl => "MY_POSTAL_CODE_INTL",
+    MyPotaRef => "MY_POTA_REF",
+    MyRig => "MY_RIG",
+    MyRigIntl => "MY_RIG_INTL",
+    MySig => "MY_SIG",
+    MySigInfo => "MY_SIG_INFO",
+    MySigInfoIntl => "MY_SIG_INFO_INTL",
+    MySigIntl => "MY_SIG_INTL",
+    MySotaRef => "MY_SOTA_REF",
+    MyState => "MY_STATE",
+    MyStreet => "MY_STREET",
+    MyStreetIntl => "MY_STREET_INTL",
+    MyUsacaCounties => "MY_USACA_COUNTIES",
+    MyVuccGrids => "MY_VUCC_GRIDS",
+    MyWwffRef => "MY_WWFF_REF",
+    Name => "NAME",
+    NameIntl => "NAME_INTL",
+    Notes => "NOTES",
+    NotesIntl => "NOTES_INTL",
+    NrBursts => "NR_BURSTS",
+    NrPings => "NR_PINGS",
+    Operator => "OPERATOR",
+    OwnerCallsign => "OWNER_CALLSIGN",
+    Pfx => "PFX",
+    PotaRef => "POTA_REF",
+    Precedence => "PRECEDENCE",
+    ProgramId => "PROGRAMID",
+    ProgramVersion => "PROGRAMVERSION",
+    PropMode => "PROP_MODE",
+    PublicKey => "PUBLIC_KEY",
+    QrzcomQsoDownloadDate => "QRZCOM_QSO_DOWNLOAD_DATE",
+    QrzcomQsoDownloadStatus => "QRZCOM_QSO_DOWNLOAD_STATUS",
+    QrzcomQsoUploadDate => "QRZCOM_QSO_UPLOAD_DATE",
+    QrzcomQsoUploadStatus => "QRZCOM_QSO_UPLOAD_STATUS",
+    Qslmsg => "QSLMSG",
+    QslmsgIntl => "QSLMSG_INTL",
+    QslmsgRcvd => "QSLMSG_RCVD",
+    Qslrdate => "QSLRDATE",
+    Qslsdate => "QSLSDATE",
+    QslRcvd => "QSL_RCVD",
+    QslRcvdVia => "QSL_RCVD_VIA",
+    QslSent => "QSL_SENT",
+    QslSentVia => "QSL_SENT_VIA",
+    QslVia => "QSL_VIA",
+    QsoComplete => "QSO_COMPLETE",
+    QsoDate => "QSO_DATE",
+    QsoDateOff => "QSO_DATE_OFF",
+    QsoRandom => "QSO_RANDOM",
+    Qth => "QTH",
+    QthIntl => "QTH_INTL",
+    Region => "REGION",
+    Rig => "RIG",
+    RigIntl => "RIG_INTL",
+    RstRcvd => "RST_RCVD",
+    RstSent => "RST_SENT",
+    RxPwr => "RX_PWR",
+    SatMode => "SAT_MODE",
+    SatName => "SAT_NAME",
+    Sfi => "SFI",
+    Sig => "SIG",
+    SigInfo => "SIG_INFO",
+    SigInfoIntl => "SIG_INFO_INTL",
+    SigIntl => "SIG_INTL",
+    SilentKey => "SILENT_KEY",
+    Skcc => "SKCC",
+    SotaRef => "SOTA_REF",
+    Srx => "SRX",
+    SrxString => "SRX_STRING",
+    State => "STATE",
+    StationCallsign => "STATION_CALLSIGN",
+    Stx => "STX",
+    StxString => "STX_STRING",
+    Submode => "SUBMODE",
+    Swl => "SWL",
+    TenTen => "TEN_TEN",
+    TimeOff => "TIME_OFF",
+    TimeOn => "TIME_ON",
+    TxPwr => "TX_PWR",
+    Uksmg => "UKSMG",
+    UsacaCounties => "USACA_COUNTIES",
+    VeProv => "VE_PROV",
+    VuccGrids => "VUCC_GRIDS",
+    Web => "WEB",
+    WwffRef => "WWFF_REF",
+}
+
+impl FieldName {
+    /// Look up a field defined by the ADIF specification (case-insensitive)
+    pub fn known(name: &str) -> Option<Self> {
+        if name.len() > MAX_KNOWN_LEN {
+            return None;
+        }
+        let mut buf = [0u8; MAX_KNOWN_LEN];
+        let upper = &mut buf[..name.len()];
+        upper.copy_from_slice(name.as_bytes());
+        upper.make_ascii_uppercase();
+        Self::from_upper(core::str::from_utf8(upper).ok()?)
+    }
+
+    /// Check if this name is defined by the ADIF specification
+    pub fn is_known(&self) -> bool {
+        !matches!(self, FieldName::Other(_))
+    }
+
+    /// Check if two names refer to the same field, ignoring ASCII case
+    pub fn is_same_field(&self, other: &FieldName) -> bool {
+        if self.is_known() && other.is_known() {
+            core::mem::discriminant(self) == core::mem::discriminant(other)
+        } else {
+            self.as_str().eq_ignore_ascii_case(other.as_str())
+        }
+    }
+
+    /// Get the data type the ADIF specification gives this field, as
+    /// listed by [`FieldSpec`]
+    ///
+    /// Only dates, times, numbers, booleans and locations are reported;
+    /// other fields (and unknown names) are [`DataType::Unspecified`].
+    pub fn data_type(&self) -> DataType {
+        FieldSpec::find(self.as_str())
+            .map_or(DataType::Unspecified, |spec| spec.field_type().data_type())
+    }
+}
+
+impl Deref for FieldName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for FieldName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for FieldName {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for FieldName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for FieldName {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (FieldName::Other(a), FieldName::Other(b)) => a == b,
+            (FieldName::Other(_), _) | (_, FieldName::Other(_)) => self.as_str() == other.as_str(),
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for FieldName {}
+
+impl Hash for FieldName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for FieldName {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FieldName {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialEq<str> for FieldName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for FieldName {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for FieldName {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl From<&str> for FieldName {
+    fn from(name: &str) -> Self {
+        FieldName::from_upper(name).unwrap_or_else(|| FieldName::Other(name.into()))
+    }
+}
+
+impl From<String> for FieldName {
+    fn from(name: String) -> Self {
+        FieldName::from_upper(&name).unwrap_or_else(|| FieldName::Other(name.into()))
+    }
+}
+
+impl From<Cow<'_, str>> for FieldName {
+    fn from(name: Cow<'_, str>) -> Self {
+        match name {
+            Cow::Borrowed(name) => FieldName::from(name),
+            Cow::Owned(name) => FieldName::from(name),
+        }
+    }
+}
+
+impl From<FieldName> for String {
+    fn from(name: FieldName) -> Self {
+        name.as_str().to_string()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FieldName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(FieldName::from(name))
+    }
+}
+
+/// Shares one allocation between repeated non-standard field names
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    names: BTreeSet<Arc<str>>,
+}
+
+impl Interner {
+    /// Create an empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the name for `name`, reusing an earlier allocation if possible
+    pub fn intern(&mut self, name: &str) -> FieldName {
+        if let Some(known) = FieldName::from_upper(name) {
+            return known;
+        }
+        if let Some(existing) = self.names.get(name) {
+            return FieldName::Other(existing.clone());
+        }
+        let name: Arc<str> = name.into();
+        self.names.insert(name.clone());
+        FieldName::Other(name)
+    }
+
+    /// Get the number of distinct non-standard names seen
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Check if no non-standard names have been interned
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_names() {
+        assert!(matches!(FieldName::from("CALL"), FieldName::Call));
+        assert!(matches!(
+            FieldName::known("gridsquare_ext"),
+            Some(FieldName::GridsquareExt)
+        ));
+        assert_eq!(FieldName::known("APP_N1MM_ID"), None);
+        assert_eq!(FieldName::known(&"X".repeat(100)), None);
+        assert_eq!(FieldName::TimeOn.as_str(), "TIME_ON");
+        assert_eq!(FieldName::QsoDate.data_type(), DataType::Date);
+        assert_eq!(FieldName::Freq.data_type(), DataType::Number);
+        assert_eq!(FieldName::EqslAg.data_type(), DataType::Boolean);
+        assert_eq!(FieldName::from("APP_X").data_type(), DataType::Unspecified);
+
+        // Exact-case conversion keeps non-uppercase names as written
+        let name = FieldName::from("Call");
+        assert!(!name.is_known());
+        assert_eq!(name, "Call");
+        assert!(name.is_same_field(&FieldName::Call));
+    }
+
+    #[test]
+    fn test_matches_field_table() {
+        let mut names = KNOWN_NAMES.to_vec();
+        names.sort_unstable();
+        let specs: Vec<&str> = FieldSpec::all().map(FieldSpec::name).collect();
+        assert_eq!(names, specs);
+    }
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(FieldName::Other("CALL".into()), FieldName::Call);
+        assert_ne!(FieldName::Call, FieldName::Band);
+        assert_eq!(FieldName::Mode, "MODE");
+        assert!(FieldName::Band < FieldName::Call);
+    }
+
+    #[test]
+    fn test_interner() {
+        let mut interner = Interner::new();
+        let a = interner.intern("APP_N1MM_ID");
+        let b = interner.intern("APP_N1MM_ID");
+        assert!(matches!(interner.intern("QSO_DATE"), FieldName::QsoDate));
+        assert_eq!(interner.len(), 1);
+
+        match (a, b) {
+            (FieldName::Other(a), FieldName::Other(b)) => assert!(Arc::ptr_eq(&a, &b)),
+            _ => panic!("expected interned names"),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::detect::{Format, detect_format, parse_auto};
+use crate::error::{AdifError, Result};
+use crate::parser::parse_adi_bytes;
+use crate::types::AdifFile;
+use std::fs;
+use std::path::Path;
+
+/// Magic bytes at the start of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+impl AdifFile {
+    /// Read and parse an ADI or ADX file from disk.
+    ///
+    /// Gzip-compressed files (as shipped by LoTW and some loggers) are
+    /// detected by their magic bytes and decompressed transparently when
+    /// the `gzip` feature is enabled.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<AdifFile> {
+        let content = fs::read(path)?;
+        let content = decompress(content)?;
+        match detect_format(&content) {
+            Some(Format::Adx) => {
+                let text = String::from_utf8(content).map_err(|e| AdifError::ParseError {
+                    position: e.utf8_error().valid_up_to(),
+                    message: "Invalid UTF-8".to_string(),
+                })?;
+                parse_auto(&text).map(|(file, _)| file)
+            }
+            _ => parse_adi_bytes(&content),
+        }
+    }
+}
+
+/// Decompress gzip data, passing anything else through unchanged
+fn decompress(content: Vec<u8>) -> Result<Vec<u8>> {
+    if !content.starts_with(&GZIP_MAGIC) {
+        return Ok(content);
+    }
+
+    #[cfg(feature = "gzip")]
+    {
+        use std::io::Read;
+
+        let mut decoded = Vec::new();
+        flate2::read::MultiGzDecoder::new(content.as_slice()).read_to_end(&mut decoded)?;
+        Ok(decoded)
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    {
+        Err(AdifError::ParseError {
+            position: 0,
+            message: "gzip-compressed input requires the gzip feature".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const SAMPLE: &str = "Log\n<ADIF_VER:5>3.1.6<EOH>\n<CALL:4>W1AW<EOR>\n<CALL:4>K1AB<EOR>\n";
+
+    #[test]
+    fn test_from_path() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE.as_bytes()).unwrap();
+
+        let result = AdifFile::from_path(file.path()).unwrap();
+        assert_eq!(result.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(result.len(), 2);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_from_path_gzip() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(SAMPLE.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&compressed).unwrap();
+
+        let result = AdifFile::from_path(file.path()).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.records[1].call(), Some("K1AB"));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_corrupt_gzip() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0x1f, 0x8b, 0x00, 0x01]).unwrap();
+
+        assert!(matches!(
+            AdifFile::from_path(file.path()),
+            Err(AdifError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        assert!(matches!(
+            AdifFile::from_path("/nonexistent/log.adi"),
+            Err(AdifError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "adx")]
+    #[test]
+    fn test_from_path_adx() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"<ADX><RECORDS><RECORD><CALL>W1AW</CALL></RECORD></RECORDS></ADX>")
+            .unwrap();
+
+        let result = AdifFile::from_path(file.path()).unwrap();
+        assert_eq!(result.records[0].call(), Some("W1AW"));
+    }
+}
@@ -0,0 +1,205 @@
+//! Automatic repair of common mistakes in logs
+
+use crate::band::Band;
+use crate::field_name::FieldName;
+use crate::frequency::Frequency;
+use crate::grid::GridSquare;
+use crate::types::{AdifFile, Field};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A change made by [`fix_common_issues`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Index of the record in the log, or `None` for the header
+    pub record: Option<usize>,
+    /// Name of the field changed
+    pub field: String,
+    /// The value before the change, or `None` if the field was added
+    pub old: Option<String>,
+    /// The value after the change
+    pub new: String,
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.record {
+            Some(record) => write!(f, "record {}: ", record)?,
+            None => f.write_str("header: ")?,
+        }
+        match &self.old {
+            Some(old) => write!(f, "{}: '{}' -> '{}'", self.field, old, self.new),
+            None => write!(f, "{}: added '{}'", self.field, self.new),
+        }
+    }
+}
+
+/// Repair common mistakes in a log, returning what was changed
+///
+/// This is opinionated and changes values in place:
+///
+/// - leading and trailing whitespace is trimmed from every value
+/// - call signs are put in upper case
+/// - locators are written with the field in upper case and the subsquares
+///   in lower case (`FN31pr`)
+/// - bands are written in lower case (`20m`)
+/// - TIME_ON and TIME_OFF with 3 or 5 digits get a leading zero
+/// - a missing BAND or BAND_RX is filled in from FREQ or FREQ_RX
+///
+/// Values that can't be repaired are left alone for [`validate`] to
+/// report.
+///
+/// [`validate`]: crate::validate
+pub fn fix_common_issues(file: &mut AdifFile) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+    let header = &mut file.header;
+    for index in 0..header.fields.len() {
+        if fix_field(&mut fixes, None, &mut header.fields[index]) {
+            let field = header.fields[index].clone();
+            header.sync(&field);
+        }
+    }
+    for (index, record) in file.records.iter_mut().enumerate() {
+        for field in &mut record.fields {
+            fix_field(&mut fixes, Some(index), field);
+        }
+        for (freq, band) in [("FREQ", "BAND"), ("FREQ_RX", "BAND_RX")] {
+            let old = record.get_value(band).map(str::to_string);
+            if old.as_ref().is_some_and(|b| !b.is_empty()) {
+                continue;
+            }
+            let Some(band_value) = record
+                .get_value(freq)
+                .and_then(|f| f.parse::<Frequency>().ok())
+                .and_then(Band::from_frequency)
+                .map(|b| b.to_string())
+            else {
+                continue;
+            };
+            record.set(band, &band_value);
+            fixes.push(Fix {
+                record: Some(index),
+                field: band.to_string(),
+                old,
+                new: band_value,
+            });
+        }
+    }
+    fixes
+}
+
+/// Repair a single value, noting any change, and return whether it changed
+fn fix_field(fixes: &mut Vec<Fix>, record: Option<usize>, field: &mut Field) -> bool {
+    let value = field.value.trim();
+    // Names are matched ignoring case, for logs parsed with preserve_case
+    let fixed = match FieldName::known(&field.name) {
+        // Call signs are written in upper case
+        Some(
+            FieldName::Call
+            | FieldName::ContactedOp
+            | FieldName::EqCall
+            | FieldName::Operator
+            | FieldName::OwnerCallsign
+            | FieldName::StationCallsign,
+        ) => value.to_ascii_uppercase(),
+        Some(FieldName::Gridsquare | FieldName::MyGridsquare) => locator(value),
+        Some(FieldName::VuccGrids | FieldName::MyVuccGrids) => value
+            .split(',')
+            .map(|grid| locator(grid.trim()))
+            .collect::<Vec<_>>()
+            .join(","),
+        Some(FieldName::GridsquareExt | FieldName::MyGridsquareExt) => value.to_ascii_lowercase(),
+        Some(FieldName::Band | FieldName::BandRx) => match value.parse::<Band>() {
+            Ok(band) => band.to_string(),
+            Err(_) => value.to_string(),
+        },
+        Some(FieldName::TimeOn | FieldName::TimeOff)
+            if matches!(value.len(), 3 | 5) && value.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            ["0", value].concat()
+        }
+        _ => value.to_string(),
+    };
+    if fixed != field.value {
+        let old = core::mem::replace(&mut field.value, fixed);
+        field.raw = None;
+        fixes.push(Fix {
+            record,
+            field: field.name.to_string(),
+            old: Some(old),
+            new: field.value.clone(),
+        });
+        return true;
+    }
+    false
+}
+
+/// Normalize a locator's case, leaving it alone if it isn't one
+fn locator(value: &str) -> String {
+    match value.parse::<GridSquare>() {
+        Ok(grid) => grid.to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_fix_common_issues() {
+        let mut file = parse_adi(
+            "<PROGRAMID:4>Foo <EOH>\
+             <CALL:6>w1aw/p<GRIDSQUARE:6>fn31PR<BAND:3>20M<TIME_ON:3>930<FREQ:6>14.074\
+             <NAME:3>Bob<EOR>\
+             <CALL:5> K1AB<VUCC_GRIDS:9>fn31,fn32<FREQ:5>7.030<TIME_OFF:5>93000<EOR>\
+             <CALL:4>N0CA<FREQ:4>27.1<BAND:3>11m<TIME_ON:4>2400<EOR>",
+        )
+        .unwrap();
+        let fixes: Vec<_> = fix_common_issues(&mut file)
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        assert_eq!(
+            fixes,
+            [
+                "header: PROGRAMID: 'Foo ' -> 'Foo'",
+                "record 0: CALL: 'w1aw/p' -> 'W1AW/P'",
+                "record 0: GRIDSQUARE: 'fn31PR' -> 'FN31pr'",
+                "record 0: BAND: '20M' -> '20m'",
+                "record 0: TIME_ON: '930' -> '0930'",
+                "record 1: CALL: ' K1AB' -> 'K1AB'",
+                "record 1: VUCC_GRIDS: 'fn31,fn32' -> 'FN31,FN32'",
+                "record 1: TIME_OFF: '93000' -> '093000'",
+                "record 1: BAND: added '40m'",
+            ]
+        );
+        assert_eq!(file.header.program_id.as_deref(), Some("Foo"));
+        assert_eq!(file.records[1].band(), Some("40m"));
+        assert_eq!(file.records[2].band(), Some("11m"));
+        assert!(fix_common_issues(&mut file).is_empty());
+    }
+
+    #[test]
+    fn test_fix_preserved_case() {
+        let options = crate::ParserOptions {
+            preserve_case: true,
+            ..Default::default()
+        };
+        let input = "<call:4>w1aw<Freq:6>14.074<band:0><EOR>";
+        let mut file = crate::parse_adi_with_options(input, &options).unwrap();
+        let fixes: Vec<_> = fix_common_issues(&mut file)
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        assert_eq!(
+            fixes,
+            [
+                "record 0: call: 'w1aw' -> 'W1AW'",
+                "record 0: BAND: '' -> '20m'",
+            ]
+        );
+    }
+}
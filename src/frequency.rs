@@ -0,0 +1,156 @@
+use crate::error::{AdifError, Result};
+use crate::from_field::{FromField, ToField};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+const MILLIHERTZ_PER_MHZ: u64 = 1_000_000_000;
+
+/// A frequency, as held in the ADIF FREQ and FREQ_RX fields
+///
+/// Frequencies are kept to the millihertz, so they compare by value:
+/// `14.074` and `14.0740` are the same frequency. They display in MHz
+/// without trailing zeros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Frequency {
+    millihertz: u64,
+}
+
+impl Frequency {
+    /// Create a frequency from a whole number of hertz
+    pub const fn from_hz(hz: u64) -> Self {
+        Frequency {
+            millihertz: hz.saturating_mul(1000),
+        }
+    }
+
+    /// Create a frequency in kilohertz, or `None` if it is negative or not finite
+    pub fn from_khz(khz: f64) -> Option<Self> {
+        Frequency::from_mhz(khz / 1e3)
+    }
+
+    /// Create a frequency in megahertz, or `None` if it is negative or not finite
+    pub fn from_mhz(mhz: f64) -> Option<Self> {
+        let millihertz = mhz * MILLIHERTZ_PER_MHZ as f64;
+        (millihertz.is_finite() && millihertz >= 0.0).then_some(Frequency {
+            millihertz: (millihertz + 0.5) as u64,
+        })
+    }
+
+    /// Get the frequency in hertz
+    pub fn hz(self) -> f64 {
+        self.millihertz as f64 / 1e3
+    }
+
+    /// Get the frequency in kilohertz
+    pub fn khz(self) -> f64 {
+        self.millihertz as f64 / 1e6
+    }
+
+    /// Get the frequency in megahertz
+    pub fn mhz(self) -> f64 {
+        self.millihertz as f64 / 1e9
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = AdifError;
+
+    /// Parse a frequency in MHz, such as `14.074`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+        let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !digits(whole) || !digits(fraction) {
+            return Err(invalid(format!("'{}' is not a frequency in MHz", s)));
+        }
+        if fraction.len() > 9 {
+            return Err(invalid(format!("'{}' is more precise than 1 mHz", s)));
+        }
+        let whole: u64 = if whole.is_empty() {
+            0
+        } else {
+            whole
+                .parse()
+                .map_err(|_| invalid(format!("'{}' is too large a frequency", s)))?
+        };
+        let fraction: u64 = format!("{:0<9}", fraction).parse().unwrap_or(0);
+        whole
+            .checked_mul(MILLIHERTZ_PER_MHZ)
+            .and_then(|w| w.checked_add(fraction))
+            .map(|millihertz| Frequency { millihertz })
+            .ok_or_else(|| invalid(format!("'{}' is too large a frequency", s)))
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.millihertz / MILLIHERTZ_PER_MHZ;
+        let fraction = format!("{:09}", self.millihertz % MILLIHERTZ_PER_MHZ);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{}", whole)
+        } else {
+            write!(f, "{}.{}", whole, fraction)
+        }
+    }
+}
+
+impl FromField for Frequency {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        match value.parse() {
+            Ok(frequency) => Ok(frequency),
+            Err(AdifError::InvalidField { message, .. }) => Err(message),
+            Err(other) => Err(format!("{}", other)),
+        }
+    }
+}
+
+impl ToField for Frequency {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn invalid(message: String) -> AdifError {
+    AdifError::InvalidField {
+        field: "Frequency".into(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse_frequency() {
+        let freq: Frequency = "14.074".parse().unwrap();
+        assert_eq!(freq, "14.0740".parse().unwrap());
+        assert_eq!(freq, Frequency::from_hz(14_074_000));
+        assert_eq!(freq.hz(), 14_074_000.0);
+        assert_eq!(freq.khz(), 14_074.0);
+        assert_eq!(freq.mhz(), 14.074);
+        assert!(freq < "14.0741".parse().unwrap());
+        assert_eq!(".5".parse::<Frequency>().unwrap().khz(), 500.0);
+        assert_eq!(
+            "10368.100000001".parse::<Frequency>().unwrap().to_string(),
+            "10368.100000001"
+        );
+        for bad in ["", ".", "-7", "7,1", "1e3", "1.0000000001"] {
+            assert!(bad.parse::<Frequency>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_display_frequency() {
+        assert_eq!(Frequency::from_hz(7_000_000).to_string(), "7");
+        assert_eq!(Frequency::from_hz(1_840_500).to_string(), "1.8405");
+        assert_eq!(Frequency::from_khz(136.0).unwrap().to_string(), "0.136");
+        assert_eq!(Frequency::from_mhz(50.313).unwrap().to_string(), "50.313");
+        assert!(Frequency::from_mhz(-1.0).is_none());
+        assert!(Frequency::from_mhz(f64::NAN).is_none());
+    }
+}
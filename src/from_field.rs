@@ -0,0 +1,249 @@
+use crate::error::{AdifError, Result};
+use crate::types::Record;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// A type that can be parsed from an ADIF field value
+///
+/// Implemented for `String`, numbers, `bool` (ADIF Boolean, `Y` or `N`),
+/// the enumeration types such as [`Band`](crate::Band),
+/// [`AdifDate`](crate::AdifDate) and [`AdifTime`](crate::AdifTime), and with
+/// the `chrono` feature, `NaiveDate` and `NaiveTime`.
+pub trait FromField: Sized {
+    /// Parse a field value, or describe why it is invalid
+    fn from_field(value: &str) -> core::result::Result<Self, String>;
+}
+
+/// A type that can be written as an ADIF field value
+///
+/// The counterpart of [`FromField`], implemented for the same types and
+/// `str`. Booleans are written `Y` or `N`.
+pub trait ToField {
+    /// Format as a field value
+    fn to_field(&self) -> String;
+}
+
+impl Record {
+    /// Set a field to a typed value, such as a [`Band`](crate::Band) or
+    /// [`AdifDate`](crate::AdifDate), as with [`set`](Record::set)
+    pub fn set_typed<T: ToField + ?Sized>(&mut self, name: &str, value: &T) {
+        self.set(name, value.to_field());
+    }
+
+    /// Get a field value parsed as a type, such as `f64` or `NaiveDate`
+    ///
+    /// Fails with [`AdifError::MissingField`] if the field is missing or
+    /// empty, and [`AdifError::InvalidField`] if the value doesn't parse.
+    pub fn get_as<T: FromField>(&self, name: &str) -> Result<T> {
+        let value = self
+            .get_value(name)
+            .filter(|v| !v.trim().is_empty())
+            .ok_or_else(|| AdifError::MissingField(name.to_ascii_uppercase()))?;
+        T::from_field(value).map_err(|message| AdifError::InvalidField {
+            field: name.to_ascii_uppercase(),
+            message,
+        })
+    }
+
+    /// Get a field value parsed as a type, or `None` if it is missing or
+    /// empty
+    pub(crate) fn get_parsed<T: FromField>(&self, name: &str) -> Option<Result<T>> {
+        match self.get_as(name) {
+            Err(AdifError::MissingField(_)) => None,
+            result => Some(result),
+        }
+    }
+
+    /// Get a Boolean field (`Y` or `N`), or `None` if it is missing or empty
+    pub fn get_bool(&self, name: &str) -> Option<Result<bool>> {
+        self.get_parsed(name)
+    }
+
+    /// Get whether the QSO was a random contact, per QSO_RANDOM
+    pub fn qso_random(&self) -> Option<Result<bool>> {
+        self.get_bool("QSO_RANDOM")
+    }
+
+    /// Get whether the QSO is forced into the log, per FORCE_INIT
+    pub fn force_init(&self) -> Option<Result<bool>> {
+        self.get_bool("FORCE_INIT")
+    }
+
+    /// Get whether the contacted operator is a silent key, per SILENT_KEY
+    pub fn silent_key(&self) -> Option<Result<bool>> {
+        self.get_bool("SILENT_KEY")
+    }
+
+    /// Get whether the record is a shortwave listener report, per SWL
+    pub fn swl(&self) -> Option<Result<bool>> {
+        self.get_bool("SWL")
+    }
+}
+
+impl FromField for String {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        Ok(value.to_string())
+    }
+}
+
+impl ToField for String {
+    fn to_field(&self) -> String {
+        self.clone()
+    }
+}
+
+impl ToField for str {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FromField for bool {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        match value.trim() {
+            "Y" | "y" => Ok(true),
+            "N" | "n" => Ok(false),
+            other => Err(format!("'{}' is not a Boolean", other)),
+        }
+    }
+}
+
+impl ToField for bool {
+    fn to_field(&self) -> String {
+        String::from(if *self { "Y" } else { "N" })
+    }
+}
+
+macro_rules! from_field_numbers {
+    ($($ty:ty),*) => {
+        $(
+            impl FromField for $ty {
+                fn from_field(value: &str) -> core::result::Result<Self, String> {
+                    let value = value.trim();
+                    value
+                        .parse()
+                        .map_err(|_| format!("'{}' is not a valid {}", value, stringify!($ty)))
+                }
+            }
+
+            impl ToField for $ty {
+                fn to_field(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+from_field_numbers!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+#[cfg(feature = "chrono")]
+impl FromField for chrono::NaiveDate {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        crate::date::AdifDate::from_field(value).map(Into::into)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromField for chrono::NaiveTime {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        crate::date::AdifTime::from_field(value).map(Into::into)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToField for chrono::NaiveDate {
+    fn to_field(&self) -> String {
+        self.format("%Y%m%d").to_string()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToField for chrono::NaiveTime {
+    fn to_field(&self) -> String {
+        self.format("%H%M%S").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use crate::{Band, Mode};
+
+    #[test]
+    fn test_get_as() {
+        let file =
+            parse_adi("<FREQ:6>14.074<BAND:3>20M<MODE:3>FT8<SWL:1>n<K_INDEX:1>3<NAME:0><EOR>")
+                .unwrap();
+        let record = &file.records[0];
+        assert_eq!(record.get_as::<f64>("freq").unwrap(), 14.074);
+        assert_eq!(record.get_as::<Band>("BAND").unwrap(), Band::M20);
+        assert_eq!(record.get_as::<Mode>("MODE").unwrap(), Mode::Ft8);
+        assert!(!record.get_as::<bool>("SWL").unwrap());
+        assert_eq!(record.get_as::<u8>("K_INDEX").unwrap(), 3);
+        assert!(matches!(
+            record.get_as::<String>("name"),
+            Err(AdifError::MissingField(name)) if name == "NAME"
+        ));
+        assert!(matches!(
+            record.get_as::<u8>("FREQ"),
+            Err(AdifError::InvalidField { field, message })
+                if field == "FREQ" && message == "'14.074' is not a valid u8"
+        ));
+    }
+
+    #[test]
+    fn test_get_bool() {
+        let file =
+            parse_adi("<QSO_RANDOM:1>y<SILENT_KEY:1>N<SWL:3>Yes<FORCE_INIT:0><EOR>").unwrap();
+        let record = &file.records[0];
+        assert!(record.qso_random().unwrap().unwrap());
+        assert!(!record.silent_key().unwrap().unwrap());
+        assert!(matches!(
+            record.swl(),
+            Some(Err(AdifError::InvalidField { field, message }))
+                if field == "SWL" && message == "'Yes' is not a Boolean"
+        ));
+        assert!(record.force_init().is_none());
+        assert!(record.get_bool("EQSL_AG").is_none());
+    }
+
+    #[test]
+    fn test_set_typed() {
+        use crate::{AdifDate, Dxcc, Frequency};
+
+        let mut record = Record::new();
+        record.set_typed("BAND", &Band::M20);
+        record.set_typed("FREQ", &"14.0740".parse::<Frequency>().unwrap());
+        record.set_typed("QSO_DATE", &"20240229".parse::<AdifDate>().unwrap());
+        record.set_typed("DXCC", &Dxcc::from_code(291).unwrap());
+        record.set_typed("SWL", &false);
+        record.set_typed("K_INDEX", &3u8);
+        record.set_typed("NAME", "Bob");
+        assert_eq!(
+            record.to_adi_string(),
+            "<BAND:3>20m<FREQ:6>14.074<QSO_DATE:8>20240229<DXCC:3>291<SWL:1>N\
+             <K_INDEX:1>3<NAME:3>Bob<EOR>\n"
+        );
+        assert_eq!(record.get_as::<Band>("BAND").unwrap(), Band::M20);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_get_as_chrono() {
+        use chrono::{NaiveDate, NaiveTime};
+
+        let file = parse_adi("<QSO_DATE:8>20240229<TIME_ON:4>1423<TIME_OFF:3>142<EOR>").unwrap();
+        let record = &file.records[0];
+        assert_eq!(
+            record.get_as::<NaiveDate>("QSO_DATE").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert_eq!(
+            record.get_as::<NaiveTime>("TIME_ON").unwrap(),
+            NaiveTime::from_hms_opt(14, 23, 0).unwrap()
+        );
+        assert!(record.get_as::<NaiveTime>("TIME_OFF").is_err());
+    }
+}
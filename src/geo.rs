@@ -0,0 +1,310 @@
+use crate::encoding::json_string;
+use crate::types::{AdifFile, Record};
+use std::fmt::Write;
+
+/// Number of segments used to draw each great-circle line
+const LINE_SEGMENTS: usize = 32;
+
+/// Mean radius of the Earth
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Options for map exports
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeoOptions {
+    /// Also draw a great-circle line from my station to each QSO
+    pub lines: bool,
+    /// My station's position as (latitude, longitude), used for records
+    /// without MY_LAT/MY_LON or MY_GRIDSQUARE
+    pub station: Option<(f64, f64)>,
+}
+
+/// A QSO placed on the map
+struct Placed<'a> {
+    record: &'a Record,
+    position: (f64, f64),
+    line: Option<Vec<(f64, f64)>>,
+}
+
+impl AdifFile {
+    /// Export the QSOs as a GeoJSON `FeatureCollection`
+    ///
+    /// Each QSO with a position (LAT/LON, or else the center of
+    /// GRIDSQUARE) becomes a `Point` feature whose properties are the
+    /// record's fields. With [`GeoOptions::lines`], a `LineString` feature
+    /// follows the great circle from my station to the QSO.
+    pub fn to_geojson(&self, options: &GeoOptions) -> String {
+        let mut s = String::from("{\"type\":\"FeatureCollection\",\"features\":[");
+        let mut first = true;
+        for placed in place(self, options) {
+            let properties = placed
+                .record
+                .fields
+                .iter()
+                .map(|f| format!("{}:{}", json_string(&f.name), json_string(&f.value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let mut feature = |geometry: String| {
+                if !first {
+                    s.push(',');
+                }
+                first = false;
+                let _ = write!(
+                    s,
+                    "{{\"type\":\"Feature\",\"geometry\":{},\"properties\":{{{}}}}}",
+                    geometry, properties
+                );
+            };
+
+            let (lat, lon) = placed.position;
+            feature(format!(
+                "{{\"type\":\"Point\",\"coordinates\":[{:.6},{:.6}]}}",
+                lon, lat
+            ));
+            if let Some(line) = &placed.line {
+                let coordinates = line
+                    .iter()
+                    .map(|(lat, lon)| format!("[{:.6},{:.6}]", lon, lat))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                feature(format!(
+                    "{{\"type\":\"LineString\",\"coordinates\":[{}]}}",
+                    coordinates
+                ));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    /// Export the QSOs as a KML document for Google Earth
+    ///
+    /// Each QSO with a position becomes a placemark named after its CALL,
+    /// with the record's fields as extended data. Positions and lines
+    /// are as in [`AdifFile::to_geojson`].
+    pub fn to_kml(&self, options: &GeoOptions) -> String {
+        let mut s = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <kml xmlns=\"http://www.opengis.net/kml/2.2\">\n<Document>\n",
+        );
+        for placed in place(self, options) {
+            let record = placed.record;
+            s.push_str("<Placemark>\n");
+            if let Some(call) = record.call() {
+                let _ = writeln!(s, "<name>{}</name>", xml_escape(call));
+            }
+            let description = [
+                record.qso_date(),
+                record.time_on(),
+                record.band(),
+                record.mode(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+            let _ = writeln!(s, "<description>{}</description>", xml_escape(&description));
+            s.push_str("<ExtendedData>\n");
+            for field in &record.fields {
+                let _ = writeln!(
+                    s,
+                    "<Data name=\"{}\"><value>{}</value></Data>",
+                    xml_escape(&field.name),
+                    xml_escape(&field.value)
+                );
+            }
+            s.push_str("</ExtendedData>\n");
+
+            let (lat, lon) = placed.position;
+            let point = format!(
+                "<Point><coordinates>{:.6},{:.6}</coordinates></Point>",
+                lon, lat
+            );
+            match &placed.line {
+                Some(line) => {
+                    let coordinates = line
+                        .iter()
+                        .map(|(lat, lon)| format!("{:.6},{:.6}", lon, lat))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let _ = writeln!(
+                        s,
+                        "<MultiGeometry>{}<LineString><coordinates>{}</coordinates></LineString></MultiGeometry>",
+                        point, coordinates
+                    );
+                }
+                None => {
+                    s.push_str(&point);
+                    s.push('\n');
+                }
+            }
+            s.push_str("</Placemark>\n");
+        }
+        s.push_str("</Document>\n</kml>\n");
+        s
+    }
+}
+
+impl Record {
+    /// Get the great-circle distance in kilometres from my station to the
+    /// contacted station
+    ///
+    /// Each end is taken from LAT/LON (or MY_LAT/MY_LON) if both are valid,
+    /// and otherwise from the center of GRIDSQUARE (or MY_GRIDSQUARE).
+    /// Returns `None` if either end has no usable position.
+    pub fn distance_km(&self) -> Option<f64> {
+        let (from, to) = self.endpoints()?;
+        let (lat1, lat2) = (from.0.to_radians(), to.0.to_radians());
+        let half_lat = (lat2 - lat1) / 2.0;
+        let half_lon = (to.1 - from.1).to_radians() / 2.0;
+        let h = half_lat.sin().powi(2) + lat1.cos() * lat2.cos() * half_lon.sin().powi(2);
+        Some(2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin())
+    }
+
+    /// Get the initial bearing in degrees (0 to 360, clockwise from true
+    /// north) from my station to the contacted station
+    ///
+    /// Positions are found as in [`Record::distance_km`].
+    pub fn bearing_deg(&self) -> Option<f64> {
+        let (from, to) = self.endpoints()?;
+        let (lat1, lat2) = (from.0.to_radians(), to.0.to_radians());
+        let dlon = (to.1 - from.1).to_radians();
+        let y = dlon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+        Some(y.atan2(x).to_degrees().rem_euclid(360.0))
+    }
+
+    fn endpoints(&self) -> Option<((f64, f64), (f64, f64))> {
+        let from = locate(self, "MY_LAT", "MY_LON", "MY_GRIDSQUARE")?;
+        Some((from, locate(self, "LAT", "LON", "GRIDSQUARE")?))
+    }
+}
+
+/// Position the records that can be placed on the map
+fn place<'a>(file: &'a AdifFile, options: &'a GeoOptions) -> impl Iterator<Item = Placed<'a>> {
+    file.records.iter().filter_map(|record| {
+        let position = locate(record, "LAT", "LON", "GRIDSQUARE")?;
+        let line = options
+            .lines
+            .then(|| locate(record, "MY_LAT", "MY_LON", "MY_GRIDSQUARE").or(options.station))
+            .flatten()
+            .map(|station| great_circle(station, position));
+        Some(Placed {
+            record,
+            position,
+            line,
+        })
+    })
+}
+
+/// Get a position from a pair of location fields, or else a grid square
+fn locate(record: &Record, lat: &str, lon: &str, grid: &str) -> Option<(f64, f64)> {
+    let degrees =
+        |name: &str, latitude: bool| Some(record.coordinate(name, latitude)?.ok()?.degrees());
+    let coordinates = degrees(lat, true).zip(degrees(lon, false));
+    coordinates.or_else(|| Some(record.locator(grid)?.ok()?.center_latlon()))
+}
+
+/// Get points along the great circle between two positions
+fn great_circle(from: (f64, f64), to: (f64, f64)) -> Vec<(f64, f64)> {
+    let vector = |(lat, lon): (f64, f64)| {
+        let (lat, lon) = (f64::to_radians(lat), f64::to_radians(lon));
+        [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+    };
+    let (a, b) = (vector(from), vector(to));
+    let dot = (a[0] * b[0] + a[1] * b[1] + a[2] * b[2]).clamp(-1.0, 1.0);
+    let angle = dot.acos();
+    if angle.sin().abs() < 1e-9 {
+        return vec![from, to];
+    }
+
+    (0..=LINE_SEGMENTS)
+        .map(|i| {
+            let f = i as f64 / LINE_SEGMENTS as f64;
+            let wa = ((1.0 - f) * angle).sin() / angle.sin();
+            let wb = (f * angle).sin() / angle.sin();
+            let [x, y, z] = [0, 1, 2].map(|k| wa * a[k] + wb * b[k]);
+            (z.atan2(x.hypot(y)).to_degrees(), y.atan2(x).to_degrees())
+        })
+        .collect()
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_distance_and_bearing() {
+        let file = parse_adi(
+            "<MY_GRIDSQUARE:4>FN31<LAT:11>N051 30.000<LON:11>W000 07.000<GRIDSQUARE:4>JJ00<EOR>\
+             <MY_LAT:11>N000 00.000<MY_LON:11>E000 00.000<GRIDSQUARE:2>JJ<EOR>\
+             <MY_GRIDSQUARE:4>FN31<EOR>",
+        )
+        .unwrap();
+        let records = &file.records;
+        // LAT/LON is preferred over GRIDSQUARE: FN31 to London
+        assert!((records[0].distance_km().unwrap() - 5451.0).abs() < 1.0);
+        assert!((records[0].bearing_deg().unwrap() - 52.0).abs() < 1.0);
+        // From 0N 0E to the center of JJ at 5N 10E
+        assert!((records[1].distance_km().unwrap() - 1242.0).abs() < 1.0);
+        assert!((records[1].bearing_deg().unwrap() - 63.3).abs() < 0.5);
+        assert!(records[2].distance_km().is_none());
+        assert!(records[2].bearing_deg().is_none());
+    }
+
+    #[test]
+    fn test_to_geojson() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<GRIDSQUARE:4>FN31<MY_GRIDSQUARE:4>JJ00<EOR>\
+             <CALL:4>K1AB<EOR>\
+             <CALL:4>K\"XY<LAT:11>N040 30.000<LON:11>W073 15.000<EOR>",
+        )
+        .unwrap();
+        let geojson = file.to_geojson(&GeoOptions::default());
+        assert_eq!(
+            geojson,
+            "{\"type\":\"FeatureCollection\",\"features\":[\
+             {\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[-73.000000,41.500000]},\
+             \"properties\":{\"CALL\":\"W1AW\",\"GRIDSQUARE\":\"FN31\",\"MY_GRIDSQUARE\":\"JJ00\"}},\
+             {\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[-73.250000,40.500000]},\
+             \"properties\":{\"CALL\":\"K\\\"XY\",\"LAT\":\"N040 30.000\",\"LON\":\"W073 15.000\"}}]}"
+        );
+
+        // Only the first record has a station position
+        let options = GeoOptions {
+            lines: true,
+            ..Default::default()
+        };
+        let geojson = file.to_geojson(&options);
+        assert_eq!(geojson.matches("LineString").count(), 1);
+        assert!(geojson.contains("[[1.000000,0.500000],"));
+        assert!(geojson.contains(",[-73.000000,41.500000]]}"));
+    }
+
+    #[test]
+    fn test_to_kml() {
+        let file = parse_adi("<CALL:5>W1&AW<QSO_DATE:8>20240115<BAND:3>20m<GRIDSQUARE:4>FN31<EOR>")
+            .unwrap();
+        let options = GeoOptions {
+            lines: true,
+            station: Some((51.5, 0.0)),
+        };
+        let kml = file.to_kml(&options);
+
+        assert!(kml.contains("<name>W1&amp;AW</name>\n<description>20240115 20m</description>"));
+        assert!(kml.contains("<Data name=\"BAND\"><value>20m</value></Data>"));
+        assert!(kml.contains(
+            "<MultiGeometry><Point><coordinates>-73.000000,41.500000</coordinates></Point>\
+             <LineString><coordinates>0.000000,51.500000 "
+        ));
+        assert!(kml.ends_with("</Document>\n</kml>\n"));
+    }
+}
@@ -0,0 +1,360 @@
+use crate::error::{AdifError, Result};
+use crate::from_field::{FromField, ToField};
+use crate::types::Record;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+/// A Maidenhead locator of 2 to 10 characters, as in GRIDSQUARE
+///
+/// Locators are normalized as they are usually written, with the field in
+/// upper case and the subsquares in lower case (`FN31pr`), so they compare
+/// regardless of the case they were given in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GridSquare {
+    chars: [u8; 10],
+    len: u8,
+}
+
+impl GridSquare {
+    /// Get the locator as a string
+    pub fn as_str(&self) -> &str {
+        // Locators only hold ASCII letters and digits
+        core::str::from_utf8(&self.chars[..usize::from(self.len)]).unwrap_or_default()
+    }
+
+    /// Get the number of characters: 2 (field), 4 (square), 6 (subsquare),
+    /// 8 or 10 (extended squares)
+    pub fn precision(&self) -> usize {
+        usize::from(self.len)
+    }
+
+    /// Get the height and width of the locator's area in degrees
+    pub fn size(&self) -> (f64, f64) {
+        let (mut lat, mut lon) = (180.0, 360.0);
+        for pair in 0..self.precision() / 2 {
+            let divisions = f64::from(divisions(pair));
+            lat /= divisions;
+            lon /= divisions;
+        }
+        (lat, lon)
+    }
+
+    /// Split the locator into its GRIDSQUARE part (up to 8 characters) and
+    /// its GRIDSQUARE_EXT part, if it has 10 characters
+    pub fn split(&self) -> (&str, Option<&str>) {
+        let s = self.as_str();
+        match s.split_at_checked(8) {
+            Some((grid, ext)) if !ext.is_empty() => (grid, Some(ext)),
+            _ => (s, None),
+        }
+    }
+
+    /// Get the locator's south-west corner as (latitude, longitude)
+    pub fn corner(&self) -> (f64, f64) {
+        let (mut lat, mut lon) = (-90.0, -180.0);
+        let (mut lat_size, mut lon_size) = (180.0, 360.0);
+        for (pair, chars) in self.chars[..self.precision()].chunks(2).enumerate() {
+            let divisions = divisions(pair);
+            let base = if divisions == 10 { b'0' } else { b'A' };
+            let digit = |c: u8| f64::from(c.to_ascii_uppercase() - base);
+            lat_size /= f64::from(divisions);
+            lon_size /= f64::from(divisions);
+            lon += digit(chars[0]) * lon_size;
+            lat += digit(chars[1]) * lat_size;
+        }
+        (lat, lon)
+    }
+
+    /// Get the locator's center as (latitude, longitude)
+    pub fn center_latlon(&self) -> (f64, f64) {
+        let (lat, lon) = self.corner();
+        let (lat_size, lon_size) = self.size();
+        (lat + lat_size / 2.0, lon + lon_size / 2.0)
+    }
+
+    /// Get the locator of a position with 2, 4, 6, 8 or 10 characters
+    ///
+    /// Returns `None` for any other precision, or a latitude or longitude
+    /// out of range. The north pole and the antimeridian fall in the
+    /// locators along the top and right edges.
+    pub fn from_latlon(lat: f64, lon: f64, precision: usize) -> Option<Self> {
+        if !matches!(precision, 2 | 4 | 6 | 8 | 10)
+            || !(-90.0..=90.0).contains(&lat)
+            || !(-180.0..=180.0).contains(&lon)
+        {
+            return None;
+        }
+        let (mut lat, mut lon) = (lat + 90.0, lon + 180.0);
+        let (mut lat_size, mut lon_size) = (180.0, 360.0);
+        let mut chars = [0; 10];
+        for pair in 0..precision / 2 {
+            let divisions = divisions(pair);
+            lat_size /= f64::from(divisions);
+            lon_size /= f64::from(divisions);
+            // Both remainders are non-negative, so truncation rounds down
+            let lon_digit = ((lon / lon_size) as u8).min(divisions - 1);
+            let lat_digit = ((lat / lat_size) as u8).min(divisions - 1);
+            lon -= f64::from(lon_digit) * lon_size;
+            lat -= f64::from(lat_digit) * lat_size;
+            let base = match pair {
+                0 => b'A',
+                _ if divisions == 10 => b'0',
+                _ => b'a',
+            };
+            chars[pair * 2] = base + lon_digit;
+            chars[pair * 2 + 1] = base + lat_digit;
+        }
+        Some(GridSquare {
+            chars,
+            len: precision as u8,
+        })
+    }
+}
+
+/// Get how many divisions a character pair of a locator has
+pub(crate) fn divisions(pair: usize) -> u8 {
+    // Fields are A-R, then digits and letters A-X alternate
+    match pair {
+        0 => 18,
+        _ if pair % 2 == 1 => 10,
+        _ => 24,
+    }
+}
+
+impl FromStr for GridSquare {
+    type Err = AdifError;
+
+    /// Parse a locator such as `FN31` or `fn31PR`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let invalid = || AdifError::InvalidField {
+            field: "GridSquare".into(),
+            message: format!("'{}' is not a Maidenhead locator", s),
+        };
+        if !matches!(s.len(), 2 | 4 | 6 | 8 | 10) {
+            return Err(invalid());
+        }
+        let mut chars = [0; 10];
+        for (i, c) in s.bytes().enumerate() {
+            let pair = i / 2;
+            chars[i] = match divisions(pair) {
+                10 if c.is_ascii_digit() => c,
+                10 => return Err(invalid()),
+                divisions => {
+                    let upper = c.to_ascii_uppercase();
+                    if !upper.is_ascii_uppercase() || upper - b'A' >= divisions {
+                        return Err(invalid());
+                    }
+                    if pair == 0 {
+                        upper
+                    } else {
+                        c.to_ascii_lowercase()
+                    }
+                }
+            };
+        }
+        Ok(GridSquare {
+            chars,
+            len: s.len() as u8,
+        })
+    }
+}
+
+impl fmt::Display for GridSquare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromField for GridSquare {
+    fn from_field(value: &str) -> core::result::Result<Self, String> {
+        match value.parse() {
+            Ok(grid) => Ok(grid),
+            Err(AdifError::InvalidField { message, .. }) => Err(message),
+            Err(other) => Err(format!("{}", other)),
+        }
+    }
+}
+
+impl ToField for GridSquare {
+    fn to_field(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Record {
+    /// Get the contacted station's locator, or `None` if GRIDSQUARE is
+    /// missing or empty
+    ///
+    /// A GRIDSQUARE_EXT is appended to an 8 character GRIDSQUARE to give a
+    /// 10 character locator.
+    pub fn gridsquare(&self) -> Option<Result<GridSquare>> {
+        self.locator("GRIDSQUARE")
+    }
+
+    /// Get the logging station's locator, from MY_GRIDSQUARE and
+    /// MY_GRIDSQUARE_EXT
+    pub fn my_gridsquare(&self) -> Option<Result<GridSquare>> {
+        self.locator("MY_GRIDSQUARE")
+    }
+
+    /// Set GRIDSQUARE, and GRIDSQUARE_EXT for a 10 character locator
+    ///
+    /// Any existing GRIDSQUARE_EXT is removed if the locator is shorter.
+    pub fn set_gridsquare(&mut self, grid: GridSquare) {
+        self.set_locator("GRIDSQUARE", grid);
+    }
+
+    /// Set MY_GRIDSQUARE, and MY_GRIDSQUARE_EXT for a 10 character locator
+    pub fn set_my_gridsquare(&mut self, grid: GridSquare) {
+        self.set_locator("MY_GRIDSQUARE", grid);
+    }
+
+    /// Get a locator field combined with its `_EXT` field
+    pub(crate) fn locator(&self, name: &str) -> Option<Result<GridSquare>> {
+        let ext_name = format!("{}_EXT", name);
+        let ext = self
+            .get_value(&ext_name)
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        let Some(ext) = ext else {
+            return self.get_parsed(name);
+        };
+        Some(self.get_as::<String>(name).and_then(|grid| {
+            let grid = grid.trim();
+            let invalid = |message| AdifError::InvalidField {
+                field: ext_name.clone(),
+                message,
+            };
+            if grid.len() != 8 {
+                return Err(invalid(format!(
+                    "'{}' needs an 8 character {}, not '{}'",
+                    ext, name, grid
+                )));
+            }
+            GridSquare::from_field(&format!("{}{}", grid, ext)).map_err(invalid)
+        }))
+    }
+
+    fn set_locator(&mut self, name: &str, grid: GridSquare) {
+        let ext_name = format!("{}_EXT", name);
+        let (grid, ext) = grid.split();
+        self.set(name, grid);
+        match ext {
+            Some(ext) => self.set(&ext_name, ext),
+            None => self
+                .fields
+                .retain(|f| !f.name.eq_ignore_ascii_case(&ext_name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse_grid() {
+        let grid: GridSquare = "fn31PR".parse().unwrap();
+        assert_eq!(grid.to_string(), "FN31pr");
+        assert_eq!(grid, "FN31pr".parse().unwrap());
+        assert_eq!(grid.precision(), 6);
+        assert_eq!(
+            "JN58td25".parse::<GridSquare>().unwrap().as_str(),
+            "JN58td25"
+        );
+        assert_eq!("RR".parse::<GridSquare>().unwrap().size(), (10.0, 20.0));
+        assert_eq!("FN31".parse::<GridSquare>().unwrap().size(), (1.0, 2.0));
+        for bad in ["F", "FN3", "SN31", "FN3A", "FN31py", "FN31pr2", ""] {
+            assert!(bad.parse::<GridSquare>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_center_latlon() {
+        let center = |s: &str| s.parse::<GridSquare>().unwrap().center_latlon();
+        assert_eq!(center("JJ"), (5.0, 10.0));
+        assert_eq!(center("FN31"), (41.5, -73.0));
+        assert_eq!(
+            "FN31".parse::<GridSquare>().unwrap().corner(),
+            (41.0, -74.0)
+        );
+        let (lat, lon) = center("fn31pr");
+        assert!((lat - 41.729).abs() < 0.001 && (lon - -72.708).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_from_latlon() {
+        let grid = |lat, lon, precision| {
+            GridSquare::from_latlon(lat, lon, precision).map(|g| g.to_string())
+        };
+        assert_eq!(grid(41.714, -72.727, 6).as_deref(), Some("FN31pr"));
+        assert_eq!(grid(-33.865, 151.209, 8).as_deref(), Some("QF56od52"));
+        assert_eq!(grid(90.0, 180.0, 4).as_deref(), Some("RR99"));
+        assert_eq!(grid(-90.0, -180.0, 2).as_deref(), Some("AA"));
+        assert_eq!(grid(0.0, 0.0, 5), None);
+        assert_eq!(grid(91.0, 0.0, 4), None);
+        for s in ["FN31pr", "JN58td25", "QF56od51ax", "RR99xx"] {
+            let g: GridSquare = s.parse().unwrap();
+            let (lat, lon) = g.center_latlon();
+            assert_eq!(GridSquare::from_latlon(lat, lon, g.precision()), Some(g));
+        }
+    }
+
+    #[test]
+    fn test_gridsquare_ext() {
+        let file = parse_adi(
+            "<GRIDSQUARE:8>JN58TD25<GRIDSQUARE_EXT:2>JK<EOR>\
+             <GRIDSQUARE:6>JN58td<GRIDSQUARE_EXT:2>jk<EOR>\
+             <MY_GRIDSQUARE:8>JN58td25<MY_GRIDSQUARE_EXT:2>zz<EOR>",
+        )
+        .unwrap();
+        let records = &file.records;
+        let grid = records[0].gridsquare().unwrap().unwrap();
+        assert_eq!(grid.as_str(), "JN58td25jk");
+        assert_eq!(grid.split(), ("JN58td25", Some("jk")));
+        assert_eq!(
+            "JN58td25".parse::<GridSquare>().unwrap().split(),
+            ("JN58td25", None)
+        );
+        assert!(matches!(
+            records[1].gridsquare(),
+            Some(Err(AdifError::InvalidField { field, message }))
+                if field == "GRIDSQUARE_EXT"
+                    && message == "'jk' needs an 8 character GRIDSQUARE, not 'JN58td'"
+        ));
+        assert!(matches!(
+            records[2].my_gridsquare(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "MY_GRIDSQUARE_EXT"
+        ));
+    }
+
+    #[test]
+    fn test_set_gridsquare() {
+        let mut record = Record::new();
+        record.set_gridsquare("JN58td25jk".parse().unwrap());
+        assert_eq!(
+            record.to_adi_string(),
+            "<GRIDSQUARE:8>JN58td25<GRIDSQUARE_EXT:2>jk<EOR>\n"
+        );
+        record.set_gridsquare("FN31".parse().unwrap());
+        assert_eq!(record.to_adi_string(), "<GRIDSQUARE:4>FN31<EOR>\n");
+        record.set_my_gridsquare("FN31pr".parse().unwrap());
+        assert_eq!(record.my_gridsquare().unwrap().unwrap().as_str(), "FN31pr");
+    }
+
+    #[test]
+    fn test_gridsquare_accessors() {
+        let file = parse_adi("<GRIDSQUARE:4>io91<MY_GRIDSQUARE:5>FN31p<EOR>").unwrap();
+        let record = &file.records[0];
+        assert_eq!(record.gridsquare().unwrap().unwrap().as_str(), "IO91");
+        assert!(matches!(
+            record.my_gridsquare(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "MY_GRIDSQUARE"
+        ));
+    }
+}
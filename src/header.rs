@@ -0,0 +1,151 @@
+use crate::date::{AdifDate, AdifTime};
+use crate::types::{AdifHeader, Field};
+use crate::version::AdifVersion;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+impl AdifHeader {
+    /// Start building a header with ADIF_VER, PROGRAMID, PROGRAMVERSION
+    /// and CREATED_TIMESTAMP filled in
+    pub fn builder() -> HeaderBuilder {
+        HeaderBuilder::default()
+    }
+
+    /// Set a header field's value, replacing the first occurrence of the
+    /// field or adding it at the end, and updating the well-known header
+    /// values
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        let field = Field::new(name, value);
+        self.sync(&field);
+        match self
+            .fields
+            .iter_mut()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+        {
+            Some(existing) => {
+                existing.value = field.value;
+                existing.raw = None;
+            }
+            None => self.fields.push(field),
+        }
+    }
+}
+
+/// Builder for an [`AdifHeader`], from [`AdifHeader::builder`]
+///
+/// ADIF_VER is the latest version this crate supports. PROGRAMID and
+/// PROGRAMVERSION default to this crate's name and version, and
+/// CREATED_TIMESTAMP to the current UTC time (with the `std` feature;
+/// without it there is none unless given).
+#[derive(Debug, Clone)]
+pub struct HeaderBuilder {
+    version: AdifVersion,
+    program_id: String,
+    program_version: String,
+    created: Option<(AdifDate, AdifTime)>,
+    preamble: String,
+    fields: Vec<Field>,
+}
+
+impl Default for HeaderBuilder {
+    fn default() -> Self {
+        #[cfg(feature = "std")]
+        let created = AdifDate::from_system_time(std::time::SystemTime::now()).ok();
+        #[cfg(not(feature = "std"))]
+        let created = None;
+        Self {
+            version: AdifVersion::LATEST,
+            program_id: env!("CARGO_PKG_NAME").to_string(),
+            program_version: env!("CARGO_PKG_VERSION").to_string(),
+            created,
+            preamble: String::new(),
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl HeaderBuilder {
+    /// Set the ADIF version the log is written to
+    pub fn version(mut self, version: AdifVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Set the program writing the log, for PROGRAMID and PROGRAMVERSION
+    pub fn program(mut self, id: impl Into<String>, version: impl Into<String>) -> Self {
+        self.program_id = id.into();
+        self.program_version = version.into();
+        self
+    }
+
+    /// Set the UTC date and time the log was created
+    pub fn created(mut self, date: AdifDate, time: AdifTime) -> Self {
+        self.created = Some((date, time));
+        self
+    }
+
+    /// Set the text before the first header field
+    pub fn preamble(mut self, preamble: impl Into<String>) -> Self {
+        self.preamble = preamble.into();
+        self
+    }
+
+    /// Add another header field, such as a USERDEFn field; a field already
+    /// set by the builder is replaced
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push(Field::new(name, value));
+        self
+    }
+
+    /// Build the header
+    pub fn build(self) -> AdifHeader {
+        let mut header = AdifHeader {
+            preamble: self.preamble,
+            ..Default::default()
+        };
+        header.set("ADIF_VER", self.version.to_string());
+        header.set("PROGRAMID", self.program_id);
+        header.set("PROGRAMVERSION", self.program_version);
+        if let Some((date, time)) = self.created {
+            let time = format!("{:02}{:02}{:02}", time.hour(), time.minute(), time.second());
+            header.set("CREATED_TIMESTAMP", format!("{} {}", date, time));
+        }
+        for field in self.fields {
+            header.set(&field.name, field.value);
+        }
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdifFile, parse_adi, write_adi};
+
+    #[test]
+    fn test_header_builder() {
+        let date: AdifDate = "20240229".parse().unwrap();
+        let time = AdifTime::new(14, 23, 0).unwrap();
+        let header = AdifHeader::builder()
+            .program("MyLogger", "1.2")
+            .created(date, time)
+            .field("USERDEF1", "EPC,{A,B}")
+            .field("programversion", "1.3")
+            .build();
+        let file = AdifFile {
+            header,
+            ..Default::default()
+        };
+        let parsed = parse_adi(&write_adi(&file)).unwrap().header;
+        assert_eq!(parsed.adif_version.as_deref(), Some("3.1.6"));
+        assert_eq!(parsed.program_id.as_deref(), Some("MyLogger"));
+        assert_eq!(parsed.program_version.as_deref(), Some("1.3"));
+        assert_eq!(parsed.created_timestamp.as_deref(), Some("20240229 142300"));
+        assert_eq!(parsed.fields.len(), 5);
+
+        let header = AdifHeader::builder().build();
+        assert_eq!(header.program_id.as_deref(), Some("adif_parser"));
+        assert_eq!(header.created_timestamp.is_some(), cfg!(feature = "std"));
+    }
+}
@@ -0,0 +1,343 @@
+use crate::error::{AdifError, Result};
+use crate::reader::RecordReader;
+use crate::types::{AdifFile, AdifHeader, Field, Record};
+use crate::validate::{Severity, ValidationIssue, ValidationReport};
+use serde_json::{Map, Value, json};
+use std::io::{BufRead, Write};
+
+impl Record {
+    /// Convert to a JSON object keyed by field name, in field order
+    ///
+    /// A name that appears more than once maps to an array of its values.
+    pub fn to_json_value(&self) -> Value {
+        Value::Object(fields_to_map(&self.fields))
+    }
+
+    /// Build a record from a JSON object keyed by field name
+    pub fn from_json_value(value: &Value) -> Result<Record> {
+        Ok(Record {
+            fields: fields_from_value(value, "record")?,
+            ..Default::default()
+        })
+    }
+}
+
+impl AdifFile {
+    /// Convert to JSON: `{"preamble": .., "header": {..}, "records": [{..}, ..]}`
+    ///
+    /// Header and records are objects mapping field names to string
+    /// values, or to arrays of them for names that repeat. The preamble is omitted when empty. Data type indicators
+    /// are not included.
+    pub fn to_json_value(&self) -> Value {
+        let mut map = Map::new();
+        if !self.header.preamble.is_empty() {
+            map.insert(
+                "preamble".to_string(),
+                Value::String(self.header.preamble.clone()),
+            );
+        }
+        map.insert(
+            "header".to_string(),
+            Value::Object(fields_to_map(&self.header.fields)),
+        );
+        map.insert(
+            "records".to_string(),
+            Value::Array(self.records.iter().map(Record::to_json_value).collect()),
+        );
+        Value::Object(map)
+    }
+
+    /// Serialize to a JSON string (see [`AdifFile::to_json_value`])
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    /// Serialize to an indented JSON string
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json_value()).expect("JSON values always serialize")
+    }
+
+    /// Parse a JSON document in the format produced by [`AdifFile::to_json`]
+    ///
+    /// Number and boolean values are accepted and stored as text; `null`
+    /// values are skipped, and each value in an array becomes a field.
+    pub fn from_json(input: &str) -> Result<AdifFile> {
+        AdifFile::from_json_value(&serde_json::from_str(input)?)
+    }
+
+    /// Build a log from a JSON value in the format produced by
+    /// [`AdifFile::to_json_value`]
+    pub fn from_json_value(value: &Value) -> Result<AdifFile> {
+        let Value::Object(map) = value else {
+            return Err(schema_error("expected a JSON object"));
+        };
+
+        let mut header = AdifHeader::default();
+        match map.get("preamble") {
+            Some(Value::String(preamble)) => header.preamble = preamble.clone(),
+            Some(Value::Null) | None => {}
+            Some(_) => return Err(schema_error("preamble must be a string")),
+        }
+        if let Some(value) = map.get("header") {
+            for field in fields_from_value(value, "header")? {
+                header.add_field(field);
+            }
+        }
+
+        let records = match map.get("records") {
+            Some(Value::Array(records)) => records
+                .iter()
+                .map(Record::from_json_value)
+                .collect::<Result<Vec<_>>>()?,
+            None => Vec::new(),
+            Some(_) => return Err(schema_error("records must be an array")),
+        };
+
+        Ok(AdifFile {
+            header,
+            records,
+            ..Default::default()
+        })
+    }
+}
+
+/// Streaming writer emitting one JSON object per record per line (NDJSON)
+///
+/// Each line is the record's [`to_json_value`](Record::to_json_value).
+pub struct NdjsonWriter<W: Write> {
+    writer: W,
+    records_written: usize,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    /// Create a writer over an output sink
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            records_written: 0,
+        }
+    }
+
+    /// Write a single record as one line of JSON
+    pub fn write_record(&mut self, record: &Record) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, &record.to_json_value())?;
+        self.writer.write_all(b"\n")?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Get the number of records written so far
+    pub fn records_written(&self) -> usize {
+        self.records_written
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Unwrap this writer, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl AdifFile {
+    /// Serialize the records as NDJSON, one object per line
+    pub fn to_ndjson(&self) -> String {
+        let mut writer = NdjsonWriter::new(Vec::new());
+        for record in &self.records {
+            // Writing to a Vec cannot fail
+            let _ = writer.write_record(record);
+        }
+        String::from_utf8(writer.into_inner()).expect("JSON output is valid UTF-8")
+    }
+}
+
+impl<R: BufRead> RecordReader<R> {
+    /// Convert the remaining records to NDJSON as they are read, without
+    /// holding more than one record in memory. Returns the number of
+    /// records written.
+    pub fn write_ndjson<W: Write>(self, writer: W) -> Result<usize> {
+        let mut writer = NdjsonWriter::new(writer);
+        for record in self {
+            writer.write_record(&record?)?;
+        }
+        writer.flush()?;
+        Ok(writer.records_written())
+    }
+}
+
+impl ValidationReport {
+    /// Convert to a JSON value, in the schema of
+    /// [`ValidationReport::to_json`]
+    pub fn to_json_value(&self) -> Value {
+        json!({
+            "valid": self.is_valid(),
+            "errors": self.count(Severity::Error),
+            "warnings": self.count(Severity::Warning),
+            "info": self.count(Severity::Info),
+            "issues": self.issues.iter().map(ValidationIssue::to_json_value).collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl ValidationIssue {
+    /// Convert to JSON as part of [`ValidationReport::to_json_value`]
+    pub fn to_json_value(&self) -> Value {
+        json!({
+            "record": self.record,
+            "field": self.field,
+            "severity": self.severity.to_string(),
+            "rule": self.rule,
+            "message": self.message,
+            "span": self.span.as_ref().map(|span| json!({"start": span.start, "end": span.end})),
+        })
+    }
+}
+
+fn fields_to_map(fields: &[Field]) -> Map<String, Value> {
+    let mut map = Map::new();
+    for field in fields {
+        let value = Value::String(field.value.clone());
+        match map.get_mut(field.name.as_str()) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(first) => *first = Value::Array(vec![first.take(), value]),
+            None => {
+                map.insert(field.name.to_string(), value);
+            }
+        }
+    }
+    map
+}
+
+fn fields_from_value(value: &Value, what: &str) -> Result<Vec<Field>> {
+    let Value::Object(map) = value else {
+        return Err(schema_error(&format!("{} must be an object", what)));
+    };
+
+    let mut fields = Vec::with_capacity(map.len());
+    for (name, value) in map {
+        let values = match value {
+            Value::Array(values) => values.as_slice(),
+            value => core::slice::from_ref(value),
+        };
+        for value in values {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => if *b { "Y" } else { "N" }.to_string(),
+                Value::Null => continue,
+                _ => {
+                    return Err(schema_error(&format!(
+                        "{} field {} must be a string, number, boolean or an array of them",
+                        what, name
+                    )));
+                }
+            };
+            fields.push(Field::new(name.as_str(), value));
+        }
+    }
+    Ok(fields)
+}
+
+fn schema_error(message: &str) -> AdifError {
+    AdifError::ParseError {
+        position: 0,
+        message: format!("Invalid log: {}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_to_json() {
+        let file = parse_adi("Log\n<ADIF_VER:5>3.1.6<EOH><CALL:4>W1AW<BAND:3>20m<EOR>").unwrap();
+        assert_eq!(
+            file.to_json(),
+            r#"{"preamble":"Log\n","header":{"ADIF_VER":"3.1.6"},"records":[{"CALL":"W1AW","BAND":"20m"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_validation_report_json() {
+        let options = crate::ParserOptions {
+            preserve_raw: true,
+            ..Default::default()
+        };
+        let input = "<CALL:4>W1AW<BAND:3>21m<EOR>";
+        let file = crate::parse_adi_with_options(input, &options).unwrap();
+        let report = crate::validate(&file);
+        assert_eq!(
+            serde_json::from_str::<Value>(&report.to_json()).unwrap(),
+            report.to_json_value()
+        );
+        let report = crate::validate(&parse_adi("<CALL:4>W1AW<EOR>").unwrap());
+        assert_eq!(report.to_json_value()["issues"], json!([]));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let file =
+            parse_adi("Log\n<ADIF_VER:5>3.1.6<EOH><CALL:4>W1AW<EOR><CALL:4>K1AB<EOR>").unwrap();
+        let back = AdifFile::from_json(&file.to_json_pretty()).unwrap();
+
+        assert_eq!(back.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(back.header.preamble, "Log\n");
+        assert_eq!(back.len(), 2);
+        assert_eq!(back.records[1].fields, file.records[1].fields);
+    }
+
+    #[test]
+    fn test_repeated_fields() {
+        let file = parse_adi("<CALL:4>W1AW<NOTES:1>a<NOTES:1>b<NOTES:1>c<EOR>").unwrap();
+        let json = file.records[0].to_json_value();
+        assert_eq!(json, json!({"CALL": "W1AW", "NOTES": ["a", "b", "c"]}));
+
+        let back = Record::from_json_value(&json).unwrap();
+        assert_eq!(back.fields, file.records[0].fields);
+        assert!(Record::from_json_value(&json!({"NOTES": [["a"]]})).is_err());
+    }
+
+    #[test]
+    fn test_from_json_values() {
+        let json = r#"{"records":[{"call":"W1AW","freq":14.074,"swl":false,"notes":null}]}"#;
+        let file = AdifFile::from_json(json).unwrap();
+
+        let record = &file.records[0];
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(record.freq(), Some("14.074"));
+        assert_eq!(record.get_value("SWL"), Some("N"));
+        assert_eq!(record.get("NOTES"), None);
+    }
+
+    #[test]
+    fn test_from_json_errors() {
+        assert!(matches!(AdifFile::from_json("{"), Err(AdifError::Json(_))));
+        assert!(matches!(
+            AdifFile::from_json(r#"{"records":[{"CALL":{"V":"W1AW"}}]}"#),
+            Err(AdifError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ndjson_streaming() {
+        let input = "<ADIF_VER:5>3.1.6<EOH>\n<CALL:4>W1AW<EOR>\n<CALL:4>K1AB<MODE:2>CW<EOR>\n";
+        let mut out = Vec::new();
+        let count = RecordReader::new(input.as_bytes())
+            .write_ndjson(&mut out)
+            .unwrap();
+
+        assert_eq!(count, 2);
+        let ndjson = String::from_utf8(out).unwrap();
+        assert_eq!(
+            ndjson,
+            "{\"CALL\":\"W1AW\"}\n{\"CALL\":\"K1AB\",\"MODE\":\"CW\"}\n"
+        );
+        assert_eq!(parse_adi(input).unwrap().to_ndjson(), ndjson);
+    }
+}
@@ -1,12 +1,183 @@
 //! ADIF Parser Library
 //!
 //! A library for parsing ADIF (Amateur Data Interchange Format) files.
-//! Supports the ADI format as specified in ADIF 3.1.6.
+//! Supports the ADI and ADX formats as specified in ADIF 3.1.6, and conversion
+//! to and from Cabrillo and EDI contest logs.
+//!
+//! Without the default `std` feature the core parser, writer and data types
+//! only need `core` and `alloc`; readers, file access and ADX require `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "adx")]
+mod adx;
+mod app;
+mod arrl_section;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "async")]
+mod async_reader;
+mod band;
+mod borrowed;
+mod cabrillo;
+#[cfg(feature = "std")]
+mod cache;
+mod callsign;
+mod clublog;
+#[cfg(any(feature = "arrow", feature = "polars", feature = "xlsx"))]
+mod columns;
+mod compact;
+mod contest;
+mod continent;
+mod coordinate;
+#[cfg(feature = "csv")]
+mod csv;
+mod date;
+#[cfg(feature = "chrono")]
+mod datetime;
+mod detect;
+mod dxcc;
+mod edi;
+mod encoding;
+mod enumeration;
+mod enums;
+mod eqsl;
 mod error;
+mod field_name;
+#[cfg(feature = "std")]
+mod file;
+mod fix;
+mod frequency;
+mod from_field;
+#[cfg(feature = "std")]
+mod geo;
+mod grid;
+mod header;
+#[cfg(feature = "json")]
+mod json;
+mod lotw;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod mode;
+#[cfg(feature = "n1mm")]
+mod n1mm;
+mod options;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parquet")]
+mod parquet;
 mod parser;
+#[cfg(feature = "polars")]
+mod polars;
+mod pota;
+mod power;
+mod profile;
+mod prop_mode;
+mod qsl;
+#[cfg(feature = "chrono")]
+mod qso;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "adx")]
+mod schema;
+mod sota;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod subdivision;
+mod table;
+#[cfg(feature = "template")]
+mod template;
+mod tokens;
 mod types;
+mod validate;
+mod version;
+mod warning;
+mod writer;
+#[cfg(feature = "wsjtx")]
+mod wsjtx;
+mod wwff;
+#[cfg(feature = "xlsx")]
+mod xlsx;
+#[cfg(feature = "yaml")]
+mod yaml;
 
+#[cfg(feature = "adx")]
+pub use adx::{parse_adx, write_adx};
+pub use arrl_section::ArrlSection;
+#[cfg(feature = "async")]
+pub use async_reader::AsyncRecordReader;
+pub use band::Band;
+pub use borrowed::{AdifFileRef, FieldRef, RecordRef};
+pub use cabrillo::{CabrilloOptions, parse_cabrillo, write_cabrillo};
+pub use callsign::Callsign;
+pub use compact::CompactRecord;
+pub use contest::{Contest, ContestId};
+pub use continent::Continent;
+pub use coordinate::Coordinate;
+#[cfg(feature = "csv")]
+pub use csv::{CsvOptions, parse_csv, parse_csv_with_options};
+pub use date::{AdifDate, AdifTime};
+pub use detect::{Format, detect_format, parse_auto};
+pub use dxcc::Dxcc;
+pub use edi::{EdiOptions, parse_edi, write_edi};
+pub use encoding::Encoding;
+pub use enums::{
+    AntPath, DownloadStatus, MorseKeyType, QslMedium, QsoComplete, Region, UploadStatus,
+};
 pub use error::AdifError;
-pub use parser::parse_adi;
-pub use types::{AdifFile, AdifHeader, DataType, Field, Record};
+pub use field_name::{FieldName, Interner};
+pub use fix::{Fix, fix_common_issues};
+pub use frequency::Frequency;
+pub use from_field::{FromField, ToField};
+#[cfg(feature = "std")]
+pub use geo::GeoOptions;
+pub use grid::GridSquare;
+pub use header::HeaderBuilder;
+#[cfg(feature = "json")]
+pub use json::NdjsonWriter;
+pub use lotw::check_lotw;
+#[cfg(feature = "mmap")]
+pub use mmap::MappedAdif;
+pub use mode::{Mode, Submode};
+#[cfg(feature = "n1mm")]
+pub use n1mm::{N1mmListener, N1mmMessage, decode_n1mm};
+pub use options::{DuplicateFields, IntlText, Limit, Limits, ParserOptions, UnknownFields};
+#[cfg(feature = "parallel")]
+pub use parallel::{parse_adi_parallel, parse_adi_parallel_with_options};
+#[cfg(feature = "parquet")]
+pub use parquet::ParquetWriter;
+pub use parser::{
+    parse_adi, parse_adi_borrowed, parse_adi_borrowed_with_options, parse_adi_lenient,
+    parse_adi_lossy, parse_adi_with_options, parse_adi_with_warnings,
+};
+#[cfg(feature = "std")]
+pub use parser::{parse_adi_bytes, parse_adi_bytes_with_options};
+pub use profile::{ExportFile, ExportProfile, Lint, LintLevel, ProfileExport, Rejection};
+pub use prop_mode::PropMode;
+pub use qsl::{QslSentStatus, QslStatus, QslVia};
+#[cfg(feature = "chrono")]
+pub use qso::Qso;
+#[cfg(feature = "std")]
+pub use reader::RecordReader;
+#[cfg(feature = "adx")]
+pub use schema::{SchemaViolation, parse_adx_validated, validate_adx, write_adx_validated};
+pub use sota::{SotaOptions, write_sota_csv};
+pub use subdivision::Subdivision;
+pub use table::{TableOptions, html_table, markdown_table};
+#[cfg(feature = "template")]
+pub use template::Template;
+pub use tokens::{Event, Tokens};
+pub use types::{AdifFile, AdifHeader, DataType, Field, RawField, Record};
+pub use validate::{
+    Enumeration, FieldSpec, FieldType, Rule, Rules, Severity, ValidationIssue, ValidationOptions,
+    ValidationReport, scrub_characters, validate, validate_with_options,
+};
+pub use version::AdifVersion;
+pub use warning::Warning;
+#[cfg(feature = "std")]
+pub use writer::AdiWriter;
+pub use writer::{IntlOutput, LessThan, WriteOptions, write_adi, write_adi_with_options};
+#[cfg(feature = "wsjtx")]
+pub use wsjtx::{WsjtxListener, decode_wsjtx};
@@ -0,0 +1,227 @@
+use crate::band::Band;
+use crate::from_field::FromField;
+use crate::mode::{Mode, Submode};
+use crate::profile::{
+    Lint, LintLevel, ProfileExport, check_date, check_time, required, subset, value,
+};
+use crate::types::{AdifFile, Record};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Station fields that must match the station location TQSL signs with
+const STATION_FIELDS: &[&str] = &[
+    "STATION_CALLSIGN",
+    "MY_DXCC",
+    "MY_GRIDSQUARE",
+    "MY_VUCC_GRIDS",
+    "MY_STATE",
+    "MY_CNTY",
+    "MY_CQ_ZONE",
+    "MY_ITU_ZONE",
+];
+
+/// Fields TQSL reads from an upload
+const FIELDS: &[&str] = &[
+    "STATION_CALLSIGN",
+    "CALL",
+    "QSO_DATE",
+    "TIME_ON",
+    "BAND",
+    "BAND_RX",
+    "FREQ",
+    "FREQ_RX",
+    "MODE",
+    "SUBMODE",
+    "PROP_MODE",
+    "SAT_NAME",
+    "MY_DXCC",
+    "MY_GRIDSQUARE",
+    "MY_VUCC_GRIDS",
+    "MY_STATE",
+    "MY_CNTY",
+    "MY_CQ_ZONE",
+    "MY_ITU_ZONE",
+];
+
+/// Export records for signing with TQSL, one file per station
+///
+/// TQSL signs a file with one station location, so records are grouped by
+/// their station fields (`STATION_CALLSIGN` and the `MY_` location
+/// fields). Files are named `lotw-CALL.adi`, with `/` in the callsign
+/// replaced by `-` and a number added for each further location of the
+/// same callsign.
+pub(crate) fn export(file: &AdifFile) -> ProfileExport {
+    let mut export = ProfileExport::default();
+    let mut stations: Vec<(Vec<Option<String>>, String)> = Vec::new();
+    for (index, record) in file.records.iter().enumerate() {
+        let problems = problems(record);
+        if !problems.is_empty() {
+            export.reject(index, problems.join("; "));
+            continue;
+        }
+        let station: Vec<Option<String>> = STATION_FIELDS
+            .iter()
+            .map(|name| value(record, name).map(str::to_ascii_uppercase))
+            .collect();
+        let name = match stations.iter().find(|(s, _)| *s == station) {
+            Some((_, name)) => name.clone(),
+            None => {
+                let call = station[0].as_deref().unwrap_or("").replace('/', "-");
+                let same_call = stations.iter().filter(|(s, _)| s[0] == station[0]).count();
+                let name = match (call.is_empty(), same_call) {
+                    (true, 0) => "lotw.adi".into(),
+                    (true, n) => format!("lotw-{}.adi", n + 1),
+                    (false, 0) => format!("lotw-{}.adi", call),
+                    (false, n) => format!("lotw-{}-{}.adi", call, n + 1),
+                };
+                stations.push((station, name.clone()));
+                name
+            }
+        };
+        export
+            .file_mut(name, file)
+            .records
+            .push(subset(record, FIELDS));
+    }
+    export
+}
+
+/// List what stops records from being signed by TQSL, in log order
+///
+/// Each record is checked for the fields TQSL requires (CALL, QSO_DATE,
+/// TIME_ON, BAND, MODE, and SAT_NAME for satellite QSOs) and for BAND,
+/// BAND_RX, MODE and SUBMODE values it won't accept. Unlike
+/// [`ExportProfile::lint`](crate::ExportProfile::lint), every problem with
+/// a record is listed, so that they can all be fixed at once.
+pub fn check_lotw(file: &AdifFile) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    for (index, record) in file.records.iter().enumerate() {
+        for message in problems(record) {
+            lints.push(Lint {
+                record: index,
+                level: LintLevel::Reject,
+                message,
+            });
+        }
+    }
+    lints
+}
+
+/// Check a record for the fields TQSL requires and the values it accepts
+fn problems(record: &Record) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut check = |result: core::result::Result<(), String>| {
+        if let Err(problem) = result {
+            problems.push(problem);
+        }
+    };
+    check(required(record, "CALL").map(drop));
+    check(required(record, "QSO_DATE").and_then(check_date));
+    check(required(record, "TIME_ON").and_then(check_time));
+    check(required(record, "BAND").and_then(|band| check_band("BAND", band)));
+    if let Some(band) = value(record, "BAND_RX") {
+        check(check_band("BAND_RX", band));
+    }
+    check(required(record, "MODE").and_then(|mode| check_mode(mode, value(record, "SUBMODE"))));
+    if value(record, "PROP_MODE").is_some_and(|p| p.eq_ignore_ascii_case("SAT")) {
+        check(required(record, "SAT_NAME").map(drop));
+    }
+    problems
+}
+
+/// Check that a band is one TQSL knows
+fn check_band(name: &str, band: &str) -> core::result::Result<(), String> {
+    match Band::from_field(band) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("{} '{}' isn't accepted by LoTW", name, band)),
+    }
+}
+
+/// Check that TQSL can map a mode, and a submode if given, to a LoTW mode
+///
+/// TQSL accepts the modes ADIF 3 moved to SUBMODE, such as `PSK31`, in
+/// MODE.
+fn check_mode(mode: &str, submode: Option<&str>) -> core::result::Result<(), String> {
+    let parsed = match Mode::from_field(mode) {
+        Ok(parsed) => parsed,
+        Err(_) if Submode::from_field(mode).is_ok() => return Ok(()),
+        Err(_) => return Err(format!("MODE '{}' isn't accepted by LoTW", mode)),
+    };
+    let Some(submode) = submode else {
+        return Ok(());
+    };
+    match Submode::from_field(submode) {
+        Ok(s) if s.mode() == parsed => Ok(()),
+        Ok(s) => Err(format!("SUBMODE '{}' isn't a submode of {}", s, parsed)),
+        Err(_) => Err(format!("SUBMODE '{}' isn't accepted by LoTW", submode)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use crate::profile::ExportProfile;
+
+    #[test]
+    fn test_export_lotw() {
+        let file = parse_adi(
+            "<STATION_CALLSIGN:4>W1AW<MY_GRIDSQUARE:4>FN31<CALL:4>K1AB<QSO_DATE:8>20240601\
+             <TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8<COMMENT:3>TNX<QSL_RCVD:1>Y<EOR>\
+             <STATION_CALLSIGN:4>W1AW<MY_GRIDSQUARE:4>FN42<CALL:4>K2AB<QSO_DATE:8>20240601\
+             <TIME_ON:4>1300<BAND:3>20m<MODE:3>FT8<EOR>\
+             <STATION_CALLSIGN:4>w1aw<MY_GRIDSQUARE:4>fn31<CALL:4>K3AB<QSO_DATE:8>20240601\
+             <TIME_ON:4>1400<BAND:3>20m<MODE:3>FT8<EOR>\
+             <CALL:4>K4AB<QSO_DATE:8>20240601<TIME_ON:4>1500<BAND:2>2m<MODE:2>FM\
+             <PROP_MODE:3>SAT<EOR>\
+             <CALL:4>K5AB<QSO_DATE:8>20240601<TIME_ON:4>1600<BAND:2>2m<EOR>",
+        )
+        .unwrap();
+        let export = ExportProfile::Lotw.export(&file);
+        let files: Vec<_> = export
+            .files
+            .iter()
+            .map(|f| (f.name.as_str(), f.file.records.len()))
+            .collect();
+        assert_eq!(files, [("lotw-W1AW.adi", 2), ("lotw-W1AW-2.adi", 1)]);
+        assert_eq!(
+            export.files[0].file.records[0].to_adi_string(),
+            "<STATION_CALLSIGN:4>W1AW<CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:4>1200\
+             <BAND:3>20m<MODE:3>FT8<MY_GRIDSQUARE:4>FN31<EOR>\n"
+        );
+        let reasons: Vec<_> = export.rejected.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            reasons,
+            ["record 3: missing SAT_NAME", "record 4: missing MODE"]
+        );
+    }
+
+    #[test]
+    fn test_check_lotw() {
+        let file = parse_adi(
+            "<CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:4>1200<BAND:3>20m<MODE:3>FT8<EOR>\
+             <CALL:4>K2AB<QSO_DATE:8>20240601<TIME_ON:4>1300<BAND:3>11m<MODE:5>PSK31<EOR>\
+             <QSO_DATE:6>240601<TIME_ON:4>1400<BAND:3>20m<BAND_RX:2>2m<MODE:3>FT9<EOR>\
+             <CALL:4>K4AB<QSO_DATE:8>20240601<TIME_ON:4>1500<BAND:3>20m<MODE:3>SSB\
+             <SUBMODE:3>FT4<EOR>",
+        )
+        .unwrap();
+        let lints: Vec<_> = check_lotw(&file).iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            lints,
+            [
+                "record 1: rejected: BAND '11m' isn't accepted by LoTW",
+                "record 2: rejected: missing CALL",
+                "record 2: rejected: invalid QSO_DATE '240601'",
+                "record 2: rejected: MODE 'FT9' isn't accepted by LoTW",
+                "record 3: rejected: SUBMODE 'FT4' isn't a submode of SSB",
+            ]
+        );
+        let export = ExportProfile::Lotw.export(&file);
+        assert_eq!(
+            export.rejected[1].reason,
+            "missing CALL; invalid QSO_DATE '240601'; MODE 'FT9' isn't accepted by LoTW"
+        );
+    }
+}
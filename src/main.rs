@@ -1,15 +1,16 @@
-use adif_parser::{AdifError, parse_adi};
+use adif_parser::{AdifError, AdifFile, Severity, validate};
 use std::env;
-use std::fs;
 use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
-        eprintln!("Usage: {} <adif_file>", args[0]);
-        eprintln!("  Parse and display contents of an ADIF file");
-        process::exit(1);
+        usage(&args[0], 1);
+    }
+
+    if args[1] == "validate" {
+        process::exit(run_validate(&args[0], &args[2..]));
     }
 
     let filename = &args[1];
@@ -21,8 +22,7 @@ fn main() {
 }
 
 fn run(filename: &str) -> Result<(), AdifError> {
-    let content = fs::read_to_string(filename)?;
-    let adif_file = parse_adi(&content)?;
+    let adif_file = AdifFile::from_path(filename)?;
 
     // Display header information
     println!("=== ADIF File: {} ===", filename);
@@ -99,6 +99,69 @@ fn run(filename: &str) -> Result<(), AdifError> {
     Ok(())
 }
 
+/// Print the usage and exit with `code`: 1 for the display command, and 2
+/// for `validate`, whose 1 means the file has problems
+fn usage(program: &str, code: i32) -> ! {
+    eprintln!("Usage: {} <adif_file>", program);
+    eprintln!("  Parse and display contents of an ADIF file");
+    eprintln!(
+        "       {} validate [--json] [--fail-on-warnings] <adif_file>",
+        program
+    );
+    eprintln!("  Check an ADIF file against the specification, exiting with 0 if it");
+    eprintln!("  passes, 1 if it has errors (or warnings, if asked) and 2 if it can't");
+    eprintln!("  be read");
+    process::exit(code);
+}
+
+/// Run the `validate` command, returning the exit code
+fn run_validate(program: &str, args: &[String]) -> i32 {
+    let mut json = false;
+    let mut fail_on = Severity::Error;
+    let mut filename = None;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--fail-on-warnings" => fail_on = Severity::Warning,
+            _ if arg.starts_with("--") || filename.is_some() => usage(program, 2),
+            _ => filename = Some(arg),
+        }
+    }
+    let Some(filename) = filename else {
+        usage(program, 2);
+    };
+
+    let adif_file = match AdifFile::from_path(filename) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 2;
+        }
+    };
+    let report = validate(&adif_file);
+
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        for issue in &report.issues {
+            println!("{}", issue);
+        }
+        println!(
+            "{}: {} error(s), {} warning(s), {} info",
+            filename,
+            report.count(Severity::Error),
+            report.count(Severity::Warning),
+            report.count(Severity::Info)
+        );
+    }
+
+    if report.at_least(fail_on).next().is_some() {
+        1
+    } else {
+        0
+    }
+}
+
 fn format_date(date: &str) -> String {
     if date.len() == 8 {
         format!("{}-{}-{}", &date[0..4], &date[4..6], &date[6..8])
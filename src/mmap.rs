@@ -0,0 +1,84 @@
+use crate::borrowed::AdifFileRef;
+use crate::error::{AdifError, Result};
+use crate::options::ParserOptions;
+use crate::parser::parse_adi_borrowed_with_options;
+use crate::types::AdifFile;
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+/// An ADI file mapped into memory
+///
+/// Parsing borrows field values straight from the mapping, so a
+/// multi-gigabyte log is never copied into the heap. The file must be
+/// UTF-8 and must not be modified while it is mapped.
+pub struct MappedAdif {
+    mmap: Mmap,
+}
+
+impl MappedAdif {
+    /// Map an ADI file into memory
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only; callers are documented not to
+        // modify the file while it is mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Self { mmap })
+    }
+
+    /// Get the mapped contents as text
+    pub fn as_str(&self) -> Result<&str> {
+        std::str::from_utf8(&self.mmap).map_err(|e| AdifError::ParseError {
+            position: e.valid_up_to(),
+            message: "Invalid UTF-8".to_string(),
+        })
+    }
+
+    /// Parse the mapped file with values borrowed from the mapping
+    pub fn parse(&self) -> Result<AdifFileRef<'_>> {
+        self.parse_with_options(&ParserOptions::default())
+    }
+
+    /// Parse the mapped file using the given options
+    pub fn parse_with_options(&self, options: &ParserOptions) -> Result<AdifFileRef<'_>> {
+        parse_adi_borrowed_with_options(self.as_str()?, options)
+    }
+}
+
+impl AdifFile {
+    /// Memory-map an ADI file for zero-copy parsing
+    pub fn open_mmap(path: impl AsRef<Path>) -> Result<MappedAdif> {
+        MappedAdif::open(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_open_mmap() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"<ADIF_VER:5>3.1.6<EOH>\n<CALL:4>W1AW<EOR>\n<CALL:4>K1AB<EOR>\n")
+            .unwrap();
+
+        let mapped = AdifFile::open_mmap(file.path()).unwrap();
+        let parsed = mapped.parse().unwrap();
+        assert_eq!(parsed.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.records[1].get_value("CALL"), Some("K1AB"));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"<NAME:4>Jos\xe9<EOR>").unwrap();
+
+        let mapped = MappedAdif::open(file.path()).unwrap();
+        assert!(matches!(
+            mapped.parse(),
+            Err(AdifError::ParseError { position: 11, .. })
+        ));
+    }
+}
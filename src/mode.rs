@@ -0,0 +1,536 @@
+use crate::enumeration::enumeration;
+use crate::from_field::FromField;
+use crate::types::{AdifFile, Record};
+
+enumeration! {
+    /// A mode, from the ADIF Mode enumeration
+    pub enum Mode for "MODE" {
+        Am => "AM",
+        Ardop => "ARDOP",
+        Atv => "ATV",
+        Chip => "CHIP",
+        Clo => "CLO",
+        Contesti => "CONTESTI",
+        Cw => "CW",
+        DigitalVoice => "DIGITALVOICE",
+        Domino => "DOMINO",
+        Dynamic => "DYNAMIC",
+        Fax => "FAX",
+        Fm => "FM",
+        Fsk441 => "FSK441",
+        Ft8 => "FT8",
+        Hell => "HELL",
+        Iscat => "ISCAT",
+        Jt4 => "JT4",
+        Jt6m => "JT6M",
+        Jt9 => "JT9",
+        Jt44 => "JT44",
+        Jt65 => "JT65",
+        Mfsk => "MFSK",
+        Msk144 => "MSK144",
+        Mt63 => "MT63",
+        Olivia => "OLIVIA",
+        Opera => "OPERA",
+        Pac => "PAC",
+        Pax => "PAX",
+        Pkt => "PKT",
+        Psk => "PSK",
+        Psk2k => "PSK2K",
+        Q15 => "Q15",
+        Qra64 => "QRA64",
+        Ros => "ROS",
+        Rtty => "RTTY",
+        Rttym => "RTTYM",
+        Ssb => "SSB",
+        Sstv => "SSTV",
+        T10 => "T10",
+        Thor => "THOR",
+        Thrb => "THRB",
+        Tor => "TOR",
+        V4 => "V4",
+        Voi => "VOI",
+        Winmor => "WINMOR",
+        Wspr => "WSPR",
+    }
+}
+
+enumeration! {
+    /// A submode, from the ADIF Submode enumeration
+    pub enum Submode for "SUBMODE" {
+        Ame => "AME",
+        Chip64 => "CHIP64",
+        Chip128 => "CHIP128",
+        Pcw => "PCW",
+        C4fm => "C4FM",
+        Dmr => "DMR",
+        Dstar => "DSTAR",
+        Freedv => "FREEDV",
+        M17 => "M17",
+        DomM => "DOM-M",
+        Dom4 => "DOM4",
+        Dom5 => "DOM5",
+        Dom8 => "DOM8",
+        Dom11 => "DOM11",
+        Dom16 => "DOM16",
+        Dom22 => "DOM22",
+        Dom44 => "DOM44",
+        Dom88 => "DOM88",
+        Dominoex => "DOMINOEX",
+        Dominof => "DOMINOF",
+        VaraHf => "VARA HF",
+        VaraSatellite => "VARA SATELLITE",
+        VaraFm1200 => "VARA FM 1200",
+        VaraFm9600 => "VARA FM 9600",
+        Fmhell => "FMHELL",
+        Fskhell => "FSKHELL",
+        Hell80 => "HELL80",
+        Hellx5 => "HELLX5",
+        Hellx9 => "HELLX9",
+        Hfsk => "HFSK",
+        Pskhell => "PSKHELL",
+        Slowhell => "SLOWHELL",
+        IscatA => "ISCAT-A",
+        IscatB => "ISCAT-B",
+        Jt4A => "JT4A",
+        Jt4B => "JT4B",
+        Jt4C => "JT4C",
+        Jt4D => "JT4D",
+        Jt4E => "JT4E",
+        Jt4F => "JT4F",
+        Jt4G => "JT4G",
+        Jt9_1 => "JT9-1",
+        Jt9_2 => "JT9-2",
+        Jt9_5 => "JT9-5",
+        Jt9_10 => "JT9-10",
+        Jt9_30 => "JT9-30",
+        Jt9A => "JT9A",
+        Jt9B => "JT9B",
+        Jt9C => "JT9C",
+        Jt9D => "JT9D",
+        Jt9E => "JT9E",
+        Jt9EFast => "JT9E FAST",
+        Jt9F => "JT9F",
+        Jt9FFast => "JT9F FAST",
+        Jt9G => "JT9G",
+        Jt9GFast => "JT9G FAST",
+        Jt9H => "JT9H",
+        Jt9HFast => "JT9H FAST",
+        Jt65A => "JT65A",
+        Jt65B => "JT65B",
+        Jt65B2 => "JT65B2",
+        Jt65C => "JT65C",
+        Jt65C2 => "JT65C2",
+        Fsqcall => "FSQCALL",
+        Fst4 => "FST4",
+        Fst4W => "FST4W",
+        Ft4 => "FT4",
+        Js8 => "JS8",
+        Jtms => "JTMS",
+        Mfsk4 => "MFSK4",
+        Mfsk8 => "MFSK8",
+        Mfsk11 => "MFSK11",
+        Mfsk16 => "MFSK16",
+        Mfsk22 => "MFSK22",
+        Mfsk31 => "MFSK31",
+        Mfsk32 => "MFSK32",
+        Mfsk64 => "MFSK64",
+        Mfsk64L => "MFSK64L",
+        Mfsk128 => "MFSK128",
+        Mfsk128L => "MFSK128L",
+        Q65 => "Q65",
+        Olivia4_125 => "OLIVIA 4/125",
+        Olivia4_250 => "OLIVIA 4/250",
+        Olivia8_250 => "OLIVIA 8/250",
+        Olivia8_500 => "OLIVIA 8/500",
+        Olivia16_500 => "OLIVIA 16/500",
+        Olivia16_1000 => "OLIVIA 16/1000",
+        Olivia32_1000 => "OLIVIA 32/1000",
+        OperaBeacon => "OPERA-BEACON",
+        OperaQso => "OPERA-QSO",
+        Pac2 => "PAC2",
+        Pac3 => "PAC3",
+        Pac4 => "PAC4",
+        Pax2 => "PAX2",
+        Psk8_125 => "8PSK125",
+        Psk8_125F => "8PSK125F",
+        Psk8_125Fl => "8PSK125FL",
+        Psk8_250 => "8PSK250",
+        Psk8_250F => "8PSK250F",
+        Psk8_250Fl => "8PSK250FL",
+        Psk8_500 => "8PSK500",
+        Psk8_500F => "8PSK500F",
+        Psk8_1000 => "8PSK1000",
+        Psk8_1000F => "8PSK1000F",
+        Psk8_1200F => "8PSK1200F",
+        Fsk31 => "FSK31",
+        Psk10 => "PSK10",
+        Psk31 => "PSK31",
+        Psk63 => "PSK63",
+        Psk63F => "PSK63F",
+        Psk63Rc4 => "PSK63RC4",
+        Psk63Rc5 => "PSK63RC5",
+        Psk63Rc10 => "PSK63RC10",
+        Psk63Rc20 => "PSK63RC20",
+        Psk63Rc32 => "PSK63RC32",
+        Psk125 => "PSK125",
+        Psk125C12 => "PSK125C12",
+        Psk125R => "PSK125R",
+        Psk125Rc10 => "PSK125RC10",
+        Psk125Rc12 => "PSK125RC12",
+        Psk125Rc16 => "PSK125RC16",
+        Psk125Rc4 => "PSK125RC4",
+        Psk125Rc5 => "PSK125RC5",
+        Psk250 => "PSK250",
+        Psk250C6 => "PSK250C6",
+        Psk250R => "PSK250R",
+        Psk250Rc2 => "PSK250RC2",
+        Psk250Rc3 => "PSK250RC3",
+        Psk250Rc5 => "PSK250RC5",
+        Psk250Rc6 => "PSK250RC6",
+        Psk250Rc7 => "PSK250RC7",
+        Psk500 => "PSK500",
+        Psk500C2 => "PSK500C2",
+        Psk500C4 => "PSK500C4",
+        Psk500R => "PSK500R",
+        Psk500Rc2 => "PSK500RC2",
+        Psk500Rc3 => "PSK500RC3",
+        Psk500Rc4 => "PSK500RC4",
+        Psk800C2 => "PSK800C2",
+        Psk800Rc2 => "PSK800RC2",
+        Psk1000 => "PSK1000",
+        Psk1000C2 => "PSK1000C2",
+        Psk1000R => "PSK1000R",
+        Psk1000Rc2 => "PSK1000RC2",
+        Pskam10 => "PSKAM10",
+        Pskam31 => "PSKAM31",
+        Pskam50 => "PSKAM50",
+        Pskfec31 => "PSKFEC31",
+        Qpsk31 => "QPSK31",
+        Qpsk63 => "QPSK63",
+        Qpsk125 => "QPSK125",
+        Qpsk250 => "QPSK250",
+        Qpsk500 => "QPSK500",
+        Sim31 => "SIM31",
+        Qra64A => "QRA64A",
+        Qra64B => "QRA64B",
+        Qra64C => "QRA64C",
+        Qra64D => "QRA64D",
+        Qra64E => "QRA64E",
+        RosEme => "ROS-EME",
+        RosHf => "ROS-HF",
+        RosMf => "ROS-MF",
+        Asci => "ASCI",
+        Lsb => "LSB",
+        Usb => "USB",
+        ThorM => "THOR-M",
+        Thor4 => "THOR4",
+        Thor5 => "THOR5",
+        Thor8 => "THOR8",
+        Thor11 => "THOR11",
+        Thor16 => "THOR16",
+        Thor22 => "THOR22",
+        Thor25X4 => "THOR25X4",
+        Thor50X1 => "THOR50X1",
+        Thor50X2 => "THOR50X2",
+        Thor100 => "THOR100",
+        Thrbx => "THRBX",
+        Thrbx1 => "THRBX1",
+        Thrbx2 => "THRBX2",
+        Thrbx4 => "THRBX4",
+        Throb1 => "THROB1",
+        Throb2 => "THROB2",
+        Throb4 => "THROB4",
+        Amtorfec => "AMTORFEC",
+        Gtor => "GTOR",
+        Navtex => "NAVTEX",
+        Sitorb => "SITORB",
+    }
+}
+
+impl Submode {
+    /// Get the mode the submode belongs to
+    pub fn mode(self) -> Mode {
+        match self {
+            Submode::Ame => Mode::Am,
+            Submode::Chip64 | Submode::Chip128 => Mode::Chip,
+            Submode::Pcw => Mode::Cw,
+            Submode::C4fm | Submode::Dmr | Submode::Dstar | Submode::Freedv | Submode::M17 => {
+                Mode::DigitalVoice
+            }
+            Submode::DomM
+            | Submode::Dom4
+            | Submode::Dom5
+            | Submode::Dom8
+            | Submode::Dom11
+            | Submode::Dom16
+            | Submode::Dom22
+            | Submode::Dom44
+            | Submode::Dom88
+            | Submode::Dominoex
+            | Submode::Dominof => Mode::Domino,
+            Submode::VaraHf
+            | Submode::VaraSatellite
+            | Submode::VaraFm1200
+            | Submode::VaraFm9600 => Mode::Dynamic,
+            Submode::Fmhell
+            | Submode::Fskhell
+            | Submode::Hell80
+            | Submode::Hellx5
+            | Submode::Hellx9
+            | Submode::Hfsk
+            | Submode::Pskhell
+            | Submode::Slowhell => Mode::Hell,
+            Submode::IscatA | Submode::IscatB => Mode::Iscat,
+            Submode::Jt4A
+            | Submode::Jt4B
+            | Submode::Jt4C
+            | Submode::Jt4D
+            | Submode::Jt4E
+            | Submode::Jt4F
+            | Submode::Jt4G => Mode::Jt4,
+            Submode::Jt9_1
+            | Submode::Jt9_2
+            | Submode::Jt9_5
+            | Submode::Jt9_10
+            | Submode::Jt9_30
+            | Submode::Jt9A
+            | Submode::Jt9B
+            | Submode::Jt9C
+            | Submode::Jt9D
+            | Submode::Jt9E
+            | Submode::Jt9EFast
+            | Submode::Jt9F
+            | Submode::Jt9FFast
+            | Submode::Jt9G
+            | Submode::Jt9GFast
+            | Submode::Jt9H
+            | Submode::Jt9HFast => Mode::Jt9,
+            Submode::Jt65A
+            | Submode::Jt65B
+            | Submode::Jt65B2
+            | Submode::Jt65C
+            | Submode::Jt65C2 => Mode::Jt65,
+            Submode::Fsqcall
+            | Submode::Fst4
+            | Submode::Fst4W
+            | Submode::Ft4
+            | Submode::Js8
+            | Submode::Jtms
+            | Submode::Mfsk4
+            | Submode::Mfsk8
+            | Submode::Mfsk11
+            | Submode::Mfsk16
+            | Submode::Mfsk22
+            | Submode::Mfsk31
+            | Submode::Mfsk32
+            | Submode::Mfsk64
+            | Submode::Mfsk64L
+            | Submode::Mfsk128
+            | Submode::Mfsk128L
+            | Submode::Q65 => Mode::Mfsk,
+            Submode::Olivia4_125
+            | Submode::Olivia4_250
+            | Submode::Olivia8_250
+            | Submode::Olivia8_500
+            | Submode::Olivia16_500
+            | Submode::Olivia16_1000
+            | Submode::Olivia32_1000 => Mode::Olivia,
+            Submode::OperaBeacon | Submode::OperaQso => Mode::Opera,
+            Submode::Pac2 | Submode::Pac3 | Submode::Pac4 => Mode::Pac,
+            Submode::Pax2 => Mode::Pax,
+            Submode::Psk8_125
+            | Submode::Psk8_125F
+            | Submode::Psk8_125Fl
+            | Submode::Psk8_250
+            | Submode::Psk8_250F
+            | Submode::Psk8_250Fl
+            | Submode::Psk8_500
+            | Submode::Psk8_500F
+            | Submode::Psk8_1000
+            | Submode::Psk8_1000F
+            | Submode::Psk8_1200F
+            | Submode::Fsk31
+            | Submode::Psk10
+            | Submode::Psk31
+            | Submode::Psk63
+            | Submode::Psk63F
+            | Submode::Psk63Rc4
+            | Submode::Psk63Rc5
+            | Submode::Psk63Rc10
+            | Submode::Psk63Rc20
+            | Submode::Psk63Rc32
+            | Submode::Psk125
+            | Submode::Psk125C12
+            | Submode::Psk125R
+            | Submode::Psk125Rc10
+            | Submode::Psk125Rc12
+            | Submode::Psk125Rc16
+            | Submode::Psk125Rc4
+            | Submode::Psk125Rc5
+            | Submode::Psk250
+            | Submode::Psk250C6
+            | Submode::Psk250R
+            | Submode::Psk250Rc2
+            | Submode::Psk250Rc3
+            | Submode::Psk250Rc5
+            | Submode::Psk250Rc6
+            | Submode::Psk250Rc7
+            | Submode::Psk500
+            | Submode::Psk500C2
+            | Submode::Psk500C4
+            | Submode::Psk500R
+            | Submode::Psk500Rc2
+            | Submode::Psk500Rc3
+            | Submode::Psk500Rc4
+            | Submode::Psk800C2
+            | Submode::Psk800Rc2
+            | Submode::Psk1000
+            | Submode::Psk1000C2
+            | Submode::Psk1000R
+            | Submode::Psk1000Rc2
+            | Submode::Pskam10
+            | Submode::Pskam31
+            | Submode::Pskam50
+            | Submode::Pskfec31
+            | Submode::Qpsk31
+            | Submode::Qpsk63
+            | Submode::Qpsk125
+            | Submode::Qpsk250
+            | Submode::Qpsk500
+            | Submode::Sim31 => Mode::Psk,
+            Submode::Qra64A
+            | Submode::Qra64B
+            | Submode::Qra64C
+            | Submode::Qra64D
+            | Submode::Qra64E => Mode::Qra64,
+            Submode::RosEme | Submode::RosHf | Submode::RosMf => Mode::Ros,
+            Submode::Asci => Mode::Rtty,
+            Submode::Lsb | Submode::Usb => Mode::Ssb,
+            Submode::ThorM
+            | Submode::Thor4
+            | Submode::Thor5
+            | Submode::Thor8
+            | Submode::Thor11
+            | Submode::Thor16
+            | Submode::Thor22
+            | Submode::Thor25X4
+            | Submode::Thor50X1
+            | Submode::Thor50X2
+            | Submode::Thor100 => Mode::Thor,
+            Submode::Thrbx
+            | Submode::Thrbx1
+            | Submode::Thrbx2
+            | Submode::Thrbx4
+            | Submode::Throb1
+            | Submode::Throb2
+            | Submode::Throb4 => Mode::Thrb,
+            Submode::Amtorfec | Submode::Gtor | Submode::Navtex | Submode::Sitorb => Mode::Tor,
+        }
+    }
+}
+
+impl Mode {
+    /// Get the submodes of the mode
+    pub fn submodes(self) -> impl Iterator<Item = Submode> {
+        Submode::ALL
+            .iter()
+            .copied()
+            .filter(move |s| s.mode() == self)
+    }
+}
+
+impl Record {
+    /// Get the submode written in MODE, if MODE holds one of the values
+    /// that ADIF 3 moved to SUBMODE, such as `PSK31` or `JT65A`
+    pub fn deprecated_mode(&self) -> Option<Submode> {
+        let mode = self.get_value("MODE")?;
+        if Mode::from_field(mode).is_ok() {
+            return None;
+        }
+        Submode::from_field(mode).ok()
+    }
+
+    /// Rewrite a deprecated MODE, such as `PSK31`, as MODE `PSK` with
+    /// SUBMODE `PSK31`, returning the submode
+    ///
+    /// An existing SUBMODE is kept. Returns `None` and leaves the record
+    /// alone if MODE isn't deprecated.
+    pub fn normalize_mode(&mut self) -> Option<Submode> {
+        let submode = self.deprecated_mode()?;
+        self.set("MODE", submode.mode().as_str());
+        if self
+            .get_value("SUBMODE")
+            .is_none_or(|s| s.trim().is_empty())
+        {
+            self.set("SUBMODE", submode.as_str());
+        }
+        Some(submode)
+    }
+}
+
+impl AdifFile {
+    /// Rewrite deprecated MODE values in every record, as
+    /// [`Record::normalize_mode`] does, returning how many changed
+    ///
+    /// To only report them instead, [`validate`](crate::validate) warns
+    /// about each under the `deprecated-mode` rule.
+    pub fn normalize_modes(&mut self) -> usize {
+        self.records
+            .iter_mut()
+            .filter_map(Record::normalize_mode)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode() {
+        assert_eq!("ft8".parse::<Mode>().unwrap(), Mode::Ft8);
+        assert_eq!(Mode::DigitalVoice.to_string(), "DIGITALVOICE");
+        assert!("FT4".parse::<Mode>().is_err());
+        assert_eq!(Mode::ALL.len(), 46);
+    }
+
+    #[test]
+    fn test_submode() {
+        assert_eq!("ft4".parse::<Submode>().unwrap().mode(), Mode::Mfsk);
+        assert_eq!(Submode::Olivia8_250.to_string(), "OLIVIA 8/250");
+        assert_eq!("8psk125fl".parse::<Submode>().unwrap(), Submode::Psk8_125Fl);
+        assert_eq!(Submode::Usb.mode(), Mode::Ssb);
+        assert_eq!(
+            Mode::Ssb.submodes().collect::<Vec<_>>(),
+            [Submode::Lsb, Submode::Usb]
+        );
+        assert_eq!(Mode::Ft8.submodes().count(), 0);
+        assert!(
+            Submode::ALL
+                .iter()
+                .all(|s| s.as_str().parse::<Mode>().is_err())
+        );
+    }
+
+    #[test]
+    fn test_normalize_modes() {
+        let mut file = crate::parse_adi(
+            "<MODE:5>psk31<EOR><MODE:5>JT65A<SUBMODE:6>JT65B2<EOR>\
+             <MODE:3>FT8<EOR><MODE:6>FSK441<EOR><MODE:3>XYZ<EOR>",
+        )
+        .unwrap();
+        assert_eq!(file.records[0].deprecated_mode(), Some(Submode::Psk31));
+        assert_eq!(file.normalize_modes(), 2);
+        assert_eq!(
+            file.records[0].to_adi_string(),
+            "<MODE:3>PSK<SUBMODE:5>PSK31<EOR>\n"
+        );
+        assert_eq!(
+            file.records[1].to_adi_string(),
+            "<MODE:4>JT65<SUBMODE:6>JT65B2<EOR>\n"
+        );
+        assert_eq!(file.records[2].mode(), Some("FT8"));
+        assert_eq!(file.records[3].mode(), Some("FSK441"));
+        assert_eq!(file.records[4].normalize_mode(), None);
+    }
+}
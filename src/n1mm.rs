@@ -0,0 +1,340 @@
+//! Decoding of N1MM Logger+ UDP contact broadcasts
+
+use crate::error::{AdifError, Result};
+use crate::frequency::Frequency;
+use crate::mode::Submode;
+use crate::types::{Field, Record};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use std::net::{ToSocketAddrs, UdpSocket};
+
+/// Largest datagram N1MM sends
+const MAX_DATAGRAM: usize = 65_536;
+
+/// ADIF bands by N1MM band, in MHz
+const BANDS: &[(&str, &str)] = &[
+    ("1.8", "160m"),
+    ("3.5", "80m"),
+    ("5", "60m"),
+    ("7", "40m"),
+    ("10", "30m"),
+    ("14", "20m"),
+    ("18", "17m"),
+    ("21", "15m"),
+    ("24", "12m"),
+    ("28", "10m"),
+    ("50", "6m"),
+    ("70", "4m"),
+    ("144", "2m"),
+    ("222", "1.25m"),
+    ("420", "70cm"),
+    ("902", "33cm"),
+    ("1240", "23cm"),
+    ("2300", "13cm"),
+    ("3300", "9cm"),
+    ("5650", "6cm"),
+    ("10000", "3cm"),
+    ("24000", "1.25cm"),
+];
+
+/// Text elements copied to ADIF fields as they are
+const TEXT_FIELDS: &[(&str, &str)] = &[
+    ("contestname", "CONTEST_ID"),
+    ("mycall", "STATION_CALLSIGN"),
+    ("operator", "OPERATOR"),
+    ("call", "CALL"),
+    ("snt", "RST_SENT"),
+    ("rcv", "RST_RCVD"),
+    ("gridsquare", "GRIDSQUARE"),
+    ("exchange1", "SRX_STRING"),
+    ("section", "ARRL_SECT"),
+    ("prec", "PRECEDENCE"),
+    ("name", "NAME"),
+    ("qth", "QTH"),
+    ("power", "RX_PWR"),
+    ("comment", "COMMENT"),
+    ("continent", "CONT"),
+    ("wpxprefix", "PFX"),
+    ("ID", "APP_N1MM_ID"),
+];
+
+/// Numeric elements copied to ADIF fields unless zero
+const NUMBER_FIELDS: &[(&str, &str)] = &[("sntnr", "STX"), ("rcvnr", "SRX"), ("ck", "CHECK")];
+
+/// A contact message broadcast by N1MM Logger+
+#[derive(Debug, Clone)]
+pub enum N1mmMessage {
+    /// A contact was logged (`contactinfo`)
+    Added(Record),
+    /// A logged contact was edited (`contactreplace`)
+    Replaced(Record),
+    /// A logged contact was deleted (`contactdelete`)
+    Deleted(Record),
+}
+
+impl N1mmMessage {
+    /// Get the contact, whatever happened to it
+    pub fn record(&self) -> &Record {
+        match self {
+            N1mmMessage::Added(record)
+            | N1mmMessage::Replaced(record)
+            | N1mmMessage::Deleted(record) => record,
+        }
+    }
+}
+
+/// Decode an N1MM Logger+ UDP datagram into a contact message
+///
+/// Returns `None` for messages other than contacts (such as radio info
+/// and spots). Contest exchanges are mapped onto ADIF fields: serial
+/// numbers to STX and SRX, `exchange1` to SRX_STRING, section to
+/// ARRL_SECT, precedence and check to PRECEDENCE and CHECK, power to
+/// RX_PWR, and zone to ITUZ in IARU contests and CQZ otherwise. The
+/// N1MM contact ID, which replace and delete messages refer to, is kept
+/// in APP_N1MM_ID.
+pub fn decode_n1mm(datagram: &[u8]) -> Result<Option<N1mmMessage>> {
+    let input = core::str::from_utf8(datagram).map_err(|e| AdifError::ParseError {
+        position: e.valid_up_to(),
+        message: "invalid UTF-8".into(),
+    })?;
+    let (root, elements) = elements(input)?;
+    let message: fn(Record) -> N1mmMessage = match root.as_str() {
+        "contactinfo" => N1mmMessage::Added,
+        "contactreplace" => N1mmMessage::Replaced,
+        "contactdelete" => N1mmMessage::Deleted,
+        _ => return Ok(None),
+    };
+    let get = |name: &str| {
+        elements
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.trim())
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut record = Record::new();
+    let mut add = |name: &str, value: &str| record.add_field(Field::new(name, value));
+    if let Some((date, time)) = get("timestamp").and_then(|t| t.split_once(' ')) {
+        add("QSO_DATE", &date.replace('-', ""));
+        add("TIME_ON", &time.replace(':', ""));
+    }
+    for (element, field) in TEXT_FIELDS {
+        if let Some(value) = get(element) {
+            add(field, value);
+        }
+    }
+    for (element, field) in NUMBER_FIELDS {
+        if let Some(value) = get(element).filter(|v| !is_zero(v)) {
+            add(field, value);
+        }
+    }
+    if let Some(zone) = get("zone").filter(|z| !is_zero(z)) {
+        let iaru = get("contestname").is_some_and(|c| c.starts_with("IARU"));
+        add(if iaru { "ITUZ" } else { "CQZ" }, zone);
+    }
+    if let Some(band) = get("band").and_then(|b| BANDS.iter().find(|(n, _)| *n == b)) {
+        add("BAND", band.1);
+    }
+    for (element, field) in [("txfreq", "FREQ"), ("rxfreq", "FREQ_RX")] {
+        // Frequencies are in units of 10 Hz
+        if let Some(freq) = get(element).and_then(|f| f.parse::<u64>().ok())
+            && freq > 0
+        {
+            add(field, &Frequency::from_hz(freq * 10).to_string());
+        }
+    }
+    if let Some(mode) = get("mode") {
+        match mode.parse::<Submode>() {
+            Ok(submode) => {
+                add("MODE", submode.mode().as_str());
+                add("SUBMODE", mode);
+            }
+            Err(_) => add("MODE", mode),
+        }
+    }
+    Ok(Some(message(record)))
+}
+
+/// Check whether a number N1MM sends for a missing value is zero
+fn is_zero(value: &str) -> bool {
+    value.bytes().all(|b| b == b'0')
+}
+
+/// Read the root element name and the text of its child elements
+fn elements(input: &str) -> Result<(String, Vec<(String, String)>)> {
+    let mut reader = Reader::from_str(input);
+    let xml_error = |reader: &Reader<&[u8]>, e: &dyn core::fmt::Display| AdifError::ParseError {
+        position: reader.error_position() as usize,
+        message: format!("XML error: {}", e),
+    };
+    let mut root = None;
+    let mut depth = 0;
+    let mut elements = Vec::new();
+    loop {
+        match reader.read_event().map_err(|e| xml_error(&reader, &e))? {
+            Event::Start(e) => {
+                let name = e.name().as_ref().to_string();
+                match depth {
+                    0 => root = Some(name),
+                    1 => elements.push((name, String::new())),
+                    _ => {}
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth -= 1,
+            Event::Text(e) if depth == 2 => {
+                if let Some((_, value)) = elements.last_mut() {
+                    value.push_str(&e.xml10_content());
+                }
+            }
+            Event::GeneralRef(e) if depth == 2 => {
+                let resolved = match e.resolve_char_ref().map_err(|e| xml_error(&reader, &e))? {
+                    Some(c) => c.to_string(),
+                    None => resolve_predefined_entity(&e)
+                        .unwrap_or_default()
+                        .to_string(),
+                };
+                if let Some((_, value)) = elements.last_mut() {
+                    value.push_str(&resolved);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let root = root.ok_or(AdifError::UnexpectedEof(input.len()))?;
+    Ok((root, elements))
+}
+
+/// Listener for contacts broadcast by N1MM Logger+ over UDP
+///
+/// N1MM sends contact broadcasts to UDP port 12060 by default, once
+/// enabled in its broadcast data configuration.
+#[derive(Debug)]
+pub struct N1mmListener {
+    socket: UdpSocket,
+    buf: Vec<u8>,
+}
+
+impl N1mmListener {
+    /// Listen on a local address, such as `0.0.0.0:12060`
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<Self> {
+        Ok(Self::from_socket(UdpSocket::bind(addr)?))
+    }
+
+    /// Listen on an already configured socket
+    pub fn from_socket(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            buf: vec![0; MAX_DATAGRAM],
+        }
+    }
+
+    /// Get the underlying socket, e.g. to set a read timeout
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Wait for the next contact message, skipping other messages
+    pub fn recv(&mut self) -> Result<N1mmMessage> {
+        loop {
+            let len = self.socket.recv(&mut self.buf)?;
+            if let Some(message) = decode_n1mm(&self.buf[..len])? {
+                return Ok(message);
+            }
+        }
+    }
+}
+
+impl Iterator for N1mmListener {
+    type Item = Result<N1mmMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.recv())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTACT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<contactinfo>
+  <app>N1MM</app>
+  <contestname>CQWWCW</contestname>
+  <contestnr>73</contestnr>
+  <timestamp>2024-11-23 16:43:38</timestamp>
+  <mycall>W1AW</mycall>
+  <band>14</band>
+  <rxfreq>1402519</rxfreq>
+  <txfreq>1402519</txfreq>
+  <operator>K1AB</operator>
+  <mode>CW</mode>
+  <call>DL1ABC</call>
+  <continent>EU</continent>
+  <snt>599</snt>
+  <sntnr>0</sntnr>
+  <rcv>599</rcv>
+  <rcvnr>0</rcvnr>
+  <gridsquare></gridsquare>
+  <comment>Q&amp;A</comment>
+  <zone>14</zone>
+  <ck>0</ck>
+  <ID>f9ffac4fcd3e479ca86e137df1338531</ID>
+</contactinfo>"#;
+
+    #[test]
+    fn test_decode_n1mm() {
+        let message = decode_n1mm(CONTACT.as_bytes()).unwrap().unwrap();
+        assert!(matches!(message, N1mmMessage::Added(_)));
+        assert_eq!(
+            message.record().to_adi_string(),
+            "<QSO_DATE:8>20241123<TIME_ON:6>164338<CONTEST_ID:6>CQWWCW\
+             <STATION_CALLSIGN:4>W1AW<OPERATOR:4>K1AB<CALL:6>DL1ABC<RST_SENT:3>599\
+             <RST_RCVD:3>599<COMMENT:3>Q&A<CONT:2>EU\
+             <APP_N1MM_ID:32>f9ffac4fcd3e479ca86e137df1338531<CQZ:2>14<BAND:3>20m\
+             <FREQ:8>14.02519<FREQ_RX:8>14.02519<MODE:2>CW<EOR>\n"
+        );
+    }
+
+    #[test]
+    fn test_decode_n1mm_exchange() {
+        let replace = "<contactreplace><contestname>IARU-HF</contestname>\
+             <timestamp>2024-07-13 12:00:00</timestamp><call>K1AB</call>\
+             <mode>USB</mode><sntnr>12</sntnr><rcvnr>7</rcvnr><zone>8</zone>\
+             <exchange1>ARRL</exchange1></contactreplace>";
+        let message = decode_n1mm(replace.as_bytes()).unwrap().unwrap();
+        let N1mmMessage::Replaced(record) = message else {
+            panic!("expected a replace message");
+        };
+        assert_eq!(record.get_value("STX"), Some("12"));
+        assert_eq!(record.get_value("SRX"), Some("7"));
+        assert_eq!(record.get_value("ITUZ"), Some("8"));
+        assert_eq!(record.get_value("SRX_STRING"), Some("ARRL"));
+        assert_eq!(record.get_value("MODE"), Some("SSB"));
+        assert_eq!(record.get_value("SUBMODE"), Some("USB"));
+    }
+
+    #[test]
+    fn test_decode_n1mm_other_messages() {
+        let radio = "<RadioInfo><Freq>1402519</Freq></RadioInfo>";
+        assert!(decode_n1mm(radio.as_bytes()).unwrap().is_none());
+        assert!(matches!(
+            decode_n1mm(b"<contactinfo><call>K1AB</mode></contactinfo>"),
+            Err(AdifError::ParseError { .. })
+        ));
+        assert!(decode_n1mm(b"").is_err());
+    }
+
+    #[test]
+    fn test_listener() {
+        let mut listener = N1mmListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.socket().local_addr().unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender.send_to(b"<spot></spot>", addr).unwrap();
+        sender.send_to(CONTACT.as_bytes(), addr).unwrap();
+        let message = listener.recv().unwrap();
+        assert_eq!(message.record().call(), Some("DL1ABC"));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::encoding::Encoding;
+use crate::error::{AdifError, Result};
+use crate::types::DataType;
+use crate::validate::is_known_field;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Options controlling how ADI data is parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// Skip malformed fields and resynchronize on the next `<` instead of
+    /// failing the whole parse
+    pub lenient: bool,
+    /// Keep field names as written instead of converting them to uppercase.
+    /// Lookups by name remain case-insensitive either way.
+    pub preserve_case: bool,
+    /// Trim leading and trailing whitespace from field values
+    pub trim_values: bool,
+    /// Retain the original source text of every field, record terminator
+    /// and trailer so that writing reproduces the input byte-for-byte
+    /// (fidelity mode). Only applies when parsing from a `&str`.
+    pub preserve_raw: bool,
+    /// Character encoding used to decode byte input. Ignored when parsing
+    /// from a `&str`, which is already decoded.
+    pub encoding: Encoding,
+    /// Resource limits; exceeding one fails the parse even in lenient mode
+    pub limits: Limits,
+    /// What to do with fields the specification doesn't define, other than
+    /// `APP_` fields and those named by a USERDEF header field
+    pub unknown_fields: UnknownFields,
+    /// What to do when a record repeats a field
+    pub duplicate_fields: DuplicateFields,
+    /// What to do with international text, which ADI doesn't allow
+    pub intl_text: IntlText,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl ParserOptions {
+    /// Options for strict parsing, which fails on the first malformed field
+    pub fn strict() -> Self {
+        Self {
+            lenient: false,
+            preserve_case: false,
+            trim_values: false,
+            preserve_raw: false,
+            encoding: Encoding::Utf8,
+            limits: Limits::none(),
+            unknown_fields: UnknownFields::Allow,
+            duplicate_fields: DuplicateFields::KeepAll,
+            intl_text: IntlText::Allow,
+        }
+    }
+
+    /// Options for lenient parsing, which skips malformed fields
+    pub fn lenient() -> Self {
+        Self {
+            lenient: true,
+            ..Self::strict()
+        }
+    }
+}
+
+/// How to treat fields that the specification doesn't define
+///
+/// `APP_` fields, `USERDEFn` header fields and the record fields they name
+/// are always allowed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UnknownFields {
+    /// Keep them silently
+    #[default]
+    Allow,
+    /// Keep them, but report each as a warning from
+    /// [`parse_adi_with_warnings`](crate::parse_adi_with_warnings) and
+    /// when validating
+    Warn,
+    /// Fail the parse with [`AdifError::UnknownField`], or drop the field
+    /// in lenient mode; report each as an error when validating
+    Error,
+}
+
+impl UnknownFields {
+    /// Fail with [`AdifError::UnknownField`] if unknown fields are errors
+    /// and `name` is one, given the field names defined by USERDEFn
+    /// header fields
+    pub(crate) fn check(self, name: &str, userdefs: &[String], position: usize) -> Result<()> {
+        if self == UnknownFields::Error && !is_known_field(name, userdefs) {
+            return Err(AdifError::UnknownField {
+                position,
+                name: name.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// How to treat a field that appears more than once in a record
+///
+/// Names are compared case-insensitively.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DuplicateFields {
+    /// Keep every occurrence, in order
+    #[default]
+    KeepAll,
+    /// Keep the first occurrence and drop the rest
+    KeepFirst,
+    /// Keep the last occurrence, at its own position, and drop the rest
+    KeepLast,
+    /// Fail the parse with [`AdifError::DuplicateField`], or drop the
+    /// repeat in lenient mode
+    Error,
+}
+
+impl DuplicateFields {
+    /// Add `field` to `fields` under this policy, handing it back if it
+    /// repeats one of them and repeats are errors
+    pub(crate) fn add<F>(
+        self,
+        fields: &mut Vec<F>,
+        field: F,
+        name: impl Fn(&F) -> &str,
+    ) -> core::result::Result<(), F> {
+        let existing = match self {
+            DuplicateFields::KeepAll => None,
+            _ => fields
+                .iter()
+                .position(|f| name(f).eq_ignore_ascii_case(name(&field))),
+        };
+        match (self, existing) {
+            (_, None) => fields.push(field),
+            (DuplicateFields::KeepLast, Some(index)) => {
+                fields.remove(index);
+                fields.push(field);
+            }
+            (DuplicateFields::Error, Some(_)) => return Err(field),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// How to treat international text in ADI
+///
+/// The specification only allows IntlString and IntlMultilineString
+/// fields (the `_INTL` fields) in ADX, and only ASCII in the fields of
+/// other types. Many loggers write UTF-8 to ADI regardless.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IntlText {
+    /// Keep it silently
+    #[default]
+    Allow,
+    /// Keep it, but report each field as a warning from
+    /// [`parse_adi_with_warnings`](crate::parse_adi_with_warnings) and
+    /// when validating
+    Warn,
+    /// Fail the parse with [`AdifError::IntlText`], or drop the field in
+    /// lenient mode; report each field as an error when validating
+    Error,
+}
+
+impl IntlText {
+    /// Fail with [`AdifError::IntlText`] if international text is an error
+    /// and the field has some
+    pub(crate) fn check(
+        self,
+        name: &str,
+        data_type: DataType,
+        value: &str,
+        position: usize,
+    ) -> Result<()> {
+        if self == IntlText::Error && is_intl_text(name, data_type, value) {
+            return Err(AdifError::IntlText {
+                position,
+                name: name.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Check for an IntlString or IntlMultilineString field, by name or by
+/// data type indicator
+pub(crate) fn is_intl_field(name: &str, data_type: DataType) -> bool {
+    let suffix = name.len().checked_sub(5).and_then(|i| name.get(i..));
+    suffix.is_some_and(|s| s.eq_ignore_ascii_case("_INTL"))
+        || matches!(
+            data_type,
+            DataType::IntlString | DataType::IntlMultilineString
+        )
+}
+
+/// Check for a field that ADI can't hold: an international field, or
+/// non-ASCII text in any other
+pub(crate) fn is_intl_text(name: &str, data_type: DataType, value: &str) -> bool {
+    is_intl_field(name, data_type) || !value.is_ascii()
+}
+
+/// A resource limit that can be exceeded by hostile input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    /// Length of a single field value
+    FieldLength,
+    /// Number of fields in one record (or in the header)
+    FieldsPerRecord,
+    /// Number of records
+    Records,
+    /// Total size of the input
+    TotalBytes,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Limit::FieldLength => "field length",
+            Limit::FieldsPerRecord => "fields per record",
+            Limit::Records => "record count",
+            Limit::TotalBytes => "input size",
+        })
+    }
+}
+
+/// Limits guarding against untrusted input exhausting memory
+///
+/// `None` means unlimited. Lengths and sizes are in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum length of a field value
+    pub max_field_length: Option<usize>,
+    /// Maximum number of fields in a record or the header
+    pub max_fields_per_record: Option<usize>,
+    /// Maximum number of records
+    pub max_records: Option<usize>,
+    /// Maximum size of the whole input
+    pub max_total_bytes: Option<usize>,
+}
+
+impl Limits {
+    /// No limits
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Conservative limits for parsing uploads: 1 MiB values, 1,000 fields
+    /// per record, 1,000,000 records and 256 MiB of input
+    pub fn untrusted() -> Self {
+        Self {
+            max_field_length: Some(1024 * 1024),
+            max_fields_per_record: Some(1000),
+            max_records: Some(1_000_000),
+            max_total_bytes: Some(256 * 1024 * 1024),
+        }
+    }
+
+    /// Get the configured maximum for a limit
+    pub fn max(&self, limit: Limit) -> Option<usize> {
+        match limit {
+            Limit::FieldLength => self.max_field_length,
+            Limit::FieldsPerRecord => self.max_fields_per_record,
+            Limit::Records => self.max_records,
+            Limit::TotalBytes => self.max_total_bytes,
+        }
+    }
+
+    /// Number of bytes a stream reader may read at `pos`: one more than the
+    /// remaining input budget, so that crossing the limit is detected
+    #[cfg(feature = "std")]
+    pub(crate) fn read_budget(&self, pos: usize) -> u64 {
+        self.max_total_bytes
+            .map_or(u64::MAX, |max| max.saturating_sub(pos) as u64 + 1)
+    }
+
+    /// Fail if `value` exceeds the maximum for `limit`
+    pub(crate) fn check(&self, limit: Limit, value: usize, position: usize) -> Result<()> {
+        match self.max(limit) {
+            Some(max) if value > max => Err(AdifError::LimitExceeded {
+                position,
+                limit,
+                max,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intl_text() {
+        for (name, data_type, value) in [
+            ("NAME_INTL", DataType::Unspecified, "Jose"),
+            ("name_intl", DataType::Unspecified, ""),
+            ("COMMENT", DataType::IntlString, "hi"),
+            ("NAME", DataType::Unspecified, "José"),
+        ] {
+            assert!(is_intl_text(name, data_type, value), "{}", name);
+            assert!(IntlText::Error.check(name, data_type, value, 0).is_err());
+            assert!(IntlText::Warn.check(name, data_type, value, 0).is_ok());
+        }
+        assert!(!is_intl_text("NAME", DataType::String, "Jose"));
+        assert!(!is_intl_field("INTL", DataType::Unspecified));
+    }
+
+    #[test]
+    fn test_check() {
+        let limits = Limits {
+            max_records: Some(2),
+            ..Limits::none()
+        };
+        assert!(limits.check(Limit::Records, 2, 0).is_ok());
+        assert!(limits.check(Limit::FieldLength, usize::MAX, 0).is_ok());
+
+        let err = limits.check(Limit::Records, 3, 42).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Limit exceeded at position 42: record count is more than 2"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::error::Result;
+use crate::field_name::Interner;
+use crate::options::{Limit, ParserOptions};
+use crate::parser::{AdiParser, is_tag, parse_adi_with_options};
+use crate::types::{AdifFile, Record};
+use rayon::prelude::*;
+
+/// Inputs smaller than this are parsed sequentially
+const MIN_CHUNK_LEN: usize = 64 * 1024;
+
+/// Parse an ADI format string, parsing records on all rayon threads
+pub fn parse_adi_parallel(input: &str) -> Result<AdifFile> {
+    parse_adi_parallel_with_options(input, &ParserOptions::default())
+}
+
+/// Parse an ADI format string in parallel using the given options
+///
+/// After the header, the input is split into chunks at `<EOR>` markers and
+/// each chunk is parsed on its own thread. A marker that actually lies
+/// inside a field value makes a chunk fail to parse or end without a real
+/// `<EOR>`; whenever any chunk does, the whole input is reparsed
+/// sequentially, so results and errors are always identical to
+/// [`parse_adi_with_options`](crate::parse_adi_with_options).
+/// Fidelity mode (`preserve_raw`) always parses sequentially.
+pub fn parse_adi_parallel_with_options(input: &str, options: &ParserOptions) -> Result<AdifFile> {
+    let len = input.len();
+    options.limits.check(Limit::TotalBytes, len, len)?;
+    if options.preserve_raw || len < 2 * MIN_CHUNK_LEN {
+        return parse_adi_with_options(input, options);
+    }
+
+    let strict = ParserOptions {
+        lenient: false,
+        ..options.clone()
+    };
+    let mut parser = AdiParser::new(input, strict.clone());
+    let header = if parser.has_header() {
+        match parser.parse_header() {
+            Ok(header) => header,
+            Err(_) => return parse_adi_with_options(input, options),
+        }
+    } else {
+        Default::default()
+    };
+
+    let body = &input[parser.pos..];
+    let userdefs = &parser.userdefs;
+    let chunks = split_records(body, rayon::current_num_threads() * 4);
+    // Records after an <EOF> marker are ignored, so it must end the input
+    if chunks[..chunks.len() - 1]
+        .iter()
+        .any(|chunk| find_tag(chunk, "EOF", 0).is_some())
+    {
+        return parse_adi_with_options(input, options);
+    }
+
+    let last = chunks.len() - 1;
+    let parsed: Result<Option<Vec<Vec<Record>>>> = chunks
+        .par_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut parser = AdiParser::new(chunk, strict.clone());
+            parser.userdefs = userdefs.clone();
+            let records = parser.parse_records()?;
+            // A chunk cut inside a value ends with an unterminated record
+            let terminated = records.last().is_some_and(|r| {
+                r.span.end == chunk.len()
+                    && r.fields.last().is_some_and(|f| f.span.end < r.span.end)
+            });
+            if i < last && !terminated {
+                return Ok(None);
+            }
+            let mut interner = Interner::new();
+            Ok(Some(
+                records
+                    .into_iter()
+                    .map(|r| r.into_owned_interned(&mut interner))
+                    .collect(),
+            ))
+        })
+        .collect();
+
+    let records: Vec<Record> = match parsed {
+        Ok(Some(chunks)) => chunks.into_iter().flatten().collect(),
+        _ => return parse_adi_with_options(input, options),
+    };
+    // Each chunk only saw its own records; reparse to report the limit
+    if options
+        .limits
+        .check(Limit::Records, records.len(), 0)
+        .is_err()
+    {
+        return parse_adi_with_options(input, options);
+    }
+    Ok(AdifFile {
+        header,
+        records,
+        ..Default::default()
+    })
+}
+
+/// Split `body` into about `count` chunks, each ending just after `<EOR>`
+fn split_records(body: &str, count: usize) -> Vec<&str> {
+    let target = (body.len() / count.max(1)).max(MIN_CHUNK_LEN);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < body.len() {
+        let end = match find_tag(body, "EOR", start + target) {
+            Some(pos) => pos + body[pos..].find('>').map_or(0, |end| end + 1),
+            None => body.len(),
+        };
+        chunks.push(&body[start..end]);
+        start = end;
+    }
+    if chunks.is_empty() {
+        chunks.push(body);
+    }
+    chunks
+}
+
+/// Find the byte offset of the next `tag_name` tag at or after `from`
+fn find_tag(input: &str, tag_name: &str, from: usize) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut pos = from;
+    while pos < bytes.len() {
+        let tag = pos + bytes[pos..].iter().position(|&b| b == b'<')?;
+        if is_tag(&bytes[tag..], tag_name) {
+            return Some(tag);
+        }
+        pos = tag + 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    fn large_log(records: usize) -> String {
+        let mut input = String::from("Log\n<ADIF_VER:5>3.1.6<EOH>\n");
+        for i in 0..records {
+            input.push_str(&format!("<CALL:6>W{:05}<BAND:3>20m<eor>\n", i % 100_000));
+        }
+        input
+    }
+
+    #[test]
+    fn test_matches_sequential() {
+        let input = large_log(20_000);
+        let parallel = parse_adi_parallel(&input).unwrap();
+        let sequential = parse_adi(&input).unwrap();
+
+        assert_eq!(parallel.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(parallel.len(), 20_000);
+        for (a, b) in parallel.records.iter().zip(sequential.records.iter()) {
+            assert_eq!(a.fields, b.fields);
+        }
+    }
+
+    #[test]
+    fn test_eor_inside_value() {
+        let mut input = large_log(10_000);
+        let note = "x".repeat(MIN_CHUNK_LEN * 2) + "<EOR>tail";
+        input.insert_str(
+            input.len() / 3,
+            &format!("<EOR><NOTES:{}>{}<EOR>", note.len(), note),
+        );
+
+        let parallel = parse_adi_parallel(&input).unwrap();
+        let sequential = parse_adi(&input).unwrap();
+        assert_eq!(parallel.len(), sequential.len());
+        assert!(
+            parallel
+                .iter()
+                .any(|r| r.get_value("NOTES").is_some_and(|n| n.ends_with("tail")))
+        );
+    }
+
+    #[test]
+    fn test_eor_text_in_value_parses() {
+        let mut input = String::new();
+        for i in 0..20_000 {
+            input.push_str(&format!(
+                "<CALL:6>W{:05}<NOTES:8>abc<EOR><BAND:3>20m<EOR>\n",
+                i
+            ));
+        }
+
+        let parallel = parse_adi_parallel(&input).unwrap();
+        let sequential = parse_adi(&input).unwrap();
+        assert_eq!(sequential.len(), 20_000);
+        assert_eq!(parallel.len(), sequential.len());
+        for (a, b) in parallel.records.iter().zip(sequential.records.iter()) {
+            assert_eq!(a.fields, b.fields);
+        }
+    }
+
+    #[test]
+    fn test_error_matches_sequential() {
+        let mut input = large_log(10_000);
+        input.push_str("<CALL:x>W1AW<EOR>");
+
+        let parallel = parse_adi_parallel(&input).unwrap_err();
+        let sequential = parse_adi(&input).unwrap_err();
+        assert_eq!(parallel.to_string(), sequential.to_string());
+    }
+
+    #[test]
+    fn test_find_tag() {
+        assert_eq!(find_tag("<CALL:1>a<eor>", "EOR", 0), Some(9));
+        assert_eq!(find_tag("<CALL:1>a<eor>", "EOR", 10), None);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::arrow::BatchBuilder;
+use crate::error::Result;
+use crate::types::{AdifFile, Record};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::io::Write;
+
+/// Number of records buffered per Parquet row group
+const BATCH_SIZE: usize = 8192;
+
+/// Streaming writer producing an Apache Parquet file
+///
+/// The columns are fixed up front. Dates are stored as `Date32`, times as
+/// `Time32(Second)`, numbers as `Float64` and booleans as `Boolean`, based
+/// on each field's type in the ADIF specification; all other fields are
+/// `Utf8`. Missing and empty values are null. The file is only complete
+/// once [`into_inner`](ParquetWriter::into_inner) is called.
+pub struct ParquetWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    batch: BatchBuilder,
+    records_written: usize,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    /// Create a writer with one column per field name
+    pub fn new(writer: W, fields: &[&str]) -> Result<Self> {
+        Self::with_batch(writer, BatchBuilder::for_fields(fields))
+    }
+
+    fn with_batch(writer: W, batch: BatchBuilder) -> Result<Self> {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        Ok(Self {
+            writer: ArrowWriter::try_new(writer, batch.schema(), Some(properties))?,
+            batch,
+            records_written: 0,
+        })
+    }
+
+    /// Write a single record
+    ///
+    /// Fails without writing anything if a value doesn't fit its column's
+    /// type. Fields without a column are dropped.
+    pub fn write_record(&mut self, record: &Record) -> Result<()> {
+        self.batch.push(record, self.records_written)?;
+        self.records_written += 1;
+        if self.batch.len() == BATCH_SIZE {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Get the number of records written so far
+    pub fn records_written(&self) -> usize {
+        self.records_written
+    }
+
+    /// Write buffered records as a row group and flush the underlying
+    /// writer
+    pub fn flush(&mut self) -> Result<()> {
+        self.write_batch()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Finish the file, returning the underlying writer
+    pub fn into_inner(mut self) -> Result<W> {
+        self.write_batch()?;
+        Ok(self.writer.into_inner()?)
+    }
+
+    fn write_batch(&mut self) -> Result<()> {
+        if self.batch.len() > 0 {
+            self.writer.write(&self.batch.finish()?)?;
+        }
+        Ok(())
+    }
+}
+
+impl AdifFile {
+    /// Write the records to an Apache Parquet file
+    ///
+    /// The columns are those of [`AdifFile::to_record_batch`].
+    pub fn write_parquet<W: Write + Send>(&self, writer: W) -> Result<W> {
+        let mut writer =
+            ParquetWriter::with_batch(writer, BatchBuilder::for_records(&self.records))?;
+        for record in &self.records {
+            writer.write_record(record)?;
+        }
+        writer.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdifError;
+    use crate::parse_adi;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Float64Type;
+    use arrow_array::{Array, RecordBatch};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn read_back(file: std::fs::File) -> RecordBatch {
+        let mut reader = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap();
+        reader.next().unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_write_parquet() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:4>1423<FREQ:6>14.074<SWL:1>N<EOR>\
+             <CALL:4>K1AB<QSO_DATE:8>19700102<TIME_ON:6>000001<FREQ:2>?<EOR>",
+        )
+        .unwrap();
+        let out = file.write_parquet(tempfile::tempfile().unwrap()).unwrap();
+
+        assert_eq!(read_back(out), file.to_record_batch().unwrap());
+    }
+
+    #[test]
+    fn test_streaming_writer() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<FREQ:6>14.074<NOTES:2>hi<EOR><CALL:4>K1AB<EOR><FREQ:3>bad<EOR>",
+        )
+        .unwrap();
+        let mut writer =
+            ParquetWriter::new(tempfile::tempfile().unwrap(), &["call", "FREQ"]).unwrap();
+        writer.write_record(&file.records[0]).unwrap();
+        writer.write_record(&file.records[1]).unwrap();
+        assert!(matches!(
+            writer.write_record(&file.records[2]),
+            Err(AdifError::Conversion { record: 2, .. })
+        ));
+        assert_eq!(writer.records_written(), 2);
+        let batch = read_back(writer.into_inner().unwrap());
+
+        assert_eq!(batch.num_columns(), 2);
+        assert_eq!(batch.schema().field(0).name(), "CALL");
+        let freq = batch.column(1).as_primitive::<Float64Type>();
+        assert_eq!(freq.value(0), 14.074);
+        assert!(freq.is_null(1));
+    }
+}
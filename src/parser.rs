@@ -1,30 +1,156 @@
+use crate::borrowed::{AdifFileRef, FieldRef, RecordRef};
+#[cfg(feature = "std")]
+use crate::encoding::Encoding;
 use crate::error::{AdifError, Result};
-use crate::types::{AdifFile, AdifHeader, DataType, Field, Record};
+use crate::options::{IntlText, Limit, ParserOptions, UnknownFields, is_intl_text};
+#[cfg(feature = "std")]
+use crate::reader::RecordReader;
+use crate::types::{AdifFile, AdifHeader, DataType, Field, RawField, Record};
+use crate::validate::{is_known_field, userdef_names};
+use crate::warning::Warning;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 /// Parse an ADI format string into an AdifFile
 pub fn parse_adi(input: &str) -> Result<AdifFile> {
-    let mut parser = AdiParser::new(input);
+    parse_adi_borrowed(input).map(AdifFileRef::into_owned)
+}
+
+/// Parse an ADI format string into records that borrow from the input
+///
+/// Field values are slices of `input`, so no per-field allocation is made
+/// beyond normalizing lowercase field names.
+pub fn parse_adi_borrowed(input: &str) -> Result<AdifFileRef<'_>> {
+    parse_adi_borrowed_with_options(input, &ParserOptions::default())
+}
+
+/// Parse an ADI format string into an AdifFile using the given options
+pub fn parse_adi_with_options(input: &str, options: &ParserOptions) -> Result<AdifFile> {
+    parse_adi_with_warnings(input, options).map(|(file, _)| file)
+}
+
+/// Parse an ADI format string using the given options, also returning
+/// anything odd that didn't stop it from parsing
+///
+/// Stray text in the records, unknown marker tags, data specifiers
+/// without a name and values that look like they swallowed the next tag
+/// are reported, as are unknown fields and international text when their
+/// policies are set to warn.
+pub fn parse_adi_with_warnings(
+    input: &str,
+    options: &ParserOptions,
+) -> Result<(AdifFile, Vec<Warning>)> {
+    let mut parser = AdiParser::new(input, options.clone());
+    let file = parser.parse()?;
+    let file = if options.preserve_raw {
+        parser.owned_file_with_raw(file)
+    } else {
+        file.into_owned()
+    };
+    Ok((file, parser.warnings))
+}
+
+/// Parse an ADI format string into borrowed records using the given options
+pub fn parse_adi_borrowed_with_options<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> Result<AdifFileRef<'a>> {
+    let mut parser = AdiParser::new(input, options.clone());
     parser.parse()
 }
 
+/// Parse ADI data from raw bytes
+///
+/// Values that aren't valid UTF-8 are decoded as Windows-1252 (a superset
+/// of the printable Latin-1 range) rather than failing the whole parse. Use
+/// [`parse_adi_bytes_with_options`] to choose the encoding.
+#[cfg(feature = "std")]
+pub fn parse_adi_bytes(input: &[u8]) -> Result<AdifFile> {
+    let options = ParserOptions {
+        encoding: Encoding::Auto,
+        ..Default::default()
+    };
+    parse_adi_bytes_with_options(input, &options)
+}
+
+/// Parse ADI data from raw bytes using the given options
+#[cfg(feature = "std")]
+pub fn parse_adi_bytes_with_options(input: &[u8], options: &ParserOptions) -> Result<AdifFile> {
+    let mut reader = RecordReader::with_options(input, options.clone());
+    let header = reader.header()?.clone();
+    let records = reader.collect::<Result<Vec<_>>>()?;
+    Ok(AdifFile {
+        header,
+        records,
+        ..Default::default()
+    })
+}
+
+/// Parse an ADI format string, skipping malformed fields
+///
+/// Broken data specifiers (bad lengths, missing `>`, etc.) are dropped and
+/// parsing resumes at the next `<`, so the remaining good fields and
+/// records are still returned. A declared length that runs past the end of
+/// the input, or that cuts a value short, is replaced by the length up to
+/// the next `<`.
+pub fn parse_adi_lenient(input: &str) -> Result<AdifFile> {
+    parse_adi_with_options(input, &ParserOptions::lenient())
+}
+
+/// Parse an ADI format string, never failing
+///
+/// Like [`parse_adi_lenient`], but also returns every error that was
+/// skipped over, so callers can report the positions of bad data while
+/// still loading everything that parsed cleanly.
+pub fn parse_adi_lossy(input: &str) -> (AdifFile, Vec<AdifError>) {
+    let mut parser = AdiParser::new(input, ParserOptions::lenient());
+    let file = match parser.parse() {
+        Ok(file) => file.into_owned(),
+        Err(e) => {
+            parser.errors.push(e);
+            AdifFile::new()
+        }
+    };
+    (file, parser.errors)
+}
+
 /// Internal parser state
-struct AdiParser<'a> {
-    input: &'a str,
-    pos: usize,
+pub(crate) struct AdiParser<'a> {
+    pub(crate) input: &'a str,
+    pub(crate) pos: usize,
+    /// Position just past the header (0 if there is none)
+    header_end: usize,
+    options: ParserOptions,
+    /// Errors recovered from in lenient mode
+    errors: Vec<AdifError>,
+    /// Oddities that didn't stop the parse
+    warnings: Vec<Warning>,
+    /// Record field names defined by USERDEFn header fields
+    pub(crate) userdefs: Vec<String>,
 }
 
 impl<'a> AdiParser<'a> {
-    fn new(input: &'a str) -> Self {
-        Self { input, pos: 0 }
+    pub(crate) fn new(input: &'a str, options: ParserOptions) -> Self {
+        Self {
+            input,
+            pos: 0,
+            header_end: 0,
+            options,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            userdefs: Vec::new(),
+        }
     }
 
-    fn parse(&mut self) -> Result<AdifFile> {
-        let mut file = AdifFile::new();
+    fn parse(&mut self) -> Result<AdifFileRef<'a>> {
+        let mut file = AdifFileRef::default();
+        let len = self.input.len();
+        self.options.limits.check(Limit::TotalBytes, len, len)?;
 
-        // Check if there's a header by looking for <EOH>
-        let has_header = self.input.to_uppercase().contains("<EOH>");
-
-        if has_header {
+        if self.has_header() {
             file.header = self.parse_header()?;
         }
 
@@ -34,13 +160,31 @@ impl<'a> AdiParser<'a> {
         Ok(file)
     }
 
-    fn parse_header(&mut self) -> Result<AdifHeader> {
+    /// Check if there's a header: an `<EOH>` tag before the first `<EOR>`
+    pub(crate) fn has_header(&self) -> bool {
+        let bytes = self.input.as_bytes();
+        let mut pos = 0;
+        while let Some(offset) = bytes[pos..].iter().position(|&b| b == b'<') {
+            let tag = &bytes[pos + offset..];
+            if is_tag(tag, "EOH") {
+                return true;
+            }
+            if is_tag(tag, "EOR") {
+                return false;
+            }
+            pos += offset + 1;
+        }
+        false
+    }
+
+    pub(crate) fn parse_header(&mut self) -> Result<AdifHeader> {
         let mut header = AdifHeader::default();
 
         // Capture any preamble text before the first tag
         let preamble_end = self.find_next_tag_start().unwrap_or(self.input.len());
         header.preamble = self.input[..preamble_end].to_string();
         self.pos = preamble_end;
+        let mut raw_pos = preamble_end;
 
         // Parse header fields until we hit <EOH>
         loop {
@@ -52,7 +196,7 @@ impl<'a> AdiParser<'a> {
 
             // Look for the next tag
             if self.peek_char() != Some('<') {
-                self.pos += 1;
+                self.skip_to_next_tag();
                 continue;
             }
 
@@ -63,26 +207,40 @@ impl<'a> AdiParser<'a> {
             }
 
             // Parse a field
-            let field = self.parse_field()?;
-
-            // Extract well-known header fields
-            match field.name.as_str() {
-                "ADIF_VER" => header.adif_version = Some(field.value.clone()),
-                "PROGRAMID" => header.program_id = Some(field.value.clone()),
-                "PROGRAMVERSION" => header.program_version = Some(field.value.clone()),
-                "CREATED_TIMESTAMP" => header.created_timestamp = Some(field.value.clone()),
-                _ => {}
+            let start_pos = self.pos;
+            match self.parse_field() {
+                Ok(field) if !self.check_field(&field, start_pos)? => {}
+                Ok(field) => {
+                    let field = if self.options.preserve_raw {
+                        let owned = self.owned_with_raw(field, raw_pos);
+                        raw_pos = self.pos;
+                        owned
+                    } else {
+                        field.into_owned()
+                    };
+                    let count = header.fields.len() + 1;
+                    self.options
+                        .limits
+                        .check(Limit::FieldsPerRecord, count, start_pos)?;
+                    header.add_field(field);
+                }
+                Err(e) => self.recover(e, start_pos)?,
             }
+        }
 
-            header.fields.push(field);
+        if self.options.preserve_raw {
+            header.terminator = Some(self.input[raw_pos..self.pos].to_string());
         }
+        self.header_end = self.pos;
+        self.userdefs = userdef_names(&header.fields);
 
         Ok(header)
     }
 
-    fn parse_records(&mut self) -> Result<Vec<Record>> {
+    pub(crate) fn parse_records(&mut self) -> Result<Vec<RecordRef<'a>>> {
         let mut records = Vec::new();
-        let mut current_record = Record::new();
+        let mut current_record = RecordRef::new();
+        let mut record_start = self.pos;
 
         loop {
             self.skip_whitespace_and_newlines();
@@ -93,7 +251,10 @@ impl<'a> AdiParser<'a> {
 
             // Look for the next tag
             if self.peek_char() != Some('<') {
-                self.pos += 1;
+                let position = self.pos;
+                self.skip_to_next_tag();
+                let text = self.input[position..self.pos].trim_end().to_string();
+                self.warnings.push(Warning::StrayText { position, text });
                 continue;
             }
 
@@ -101,8 +262,21 @@ impl<'a> AdiParser<'a> {
             if self.check_tag("EOR") {
                 self.skip_tag("EOR")?;
                 if !current_record.fields.is_empty() {
+                    // The line ending after <EOR> belongs to this record
+                    let rest = &self.input[self.pos..];
+                    let newline = if rest.starts_with("\r\n") {
+                        2
+                    } else {
+                        usize::from(rest.starts_with('\n'))
+                    };
+                    let end = self.pos + newline;
+                    current_record.span = record_start..end;
+                    self.options
+                        .limits
+                        .check(Limit::Records, records.len() + 1, record_start)?;
                     records.push(current_record);
-                    current_record = Record::new();
+                    current_record = RecordRef::new();
+                    record_start = end;
                 }
                 continue;
             }
@@ -114,19 +288,43 @@ impl<'a> AdiParser<'a> {
             }
 
             // Parse a field
-            let field = self.parse_field()?;
-            current_record.add_field(field);
+            let start_pos = self.pos;
+            match self.parse_field() {
+                Ok(field) if !self.check_field(&field, start_pos)? => {}
+                Ok(field) => {
+                    let count = current_record.fields.len() + 1;
+                    self.options
+                        .limits
+                        .check(Limit::FieldsPerRecord, count, start_pos)?;
+                    let policy = self.options.duplicate_fields;
+                    if let Err(field) = policy.add(&mut current_record.fields, field, |f| &f.name) {
+                        let error = AdifError::DuplicateField {
+                            position: start_pos,
+                            name: field.name.into_owned(),
+                        };
+                        if !self.options.lenient {
+                            return Err(error);
+                        }
+                        self.errors.push(error);
+                    }
+                }
+                Err(e) => self.recover(e, start_pos)?,
+            }
         }
 
         // Don't forget any trailing record without EOR
-        if !current_record.fields.is_empty() {
+        if let Some(last) = current_record.fields.last() {
+            current_record.span = record_start..last.span.end;
+            self.options
+                .limits
+                .check(Limit::Records, records.len() + 1, record_start)?;
             records.push(current_record);
         }
 
         Ok(records)
     }
 
-    fn parse_field(&mut self) -> Result<Field> {
+    pub(crate) fn parse_field(&mut self) -> Result<FieldRef<'a>> {
         let start_pos = self.pos;
 
         // Expect '<'
@@ -138,27 +336,20 @@ impl<'a> AdiParser<'a> {
         }
         self.pos += 1;
 
-        // Parse field name (until ':' or '>')
+        // Parse field name (until ':' or '>'; a '<' means the tag is broken)
         let name_start = self.pos;
         while let Some(c) = self.peek_char() {
-            if c == ':' || c == '>' {
+            if c == ':' || c == '>' || c == '<' {
                 break;
             }
-            self.pos += 1;
-        }
-        let name = self.input[name_start..self.pos].to_uppercase();
-
-        if name.is_empty() {
-            return Err(AdifError::InvalidDataSpecifier {
-                position: start_pos,
-                message: "Empty field name".to_string(),
-            });
+            self.pos += c.len_utf8();
         }
+        let name = &self.input[name_start..self.pos];
 
         // Check if this is a marker tag (no length)
         if self.peek_char() == Some('>') {
             self.pos += 1;
-            return Ok(Field::new(name, ""));
+            return Ok(self.make_field(start_pos, name, DataType::Unspecified, ""));
         }
 
         // Expect ':'
@@ -186,14 +377,15 @@ impl<'a> AdiParser<'a> {
                 position: length_start,
                 message: format!("Invalid length: '{}'", length_str),
             })?;
+        self.options
+            .limits
+            .check(Limit::FieldLength, length, start_pos)?;
 
         // Check for optional type indicator
         let data_type = if self.peek_char() == Some(':') {
             self.pos += 1;
-            let type_char = self
-                .peek_char()
-                .ok_or_else(|| AdifError::UnexpectedEof(self.pos))?;
-            self.pos += 1;
+            let type_char = self.peek_char().ok_or(AdifError::UnexpectedEof(self.pos))?;
+            self.pos += type_char.len_utf8();
             DataType::from_char(type_char).unwrap_or(DataType::Unspecified)
         } else {
             DataType::Unspecified
@@ -208,19 +400,228 @@ impl<'a> AdiParser<'a> {
         }
         self.pos += 1;
 
-        // Read the value (exactly 'length' characters)
-        if self.pos + length > self.input.len() {
-            return Err(AdifError::InvalidFieldLength {
-                position: self.pos,
-                expected: length,
-                found: self.input.len() - self.pos,
+        // Read the value (exactly 'length' bytes)
+        let end = self.value_end(length)?;
+        let value = &self.input[self.pos..end];
+        self.pos = end;
+
+        Ok(self.make_field(start_pos, name, data_type, value))
+    }
+
+    /// Find the end of a value of the declared length starting at the
+    /// current position
+    ///
+    /// In lenient mode a length that overruns the input, or that cuts the
+    /// value short (leaving text directly after it, or splitting a
+    /// character), is replaced by the length up to the next `<`, and the
+    /// mismatch is recorded.
+    fn value_end(&mut self, length: usize) -> Result<usize> {
+        let input = self.input;
+        let lenient = self.options.lenient;
+        let start = self.pos;
+        let end = start + length;
+        let next_tag = |from: usize| {
+            input.as_bytes()[from..]
+                .iter()
+                .position(|&b| b == b'<')
+                .map_or(input.len(), |i| from + i)
+        };
+        // A value cut short is extended to the next tag, unless the
+        // declared length already ran into one
+        let extend = || {
+            let mut declared = end;
+            while !input.is_char_boundary(declared) {
+                declared -= 1;
+            }
+            if find_tag(&input[start..declared]).is_some() {
+                next_tag(start)
+            } else {
+                next_tag(end)
+            }
+        };
+        let found = if end > input.len() {
+            if !lenient {
+                return Err(AdifError::InvalidFieldLength {
+                    position: start,
+                    expected: length,
+                    found: input.len() - start,
+                });
+            }
+            next_tag(start)
+        } else if !input.is_char_boundary(end) {
+            if !lenient {
+                return Err(AdifError::ParseError {
+                    position: start,
+                    message: format!("Field length {} splits a multi-byte character", length),
+                });
+            }
+            extend()
+        } else if lenient
+            && input[end..]
+                .chars()
+                .next()
+                .is_some_and(|c| c != '<' && !c.is_whitespace())
+        {
+            extend()
+        } else {
+            return Ok(end);
+        };
+        self.errors.push(AdifError::InvalidFieldLength {
+            position: start,
+            expected: length,
+            found: found - start,
+        });
+        Ok(found)
+    }
+
+    /// Build a field starting at `start` and ending at the current
+    /// position, applying the case and trimming options
+    fn make_field(
+        &self,
+        start: usize,
+        name: &'a str,
+        data_type: DataType,
+        value: &'a str,
+    ) -> FieldRef<'a> {
+        let value = if self.options.trim_values {
+            value.trim()
+        } else {
+            value
+        };
+        let mut field = if self.options.preserve_case {
+            FieldRef {
+                name: Cow::Borrowed(name),
+                data_type,
+                value,
+                span: 0..0,
+            }
+        } else {
+            FieldRef::new(name, data_type, value)
+        };
+        field.span = start..self.pos;
+        field
+    }
+
+    /// Convert a parsed field to an owned one, keeping the source text
+    /// from `raw_pos` (the end of the previous field) onwards
+    fn owned_with_raw(&self, field: FieldRef<'a>, raw_pos: usize) -> Field {
+        let span = field.span.clone();
+        // The data specifier runs up to the first '>', which names can't contain
+        let specifier_end = self.input[span.start..]
+            .find('>')
+            .map_or(span.end, |i| span.start + i + 1);
+        let raw = RawField {
+            leading: self.input[raw_pos..span.start].to_string(),
+            specifier: self.input[span.start..specifier_end].to_string(),
+            span,
+        };
+        let mut owned = field.into_owned();
+        owned.raw = Some(Box::new(raw));
+        owned
+    }
+
+    /// Convert a parsed file to an owned one, keeping all source text so
+    /// that it can be written back unchanged
+    fn owned_file_with_raw(&self, file: AdifFileRef<'a>) -> AdifFile {
+        let mut records = Vec::with_capacity(file.records.len());
+        let mut end = self.header_end;
+        for record in file.records {
+            let mut raw_pos = record.span.start;
+            let mut fields = Vec::with_capacity(record.fields.len());
+            for field in record.fields {
+                let next_pos = field.span.end;
+                fields.push(self.owned_with_raw(field, raw_pos));
+                raw_pos = next_pos;
+            }
+            records.push(Record {
+                fields,
+                terminator: Some(self.input[raw_pos..record.span.end].to_string()),
             });
+            end = record.span.end;
         }
 
-        let value = self.input[self.pos..self.pos + length].to_string();
-        self.pos += length;
+        AdifFile {
+            header: file.header,
+            records,
+            trailer: Some(self.input[end..].to_string()),
+        }
+    }
 
-        Ok(Field::with_type(name, data_type, value))
+    /// Handle a field error: fail in strict mode, or record it and
+    /// resynchronize on the next '<' in lenient mode
+    fn recover(&mut self, error: AdifError, start_pos: usize) -> Result<()> {
+        if !self.options.lenient || matches!(error, AdifError::LimitExceeded { .. }) {
+            return Err(error);
+        }
+        self.errors.push(error);
+        self.pos = start_pos + 1;
+        self.skip_to_next_tag();
+        Ok(())
+    }
+
+    /// Apply the unknown-field and international text policies to a parsed
+    /// field: fail in strict mode, or record the error and report that the
+    /// field should be dropped in lenient mode
+    ///
+    /// Fields without a name are dropped, and anything else odd about the
+    /// field is recorded as a warning.
+    fn check_field(&mut self, field: &FieldRef, start_pos: usize) -> Result<bool> {
+        if field.name.is_empty() {
+            let position = start_pos;
+            self.warnings.push(Warning::EmptyFieldName { position });
+            return Ok(false);
+        }
+        let options = &self.options;
+        let checked = options
+            .unknown_fields
+            .check(&field.name, &self.userdefs, start_pos)
+            .and_then(|()| {
+                options
+                    .intl_text
+                    .check(&field.name, field.data_type, field.value, start_pos)
+            });
+        match checked {
+            Ok(()) => {}
+            Err(e) if self.options.lenient => {
+                self.errors.push(e);
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        }
+
+        let position = start_pos;
+        let name = || field.name.to_string();
+        // Names can't contain ':', so a tag without one has no length
+        if !self.input[field.span.clone()].contains(':') {
+            let name = name();
+            self.warnings.push(Warning::MarkerTag { position, name });
+        }
+        if let Some(tag) = find_tag(field.value) {
+            let tag = tag.to_string();
+            let name = name();
+            self.warnings.push(Warning::SwallowedTag {
+                position,
+                name,
+                tag,
+            });
+        }
+        if options.unknown_fields == UnknownFields::Warn
+            && !is_known_field(&field.name, &self.userdefs)
+        {
+            let name = name();
+            self.warnings.push(Warning::UnknownField { position, name });
+        }
+        if options.intl_text == IntlText::Warn
+            && is_intl_text(&field.name, field.data_type, field.value)
+        {
+            let name = name();
+            self.warnings.push(Warning::IntlText { position, name });
+        }
+        Ok(true)
+    }
+
+    fn skip_to_next_tag(&mut self) {
+        self.pos = self.find_next_tag_start().unwrap_or(self.input.len());
     }
 
     fn peek_char(&self) -> Option<char> {
@@ -237,33 +638,15 @@ impl<'a> AdiParser<'a> {
         }
     }
 
-    fn find_next_tag_start(&self) -> Option<usize> {
+    pub(crate) fn find_next_tag_start(&self) -> Option<usize> {
         self.input[self.pos..].find('<').map(|i| self.pos + i)
     }
 
-    fn check_tag(&self, tag_name: &str) -> bool {
-        let remaining = &self.input[self.pos..];
-        if remaining.len() < tag_name.len() + 2 {
-            return false;
-        }
-
-        // Check for <TAG_NAME> or <TAG_NAME: (with possible content after)
-        let upper = remaining.to_uppercase();
-        if !upper.starts_with('<') {
-            return false;
-        }
-
-        let after_bracket = &upper[1..];
-        if after_bracket.starts_with(tag_name) {
-            let after_name = &after_bracket[tag_name.len()..];
-            // Must be followed by '>' or ':'
-            after_name.starts_with('>') || after_name.starts_with(':')
-        } else {
-            false
-        }
+    pub(crate) fn check_tag(&self, tag_name: &str) -> bool {
+        is_tag(&self.input.as_bytes()[self.pos..], tag_name)
     }
 
-    fn skip_tag(&mut self, tag_name: &str) -> Result<()> {
+    pub(crate) fn skip_tag(&mut self, tag_name: &str) -> Result<()> {
         // Skip '<'
         if self.peek_char() != Some('<') {
             return Err(AdifError::ParseError {
@@ -288,6 +671,47 @@ impl<'a> AdiParser<'a> {
     }
 }
 
+/// Check for `<TAG_NAME>` or `<TAG_NAME:` (ASCII case-insensitive) at the
+/// start of `bytes`
+pub(crate) fn is_tag(bytes: &[u8], tag_name: &str) -> bool {
+    let name = tag_name.as_bytes();
+    bytes.len() >= name.len() + 2
+        && bytes[0] == b'<'
+        && bytes[1..=name.len()].eq_ignore_ascii_case(name)
+        && matches!(bytes[name.len() + 1], b'>' | b':')
+}
+
+/// Find text in a value that looks like a tag swallowed by a length that
+/// overran into the next field: a data specifier such as `<BAND:3>`, an
+/// `<EOR>` or `<EOH>`, or the start of a data specifier cut off at the end
+/// of the value
+pub(crate) fn find_tag(value: &str) -> Option<&str> {
+    value.match_indices('<').find_map(|(i, _)| {
+        let rest = &value[i..];
+        let Some(end) = rest.find('>') else {
+            let partial = rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+                && rest[1..]
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b':');
+            return partial.then_some(rest);
+        };
+        let tag = &rest[..=end];
+        let mut parts = tag[1..end].splitn(3, ':');
+        let name = parts.next().unwrap_or_default();
+        let looks_like_tag = match parts.next() {
+            None => name.eq_ignore_ascii_case("EOR") || name.eq_ignore_ascii_case("EOH"),
+            Some(length) => {
+                name.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+                    && !length.is_empty()
+                    && length.bytes().all(|b| b.is_ascii_digit())
+                    && parts.next().is_none_or(|t| t.len() == 1)
+            }
+        };
+        looks_like_tag.then_some(tag)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -361,6 +785,45 @@ mod tests {
         assert_eq!(calls.len(), 3);
     }
 
+    #[test]
+    fn test_duplicate_fields() {
+        use crate::options::DuplicateFields;
+
+        let input = "<call:5>W1AW1<BAND:3>20m<Call:5>W1AW2<CALL:5>W1AW3<EOR>";
+        let calls = |duplicate_fields| {
+            let options = ParserOptions {
+                duplicate_fields,
+                ..Default::default()
+            };
+            parse_adi_with_options(input, &options).map(|mut file| {
+                let record = file.records.remove(0);
+                record
+                    .fields
+                    .into_iter()
+                    .map(|f| f.value)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(
+            calls(DuplicateFields::KeepAll).unwrap(),
+            ["W1AW1", "20m", "W1AW2", "W1AW3"]
+        );
+        assert_eq!(calls(DuplicateFields::KeepFirst).unwrap(), ["W1AW1", "20m"]);
+        assert_eq!(calls(DuplicateFields::KeepLast).unwrap(), ["20m", "W1AW3"]);
+        assert!(matches!(
+            calls(DuplicateFields::Error),
+            Err(AdifError::DuplicateField { position: 24, ref name }) if name == "CALL"
+        ));
+
+        let options = ParserOptions {
+            duplicate_fields: DuplicateFields::Error,
+            ..ParserOptions::lenient()
+        };
+        let result = parse_adi_with_options(input, &options).unwrap();
+        assert_eq!(result.records[0].fields.len(), 2);
+    }
+
     #[test]
     fn test_parse_with_eof() {
         let input = "<CALL:5>W1AW1<EOR><EOF>";
@@ -385,4 +848,343 @@ mod tests {
         assert_eq!(result.header.adif_version, Some("3.1.4".to_string()));
         assert!(result.records.is_empty());
     }
+
+    #[test]
+    fn test_header_detection() {
+        let input = "Straße <adif_ver:5>3.1.4<eoh><CALL:4>W1AW<EOR>";
+        let result = parse_adi(input).unwrap();
+        assert_eq!(result.header.adif_version, Some("3.1.4".to_string()));
+        assert_eq!(result.records.len(), 1);
+
+        // An <EOH> after the first record doesn't make a header
+        let input = "<CALL:4>W1AW<EOR><CALL:4>K1AB<EOH><EOR>";
+        let result = parse_adi(input).unwrap();
+        assert!(result.header.fields.is_empty());
+        assert_eq!(result.records.len(), 2);
+    }
+
+    #[test]
+    fn test_limits() {
+        use crate::options::{Limit, Limits};
+
+        let input = "<ADIF_VER:5>3.1.6<EOH><CALL:4>W1AW<MODE:2>CW<EOR><CALL:4>K1AB<EOR>";
+        let parse = |limits: Limits| {
+            let options = ParserOptions {
+                lenient: true,
+                limits,
+                ..Default::default()
+            };
+            match parse_adi_with_options(input, &options) {
+                Err(AdifError::LimitExceeded { limit, .. }) => Some(limit),
+                Err(e) => panic!("unexpected error: {}", e),
+                Ok(_) => None,
+            }
+        };
+
+        assert_eq!(parse(Limits::untrusted()), None);
+        let cases = [
+            (Limit::FieldLength, 5, 4),
+            (Limit::FieldsPerRecord, 2, 1),
+            (Limit::Records, 2, 1),
+            (Limit::TotalBytes, input.len(), input.len() - 1),
+        ];
+        for (limit, ok, too_small) in cases {
+            let limits = |max| {
+                let mut limits = Limits::none();
+                match limit {
+                    Limit::FieldLength => limits.max_field_length = Some(max),
+                    Limit::FieldsPerRecord => limits.max_fields_per_record = Some(max),
+                    Limit::Records => limits.max_records = Some(max),
+                    Limit::TotalBytes => limits.max_total_bytes = Some(max),
+                }
+                limits
+            };
+            assert_eq!(parse(limits(ok)), None);
+            assert_eq!(parse(limits(too_small)), Some(limit));
+        }
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        use crate::options::UnknownFields;
+
+        let input = "<USERDEF1:3:N>EPC<EOH><CALL:4>W1AW<EPC:3>123<MY_FIELD:1>y<APP_X_Y:1>z<EOR>";
+        let mut options = ParserOptions {
+            unknown_fields: UnknownFields::Warn,
+            ..Default::default()
+        };
+        let result = parse_adi_with_options(input, &options).unwrap();
+        assert_eq!(result.records[0].fields.len(), 4);
+
+        options.unknown_fields = UnknownFields::Error;
+        assert!(matches!(
+            parse_adi_with_options(input, &options),
+            Err(AdifError::UnknownField { position: 44, ref name }) if name == "MY_FIELD"
+        ));
+
+        options.lenient = true;
+        let result = parse_adi_with_options(input, &options).unwrap();
+        let names: Vec<_> = result.records[0].fields.iter().map(|f| &*f.name).collect();
+        assert_eq!(names, ["CALL", "EPC", "APP_X_Y"]);
+    }
+
+    #[test]
+    fn test_intl_text() {
+        use crate::options::IntlText;
+
+        let input = "<CALL:4>W1AW<NAME:5>José<NAME_INTL:5>José<QTH:3>Rio<EOR>";
+        let mut options = ParserOptions {
+            intl_text: IntlText::Warn,
+            ..Default::default()
+        };
+        let result = parse_adi_with_options(input, &options).unwrap();
+        assert_eq!(result.records[0].fields.len(), 4);
+
+        options.intl_text = IntlText::Error;
+        assert!(matches!(
+            parse_adi_with_options(input, &options),
+            Err(AdifError::IntlText { position: 12, ref name }) if name == "NAME"
+        ));
+
+        options.lenient = true;
+        let result = parse_adi_with_options(input, &options).unwrap();
+        let names: Vec<_> = result.records[0].fields.iter().map(|f| &*f.name).collect();
+        assert_eq!(names, ["CALL", "QTH"]);
+    }
+
+    #[test]
+    fn test_lenient_skips_bad_length() {
+        let input = "<CALL:5>W1AW1<BAND:x>20m<MODE:2>CW<EOR><CALL:5>W1AW2<EOR>";
+        assert!(parse_adi(input).is_err());
+
+        let result = parse_adi_lenient(input).unwrap();
+        assert_eq!(result.records.len(), 2);
+        assert_eq!(result.records[0].call(), Some("W1AW1"));
+        assert_eq!(result.records[0].band(), None);
+        assert_eq!(result.records[0].mode(), Some("CW"));
+        assert_eq!(result.records[1].call(), Some("W1AW2"));
+    }
+
+    #[test]
+    fn test_lenient_missing_close_bracket() {
+        let input = "<CALL:5 W1AW1<MODE:2>CW<EOR><CALL:5>W1AW2<EOR>";
+        let result = parse_adi_lenient(input).unwrap();
+
+        assert_eq!(result.records.len(), 2);
+        assert_eq!(result.records[0].call(), None);
+        assert_eq!(result.records[0].mode(), Some("CW"));
+    }
+
+    #[test]
+    fn test_lenient_overrun_at_end() {
+        let input = "<CALL:5>W1AW1<EOR><CALL:20>W1AW2";
+        assert!(parse_adi(input).is_err());
+        let result = parse_adi_lenient(input).unwrap();
+
+        assert_eq!(result.records.len(), 2);
+        assert_eq!(result.records[0].call(), Some("W1AW1"));
+        assert_eq!(result.records[1].call(), Some("W1AW2"));
+
+        let (result, errors) = parse_adi_lossy("<CALL:4>W1AW<NAME:10>Bob<EOR>");
+        assert_eq!(result.records[0].get_value("NAME"), Some("Bob"));
+        assert!(matches!(
+            errors[..],
+            [AdifError::InvalidFieldLength {
+                position: 21,
+                expected: 10,
+                found: 3
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_parse_with_warnings() {
+        let input = "<ADIF_VER:5>3.1.6<EOH>\n\
+                     <CALL:4>W1AW junk\n<QSL><:3>foo<NAME:11>Bob<BAND:3><EOR>\n\
+                     <:5>hello<>\n<FOO:1>X<COMMENT_INTL:3>Hey<EOR>";
+        let options = ParserOptions {
+            unknown_fields: UnknownFields::Warn,
+            intl_text: IntlText::Warn,
+            ..Default::default()
+        };
+        let (file, warnings) = parse_adi_with_warnings(input, &options).unwrap();
+        assert_eq!(file.records.len(), 2);
+        assert_eq!(file.records[0].fields.len(), 3);
+        assert_eq!(file.records[0].get_value("NAME"), Some("Bob<BAND:3>"));
+        assert_eq!(file.records[1].fields.len(), 2);
+
+        let warnings: Vec<_> = warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            warnings,
+            [
+                "position 36: skipped text 'junk'",
+                "position 41: unknown tag <QSL>",
+                "position 41: unknown field QSL",
+                "position 46: skipped field without a name",
+                "position 53: NAME contains '<BAND:3>', which looks like a tag",
+                "position 79: skipped field without a name",
+                "position 88: skipped field without a name",
+                "position 91: unknown field FOO",
+                "position 99: international text in field COMMENT_INTL",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_tag() {
+        assert_eq!(find_tag("W1AW<BAND:3>20m"), Some("<BAND:3>"));
+        assert_eq!(find_tag("Hi <3 <eor>"), Some("<eor>"));
+        assert_eq!(find_tag("W1AW<BAND:3"), Some("<BAND:3"));
+        assert_eq!(find_tag("W1AW<B"), Some("<B"));
+        assert_eq!(find_tag("a <b> c"), None);
+        assert_eq!(find_tag("x < y"), None);
+        assert_eq!(find_tag("<3"), None);
+    }
+
+    #[test]
+    fn test_lenient_short_length() {
+        let input = "<NAME:3>Robert<QTH:1>é <CALL:4>W1AW // note\n<EOR>";
+        let (result, errors) = parse_adi_lossy(input);
+        let record = &result.records[0];
+        assert_eq!(record.get_value("NAME"), Some("Robert"));
+        assert_eq!(record.get_value("QTH"), Some("é "));
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].to_string(),
+            "Invalid field length at position 8: expected 3, found 6"
+        );
+
+        let (result, errors) = parse_adi_lossy("<NAME:10>Bob<CALL:4>W1AW<EOR>");
+        let record = &result.records[0];
+        assert_eq!(record.get_value("NAME"), Some("Bob"));
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(
+            errors[0].to_string(),
+            "Invalid field length at position 9: expected 10, found 3"
+        );
+    }
+
+    #[test]
+    fn test_stray_multibyte_text() {
+        let input = "<CALL:5>W1AW1<EOR>\n— café —\n<CALL:5>W1AW2<EOR>";
+        let result = parse_adi(input).unwrap();
+
+        assert_eq!(result.records.len(), 2);
+    }
+
+    #[test]
+    fn test_length_splitting_character() {
+        let input = "<NAME:1>é<EOR>";
+        assert!(parse_adi(input).is_err());
+    }
+
+    #[test]
+    fn test_lossy_reports_errors() {
+        let input = "<CALL:5>W1AW1<BAND:x>20m<EOR><CALL:5>W1AW2<EOR><NAME:50>Bob";
+        let (result, errors) = parse_adi_lossy(input);
+
+        assert_eq!(result.records.len(), 3);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            AdifError::InvalidDataSpecifier { position: 19, .. }
+        ));
+        assert!(matches!(
+            errors[1],
+            AdifError::InvalidFieldLength {
+                expected: 50,
+                found: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_lossy_clean_input() {
+        let (result, errors) = parse_adi_lossy("<CALL:5>W1AW1<EOR>");
+
+        assert_eq!(result.records.len(), 1);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_options_preserve_case() {
+        let options = ParserOptions {
+            preserve_case: true,
+            ..Default::default()
+        };
+        let input = "<adif_ver:5>3.1.6<eoh><Call:4>W1AW<EOR>";
+        let result = parse_adi_with_options(input, &options).unwrap();
+
+        assert_eq!(result.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(result.records[0].fields[0].name, "Call");
+        assert_eq!(result.records[0].call(), Some("W1AW"));
+    }
+
+    #[test]
+    fn test_options_trim_values() {
+        let options = ParserOptions {
+            trim_values: true,
+            ..Default::default()
+        };
+        let input = "<CALL:6> W1AW <NAME:5>Bob\r\n<EOR>";
+        let result = parse_adi_with_options(input, &options).unwrap();
+
+        assert_eq!(result.records[0].call(), Some("W1AW"));
+        assert_eq!(result.records[0].get_value("NAME"), Some("Bob"));
+    }
+
+    #[test]
+    fn test_options_lenient() {
+        let input = "<CALL:x>W1AW<MODE:2>CW<EOR>";
+        assert!(parse_adi_with_options(input, &ParserOptions::strict()).is_err());
+
+        let result = parse_adi_with_options(input, &ParserOptions::lenient()).unwrap();
+        assert_eq!(result.records[0].mode(), Some("CW"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_bytes_invalid_utf8() {
+        // "Bjørn" as Latin-1, with the length counting bytes
+        let input = b"<CALL:4>LA1X<NAME:5>Bj\xf8rn<EOR>";
+        let result = parse_adi_bytes(input).unwrap();
+
+        assert_eq!(result.records[0].call(), Some("LA1X"));
+        assert_eq!(result.records[0].get_value("NAME"), Some("Bjørn"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_bytes_lossy() {
+        let input = b"<NAME:5>Bj\xf8rn<EOR>";
+        let options = ParserOptions {
+            encoding: Encoding::Utf8Lossy,
+            ..Default::default()
+        };
+        let result = parse_adi_bytes_with_options(input, &options).unwrap();
+        assert_eq!(result.records[0].get_value("NAME"), Some("Bj\u{FFFD}rn"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_bytes_strict_utf8() {
+        let input = b"<NAME:5>Bj\xf8rn<EOR>";
+        let result = parse_adi_bytes_with_options(input, &ParserOptions::default());
+        assert!(matches!(
+            result,
+            Err(AdifError::ParseError { position: 10, .. })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_bytes_with_header() {
+        let input = "Log\n<ADIF_VER:5>3.1.6<EOH><NAME:5>José<EOR>".as_bytes();
+        let result = parse_adi_bytes(input).unwrap();
+
+        assert_eq!(result.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(result.records[0].get_value("NAME"), Some("José"));
+    }
 }
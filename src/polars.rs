@@ -0,0 +1,131 @@
+use crate::columns::{ColumnType, parse_bool, parse_number, record_columns};
+use crate::date::{AdifDate, AdifTime};
+use crate::error::Result;
+use crate::field_name::FieldName;
+use crate::from_field::FromField;
+use crate::types::AdifFile;
+use polars::prelude::{Column, DataFrame, DataType as PolarsType, NamedFrom, Series};
+
+impl AdifFile {
+    /// Convert the records to a Polars `DataFrame` with one row per record
+    ///
+    /// Each requested field becomes a column, named in uppercase; with no
+    /// fields requested, every field found in the records is a column, in
+    /// order of first appearance. Dates, times, numbers and booleans get
+    /// `Date`, `Time`, `Float64` and `Boolean` columns, based on each
+    /// field's type in the ADIF specification; a column with a value that
+    /// doesn't parse is kept as `String`. Missing and empty fields are
+    /// null.
+    pub fn to_dataframe(&self, fields: &[&str]) -> Result<DataFrame> {
+        let found = record_columns(&self.records);
+        let columns = if fields.is_empty() {
+            found
+        } else {
+            fields
+                .iter()
+                .map(|name| {
+                    let name = name.to_ascii_uppercase();
+                    let column_type = match found.iter().find(|(n, _)| *n == name) {
+                        Some((_, column_type)) => *column_type,
+                        None => ColumnType::of(FieldName::from(name.as_str()).data_type()),
+                    };
+                    (name, column_type)
+                })
+                .collect()
+        };
+
+        let mut series = Vec::with_capacity(columns.len());
+        for (name, column_type) in &columns {
+            let values = self
+                .records
+                .iter()
+                .map(|r| r.get_value(name).filter(|v| !v.is_empty()));
+            let column = match column_type {
+                ColumnType::Text => Series::new(name.into(), values.collect::<Vec<_>>()),
+                ColumnType::Date => {
+                    let values: Vec<Option<i32>> = values
+                        .map(|v| {
+                            v.and_then(|v| AdifDate::from_field(v).ok())
+                                .map(AdifDate::unix_days)
+                        })
+                        .collect();
+                    Series::new(name.into(), values).cast(&PolarsType::Date)?
+                }
+                ColumnType::Time => {
+                    let values: Vec<Option<i64>> = values
+                        .map(|v| {
+                            v.and_then(|v| AdifTime::from_field(v).ok())
+                                .map(|t| t.to_duration().as_nanos() as i64)
+                        })
+                        .collect();
+                    Series::new(name.into(), values).cast(&PolarsType::Time)?
+                }
+                ColumnType::Number => {
+                    let values: Vec<Option<f64>> =
+                        values.map(|v| v.and_then(parse_number)).collect();
+                    Series::new(name.into(), values)
+                }
+                ColumnType::Boolean => {
+                    let values: Vec<Option<bool>> =
+                        values.map(|v| v.and_then(parse_bool)).collect();
+                    Series::new(name.into(), values)
+                }
+            };
+            series.push(Column::from(column));
+        }
+        Ok(DataFrame::new(self.records.len(), series)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use polars::prelude::AnyValue;
+
+    #[test]
+    fn test_to_dataframe() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:4>1423<FREQ:6>14.074<SWL:1>N<EOR>\
+             <CALL:4>K1AB<QSO_DATE:8>19700102<FREQ:0><EOR>",
+        )
+        .unwrap();
+        let df = file.to_dataframe(&[]).unwrap();
+        assert_eq!(df.shape(), (2, 5));
+        let dtypes: Vec<_> = df.columns().iter().map(|c| c.dtype().clone()).collect();
+        assert_eq!(
+            dtypes,
+            [
+                PolarsType::String,
+                PolarsType::Date,
+                PolarsType::Time,
+                PolarsType::Float64,
+                PolarsType::Boolean,
+            ]
+        );
+        let call = df.column("CALL").unwrap();
+        assert_eq!(call.get(1).unwrap(), AnyValue::String("K1AB"));
+        assert_eq!(
+            df.column("QSO_DATE").unwrap().get(1).unwrap(),
+            AnyValue::Date(1)
+        );
+        assert_eq!(
+            df.column("TIME_ON").unwrap().get(0).unwrap(),
+            AnyValue::Time((14 * 3600 + 23 * 60) * 1_000_000_000)
+        );
+        assert_eq!(df.column("FREQ").unwrap().get(1).unwrap(), AnyValue::Null);
+    }
+
+    #[test]
+    fn test_to_dataframe_fields() {
+        let file = parse_adi("<CALL:4>W1AW<QSO_DATE:4>soon<EOR><CALL:4>K1AB<EOR>").unwrap();
+        let df = file
+            .to_dataframe(&["qso_date", "CALL", "RST_SENT"])
+            .unwrap();
+        let names: Vec<_> = df.columns().iter().map(|c| c.name().as_str()).collect();
+        assert_eq!(names, ["QSO_DATE", "CALL", "RST_SENT"]);
+        // A bad date keeps the column as text
+        assert_eq!(df.column("QSO_DATE").unwrap().dtype(), &PolarsType::String);
+        assert_eq!(df.column("RST_SENT").unwrap().null_count(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::profile::{ProfileExport, check_date, required, station_callsign, subset, value};
+use crate::types::{AdifFile, Record};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Fields kept in a POTA upload
+const FIELDS: &[&str] = &[
+    "STATION_CALLSIGN",
+    "OPERATOR",
+    "CALL",
+    "QSO_DATE",
+    "TIME_ON",
+    "BAND",
+    "FREQ",
+    "MODE",
+    "SUBMODE",
+    "RST_SENT",
+    "RST_RCVD",
+    "MY_SIG",
+    "MY_SIG_INFO",
+    "MY_POTA_REF",
+    "MY_STATE",
+    "SIG",
+    "SIG_INFO",
+    "POTA_REF",
+    "STATE",
+];
+
+/// Export POTA activations, one file per station, park and UTC day
+///
+/// The activated parks come from `MY_POTA_REF`, or `MY_SIG_INFO` when
+/// `MY_SIG` is `POTA`, and may list several parks separated by commas; a
+/// QSO from several parks is copied into each park's file. Files are named
+/// `CALL@PARK-YYYYMMDD.adi`, with `/` in the callsign replaced by `-`.
+pub(crate) fn export(file: &AdifFile) -> ProfileExport {
+    let mut export = ProfileExport::default();
+    for (index, record) in file.records.iter().enumerate() {
+        let (call, date, parks) = match check(record) {
+            Ok(checked) => checked,
+            Err(reason) => {
+                export.reject(index, reason);
+                continue;
+            }
+        };
+        for park in parks {
+            let mut qso = record.clone();
+            qso.set("STATION_CALLSIGN", &call);
+            qso.set("MY_SIG", "POTA");
+            qso.set("MY_SIG_INFO", &park);
+            qso.set("MY_POTA_REF", &park);
+            let qso = subset(&qso, FIELDS);
+            let name = format!("{}@{}-{}.adi", call.replace('/', "-"), park, date);
+            export.file_mut(name, file).records.push(qso);
+        }
+    }
+    export
+}
+
+/// Check a record for the fields POTA requires, returning the station
+/// callsign, date and activated parks
+fn check(record: &Record) -> core::result::Result<(String, &str, Vec<String>), String> {
+    let call = station_callsign(record)
+        .ok_or("missing STATION_CALLSIGN")?
+        .to_ascii_uppercase();
+    let refs = value(record, "MY_POTA_REF")
+        .or_else(|| {
+            value(record, "MY_SIG")
+                .filter(|sig| sig.eq_ignore_ascii_case("POTA"))
+                .and_then(|_| value(record, "MY_SIG_INFO"))
+        })
+        .ok_or("missing MY_POTA_REF or MY_SIG_INFO")?;
+    let mut parks = Vec::new();
+    for park in refs.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if !is_park_ref(park) {
+            return Err(format!("invalid park reference '{}'", park));
+        }
+        let park = park.to_ascii_uppercase();
+        if !parks.contains(&park) {
+            parks.push(park);
+        }
+    }
+    if parks.is_empty() {
+        return Err("missing MY_POTA_REF or MY_SIG_INFO".into());
+    }
+    for name in ["CALL", "TIME_ON", "BAND", "MODE"] {
+        required(record, name)?;
+    }
+    let date = required(record, "QSO_DATE")?;
+    check_date(date)?;
+    Ok((call, date, parks))
+}
+
+/// Check a POTA park reference, such as `US-0001`, optionally followed by
+/// a location, such as `US-0001@US-ME`
+pub(crate) fn is_park_ref(park: &str) -> bool {
+    let park = park.split_once('@').map_or(park, |(park, _)| park);
+    let Some((prefix, number)) = park.split_once('-') else {
+        return false;
+    };
+    (1..=4).contains(&prefix.len())
+        && prefix.bytes().all(|b| b.is_ascii_alphanumeric())
+        && (4..=5).contains(&number.len())
+        && number.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_adi;
+    use crate::profile::ExportProfile;
+
+    #[test]
+    fn test_export_pota() {
+        let file = parse_adi(
+            "Log<ADIF_VER:5>3.1.6<EOH>\
+             <OPERATOR:7>N0CALL/<CALL:4>W1AW<QSO_DATE:8>20240601<TIME_ON:4>1200\
+             <BAND:3>20m<MODE:3>SSB<MY_POTA_REF:15>us-0001,US-0002<NAME:5>Hiram<EOR>\
+             <STATION_CALLSIGN:6>N0CALL<CALL:4>K1AB<QSO_DATE:8>20240601<TIME_ON:4>1210\
+             <BAND:3>40m<MODE:2>CW<MY_SIG:4>POTA<MY_SIG_INFO:7>US-0001<EOR>\
+             <STATION_CALLSIGN:6>N0CALL<CALL:4>K2AB<QSO_DATE:8>20240601<TIME_ON:4>1220\
+             <BAND:3>40m<MODE:2>CW<EOR>\
+             <STATION_CALLSIGN:6>N0CALL<CALL:4>K3AB<QSO_DATE:8>20240601<TIME_ON:4>1230\
+             <BAND:3>40m<MODE:2>CW<MY_POTA_REF:4>0001<EOR>\
+             <STATION_CALLSIGN:6>N0CALL<CALL:4>K4AB<QSO_DATE:8>20240601<TIME_ON:4>1240\
+             <MODE:2>CW<MY_POTA_REF:7>US-0001<EOR>",
+        )
+        .unwrap();
+        let export = ExportProfile::Pota.export(&file);
+        let names: Vec<_> = export.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "N0CALL-@US-0001-20240601.adi",
+                "N0CALL-@US-0002-20240601.adi",
+                "N0CALL@US-0001-20240601.adi",
+            ]
+        );
+        let reasons: Vec<_> = export.rejected.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            reasons,
+            [
+                "record 2: missing MY_POTA_REF or MY_SIG_INFO",
+                "record 3: invalid park reference '0001'",
+                "record 4: missing BAND",
+            ]
+        );
+
+        let second = &export.files[1].file;
+        assert_eq!(second.header.adif_version.as_deref(), Some("3.1.6"));
+        assert_eq!(
+            second.records[0].to_adi_string(),
+            "<STATION_CALLSIGN:7>N0CALL/<OPERATOR:7>N0CALL/<CALL:4>W1AW\
+             <QSO_DATE:8>20240601<TIME_ON:4>1200<BAND:3>20m<MODE:3>SSB\
+             <MY_SIG:4>POTA<MY_SIG_INFO:7>US-0002<MY_POTA_REF:7>US-0002<EOR>\n"
+        );
+        assert_eq!(export.files[2].file.records.len(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::error::{AdifError, Result};
+use crate::types::Record;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+impl Record {
+    /// Get the transmit power in watts, or `None` if TX_PWR is missing or
+    /// empty
+    ///
+    /// Accepts a unit after the number, as some programs write: `100W`,
+    /// `5 w` and `1.5kW` all parse.
+    pub fn tx_pwr(&self) -> Option<Result<f64>> {
+        self.power("TX_PWR")
+    }
+
+    /// Set TX_PWR to a power in watts, replacing any existing value
+    ///
+    /// Fails if the power is negative or not finite.
+    pub fn set_tx_pwr(&mut self, watts: f64) -> Result<()> {
+        if !watts.is_finite() || watts < 0.0 {
+            return Err(AdifError::InvalidField {
+                field: "TX_PWR".into(),
+                message: format!("{} is not a power in watts", watts),
+            });
+        }
+        self.set("TX_PWR", watts.to_string());
+        Ok(())
+    }
+
+    /// Get the contacted station's transmit power in watts, parsed like
+    /// [`Record::tx_pwr`]
+    pub fn rx_pwr(&self) -> Option<Result<f64>> {
+        self.power("RX_PWR")
+    }
+
+    fn power(&self, name: &str) -> Option<Result<f64>> {
+        let value = self
+            .get_value(name)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())?;
+        Some(watts(value).map_err(|message| AdifError::InvalidField {
+            field: name.into(),
+            message,
+        }))
+    }
+}
+
+/// Parse a power in watts, with an optional `W` or `kW` unit
+fn watts(value: &str) -> core::result::Result<f64, String> {
+    let lower = value.to_ascii_lowercase();
+    let (number, scale) = if let Some(number) = lower.strip_suffix("kw") {
+        (number, 1000.0)
+    } else {
+        (lower.strip_suffix('w').unwrap_or(&lower), 1.0)
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|w| w.is_finite() && *w >= 0.0)
+        .map(|w| w * scale)
+        .ok_or_else(|| format!("'{}' is not a power in watts", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_tx_pwr() {
+        let file = parse_adi(
+            "<TX_PWR:3>100<EOR><TX_PWR:4>100W<EOR><TX_PWR:3>0.5<EOR>\
+             <TX_PWR:5>1.5kW<EOR><TX_PWR:4>QRP!<EOR><TX_PWR:2>-5<EOR><CALL:4>W1AW<EOR>",
+        )
+        .unwrap();
+        let power = |i: usize| file.records[i].tx_pwr();
+        assert_eq!(power(0).unwrap().unwrap(), 100.0);
+        assert_eq!(power(1).unwrap().unwrap(), 100.0);
+        assert_eq!(power(2).unwrap().unwrap(), 0.5);
+        assert_eq!(power(3).unwrap().unwrap(), 1500.0);
+        assert!(matches!(
+            power(4),
+            Some(Err(AdifError::InvalidField { field, message }))
+                if field == "TX_PWR" && message == "'QRP!' is not a power in watts"
+        ));
+        assert!(power(5).unwrap().is_err());
+        assert!(power(6).is_none());
+    }
+
+    #[test]
+    fn test_set_tx_pwr() {
+        let mut record = Record::new();
+        record.set_tx_pwr(5.0).unwrap();
+        record.set_tx_pwr(0.25).unwrap();
+        assert_eq!(record.to_adi_string(), "<TX_PWR:4>0.25<EOR>\n");
+        assert_eq!(record.tx_pwr().unwrap().unwrap(), 0.25);
+        assert!(record.set_tx_pwr(f64::NAN).is_err());
+        assert!(record.set_tx_pwr(-1.0).is_err());
+    }
+}
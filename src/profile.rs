@@ -0,0 +1,267 @@
+//! Export profiles that prepare a log for upload to a logging or awards
+//! service
+
+use crate::date::{AdifDate, AdifTime};
+use crate::from_field::FromField;
+use crate::types::{AdifFile, AdifHeader, Field, Record};
+use crate::{clublog, eqsl, lotw, pota, wwff};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Header fields copied from the source log into exported files
+const HEADER_FIELDS: &[&str] = &[
+    "ADIF_VER",
+    "PROGRAMID",
+    "PROGRAMVERSION",
+    "CREATED_TIMESTAMP",
+];
+
+/// A service that a log can be exported for
+///
+/// Each profile checks every record for the fields the service requires,
+/// keeps only the fields it accepts, and reports the records it had to
+/// leave out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ExportProfile {
+    /// Parks on the Air activations, one file per park and UTC day
+    Pota,
+    /// World Wide Flora and Fauna activations, one file per reference and
+    /// UTC day
+    Wwff,
+    /// eQSL uploads, as a single file
+    Eqsl,
+    /// Logbook of the World uploads, ready for signing with TQSL, one file
+    /// per station location
+    Lotw,
+    /// Club Log uploads, as a single file
+    ClubLog,
+}
+
+impl ExportProfile {
+    /// Export a log for this profile
+    pub fn export(self, file: &AdifFile) -> ProfileExport {
+        match self {
+            ExportProfile::Pota => pota::export(file),
+            ExportProfile::Wwff => wwff::export(file),
+            ExportProfile::Eqsl => eqsl::export(file),
+            ExportProfile::Lotw => lotw::export(file),
+            ExportProfile::ClubLog => clublog::export(file),
+        }
+    }
+
+    /// Check a log against this profile without exporting it
+    ///
+    /// Reports the records the export would leave out, and for Club Log
+    /// also the records the service accepts but may misinterpret, such as
+    /// unknown QSL statuses or duplicates. Issues are in log order.
+    pub fn lint(self, file: &AdifFile) -> Vec<Lint> {
+        let mut lints: Vec<Lint> = self
+            .export(file)
+            .rejected
+            .into_iter()
+            .map(|r| Lint {
+                record: r.record,
+                level: LintLevel::Reject,
+                message: r.reason,
+            })
+            .collect();
+        if self == ExportProfile::ClubLog {
+            lints.extend(clublog::warnings(file));
+        }
+        lints.sort_by_key(|l| l.record);
+        lints
+    }
+}
+
+/// How serious a [`Lint`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    /// The export leaves the record out
+    Reject,
+    /// The service accepts the record but may misinterpret it
+    Warning,
+}
+
+/// A problem with a record found by [`ExportProfile::lint`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// Index of the record in the log
+    pub record: usize,
+    /// How serious the problem is
+    pub level: LintLevel,
+    /// What is wrong
+    pub message: String,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            LintLevel::Reject => "rejected",
+            LintLevel::Warning => "warning",
+        };
+        write!(f, "record {}: {}: {}", self.record, level, self.message)
+    }
+}
+
+/// A record left out of an export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// Index of the record in the source log
+    pub record: usize,
+    /// Why the record was left out
+    pub reason: String,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "record {}: {}", self.record, self.reason)
+    }
+}
+
+/// A log ready for upload, with a suggested file name
+#[derive(Debug, Clone)]
+pub struct ExportFile {
+    /// Suggested file name, following the service's naming convention
+    pub name: String,
+    /// The exported log
+    pub file: AdifFile,
+}
+
+/// The result of exporting a log with an [`ExportProfile`]
+#[derive(Debug, Clone, Default)]
+pub struct ProfileExport {
+    /// Files to upload
+    pub files: Vec<ExportFile>,
+    /// Records left out, in log order
+    pub rejected: Vec<Rejection>,
+}
+
+impl ProfileExport {
+    /// Record that a record was left out
+    pub(crate) fn reject(&mut self, record: usize, reason: String) {
+        self.rejected.push(Rejection { record, reason });
+    }
+
+    /// Get the file with a name, adding an empty one with the source log's
+    /// header if needed
+    pub(crate) fn file_mut(&mut self, name: String, source: &AdifFile) -> &mut AdifFile {
+        let index = match self.files.iter().position(|f| f.name == name) {
+            Some(index) => index,
+            None => {
+                self.files.push(ExportFile {
+                    name,
+                    file: AdifFile {
+                        header: header(&source.header),
+                        ..AdifFile::default()
+                    },
+                });
+                self.files.len() - 1
+            }
+        };
+        &mut self.files[index].file
+    }
+}
+
+/// Copy the well-known fields of a header, dropping everything else
+fn header(source: &AdifHeader) -> AdifHeader {
+    let mut header = AdifHeader::default();
+    for name in HEADER_FIELDS {
+        if let Some(field) = source.get(name) {
+            header.add_field(Field::with_type(
+                name.to_string(),
+                field.data_type,
+                field.value.clone(),
+            ));
+        }
+    }
+    header
+}
+
+/// Get a field value, trimmed, if present and not empty
+pub(crate) fn value<'a>(record: &'a Record, name: &str) -> Option<&'a str> {
+    record
+        .get_value(name)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// Get a field value that must be present
+pub(crate) fn required<'a>(
+    record: &'a Record,
+    name: &str,
+) -> core::result::Result<&'a str, String> {
+    value(record, name).ok_or_else(|| format!("missing {}", name))
+}
+
+/// Get the callsign used on the air: `STATION_CALLSIGN`, then `OPERATOR`
+pub(crate) fn station_callsign(record: &Record) -> Option<&str> {
+    record.my_callsign().map(str::trim)
+}
+
+/// Check that a QSO_DATE is a valid ADIF date
+pub(crate) fn check_date(date: &str) -> core::result::Result<(), String> {
+    AdifDate::from_field(date)
+        .map(drop)
+        .map_err(|_| format!("invalid QSO_DATE '{}'", date))
+}
+
+/// Check that a TIME_ON is a valid ADIF time
+pub(crate) fn check_time(time: &str) -> core::result::Result<(), String> {
+    AdifTime::from_field(time)
+        .map(drop)
+        .map_err(|_| format!("invalid TIME_ON '{}'", time))
+}
+
+/// Copy the fields of a record that a service accepts, in the given order
+pub(crate) fn subset(record: &Record, names: &[&str]) -> Record {
+    let mut subset = Record::new();
+    for name in names {
+        if let Some(value) = value(record, name) {
+            subset.add_field(Field::new(name.to_string(), value));
+        }
+    }
+    subset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_subset_and_set() {
+        let file = parse_adi("<NAME:3>Bob<CALL:4>W1AW<BAND:0><EOR>").unwrap();
+        let mut record = subset(&file.records[0], &["CALL", "BAND", "MODE"]);
+        record.set("mode", "CW");
+        record.set("CALL", "K1AB");
+        assert_eq!(record.to_adi_string(), "<CALL:4>K1AB<MODE:2>CW<EOR>\n");
+    }
+
+    #[test]
+    fn test_check_date_and_time() {
+        assert!(check_date("20240229").is_ok());
+        assert!(check_date("20241345").is_err());
+        assert!(check_date("19291231").is_err());
+        assert!(check_time("1430").is_ok());
+        assert!(check_time("2561").is_err());
+    }
+
+    #[test]
+    fn test_file_mut() {
+        let source =
+            parse_adi("Log<ADIF_VER:5>3.1.6<USERDEF1:3>FOO<EOH><CALL:4>W1AW<EOR>").unwrap();
+        let mut export = ProfileExport::default();
+        export
+            .file_mut("a.adi".into(), &source)
+            .records
+            .push(Record::new());
+        export.file_mut("a.adi".into(), &source);
+        assert_eq!(export.files.len(), 1);
+        let header = &export.files[0].file.header;
+        assert_eq!(header.adif_version.as_deref(), Some("3.1.6"));
+        assert_eq!(header.fields.len(), 1);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::enumeration::enumeration;
+use crate::error::Result;
+use crate::types::Record;
+
+enumeration! {
+    /// A propagation mode, from the ADIF Propagation Mode enumeration
+    pub enum PropMode for "PROP_MODE" {
+        As => "AS",
+        Aue => "AUE",
+        Aur => "AUR",
+        Bs => "BS",
+        Ech => "ECH",
+        Eme => "EME",
+        Es => "ES",
+        F2 => "F2",
+        Fai => "FAI",
+        Gwave => "GWAVE",
+        Internet => "INTERNET",
+        Ion => "ION",
+        Irl => "IRL",
+        Los => "LOS",
+        Ms => "MS",
+        Rpt => "RPT",
+        Rs => "RS",
+        Sat => "SAT",
+        Tep => "TEP",
+        Tr => "TR",
+    }
+}
+
+impl PropMode {
+    /// Get the description of the mode from the specification
+    pub fn description(self) -> &'static str {
+        match self {
+            PropMode::As => "Aircraft Scatter",
+            PropMode::Aue => "Aurora-E",
+            PropMode::Aur => "Aurora",
+            PropMode::Bs => "Back scatter",
+            PropMode::Ech => "EchoLink",
+            PropMode::Eme => "Earth-Moon-Earth",
+            PropMode::Es => "Sporadic E",
+            PropMode::F2 => "F2 Reflection",
+            PropMode::Fai => "Field Aligned Irregularities",
+            PropMode::Gwave => "Ground Wave",
+            PropMode::Internet => "Internet-assisted",
+            PropMode::Ion => "Ionoscatter",
+            PropMode::Irl => "IRLP",
+            PropMode::Los => {
+                "Line of Sight (includes transmission through obstacles such as walls)"
+            }
+            PropMode::Ms => "Meteor scatter",
+            PropMode::Rpt => "Terrestrial or atmospheric repeater or transponder",
+            PropMode::Rs => "Rain scatter",
+            PropMode::Sat => "Satellite",
+            PropMode::Tep => "Trans-equatorial",
+            PropMode::Tr => "Tropospheric ducting",
+        }
+    }
+}
+
+impl Record {
+    /// Get the propagation mode, or `None` if PROP_MODE is missing or empty
+    pub fn prop_mode(&self) -> Option<Result<PropMode>> {
+        self.get_parsed("PROP_MODE")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdifError, parse_adi};
+
+    #[test]
+    fn test_prop_mode() {
+        let file =
+            parse_adi("<PROP_MODE:3>sat<EOR><PROP_MODE:4>SATX<EOR><CALL:4>W1AW<EOR>").unwrap();
+        let mode = file.records[0].prop_mode().unwrap().unwrap();
+        assert_eq!(mode, PropMode::Sat);
+        assert_eq!(mode.to_string(), "SAT");
+        assert_eq!(PropMode::Ms.description(), "Meteor scatter");
+        assert!(matches!(
+            file.records[1].prop_mode(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "PROP_MODE"
+        ));
+        assert!(file.records[2].prop_mode().is_none());
+    }
+}
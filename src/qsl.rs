@@ -0,0 +1,119 @@
+use crate::enumeration::enumeration;
+use crate::error::Result;
+use crate::types::Record;
+
+enumeration! {
+    /// A QSL received status, from the ADIF QSL_RCVD enumeration
+    ///
+    /// Also used by LOTW_QSL_RCVD and EQSL_QSL_RCVD.
+    pub enum QslStatus for "QSL_RCVD" {
+        Yes => "Y",
+        No => "N",
+        Requested => "R",
+        Ignore => "I",
+        Verified => "V",
+    }
+}
+
+enumeration! {
+    /// A QSL sent status, from the ADIF QSL_SENT enumeration
+    ///
+    /// Also used by LOTW_QSL_SENT and EQSL_QSL_SENT.
+    pub enum QslSentStatus for "QSL_SENT" {
+        Yes => "Y",
+        No => "N",
+        Requested => "R",
+        Queued => "Q",
+        Ignore => "I",
+    }
+}
+
+enumeration! {
+    /// A route for a QSL card, from the ADIF QSL_VIA enumeration used by
+    /// QSL_SENT_VIA and QSL_RCVD_VIA
+    pub enum QslVia for "QSL_SENT_VIA" {
+        Bureau => "B",
+        Direct => "D",
+        Electronic => "E",
+        Manager => "M",
+    }
+}
+
+impl QslStatus {
+    /// Check whether the status confirms the QSO (`Y`, or the deprecated
+    /// `V`)
+    pub fn is_confirmed(self) -> bool {
+        matches!(self, QslStatus::Yes | QslStatus::Verified)
+    }
+}
+
+impl Record {
+    /// Get the paper QSL received status, or `None` if QSL_RCVD is missing
+    /// or empty
+    pub fn qsl_rcvd(&self) -> Option<Result<QslStatus>> {
+        self.get_parsed("QSL_RCVD")
+    }
+
+    /// Get the paper QSL sent status, or `None` if QSL_SENT is missing or
+    /// empty
+    pub fn qsl_sent(&self) -> Option<Result<QslSentStatus>> {
+        self.get_parsed("QSL_SENT")
+    }
+
+    /// Get the route the QSL card was received by
+    pub fn qsl_rcvd_via(&self) -> Option<Result<QslVia>> {
+        self.get_parsed("QSL_RCVD_VIA")
+    }
+
+    /// Get the route the QSL card was sent by
+    pub fn qsl_sent_via(&self) -> Option<Result<QslVia>> {
+        self.get_parsed("QSL_SENT_VIA")
+    }
+
+    /// Get the LoTW QSL received status
+    pub fn lotw_qsl_rcvd(&self) -> Option<Result<QslStatus>> {
+        self.get_parsed("LOTW_QSL_RCVD")
+    }
+
+    /// Get the LoTW QSL sent status
+    pub fn lotw_qsl_sent(&self) -> Option<Result<QslSentStatus>> {
+        self.get_parsed("LOTW_QSL_SENT")
+    }
+
+    /// Get the eQSL received status
+    pub fn eqsl_qsl_rcvd(&self) -> Option<Result<QslStatus>> {
+        self.get_parsed("EQSL_QSL_RCVD")
+    }
+
+    /// Get the eQSL sent status
+    pub fn eqsl_qsl_sent(&self) -> Option<Result<QslSentStatus>> {
+        self.get_parsed("EQSL_QSL_SENT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdifError, parse_adi};
+
+    #[test]
+    fn test_qsl_status() {
+        let file = parse_adi(
+            "<QSL_RCVD:1>v<QSL_SENT:1>Q<QSL_SENT_VIA:1>B<LOTW_QSL_RCVD:1>Q\
+             <EQSL_QSL_RCVD:1>N<EOR>",
+        )
+        .unwrap();
+        let record = &file.records[0];
+        let rcvd = record.qsl_rcvd().unwrap().unwrap();
+        assert_eq!(rcvd, QslStatus::Verified);
+        assert!(rcvd.is_confirmed());
+        assert_eq!(record.qsl_sent().unwrap().unwrap(), QslSentStatus::Queued);
+        assert_eq!(record.qsl_sent_via().unwrap().unwrap(), QslVia::Bureau);
+        assert!(matches!(
+            record.lotw_qsl_rcvd(),
+            Some(Err(AdifError::InvalidField { field, .. })) if field == "LOTW_QSL_RCVD"
+        ));
+        assert!(!record.eqsl_qsl_rcvd().unwrap().unwrap().is_confirmed());
+        assert!(record.eqsl_qsl_sent().is_none());
+    }
+}
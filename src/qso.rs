@@ -0,0 +1,211 @@
+use crate::band::Band;
+use crate::error::{AdifError, Result};
+use crate::mode::Mode;
+use crate::types::{Field, Record};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use chrono::{NaiveDate, NaiveTime};
+
+/// Fields held in the typed members of [`Qso`]
+const TYPED_FIELDS: &[&str] = &[
+    "CALL",
+    "QSO_DATE",
+    "TIME_ON",
+    "QSO_DATE_OFF",
+    "TIME_OFF",
+    "BAND",
+    "FREQ",
+    "MODE",
+    "SUBMODE",
+    "RST_SENT",
+    "RST_RCVD",
+    "GRIDSQUARE",
+    "TX_PWR",
+    "NAME",
+    "QTH",
+    "COMMENT",
+    "STATION_CALLSIGN",
+    "OPERATOR",
+    "MY_GRIDSQUARE",
+];
+
+/// A QSO with typed values for the common ADIF fields
+///
+/// Converting from a [`Record`] checks that CALL, QSO_DATE and TIME_ON are
+/// present and that every typed field parses; empty fields count as
+/// missing. Fields without a member of their own are kept in `other`, so
+/// converting back to a `Record` loses nothing but formatting: times are
+/// written as HHMMSS and frequencies without trailing zeros.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qso {
+    /// Callsign of the contacted station
+    pub call: String,
+    /// Date the QSO started (UTC)
+    pub qso_date: NaiveDate,
+    /// Time the QSO started (UTC)
+    pub time_on: NaiveTime,
+    /// Date the QSO ended (UTC)
+    pub qso_date_off: Option<NaiveDate>,
+    /// Time the QSO ended (UTC)
+    pub time_off: Option<NaiveTime>,
+    /// Band
+    pub band: Option<Band>,
+    /// Transmit frequency in MHz
+    pub freq: Option<f64>,
+    /// Mode
+    pub mode: Option<Mode>,
+    /// Submode
+    pub submode: Option<String>,
+    /// Signal report sent
+    pub rst_sent: Option<String>,
+    /// Signal report received
+    pub rst_rcvd: Option<String>,
+    /// Maidenhead locator of the contacted station
+    pub gridsquare: Option<String>,
+    /// Transmit power in watts
+    pub tx_pwr: Option<f64>,
+    /// Name of the contacted operator
+    pub name: Option<String>,
+    /// City of the contacted station
+    pub qth: Option<String>,
+    /// Comment
+    pub comment: Option<String>,
+    /// Callsign used on the air
+    pub station_callsign: Option<String>,
+    /// Callsign of the operator
+    pub operator: Option<String>,
+    /// Maidenhead locator of the logging station
+    pub my_gridsquare: Option<String>,
+    /// All other fields, in record order
+    pub other: Vec<Field>,
+}
+
+impl TryFrom<&Record> for Qso {
+    type Error = AdifError;
+
+    fn try_from(record: &Record) -> Result<Self> {
+        let text = |name: &str| {
+            record
+                .get_value(name)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let owned = |name: &str| text(name).map(str::to_string);
+
+        Ok(Qso {
+            call: record.get_as::<String>("CALL")?.trim().to_string(),
+            qso_date: record.get_as("QSO_DATE")?,
+            time_on: record.get_as("TIME_ON")?,
+            qso_date_off: record.get_parsed("QSO_DATE_OFF").transpose()?,
+            time_off: record.get_parsed("TIME_OFF").transpose()?,
+            band: record.get_parsed("BAND").transpose()?,
+            freq: record.get_parsed("FREQ").transpose()?,
+            mode: record.get_parsed("MODE").transpose()?,
+            submode: owned("SUBMODE"),
+            rst_sent: owned("RST_SENT"),
+            rst_rcvd: owned("RST_RCVD"),
+            gridsquare: owned("GRIDSQUARE"),
+            tx_pwr: record.tx_pwr().transpose()?,
+            name: owned("NAME"),
+            qth: owned("QTH"),
+            comment: owned("COMMENT"),
+            station_callsign: owned("STATION_CALLSIGN"),
+            operator: owned("OPERATOR"),
+            my_gridsquare: owned("MY_GRIDSQUARE"),
+            other: record
+                .fields
+                .iter()
+                .filter(|f| !TYPED_FIELDS.iter().any(|n| f.name.eq_ignore_ascii_case(n)))
+                .cloned()
+                .collect(),
+        })
+    }
+}
+
+impl From<Qso> for Record {
+    fn from(qso: Qso) -> Self {
+        let mut record = Record::new();
+        let mut add = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                record.add_field(Field::new(name, value));
+            }
+        };
+        let date = |d: NaiveDate| d.format("%Y%m%d").to_string();
+        let time = |t: NaiveTime| t.format("%H%M%S").to_string();
+
+        add("CALL", Some(qso.call));
+        add("QSO_DATE", Some(date(qso.qso_date)));
+        add("TIME_ON", Some(time(qso.time_on)));
+        add("QSO_DATE_OFF", qso.qso_date_off.map(date));
+        add("TIME_OFF", qso.time_off.map(time));
+        add("BAND", qso.band.map(|b| b.as_str().to_string()));
+        add("FREQ", qso.freq.map(|f| f.to_string()));
+        add("MODE", qso.mode.map(|m| m.as_str().to_string()));
+        add("SUBMODE", qso.submode);
+        add("RST_SENT", qso.rst_sent);
+        add("RST_RCVD", qso.rst_rcvd);
+        add("GRIDSQUARE", qso.gridsquare);
+        add("TX_PWR", qso.tx_pwr.map(|p| p.to_string()));
+        add("NAME", qso.name);
+        add("QTH", qso.qth);
+        add("COMMENT", qso.comment);
+        add("STATION_CALLSIGN", qso.station_callsign);
+        add("OPERATOR", qso.operator);
+        add("MY_GRIDSQUARE", qso.my_gridsquare);
+        record.fields.extend(qso.other);
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_qso_from_record() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:4>1423<BAND:3>20M<FREQ:7>14.0740\
+             <MODE:4>MFSK<SUBMODE:3>FT4<TX_PWR:3>100<APP_X_Y:1>z<EOR>",
+        )
+        .unwrap();
+        let qso = Qso::try_from(&file.records[0]).unwrap();
+        assert_eq!(qso.call, "W1AW");
+        assert_eq!(qso.qso_date, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert_eq!(qso.time_on, NaiveTime::from_hms_opt(14, 23, 0).unwrap());
+        assert_eq!(qso.band, Some(Band::M20));
+        assert_eq!(qso.freq, Some(14.074));
+        assert_eq!(qso.mode, Some(Mode::Mfsk));
+        assert_eq!(qso.tx_pwr, Some(100.0));
+        assert_eq!(qso.other.len(), 1);
+
+        let record = Record::from(qso);
+        assert_eq!(
+            record.to_adi_string(),
+            "<CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:6>142300<BAND:3>20m<FREQ:6>14.074\
+             <MODE:4>MFSK<SUBMODE:3>FT4<TX_PWR:3>100<APP_X_Y:1>z<EOR>\n"
+        );
+    }
+
+    #[test]
+    fn test_qso_errors() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240115<EOR>\
+             <CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:4>2560<EOR>\
+             <CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:4>1200<MODE:3>FT4<EOR>",
+        )
+        .unwrap();
+        assert!(matches!(
+            Qso::try_from(&file.records[0]),
+            Err(AdifError::MissingField(name)) if name == "TIME_ON"
+        ));
+        assert!(matches!(
+            Qso::try_from(&file.records[1]),
+            Err(AdifError::InvalidField { field, .. }) if field == "TIME_ON"
+        ));
+        assert!(matches!(
+            Qso::try_from(&file.records[2]),
+            Err(AdifError::InvalidField { field, .. }) if field == "MODE"
+        ));
+    }
+}
@@ -0,0 +1,736 @@
+use crate::error::{AdifError, Result};
+use crate::field_name::{FieldName, Interner};
+use crate::options::{Limit, ParserOptions};
+use crate::types::{AdifHeader, DataType, Field, Record};
+use crate::validate::userdef_names;
+use std::io::{BufRead, ErrorKind};
+
+/// A piece of ADI data read from the stream
+enum Token {
+    Field(Field),
+    Eoh,
+    Eor,
+    Eof,
+}
+
+/// Streaming ADI reader that yields one record at a time
+///
+/// Fields seen before an `<EOH>` tag form the header; if the first
+/// record ends before any `<EOH>` is seen, the file has no header. Only
+/// the record currently being read is held in memory.
+pub struct RecordReader<R> {
+    reader: R,
+    core: ReaderCore,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    /// Create a reader over a buffered ADI source
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParserOptions::default())
+    }
+
+    /// Create a reader using the given parser options.
+    ///
+    /// In lenient mode a malformed field is dropped and reading resumes at
+    /// the next `<`, and a value whose length runs past the end of the
+    /// input is kept up to the next `<`. Since the stream can't be rewound,
+    /// a field whose length overruns into following tags still swallows
+    /// them.
+    pub fn with_options(reader: R, options: ParserOptions) -> Self {
+        Self {
+            reader,
+            core: ReaderCore::new(options),
+        }
+    }
+
+    /// Get the file header, reading it from the stream if necessary
+    pub fn header(&mut self) -> Result<&AdifHeader> {
+        while self.core.header().is_none() {
+            self.fill()?;
+        }
+        Ok(self.core.header().expect("header was read"))
+    }
+
+    /// Read the next record, or `None` at the end of the data
+    pub fn read_record(&mut self) -> Result<Option<Record>> {
+        loop {
+            if let Some(result) = self.core.next_record() {
+                return result;
+            }
+            self.fill()?;
+        }
+    }
+
+    /// Pass the buffered input to the core
+    fn fill(&mut self) -> Result<()> {
+        let n = match self.reader.fill_buf() {
+            Ok(buf) => self.core.feed(buf)?,
+            Err(e) if e.kind() == ErrorKind::Interrupted => return Ok(()),
+            Err(e) => {
+                self.core.fail();
+                return Err(e.into());
+            }
+        };
+        self.reader.consume(n);
+        Ok(())
+    }
+}
+
+/// Where [`ReaderCore`] is in the ADI syntax
+enum Lex {
+    /// Skipping text up to the next `<`
+    Text,
+    /// Reading a data specifier whose `<` is at `start`
+    Tag { start: usize },
+    /// Reading `length` bytes of a value that starts at `start`
+    Value {
+        name: String,
+        data_type: DataType,
+        length: usize,
+        tag_start: usize,
+        start: usize,
+    },
+}
+
+/// Tokenizer and record builder shared by the stream readers
+///
+/// It doesn't do any I/O: a reader passes whatever input it has buffered
+/// to [`ReaderCore::feed`], and an empty slice at the end of the input,
+/// until the header or the next record is ready.
+pub(crate) struct ReaderCore {
+    pos: usize,
+    options: ParserOptions,
+    lex: Lex,
+    /// The text, tag or value being read
+    buf: Vec<u8>,
+    header: Option<AdifHeader>,
+    /// Text before the first field, while the header isn't known yet
+    preamble: Vec<u8>,
+    /// Fields read while the header isn't known yet
+    header_fields: Vec<Field>,
+    record: Record,
+    ready: Option<Record>,
+    done: bool,
+    records_read: usize,
+    interner: Interner,
+    /// Record field names defined by USERDEFn header fields
+    userdefs: Vec<String>,
+}
+
+impl ReaderCore {
+    pub(crate) fn new(options: ParserOptions) -> Self {
+        Self {
+            pos: 0,
+            options,
+            lex: Lex::Text,
+            buf: Vec::new(),
+            header: None,
+            preamble: Vec::new(),
+            header_fields: Vec::new(),
+            record: Record::new(),
+            ready: None,
+            done: false,
+            records_read: 0,
+            interner: Interner::new(),
+            userdefs: Vec::new(),
+        }
+    }
+
+    /// Get the header, once enough input has been fed to know it
+    pub(crate) fn header(&self) -> Option<&AdifHeader> {
+        self.header.as_ref()
+    }
+
+    /// Take the next record, `Some(Ok(None))` at the end of the data, or
+    /// `None` if more input is needed
+    pub(crate) fn next_record(&mut self) -> Option<Result<Option<Record>>> {
+        self.header.as_ref()?;
+        if let Some(record) = self.ready.take() {
+            self.records_read += 1;
+            let result = self
+                .options
+                .limits
+                .check(Limit::Records, self.records_read, self.pos);
+            if result.is_err() {
+                self.fail();
+            }
+            return Some(result.map(|()| Some(record)));
+        }
+        self.done.then_some(Ok(None))
+    }
+
+    /// Read from `input` until the header or a record is ready, returning
+    /// the number of bytes used. An empty `input` is the end of the data.
+    pub(crate) fn feed(&mut self, input: &[u8]) -> Result<usize> {
+        let result = self.read(input);
+        if result.is_err() {
+            self.fail();
+        }
+        result
+    }
+
+    /// Stop reading after an error
+    pub(crate) fn fail(&mut self) {
+        self.done = true;
+        self.ready = None;
+        self.header.get_or_insert_with(AdifHeader::default);
+    }
+
+    fn read(&mut self, input: &[u8]) -> Result<usize> {
+        if self.done {
+            return Ok(0);
+        }
+        if input.is_empty() {
+            self.finish()?;
+            return Ok(0);
+        }
+        // Crossing the total limit is detected without reading much past it
+        let budget = self.options.limits.read_budget(self.pos);
+        let input = &input[..input.len().min(budget.try_into().unwrap_or(usize::MAX))];
+        let had_header = self.header.is_some();
+        let mut used = 0;
+        while used < input.len()
+            && self.ready.is_none()
+            && !self.done
+            && self.header.is_some() == had_header
+        {
+            used += self.step(&input[used..])?;
+        }
+        Ok(used)
+    }
+
+    /// Use some of `input` in the current state, returning how much
+    fn step(&mut self, input: &[u8]) -> Result<usize> {
+        match self.lex {
+            Lex::Text => {
+                let end = input.iter().position(|&b| b == b'<');
+                if self.header.is_none() && self.header_fields.is_empty() {
+                    self.preamble
+                        .extend_from_slice(&input[..end.unwrap_or(input.len())]);
+                }
+                let n = end.map_or(input.len(), |i| i + 1);
+                self.advance(n)?;
+                if end.is_some() {
+                    self.lex = Lex::Tag {
+                        start: self.pos - 1,
+                    };
+                }
+                Ok(n)
+            }
+            Lex::Tag { start } => {
+                let end = input.iter().position(|&b| b == b'>');
+                self.buf
+                    .extend_from_slice(&input[..end.unwrap_or(input.len())]);
+                let n = end.map_or(input.len(), |i| i + 1);
+                self.advance(n)?;
+                if end.is_some() {
+                    self.end_tag(start)?;
+                }
+                Ok(n)
+            }
+            Lex::Value { length, .. } => {
+                let n = (length - self.buf.len()).min(input.len());
+                self.buf.extend_from_slice(&input[..n]);
+                self.advance(n)?;
+                if self.buf.len() == length {
+                    self.end_value()?;
+                }
+                Ok(n)
+            }
+        }
+    }
+
+    fn advance(&mut self, n: usize) -> Result<()> {
+        self.pos += n;
+        self.options
+            .limits
+            .check(Limit::TotalBytes, self.pos, self.pos)
+    }
+
+    fn end_tag(&mut self, start: usize) -> Result<()> {
+        self.lex = Lex::Text;
+        let tag = core::mem::take(&mut self.buf);
+        let (name, data_type, length) = match parse_tag(&tag, start, &self.options) {
+            Ok(Tag::Field {
+                name,
+                data_type,
+                length,
+            }) => (name, data_type, length),
+            Ok(Tag::Token(token)) => return self.token(Ok(token)),
+            Err(e) => return self.token(Err(e)),
+        };
+        if let Err(e) = self.options.limits.check(Limit::FieldLength, length, start) {
+            return self.token(Err(e));
+        }
+        self.buf = Vec::with_capacity(length.min(8192));
+        self.lex = Lex::Value {
+            name,
+            data_type,
+            length,
+            tag_start: start,
+            start: self.pos,
+        };
+        if length == 0 {
+            self.end_value()?;
+        }
+        Ok(())
+    }
+
+    /// Finish a value, which is short if the input ended
+    fn end_value(&mut self) -> Result<()> {
+        let Lex::Value {
+            name,
+            data_type,
+            length,
+            tag_start,
+            start,
+        } = core::mem::replace(&mut self.lex, Lex::Text)
+        else {
+            return Ok(());
+        };
+        let mut value = core::mem::take(&mut self.buf);
+        if value.len() < length {
+            if !self.options.lenient {
+                return Err(AdifError::InvalidFieldLength {
+                    position: start,
+                    expected: length,
+                    found: value.len(),
+                });
+            }
+            // The value overruns the input; keep it up to the next tag
+            if let Some(end) = value.iter().position(|&b| b == b'<') {
+                value.truncate(end);
+            }
+        }
+        let field = self.field(&name, data_type, value, tag_start, start);
+        self.token(field.map(Token::Field))
+    }
+
+    /// Build a field from a complete value.
+    ///
+    /// Checked once the value is consumed, so that lenient mode can drop
+    /// the field without losing its place in the stream.
+    fn field(
+        &mut self,
+        name: &str,
+        data_type: DataType,
+        value: Vec<u8>,
+        tag_start: usize,
+        start: usize,
+    ) -> Result<Field> {
+        self.options
+            .unknown_fields
+            .check(name, &self.userdefs, tag_start)?;
+        let value = decode(value, start, &self.options)?;
+        self.options
+            .intl_text
+            .check(name, data_type, &value, tag_start)?;
+        Ok(make_field(
+            self.interner.intern(name),
+            data_type,
+            &value,
+            &self.options,
+        ))
+    }
+
+    /// Handle the end of the input
+    fn finish(&mut self) -> Result<()> {
+        match self.lex {
+            Lex::Text => {}
+            Lex::Tag { .. } => {
+                self.lex = Lex::Text;
+                self.buf.clear();
+                self.token(Err(AdifError::UnexpectedEof(self.pos)))?;
+            }
+            Lex::Value { .. } => self.end_value()?,
+        }
+        self.handle(Token::Eof)
+    }
+
+    /// Handle a token, skipping a malformed field in lenient mode
+    fn token(&mut self, token: Result<Token>) -> Result<()> {
+        match token {
+            Ok(token) => self.handle(token),
+            Err(e @ AdifError::LimitExceeded { .. }) => Err(e),
+            Err(_) if self.options.lenient => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn handle(&mut self, token: Token) -> Result<()> {
+        if self.header.is_none() {
+            return self.handle_header(token);
+        }
+        match token {
+            Token::Field(field) => {
+                let count = self.record.fields.len() + 1;
+                self.options
+                    .limits
+                    .check(Limit::FieldsPerRecord, count, self.pos)?;
+                self.add_to_record(field)?;
+            }
+            Token::Eor => self.end_record(),
+            // A stray <EOH> after the header is ignored
+            Token::Eoh => {}
+            Token::Eof => {
+                self.done = true;
+                // Don't forget any trailing record without EOR
+                self.end_record();
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a token up to the first `<EOH>` or `<EOR>`, which decides
+    /// whether a header exists
+    fn handle_header(&mut self, token: Token) -> Result<()> {
+        match token {
+            Token::Field(field) => {
+                let count = self.header_fields.len() + 1;
+                self.options
+                    .limits
+                    .check(Limit::FieldsPerRecord, count, self.pos)?;
+                self.header_fields.push(field);
+            }
+            Token::Eoh => {
+                let mut header = AdifHeader {
+                    preamble: decode(core::mem::take(&mut self.preamble), 0, &self.options)?,
+                    ..Default::default()
+                };
+                for field in core::mem::take(&mut self.header_fields) {
+                    header.add_field(field);
+                }
+                self.userdefs = userdef_names(&header.fields);
+                self.header = Some(header);
+            }
+            token => {
+                self.done = matches!(token, Token::Eof);
+                for field in core::mem::take(&mut self.header_fields) {
+                    self.add_to_record(field)?;
+                }
+                self.end_record();
+                self.header = Some(AdifHeader::default());
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a field to the current record, applying the duplicate-field policy
+    fn add_to_record(&mut self, field: Field) -> Result<()> {
+        match self
+            .record
+            .add_field_with(field, self.options.duplicate_fields)
+        {
+            Err(field) if !self.options.lenient => Err(AdifError::DuplicateField {
+                position: self.pos,
+                name: field.name.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn end_record(&mut self) {
+        if !self.record.fields.is_empty() {
+            self.ready = Some(core::mem::take(&mut self.record));
+        }
+    }
+}
+
+/// A parsed data specifier
+enum Tag {
+    Token(Token),
+    Field {
+        name: String,
+        data_type: DataType,
+        length: usize,
+    },
+}
+
+/// Parse the contents of a tag (without the angle brackets) starting at
+/// `start_pos` in the input
+fn parse_tag(tag: &[u8], start_pos: usize, options: &ParserOptions) -> Result<Tag> {
+    let tag = std::str::from_utf8(tag).map_err(|_| AdifError::InvalidDataSpecifier {
+        position: start_pos,
+        message: "Invalid UTF-8 in data specifier".to_string(),
+    })?;
+
+    let mut parts = tag.splitn(3, ':');
+    let raw_name = parts.next().unwrap_or_default();
+    let name = if options.preserve_case {
+        raw_name.to_string()
+    } else {
+        raw_name.to_ascii_uppercase()
+    };
+    match raw_name.to_ascii_uppercase().as_str() {
+        "" => {
+            return Err(AdifError::InvalidDataSpecifier {
+                position: start_pos,
+                message: "Empty field name".to_string(),
+            });
+        }
+        "EOH" => return Ok(Tag::Token(Token::Eoh)),
+        "EOR" => return Ok(Tag::Token(Token::Eor)),
+        "EOF" => return Ok(Tag::Token(Token::Eof)),
+        _ => {}
+    }
+
+    // A marker tag (no length) has an empty value
+    let Some(length_str) = parts.next() else {
+        return Ok(Tag::Field {
+            name,
+            data_type: DataType::Unspecified,
+            length: 0,
+        });
+    };
+    let length: usize = length_str
+        .parse()
+        .map_err(|_| AdifError::InvalidDataSpecifier {
+            position: start_pos + name.len() + 2,
+            message: format!("Invalid length: '{}'", length_str),
+        })?;
+
+    let data_type = match parts.next() {
+        Some(t) => {
+            let mut chars = t.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => DataType::from_char(c).unwrap_or(DataType::Unspecified),
+                (None, _) => return Err(AdifError::UnexpectedEof(start_pos + tag.len() + 2)),
+                _ => {
+                    return Err(AdifError::InvalidDataSpecifier {
+                        position: start_pos,
+                        message: format!("Invalid data type indicator '{}'", t),
+                    });
+                }
+            }
+        }
+        None => DataType::Unspecified,
+    };
+
+    Ok(Tag::Field {
+        name,
+        data_type,
+        length,
+    })
+}
+
+/// Build a field, applying the trimming option
+fn make_field(name: FieldName, data_type: DataType, value: &str, options: &ParserOptions) -> Field {
+    let value = if options.trim_values {
+        value.trim()
+    } else {
+        value
+    };
+    Field {
+        name,
+        data_type,
+        value: value.to_string(),
+        raw: None,
+    }
+}
+
+/// Decode a value using the configured encoding
+fn decode(bytes: Vec<u8>, position: usize, options: &ParserOptions) -> Result<String> {
+    options
+        .encoding
+        .decode(bytes)
+        .map_err(|offset| AdifError::ParseError {
+            position: position + offset,
+            message: "Invalid UTF-8".to_string(),
+        })
+}
+
+impl<R: BufRead> Iterator for RecordReader<R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_read_records_with_header() {
+        let input = "ADIF Export\n<ADIF_VER:5>3.1.4\n<PROGRAMID:4>Test\n<EOH>\n\
+                     <CALL:5>W1AW1<BAND:3>20m<EOR>\n<CALL:5>W1AW2<EOR>\n";
+        let mut reader = RecordReader::new(input.as_bytes());
+
+        let header = reader.header().unwrap();
+        assert_eq!(header.adif_version, Some("3.1.4".to_string()));
+        assert_eq!(header.program_id, Some("Test".to_string()));
+        assert_eq!(header.preamble, "ADIF Export\n");
+
+        let records: Vec<Record> = reader.map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].call(), Some("W1AW1"));
+        assert_eq!(records[0].band(), Some("20m"));
+        assert_eq!(records[1].call(), Some("W1AW2"));
+    }
+
+    #[test]
+    fn test_read_records_without_header() {
+        let input = "<CALL:5>W1AW1<EOR><CALL:5>W1AW2<EOR><EOF>";
+        let mut reader = RecordReader::new(input.as_bytes());
+
+        assert!(reader.header().unwrap().fields.is_empty());
+        let calls: Vec<String> = reader
+            .map(|r| r.unwrap().call().unwrap().to_string())
+            .collect();
+        assert_eq!(calls, vec!["W1AW1", "W1AW2"]);
+    }
+
+    #[test]
+    fn test_matches_parse_adi() {
+        let input = "Log\n<ADIF_VER:5>3.1.6<EOH>\n\
+                     <call:4>K1AB<FREQ:6:N>14.074<QSO_DATE:8>20240115<EOR>\n\
+                     <CALL:4>K2CD<EOR>\n<CALL:4>K3EF";
+        let parsed = crate::parse_adi(input).unwrap();
+        let mut reader = RecordReader::new(BufReader::with_capacity(4, input.as_bytes()));
+
+        assert_eq!(reader.header().unwrap().fields, parsed.header.fields);
+        let records: Vec<Record> = reader.map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), parsed.records.len());
+        for (a, b) in records.iter().zip(parsed.records.iter()) {
+            assert_eq!(a.fields, b.fields);
+        }
+    }
+
+    #[test]
+    fn test_core_byte_at_a_time() {
+        let input = "Log\n<ADIF_VER:5>3.1.6<EOH>\n<CALL:4>K1AB<NAME:0><EOR>\n<CALL:4>K2CD";
+        let mut core = ReaderCore::new(ParserOptions::default());
+        let mut rest = input.as_bytes();
+        let mut calls = Vec::new();
+        loop {
+            match core.next_record() {
+                Some(result) => match result.unwrap() {
+                    Some(record) => calls.push(record.call().unwrap().to_string()),
+                    None => break,
+                },
+                None => {
+                    let n = core.feed(&rest[..rest.len().min(1)]).unwrap();
+                    rest = &rest[n..];
+                }
+            }
+        }
+        assert_eq!(core.header().unwrap().preamble, "Log\n");
+        assert_eq!(calls, vec!["K1AB", "K2CD"]);
+    }
+
+    #[test]
+    fn test_multibyte_value() {
+        let input = "<NAME:5>José<EOR>";
+        let mut reader = RecordReader::new(input.as_bytes());
+
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(record.get_value("NAME"), Some("José"));
+    }
+
+    #[test]
+    fn test_truncated_value() {
+        let input = "<CALL:5>W1AW1<EOR><CALL:10>W1AW";
+        let mut reader = RecordReader::new(input.as_bytes());
+
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(AdifError::InvalidFieldLength {
+                expected: 10,
+                found: 4,
+                ..
+            }))
+        ));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        let input = "<CALL:x>W1AW<EOR>";
+        let mut reader = RecordReader::new(input.as_bytes());
+
+        assert!(matches!(
+            reader.next(),
+            Some(Err(AdifError::InvalidDataSpecifier { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_reader_options() {
+        let input = "<Call:6> W1AW <MODE:x>CW<BAND:3>20m<EOR><CALL:12>K1AB<EOR>";
+        let options = ParserOptions {
+            lenient: true,
+            preserve_case: true,
+            trim_values: true,
+            ..Default::default()
+        };
+        let mut reader = RecordReader::with_options(input.as_bytes(), options);
+
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(record.fields[0].name, "Call");
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(record.mode(), None);
+        assert_eq!(record.band(), Some("20m"));
+        assert_eq!(reader.next().unwrap().unwrap().call(), Some("K1AB"));
+    }
+
+    #[test]
+    fn test_limits() {
+        use crate::options::{Limit, Limits};
+
+        let options = |limits| ParserOptions {
+            lenient: true,
+            limits,
+            ..Default::default()
+        };
+
+        // A huge field length is refused before anything is read
+        let input = "<NOTES:999999999999>x";
+        let limits = Limits {
+            max_field_length: Some(1024),
+            ..Limits::none()
+        };
+        let mut reader = RecordReader::with_options(input.as_bytes(), options(limits));
+        assert!(matches!(
+            reader.next(),
+            Some(Err(AdifError::LimitExceeded {
+                limit: Limit::FieldLength,
+                max: 1024,
+                ..
+            }))
+        ));
+
+        // Garbage without any tags still counts towards the total
+        let input = "x".repeat(10_000);
+        let limits = Limits {
+            max_total_bytes: Some(100),
+            ..Limits::none()
+        };
+        let mut reader = RecordReader::with_options(input.as_bytes(), options(limits));
+        assert!(matches!(
+            reader.next(),
+            Some(Err(AdifError::LimitExceeded {
+                limit: Limit::TotalBytes,
+                position: 101,
+                ..
+            }))
+        ));
+
+        let input = "<CALL:4>W1AW<EOR><CALL:4>K1AB<EOR>";
+        let limits = Limits {
+            max_records: Some(1),
+            ..Limits::none()
+        };
+        let mut reader = RecordReader::with_options(input.as_bytes(), options(limits));
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(AdifError::LimitExceeded {
+                limit: Limit::Records,
+                ..
+            }))
+        ));
+    }
+}
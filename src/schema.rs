@@ -0,0 +1,462 @@
+//! Validation of ADX documents against the rules of the ADIF 3.1.6 ADX
+//! schema
+
+use crate::adx::{data_type_from_attribute, parse_adx, write_adx};
+use crate::coordinate::Coordinate;
+use crate::date::{AdifDate, AdifTime};
+use crate::error::{AdifError, Result};
+use crate::field_name::FieldName;
+use crate::from_field::FromField;
+use crate::types::{AdifFile, DataType};
+use crate::validate::is_number;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Elements allowed in `<HEADER>` besides `USERDEF` and `APP`
+const HEADER_FIELDS: &[&str] = &[
+    "ADIF_VER",
+    "CREATED_TIMESTAMP",
+    "PROGRAMID",
+    "PROGRAMVERSION",
+];
+
+/// A place where an ADX document breaks the ADX schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Byte offset in the document
+    pub position: usize,
+    /// Name of the offending element, as written (empty for the document)
+    pub element: String,
+    /// What is wrong
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.element.is_empty() {
+            write!(f, "at position {}: {}", self.position, self.message)
+        } else {
+            write!(
+                f,
+                "<{}> at position {}: {}",
+                self.element, self.position, self.message
+            )
+        }
+    }
+}
+
+/// Check an ADX document against the ADIF 3.1.6 ADX schema
+///
+/// The bundled rules follow the official XSD: the `ADX` root holds an
+/// optional `HEADER` then `RECORDS`; element names are uppercase; the
+/// header only holds header fields, `USERDEF` (with `FIELDID`) and `APP`
+/// (with `PROGRAMID` and `FIELDNAME`); records only hold fields defined by
+/// the specification, each at most once, plus `USERDEF` (with
+/// `FIELDNAME`) and `APP`. Values of date, time, number, boolean and
+/// location fields must be well-formed, and only `_INTL` fields may hold
+/// non-ASCII text. Returns every violation found, in document order; a
+/// document that isn't well-formed XML yields a single violation where
+/// parsing stopped.
+pub fn validate_adx(input: &str) -> Vec<SchemaViolation> {
+    let mut validator = Validator {
+        reader: Reader::from_str(input),
+        stack: Vec::new(),
+        seen: HashSet::new(),
+        value: String::new(),
+        value_type: DataType::Unspecified,
+        intl: false,
+        has_root: false,
+        has_header: false,
+        has_records: false,
+        violations: Vec::new(),
+    };
+    validator.run();
+    validator.violations
+}
+
+/// Parse an ADX document (see [`parse_adx`]), first validating it
+///
+/// Fails with [`AdifError::Schema`] listing every violation if the
+/// document doesn't follow the schema (see [`validate_adx`]).
+pub fn parse_adx_validated(input: &str) -> Result<AdifFile> {
+    let violations = validate_adx(input);
+    if !violations.is_empty() {
+        return Err(AdifError::Schema(violations));
+    }
+    parse_adx(input)
+}
+
+/// Serialize to ADX (see [`write_adx`]), checking that the output follows
+/// the schema
+///
+/// Fails with [`AdifError::Schema`] if the log can't be written as valid
+/// ADX, e.g. because it has fields that aren't defined by the
+/// specification or malformed dates.
+pub fn write_adx_validated(file: &AdifFile) -> Result<String> {
+    let output = write_adx(file);
+    let violations = validate_adx(&output);
+    if !violations.is_empty() {
+        return Err(AdifError::Schema(violations));
+    }
+    Ok(output)
+}
+
+struct Validator<'a> {
+    reader: Reader<&'a [u8]>,
+    /// Uppercased names of the open elements
+    stack: Vec<String>,
+    /// Fields seen in the current record
+    seen: HashSet<String>,
+    /// Text of the open field
+    value: String,
+    value_type: DataType,
+    intl: bool,
+    has_root: bool,
+    has_header: bool,
+    has_records: bool,
+    violations: Vec<SchemaViolation>,
+}
+
+impl Validator<'_> {
+    fn run(&mut self) {
+        loop {
+            let position = self.reader.buffer_position() as usize;
+            let event = match self.reader.read_event() {
+                Ok(event) => event,
+                Err(e) => {
+                    let position = self.reader.error_position() as usize;
+                    self.violation(position, "", format!("XML error: {}", e));
+                    return;
+                }
+            };
+            match event {
+                Event::Start(e) => self.start(&e, position),
+                Event::Empty(e) => {
+                    self.start(&e, position);
+                    self.end(position);
+                }
+                Event::End(_) => self.end(position),
+                Event::Text(e) => self.text(&e.xml10_content(), position),
+                Event::CData(e) => self.text(&e.xml10_content(), position),
+                Event::GeneralRef(e) => {
+                    if let Ok(Some(c)) = e.resolve_char_ref() {
+                        self.value.push(c);
+                    } else if let Some(s) = quick_xml::escape::resolve_predefined_entity(&e) {
+                        self.value.push_str(s);
+                    } else {
+                        self.violation(position, "", format!("Unknown entity '&{};'", &*e));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        let position = self.reader.buffer_position() as usize;
+        if !self.has_root {
+            self.violation(position, "", "Missing <ADX> root element".to_string());
+        } else if let Some(name) = self.stack.last().cloned() {
+            self.violation(position, &name, "Element is not closed".to_string());
+        }
+    }
+
+    fn start(&mut self, e: &BytesStart, position: usize) {
+        let written = e.name().as_ref().to_string();
+        let name = written.to_uppercase();
+        if name != written {
+            self.violation(
+                position,
+                &written,
+                "Element names must be uppercase".to_string(),
+            );
+        }
+
+        let parent = self.stack.last().map(String::as_str);
+        let problem = match (parent, name.as_str()) {
+            (None, "ADX") if !self.has_root => {
+                self.has_root = true;
+                None
+            }
+            (None, _) => Some("Root element must be <ADX>".to_string()),
+            (Some("ADX"), "HEADER") if self.has_header || self.has_records => {
+                Some("<HEADER> must come once, before <RECORDS>".to_string())
+            }
+            (Some("ADX"), "HEADER") => {
+                self.has_header = true;
+                None
+            }
+            (Some("ADX"), "RECORDS") if self.has_records => {
+                Some("<RECORDS> must come once".to_string())
+            }
+            (Some("ADX"), "RECORDS") => {
+                self.has_records = true;
+                None
+            }
+            (Some("RECORDS"), "RECORD") => {
+                self.seen.clear();
+                None
+            }
+            (Some("HEADER"), _) => self.header_field(e, &name),
+            (Some("RECORD"), _) => self.record_field(e, &name),
+            (Some("ADX" | "RECORDS"), _) => {
+                Some(format!("Not allowed in <{}>", parent.unwrap_or_default()))
+            }
+            (Some(parent), _) => Some(format!("Not allowed in field <{}>", parent)),
+        };
+        if let Some(message) = problem {
+            self.violation(position, &written, message);
+        }
+        self.value.clear();
+        self.stack.push(name);
+    }
+
+    /// Check a field element in `<HEADER>`, returning what's wrong with it
+    fn header_field(&mut self, e: &BytesStart, name: &str) -> Option<String> {
+        self.begin_field(DataType::Unspecified, false);
+        match name {
+            // TYPE here is the type of the defined field, not of the name
+            "USERDEF" => self.special_field(e, &["FIELDID"]).or_else(|| {
+                self.value_type = DataType::Unspecified;
+                None
+            }),
+            "APP" => self.special_field(e, &["PROGRAMID", "FIELDNAME"]),
+            _ if HEADER_FIELDS.contains(&name) => None,
+            _ => Some("Not a header field".to_string()),
+        }
+    }
+
+    /// Check a field element in `<RECORD>`, returning what's wrong with it
+    fn record_field(&mut self, e: &BytesStart, name: &str) -> Option<String> {
+        match name {
+            "USERDEF" => {
+                self.begin_field(DataType::Unspecified, false);
+                self.special_field(e, &["FIELDNAME"])
+            }
+            "APP" => {
+                self.begin_field(DataType::Unspecified, false);
+                self.special_field(e, &["PROGRAMID", "FIELDNAME"])
+            }
+            _ => {
+                let field = FieldName::known(name).filter(|f| !HEADER_FIELDS.contains(&f.as_str()));
+                let data_type = field
+                    .as_ref()
+                    .map_or(DataType::Unspecified, |f| f.data_type());
+                self.begin_field(data_type, name.ends_with("_INTL"));
+                if field.is_none() {
+                    Some("Not a field defined by the ADIF specification".to_string())
+                } else if !self.seen.insert(name.to_string()) {
+                    Some("Field appears more than once in the record".to_string())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Check the attributes of a `USERDEF` or `APP` element
+    fn special_field(&mut self, e: &BytesStart, required: &[&str]) -> Option<String> {
+        let mut present = Vec::new();
+        for attr in e.attributes() {
+            let Ok(attr) = attr else {
+                return Some("Malformed attribute".to_string());
+            };
+            let key = attr.key.as_ref().to_string();
+            let value = attr.value.to_string();
+            match key.as_str() {
+                "TYPE" => match data_type_from_attribute(&value) {
+                    Some(data_type) => self.value_type = data_type,
+                    None => return Some(format!("Invalid TYPE attribute '{}'", value)),
+                },
+                "FIELDID" if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) => {
+                    return Some(format!("Invalid FIELDID attribute '{}'", value));
+                }
+                _ => {}
+            }
+            present.push(key);
+        }
+        required
+            .iter()
+            .find(|key| !present.iter().any(|p| p == *key))
+            .map(|key| format!("Missing {} attribute", key))
+    }
+
+    fn begin_field(&mut self, data_type: DataType, intl: bool) {
+        self.value_type = data_type;
+        self.intl = intl;
+    }
+
+    fn end(&mut self, position: usize) {
+        let Some(name) = self.stack.pop() else {
+            return;
+        };
+        let in_field = matches!(
+            self.stack.last().map(String::as_str),
+            Some("HEADER" | "RECORD")
+        );
+        if in_field {
+            let value = std::mem::take(&mut self.value);
+            if let Some(message) = check_value(&value, self.value_type, self.intl) {
+                self.violation(position, &name, message);
+            }
+        } else if name == "ADX" && !self.has_records {
+            self.violation(position, &name, "Missing <RECORDS>".to_string());
+        }
+    }
+
+    fn text(&mut self, text: &str, position: usize) {
+        let in_field = self.stack.len() >= 2
+            && matches!(
+                self.stack[self.stack.len() - 2].as_str(),
+                "HEADER" | "RECORD"
+            );
+        if in_field {
+            self.value.push_str(text);
+        } else if !text.trim().is_empty() {
+            let element = self.stack.last().cloned().unwrap_or_default();
+            self.violation(
+                position,
+                &element,
+                format!("Unexpected text '{}'", text.trim()),
+            );
+        }
+    }
+
+    fn violation(&mut self, position: usize, element: &str, message: String) {
+        self.violations.push(SchemaViolation {
+            position,
+            element: element.to_string(),
+            message,
+        });
+    }
+}
+
+/// Check a field value against its data type, returning what's wrong
+fn check_value(value: &str, data_type: DataType, intl: bool) -> Option<String> {
+    if !intl && !value.is_ascii() {
+        return Some("Non-ASCII text is only allowed in _INTL fields".to_string());
+    }
+    // The same checks as validate, so the two can't disagree
+    let valid = match data_type {
+        _ if value.is_empty() => true,
+        DataType::Date => AdifDate::from_field(value).is_ok(),
+        DataType::Time => AdifTime::from_field(value).is_ok(),
+        DataType::Number => is_number(value),
+        DataType::Boolean => bool::from_field(value).is_ok(),
+        DataType::Location => Coordinate::from_field(value).is_ok(),
+        _ => true,
+    };
+    (!valid).then(|| format!("Invalid {:?} value '{}'", data_type, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    const VALID: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ADX>
+  <HEADER>
+    <ADIF_VER>3.1.6</ADIF_VER>
+    <USERDEF FIELDID="1" TYPE="N">EPC</USERDEF>
+  </HEADER>
+  <RECORDS>
+    <RECORD>
+      <QSO_DATE>20240115</QSO_DATE>
+      <TIME_ON>1430</TIME_ON>
+      <CALL>VK9NS</CALL>
+      <NAME_INTL>Jürgen</NAME_INTL>
+      <LAT>S034 12.345</LAT>
+      <APP PROGRAMID="MONOLOG" FIELDNAME="Compression" TYPE="s">off</APP>
+      <USERDEF FIELDNAME="EPC">32123</USERDEF>
+      <NOTES/>
+    </RECORD>
+  </RECORDS>
+</ADX>
+"#;
+
+    #[test]
+    fn test_valid_document() {
+        assert_eq!(validate_adx(VALID), []);
+        assert_eq!(parse_adx_validated(VALID).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_violations() {
+        let input = r#"<ADX>
+  <RECORDS>
+    <RECORD>
+      <call>W1AW</call>
+      <QSO_DATE>2024011</QSO_DATE>
+      <FREQ>14.07.4</FREQ>
+      <NAME>Jürgen</NAME>
+      <MY_FIELD>x</MY_FIELD>
+      <ADIF_VER>3.1.6</ADIF_VER>
+      <CALL>K1AB</CALL>
+      <APP PROGRAMID="X">1</APP>
+    </RECORD>
+  </RECORDS>
+  <HEADER/>
+</ADX>"#;
+        let violations = validate_adx(input);
+        let found: Vec<_> = violations
+            .iter()
+            .map(|v| (v.element.as_str(), v.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("call", "Element names must be uppercase"),
+                ("QSO_DATE", "Invalid Date value '2024011'"),
+                ("FREQ", "Invalid Number value '14.07.4'"),
+                ("NAME", "Non-ASCII text is only allowed in _INTL fields"),
+                ("MY_FIELD", "Not a field defined by the ADIF specification"),
+                ("ADIF_VER", "Not a field defined by the ADIF specification"),
+                ("CALL", "Field appears more than once in the record"),
+                ("APP", "Missing FIELDNAME attribute"),
+                ("HEADER", "<HEADER> must come once, before <RECORDS>"),
+            ]
+        );
+        assert_eq!(&input[violations[1].position..][..10], "</QSO_DATE");
+
+        assert!(matches!(
+            parse_adx_validated(input),
+            Err(AdifError::Schema(v)) if v.len() == 9
+        ));
+    }
+
+    #[test]
+    fn test_malformed_documents() {
+        assert_eq!(validate_adx("<ADX><RECORDS/></ADX>"), []);
+        assert_eq!(
+            validate_adx("<ADIF><RECORDS/></ADIF>")[0].message,
+            "Root element must be <ADX>"
+        );
+        assert_eq!(validate_adx("<ADX></ADX>")[0].message, "Missing <RECORDS>");
+        assert_eq!(
+            validate_adx("<ADX><RECORDS>")[0].message,
+            "Element is not closed"
+        );
+        assert!(
+            validate_adx("<ADX></RECORDS>")[0]
+                .message
+                .starts_with("XML error")
+        );
+        assert_eq!(validate_adx("")[0].message, "Missing <ADX> root element");
+    }
+
+    #[test]
+    fn test_write_adx_validated() {
+        let file = parse_adi("<CALL:4>W1AW<QSO_DATE:8>20240115<APP_X_Y:1>z<EOR>").unwrap();
+        assert!(write_adx_validated(&file).is_ok());
+
+        let file = parse_adi("<CALL:4>W1AW<MY_FIELD:1>z<EOR>").unwrap();
+        match write_adx_validated(&file) {
+            Err(AdifError::Schema(violations)) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].element, "MY_FIELD");
+            }
+            other => panic!("expected schema violations, got {:?}", other),
+        }
+    }
+}
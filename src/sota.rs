@@ -0,0 +1,219 @@
+use crate::band::Band;
+use crate::error::{AdifError, Result};
+use crate::from_field::FromField;
+use crate::profile::{check_date, check_time, required, value};
+use crate::types::{AdifFile, Record};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+/// SOTA band names by ADIF band
+const BANDS: &[(Band, &str)] = &[
+    (Band::M2190, "136kHz"),
+    (Band::M630, "475kHz"),
+    (Band::M160, "1.8MHz"),
+    (Band::M80, "3.5MHz"),
+    (Band::M60, "5MHz"),
+    (Band::M40, "7MHz"),
+    (Band::M30, "10MHz"),
+    (Band::M20, "14MHz"),
+    (Band::M17, "18MHz"),
+    (Band::M15, "21MHz"),
+    (Band::M12, "24MHz"),
+    (Band::M10, "28MHz"),
+    (Band::M6, "50MHz"),
+    (Band::M4, "70MHz"),
+    (Band::M2, "144MHz"),
+    (Band::M1_25, "220MHz"),
+    (Band::Cm70, "433MHz"),
+    (Band::Cm33, "900MHz"),
+    (Band::Cm23, "1240MHz"),
+    (Band::Cm13, "2.3GHz"),
+    (Band::Cm9, "3.4GHz"),
+    (Band::Cm6, "5.7GHz"),
+    (Band::Cm3, "10GHz"),
+    (Band::Cm1_25, "24GHz"),
+];
+
+/// Settings for a SOTA CSV upload
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SotaOptions {
+    /// Callsign used for records without `STATION_CALLSIGN` or `OPERATOR`
+    pub callsign: String,
+}
+
+impl SotaOptions {
+    /// Create options with a fallback callsign
+    pub fn new(callsign: impl Into<String>) -> Self {
+        Self {
+            callsign: callsign.into(),
+        }
+    }
+}
+
+/// Serialize the SOTA contacts in a log to the SOTA database CSV V2
+/// upload format
+///
+/// Each record with `MY_SOTA_REF` (an activation) or `SOTA_REF` (a chase)
+/// becomes a line of `V2,my call,my summit,date,time,band,mode,call,
+/// summit,notes`, with the date as DD/MM/YY, the band taken from FREQ (or
+/// BAND), and COMMENT as the notes. Other records are skipped. Fails if a
+/// SOTA record is missing a required field.
+pub fn write_sota_csv(file: &AdifFile, options: &SotaOptions) -> Result<String> {
+    let mut s = String::new();
+    for (index, record) in file.records.iter().enumerate() {
+        let my_summit = value(record, "MY_SOTA_REF").unwrap_or_default();
+        let summit = value(record, "SOTA_REF").unwrap_or_default();
+        if my_summit.is_empty() && summit.is_empty() {
+            continue;
+        }
+        let line = sota_line(record, my_summit, summit, options).map_err(|message| {
+            AdifError::Conversion {
+                record: index,
+                message,
+            }
+        })?;
+        s.push_str(&line);
+        s.push('\n');
+    }
+    Ok(s)
+}
+
+/// Build the CSV line for a SOTA record
+fn sota_line(
+    record: &Record,
+    my_summit: &str,
+    summit: &str,
+    options: &SotaOptions,
+) -> core::result::Result<String, String> {
+    let my_call = record
+        .my_callsign()
+        .map(str::trim)
+        .unwrap_or(options.callsign.as_str());
+    if my_call.is_empty() {
+        return Err("missing STATION_CALLSIGN".to_string());
+    }
+    let date = required(record, "QSO_DATE")?;
+    check_date(date)?;
+    let time = required(record, "TIME_ON")?;
+    check_time(time)?;
+    let band = band(record)?;
+    let mode = mode(required(record, "MODE")?);
+    let call = required(record, "CALL")?;
+    let notes = value(record, "COMMENT").unwrap_or_default();
+
+    let mut line = String::new();
+    let _ = write!(
+        line,
+        "V2,{},{},{}/{}/{},{},{},{},{},{},{}",
+        csv_field(my_call),
+        csv_field(my_summit),
+        &date[6..8],
+        &date[4..6],
+        &date[2..4],
+        &time[..4],
+        band,
+        mode,
+        csv_field(call),
+        csv_field(summit),
+        csv_field(notes)
+    );
+    Ok(line)
+}
+
+/// Check a SOTA summit reference: an association of up to four characters,
+/// a `/`, a two character region, a hyphen and three digits, such as
+/// `W7A/NM-001` or `G/LD-003`
+pub(crate) fn is_summit_ref(summit: &str) -> bool {
+    let Some((association, rest)) = summit.split_once('/') else {
+        return false;
+    };
+    let Some((region, number)) = rest.split_once('-') else {
+        return false;
+    };
+    let alphanumeric = |s: &str| s.bytes().all(|b| b.is_ascii_alphanumeric());
+    (1..=4).contains(&association.len())
+        && alphanumeric(association)
+        && association.bytes().any(|b| b.is_ascii_alphabetic())
+        && region.len() == 2
+        && alphanumeric(region)
+        && number.len() == 3
+        && number.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Get the SOTA band from FREQ, falling back to BAND
+fn band(record: &Record) -> core::result::Result<String, String> {
+    if let Some(freq) = record.freq().map(str::trim).filter(|f| !f.is_empty()) {
+        let mhz: f64 = freq
+            .parse()
+            .ok()
+            .filter(|f: &f64| f.is_finite() && *f > 0.0)
+            .ok_or_else(|| format!("invalid FREQ '{}'", freq))?;
+        return Ok(format!("{}MHz", mhz));
+    }
+    let band = value(record, "BAND").ok_or("missing FREQ or BAND")?;
+    Band::from_field(band)
+        .ok()
+        .and_then(|b| BANDS.iter().find(|(name, _)| *name == b))
+        .map(|(_, sota)| sota.to_string())
+        .ok_or_else(|| format!("unsupported BAND '{}'", band))
+}
+
+/// Map an ADIF mode to a SOTA mode
+fn mode(mode: &str) -> &'static str {
+    match mode.to_ascii_uppercase().as_str() {
+        "CW" => "CW",
+        "SSB" => "SSB",
+        "AM" => "AM",
+        "FM" => "FM",
+        "DIGITALVOICE" => "DV",
+        "ATV" | "SSTV" | "FAX" => "Other",
+        _ => "Data",
+    }
+}
+
+/// Quote a CSV field if needed
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_write_sota_csv() {
+        let file = parse_adi(
+            "<STATION_CALLSIGN:6>M0XYZ/<MY_SOTA_REF:8>G/LD-001<CALL:5>G4ABC\
+             <QSO_DATE:8>20240601<TIME_ON:6>123045<FREQ:6>14.062<MODE:2>CW<EOR>\
+             <CALL:5>K1ABC<QSO_DATE:8>20240602<TIME_ON:4>0800<BAND:2>2m<MODE:3>FT8<EOR>\
+             <CALL:5>G4DEF<SOTA_REF:8>G/SP-004<QSO_DATE:8>20240603<TIME_ON:4>0915\
+             <BAND:3>40m<MODE:3>SSB<COMMENT:8>S2S, 5/9<EOR>",
+        )
+        .unwrap();
+        assert_eq!(
+            write_sota_csv(&file, &SotaOptions::new("M0XYZ")).unwrap(),
+            "V2,M0XYZ/,G/LD-001,01/06/24,1230,14.062MHz,CW,G4ABC,,\n\
+             V2,M0XYZ,,03/06/24,0915,7MHz,SSB,G4DEF,G/SP-004,\"S2S, 5/9\"\n"
+        );
+    }
+
+    #[test]
+    fn test_write_sota_csv_errors() {
+        let file =
+            parse_adi("<MY_SOTA_REF:8>G/LD-001<CALL:5>G4ABC<QSO_DATE:8>20240601<EOR>").unwrap();
+        assert!(matches!(
+            write_sota_csv(&file, &SotaOptions::new("M0XYZ")),
+            Err(AdifError::Conversion { record: 0, message }) if message == "missing TIME_ON"
+        ));
+        assert!(matches!(
+            write_sota_csv(&file, &SotaOptions::default()),
+            Err(AdifError::Conversion { message, .. }) if message == "missing STATION_CALLSIGN"
+        ));
+    }
+}
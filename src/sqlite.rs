@@ -0,0 +1,224 @@
+use crate::error::Result;
+use crate::field_name::{FieldName, KNOWN_NAMES};
+use crate::types::{AdifFile, AdifHeader, Field, Record};
+use rusqlite::{Connection, params};
+use std::collections::HashMap;
+use std::path::Path;
+
+impl AdifFile {
+    /// Write the log into a SQLite database
+    ///
+    /// Creates a `qso` table with one row per record and one text column
+    /// per field defined by the ADIF specification, a `qso_field` table
+    /// (`qso_id`, `position`, `name`, `value`) holding all other fields
+    /// and repeated ones, a `header` table with the header fields and a
+    /// `log` table with the preamble. The tables must not already exist.
+    /// Data type indicators are not stored.
+    pub fn write_sqlite(&self, conn: &mut Connection) -> Result<()> {
+        let columns: Vec<String> = KNOWN_NAMES.iter().map(|n| format!("\"{}\"", n)).collect();
+        let tx = conn.transaction()?;
+        tx.execute_batch(&format!(
+            "CREATE TABLE log (preamble TEXT NOT NULL);
+             CREATE TABLE header (position INTEGER NOT NULL, name TEXT NOT NULL, value TEXT NOT NULL);
+             CREATE TABLE qso (id INTEGER PRIMARY KEY, {});
+             CREATE TABLE qso_field (
+                 qso_id INTEGER NOT NULL REFERENCES qso(id),
+                 position INTEGER NOT NULL,
+                 name TEXT NOT NULL,
+                 value TEXT NOT NULL
+             );
+             CREATE INDEX qso_field_qso_id ON qso_field (qso_id);",
+            columns
+                .iter()
+                .map(|c| format!("{} TEXT", c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))?;
+
+        tx.execute(
+            "INSERT INTO log (preamble) VALUES (?1)",
+            [&self.header.preamble],
+        )?;
+        {
+            let mut insert =
+                tx.prepare("INSERT INTO header (position, name, value) VALUES (?1, ?2, ?3)")?;
+            for (position, field) in self.header.fields.iter().enumerate() {
+                insert.execute(params![position as i64, field.name.as_str(), field.value])?;
+            }
+
+            let mut insert_qso = tx.prepare(&format!(
+                "INSERT INTO qso (id, {}) VALUES (?1, {})",
+                columns.join(", "),
+                (2..=columns.len() + 1)
+                    .map(|i| format!("?{}", i))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))?;
+            let mut insert_field = tx.prepare(
+                "INSERT INTO qso_field (qso_id, position, name, value) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (id, record) in (0i64..).zip(&self.records) {
+                let mut values: Vec<Option<&str>> = vec![None; KNOWN_NAMES.len()];
+                let mut others = Vec::new();
+                for (position, field) in (0i64..).zip(&record.fields) {
+                    let column = FieldName::known(&field.name)
+                        .and_then(|name| KNOWN_NAMES.iter().position(|n| *n == name.as_str()));
+                    match column {
+                        Some(column) if values[column].is_none() => {
+                            values[column] = Some(&field.value);
+                        }
+                        _ => others.push((position, field)),
+                    }
+                }
+                let mut params: Vec<&dyn rusqlite::ToSql> = vec![&id];
+                params.extend(values.iter().map(|v| v as &dyn rusqlite::ToSql));
+                insert_qso.execute(params.as_slice())?;
+                for (position, field) in others {
+                    insert_field.execute(params![
+                        id,
+                        position,
+                        field.name.as_str(),
+                        field.value
+                    ])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Write the log into a new SQLite database file (see
+    /// [`AdifFile::write_sqlite`])
+    pub fn to_sqlite(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.write_sqlite(&mut Connection::open(path)?)
+    }
+
+    /// Read a log written by [`AdifFile::write_sqlite`]
+    ///
+    /// Each record has its specified fields in alphabetical order,
+    /// followed by the rest in their original order.
+    pub fn read_sqlite(conn: &Connection) -> Result<AdifFile> {
+        let mut header = AdifHeader {
+            preamble: conn.query_row("SELECT preamble FROM log", [], |row| row.get(0))?,
+            ..Default::default()
+        };
+        let mut select = conn.prepare("SELECT name, value FROM header ORDER BY position")?;
+        let fields = select.query_map([], |row| {
+            Ok(Field::new(
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+            ))
+        })?;
+        for field in fields {
+            header.add_field(field?);
+        }
+
+        let mut records = Vec::new();
+        let mut ids = HashMap::new();
+        let mut select = conn.prepare("SELECT * FROM qso ORDER BY id")?;
+        let names: Vec<String> = select
+            .column_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let mut rows = select.query([])?;
+        while let Some(row) = rows.next()? {
+            let mut record = Record::new();
+            for (index, name) in names.iter().enumerate().skip(1) {
+                if let Some(value) = row.get::<_, Option<String>>(index)? {
+                    record.add_field(Field::new(name.as_str(), value));
+                }
+            }
+            ids.insert(row.get::<_, i64>(0)?, records.len());
+            records.push(record);
+        }
+
+        let mut select =
+            conn.prepare("SELECT qso_id, name, value FROM qso_field ORDER BY qso_id, position")?;
+        let mut rows = select.query([])?;
+        while let Some(row) = rows.next()? {
+            if let Some(&index) = ids.get(&row.get::<_, i64>(0)?) {
+                let field = Field::new(row.get::<_, String>(1)?, row.get::<_, String>(2)?);
+                records[index].add_field(field);
+            }
+        }
+
+        Ok(AdifFile {
+            header,
+            records,
+            ..Default::default()
+        })
+    }
+
+    /// Read a log from a SQLite database file (see
+    /// [`AdifFile::read_sqlite`])
+    pub fn from_sqlite(path: impl AsRef<Path>) -> Result<AdifFile> {
+        AdifFile::read_sqlite(&Connection::open(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_sqlite_round_trip() {
+        let file = parse_adi(
+            "Log\n<ADIF_VER:5>3.1.6<EOH>\
+             <CALL:4>W1AW<APP_N1MM_ID:3>abc<QSO_DATE:8>20240115<EOR>\
+             <BAND:3>20m<CALL:4>K1AB<CALL:4>K1XY<EOR>",
+        )
+        .unwrap();
+        let mut conn = Connection::open_in_memory().unwrap();
+        file.write_sqlite(&mut conn).unwrap();
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT count(*) FROM qso WHERE \"CALL\" = 'K1AB'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let back = AdifFile::read_sqlite(&conn).unwrap();
+        assert_eq!(back.header.preamble, "Log\n");
+        assert_eq!(back.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(back.len(), 2);
+        let fields: Vec<_> = back.records[0]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.value.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("CALL", "W1AW"),
+                ("QSO_DATE", "20240115"),
+                ("APP_N1MM_ID", "abc")
+            ]
+        );
+        let fields: Vec<_> = back.records[1]
+            .fields
+            .iter()
+            .map(|f| f.value.as_str())
+            .collect();
+        assert_eq!(fields, ["20m", "K1AB", "K1XY"]);
+    }
+
+    #[test]
+    fn test_sqlite_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.db");
+        let file = parse_adi("<CALL:4>W1AW<EOR>").unwrap();
+        file.to_sqlite(&path).unwrap();
+
+        assert_eq!(
+            AdifFile::from_sqlite(&path).unwrap().records[0].call(),
+            Some("W1AW")
+        );
+        // The tables already exist
+        assert!(file.to_sqlite(&path).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::dxcc::Dxcc;
+use crate::error::{AdifError, Result};
+use crate::types::Record;
+use alloc::format;
+use core::fmt;
+
+/// Primary administrative subdivisions: DXCC entity code, code, name
+const SUBDIVISIONS: &[(u16, &str, &str)] = &[
+    (1, "AB", "Alberta"),
+    (1, "BC", "British Columbia"),
+    (1, "MB", "Manitoba"),
+    (1, "NB", "New Brunswick"),
+    (1, "NL", "Newfoundland and Labrador"),
+    (1, "NS", "Nova Scotia"),
+    (1, "NT", "Northwest Territories"),
+    (1, "NU", "Nunavut"),
+    (1, "ON", "Ontario"),
+    (1, "PE", "Prince Edward Island"),
+    (1, "QC", "Quebec"),
+    (1, "SK", "Saskatchewan"),
+    (1, "YT", "Yukon"),
+    (6, "AK", "Alaska"),
+    (110, "HI", "Hawaii"),
+    (150, "ACT", "Australian Capital Territory"),
+    (150, "NSW", "New South Wales"),
+    (150, "NT", "Northern Territory"),
+    (150, "QLD", "Queensland"),
+    (150, "SA", "South Australia"),
+    (150, "TAS", "Tasmania"),
+    (150, "VIC", "Victoria"),
+    (150, "WA", "Western Australia"),
+    (291, "AL", "Alabama"),
+    (291, "AR", "Arkansas"),
+    (291, "AZ", "Arizona"),
+    (291, "CA", "California"),
+    (291, "CO", "Colorado"),
+    (291, "CT", "Connecticut"),
+    (291, "DC", "District of Columbia"),
+    (291, "DE", "Delaware"),
+    (291, "FL", "Florida"),
+    (291, "GA", "Georgia"),
+    (291, "IA", "Iowa"),
+    (291, "ID", "Idaho"),
+    (291, "IL", "Illinois"),
+    (291, "IN", "Indiana"),
+    (291, "KS", "Kansas"),
+    (291, "KY", "Kentucky"),
+    (291, "LA", "Louisiana"),
+    (291, "MA", "Massachusetts"),
+    (291, "MD", "Maryland"),
+    (291, "ME", "Maine"),
+    (291, "MI", "Michigan"),
+    (291, "MN", "Minnesota"),
+    (291, "MO", "Missouri"),
+    (291, "MS", "Mississippi"),
+    (291, "MT", "Montana"),
+    (291, "NC", "North Carolina"),
+    (291, "ND", "North Dakota"),
+    (291, "NE", "Nebraska"),
+    (291, "NH", "New Hampshire"),
+    (291, "NJ", "New Jersey"),
+    (291, "NM", "New Mexico"),
+    (291, "NV", "Nevada"),
+    (291, "NY", "New York"),
+    (291, "OH", "Ohio"),
+    (291, "OK", "Oklahoma"),
+    (291, "OR", "Oregon"),
+    (291, "PA", "Pennsylvania"),
+    (291, "RI", "Rhode Island"),
+    (291, "SC", "South Carolina"),
+    (291, "SD", "South Dakota"),
+    (291, "TN", "Tennessee"),
+    (291, "TX", "Texas"),
+    (291, "UT", "Utah"),
+    (291, "VA", "Virginia"),
+    (291, "VT", "Vermont"),
+    (291, "WA", "Washington"),
+    (291, "WI", "Wisconsin"),
+    (291, "WV", "West Virginia"),
+    (291, "WY", "Wyoming"),
+    (339, "01", "Hokkaido"),
+    (339, "02", "Aomori"),
+    (339, "03", "Iwate"),
+    (339, "04", "Akita"),
+    (339, "05", "Yamagata"),
+    (339, "06", "Miyagi"),
+    (339, "07", "Fukushima"),
+    (339, "08", "Niigata"),
+    (339, "09", "Nagano"),
+    (339, "10", "Tokyo"),
+    (339, "11", "Kanagawa"),
+    (339, "12", "Chiba"),
+    (339, "13", "Saitama"),
+    (339, "14", "Ibaraki"),
+    (339, "15", "Tochigi"),
+    (339, "16", "Gunma"),
+    (339, "17", "Yamanashi"),
+    (339, "18", "Shizuoka"),
+    (339, "19", "Gifu"),
+    (339, "20", "Aichi"),
+    (339, "21", "Mie"),
+    (339, "22", "Kyoto"),
+    (339, "23", "Shiga"),
+    (339, "24", "Nara"),
+    (339, "25", "Osaka"),
+    (339, "26", "Wakayama"),
+    (339, "27", "Hyogo"),
+    (339, "28", "Toyama"),
+    (339, "29", "Fukui"),
+    (339, "30", "Ishikawa"),
+    (339, "31", "Okayama"),
+    (339, "32", "Shimane"),
+    (339, "33", "Yamaguchi"),
+    (339, "34", "Tottori"),
+    (339, "35", "Hiroshima"),
+    (339, "36", "Kagawa"),
+    (339, "37", "Tokushima"),
+    (339, "38", "Ehime"),
+    (339, "39", "Kochi"),
+    (339, "40", "Fukuoka"),
+    (339, "41", "Saga"),
+    (339, "42", "Nagasaki"),
+    (339, "43", "Kumamoto"),
+    (339, "44", "Oita"),
+    (339, "45", "Miyazaki"),
+    (339, "46", "Kagoshima"),
+    (339, "47", "Okinawa"),
+];
+
+/// A primary administrative subdivision (state, province or prefecture),
+/// from the ADIF Primary Administrative Subdivision enumeration
+///
+/// Codes are only unique within a DXCC entity, so subdivisions are looked
+/// up by entity. The subdivisions of Canada, the United States (with
+/// Alaska and Hawaii), Japan and Australia are included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Subdivision {
+    dxcc: u16,
+    code: &'static str,
+    name: &'static str,
+}
+
+impl Subdivision {
+    /// Look up a subdivision of a DXCC entity by code, ignoring case
+    pub fn find(dxcc: u16, code: &str) -> Option<Subdivision> {
+        let code = code.trim();
+        Subdivision::for_dxcc(dxcc).find(|s| s.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Get the subdivisions of a DXCC entity, empty if none are known
+    pub fn for_dxcc(dxcc: u16) -> impl Iterator<Item = Subdivision> {
+        SUBDIVISIONS
+            .iter()
+            .filter(move |(entity, ..)| *entity == dxcc)
+            .map(|&(dxcc, code, name)| Subdivision { dxcc, code, name })
+    }
+
+    /// Get the code of the DXCC entity the subdivision is in
+    pub fn dxcc(self) -> u16 {
+        self.dxcc
+    }
+
+    /// Get the subdivision code, as written in ADIF
+    pub fn code(self) -> &'static str {
+        self.code
+    }
+
+    /// Get the subdivision name
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+}
+
+impl fmt::Display for Subdivision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code)
+    }
+}
+
+impl Record {
+    /// Get the state, province or prefecture of the contacted station,
+    /// checked against its DXCC entity
+    ///
+    /// Reads STATE, falling back to the deprecated VE_PROV. Returns `None`
+    /// if neither is present, or if DXCC is missing or is an entity whose
+    /// subdivisions aren't known, as the value can't be checked.
+    pub fn state(&self) -> Option<Result<Subdivision>> {
+        let (field, value) = ["STATE", "VE_PROV"].into_iter().find_map(|name| {
+            self.get_value(name)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| (name, v))
+        })?;
+        let dxcc = self.get_as::<u16>("DXCC").ok()?;
+        Subdivision::for_dxcc(dxcc).next()?;
+        Some(Subdivision::find(dxcc, value).ok_or_else(|| {
+            let entity = Dxcc::from_code(dxcc).map_or("", Dxcc::name);
+            AdifError::InvalidField {
+                field: field.into(),
+                message: format!("'{}' is not a subdivision of {}", value, entity),
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_find_subdivision() {
+        assert_eq!(Subdivision::find(291, "ct").unwrap().name(), "Connecticut");
+        assert_eq!(
+            Subdivision::find(1, "NT").unwrap().name(),
+            "Northwest Territories"
+        );
+        assert_eq!(
+            Subdivision::find(150, "NT").unwrap().name(),
+            "Northern Territory"
+        );
+        assert_eq!(Subdivision::find(339, "10").unwrap().name(), "Tokyo");
+        assert!(Subdivision::find(291, "AK").is_none());
+        assert_eq!(Subdivision::for_dxcc(291).count(), 49);
+        assert_eq!(Subdivision::for_dxcc(339).count(), 47);
+        assert_eq!(Subdivision::for_dxcc(223).count(), 0);
+    }
+
+    #[test]
+    fn test_record_state() {
+        let file = parse_adi(
+            "<STATE:2>NH<DXCC:3>291<EOR>\
+             <STATE:2>ON<DXCC:3>291<EOR>\
+             <VE_PROV:2>on<DXCC:1>1<EOR>\
+             <STATE:2>XX<DXCC:3>223<EOR>\
+             <STATE:2>XX<EOR>",
+        )
+        .unwrap();
+        let state = |i: usize| file.records[i].state();
+        assert_eq!(state(0).unwrap().unwrap().name(), "New Hampshire");
+        assert!(matches!(
+            state(1),
+            Some(Err(AdifError::InvalidField { field, message }))
+                if field == "STATE"
+                    && message == "'ON' is not a subdivision of UNITED STATES OF AMERICA"
+        ));
+        assert_eq!(state(2).unwrap().unwrap().code(), "ON");
+        assert!(state(3).is_none());
+        assert!(state(4).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::types::Record;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Column selection for table exports
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Columns as (field name, heading) pairs, in order. When empty, every
+    /// field found in the records is a column headed by its name, in order
+    /// of first appearance.
+    pub columns: Vec<(String, String)>,
+}
+
+impl TableOptions {
+    /// Add a column showing a field under a heading
+    pub fn with_column(mut self, field: impl Into<String>, heading: impl Into<String>) -> Self {
+        self.columns.push((field.into(), heading.into()));
+        self
+    }
+
+    /// Get the columns to show for a set of records
+    fn resolve(&self, records: &[&Record]) -> Vec<(String, String)> {
+        if !self.columns.is_empty() {
+            return self.columns.clone();
+        }
+        let mut columns: Vec<(String, String)> = Vec::new();
+        for field in records.iter().flat_map(|r| &r.fields) {
+            if !columns
+                .iter()
+                .any(|(name, _)| field.name.eq_ignore_ascii_case(name))
+            {
+                columns.push((field.name.to_string(), field.name.to_string()));
+            }
+        }
+        columns
+    }
+}
+
+/// Render records as a Markdown (GitHub-flavored) table
+///
+/// Pass the records to include, e.g. `file.iter().filter(..)`. Missing
+/// fields are left blank.
+pub fn markdown_table<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    options: &TableOptions,
+) -> String {
+    let records: Vec<&Record> = records.into_iter().collect();
+    let columns = options.resolve(&records);
+    let escape = |value: &str| {
+        value
+            .replace('\\', "\\\\")
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    };
+
+    let mut s = String::new();
+    let row = |s: &mut String, cells: &mut dyn Iterator<Item = String>| {
+        s.push('|');
+        for cell in cells {
+            let _ = write!(s, " {} |", cell);
+        }
+        s.push('\n');
+    };
+    row(
+        &mut s,
+        &mut columns.iter().map(|(_, heading)| escape(heading)),
+    );
+    row(&mut s, &mut columns.iter().map(|_| "---".to_string()));
+    for record in records {
+        row(
+            &mut s,
+            &mut columns
+                .iter()
+                .map(|(name, _)| escape(record.get_value(name).unwrap_or_default())),
+        );
+    }
+    s
+}
+
+/// Render records as an HTML `<table>`
+///
+/// Pass the records to include, e.g. `file.iter().filter(..)`. Missing
+/// fields are left blank.
+pub fn html_table<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    options: &TableOptions,
+) -> String {
+    let records: Vec<&Record> = records.into_iter().collect();
+    let columns = options.resolve(&records);
+    let escape = |value: &str| {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    };
+
+    let mut s = String::from("<table>\n<thead>\n<tr>");
+    for (_, heading) in &columns {
+        let _ = write!(s, "<th>{}</th>", escape(heading));
+    }
+    s.push_str("</tr>\n</thead>\n<tbody>\n");
+    for record in records {
+        s.push_str("<tr>");
+        for (name, _) in &columns {
+            let _ = write!(
+                s,
+                "<td>{}</td>",
+                escape(record.get_value(name).unwrap_or_default())
+            );
+        }
+        s.push_str("</tr>\n");
+    }
+    s.push_str("</tbody>\n</table>\n");
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_markdown_table() {
+        let file = parse_adi("<CALL:4>W1AW<BAND:3>20m<EOR><CALL:4>K1AB<NOTES:3>a|b<EOR>").unwrap();
+        assert_eq!(
+            markdown_table(&file.records, &TableOptions::default()),
+            "| CALL | BAND | NOTES |\n\
+             | --- | --- | --- |\n\
+             | W1AW | 20m |  |\n\
+             | K1AB |  | a\\|b |\n"
+        );
+
+        let options = TableOptions::default()
+            .with_column("call", "Call")
+            .with_column("BAND", "Band");
+        let table = markdown_table(file.iter().filter(|r| r.band().is_some()), &options);
+        assert_eq!(table, "| Call | Band |\n| --- | --- |\n| W1AW | 20m |\n");
+    }
+
+    #[test]
+    fn test_html_table() {
+        let file = parse_adi("<CALL:4>W1AW<NAME:7>A & <B><EOR>").unwrap();
+        let options = TableOptions::default()
+            .with_column("CALL", "Call")
+            .with_column("NAME", "Name");
+        assert_eq!(
+            html_table(&file.records, &options),
+            "<table>\n<thead>\n<tr><th>Call</th><th>Name</th></tr>\n</thead>\n<tbody>\n\
+             <tr><td>W1AW</td><td>A &amp; &lt;B&gt;</td></tr>\n</tbody>\n</table>\n"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::Result;
+use crate::types::{AdifFile, Field, Record};
+use minijinja::syntax::SyntaxConfig;
+use minijinja::{Environment, Value};
+use std::collections::BTreeMap;
+
+const HEADER: &str = "header";
+const RECORD: &str = "record";
+
+/// Templates for rendering a log as arbitrary text, such as QSL labels,
+/// emails or BBS posts
+///
+/// Templates use Jinja syntax (see the `minijinja` crate). Each field is a
+/// variable named by its uppercase name, e.g. `{{ CALL }}`; the first of
+/// repeated fields wins. `fields` lists all fields in order as
+/// `(name, value)` pairs. The header template also gets `preamble` and
+/// `count` (the number of records), and the record template gets `index`
+/// (starting at 0). Trailing newlines are kept, so a record template
+/// ending in a newline renders one line per record.
+#[derive(Debug)]
+pub struct Template {
+    env: Environment<'static>,
+}
+
+impl Template {
+    /// Compile a header template and a record template
+    pub fn new(header: impl Into<String>, record: impl Into<String>) -> Result<Self> {
+        let mut env = Environment::new();
+        env.set_syntax(
+            SyntaxConfig::builder()
+                .keep_trailing_newline(true)
+                .build()?,
+        );
+        env.add_template_owned(HEADER, header.into())?;
+        env.add_template_owned(RECORD, record.into())?;
+        Ok(Template { env })
+    }
+
+    /// Render the header template followed by the record template for
+    /// each record
+    pub fn render(&self, file: &AdifFile) -> Result<String> {
+        let mut s = self.render_header(file)?;
+        for (index, record) in file.records.iter().enumerate() {
+            s.push_str(&self.render_record(record, index)?);
+        }
+        Ok(s)
+    }
+
+    /// Render the header template for a log
+    pub fn render_header(&self, file: &AdifFile) -> Result<String> {
+        let mut context = context(&file.header.fields);
+        context.insert(
+            "preamble".into(),
+            Value::from(file.header.preamble.as_str()),
+        );
+        context.insert("count".into(), Value::from(file.records.len()));
+        Ok(self.env.get_template(HEADER)?.render(context)?)
+    }
+
+    /// Render the record template for one record
+    pub fn render_record(&self, record: &Record, index: usize) -> Result<String> {
+        let mut context = context(&record.fields);
+        context.insert("index".into(), Value::from(index));
+        Ok(self.env.get_template(RECORD)?.render(context)?)
+    }
+}
+
+fn context(fields: &[Field]) -> BTreeMap<String, Value> {
+    let mut context = BTreeMap::new();
+    for field in fields {
+        context
+            .entry(field.name.to_ascii_uppercase())
+            .or_insert_with(|| Value::from(field.value.as_str()));
+    }
+    let pairs: Vec<Value> = fields
+        .iter()
+        .map(|f| Value::from(vec![f.name.to_string(), f.value.clone()]))
+        .collect();
+    context.insert("fields".into(), Value::from(pairs));
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_render() {
+        let file = parse_adi(
+            "<PROGRAMID:4>Test<EOH>\
+             <call:4>W1AW<BAND:3>20m<NAME:5>Hiram<EOR>\
+             <CALL:4>K1AB<BAND:2>2m<EOR>",
+        )
+        .unwrap();
+        let template = Template::new(
+            "Log from {{ PROGRAMID }}, {{ count }} QSOs\n",
+            "{{ index + 1 }}. {{ CALL }} on {{ BAND }}\
+             {% if NAME %} ({{ NAME | title }}){% endif %}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&file).unwrap(),
+            "Log from Test, 2 QSOs\n1. W1AW on 20m (Hiram)\n2. K1AB on 2m\n"
+        );
+
+        let template = Template::new(
+            "",
+            "{% for name, value in fields %}{{ name }}={{ value }};{% endfor %}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render_record(&file.records[1], 1).unwrap(),
+            "CALL=K1AB;BAND=2m;"
+        );
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        assert!(Template::new("", "{% if CALL %}").is_err());
+    }
+}
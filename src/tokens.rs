@@ -0,0 +1,198 @@
+use crate::borrowed::FieldRef;
+use crate::error::Result;
+use crate::options::ParserOptions;
+use crate::parser::AdiParser;
+use core::ops::Range;
+
+/// A syntactic event in ADI data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// A field before `<EOH>`
+    HeaderField(FieldRef<'a>),
+    /// A field after the header (or anywhere, if there is no header)
+    RecordField(FieldRef<'a>),
+    /// The `<EOH>` end-of-header marker
+    Eoh(Range<usize>),
+    /// The `<EOR>` end-of-record marker
+    Eor(Range<usize>),
+    /// The `<EOF>` end-of-file marker
+    Eof(Range<usize>),
+    /// Text between tags (whitespace, comments, stray characters), and
+    /// anything after `<EOF>`
+    Garbage { text: &'a str, span: Range<usize> },
+}
+
+impl Event<'_> {
+    /// Get the byte range of this event in the input
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Event::HeaderField(field) | Event::RecordField(field) => field.span.clone(),
+            Event::Eoh(span) | Event::Eor(span) | Event::Eof(span) => span.clone(),
+            Event::Garbage { span, .. } => span.clone(),
+        }
+    }
+}
+
+/// Pull parser yielding ADI events without building records
+///
+/// Together the events cover the whole input, so formatters, linters and
+/// syntax highlighters can work from their spans. After a malformed field
+/// an error is yielded, then the text up to the next `<` as garbage, and
+/// tokenizing resumes there.
+pub struct Tokens<'a> {
+    parser: AdiParser<'a>,
+    in_header: bool,
+    after_eof: bool,
+    /// End of the text to yield as garbage after an error
+    resync: Option<usize>,
+}
+
+impl<'a> Tokens<'a> {
+    /// Create a tokenizer over ADI input
+    pub fn new(input: &'a str) -> Self {
+        let parser = AdiParser::new(input, ParserOptions::default());
+        let in_header = parser.has_header();
+        Self {
+            parser,
+            in_header,
+            after_eof: false,
+            resync: None,
+        }
+    }
+
+    fn marker(&mut self, tag: &str) -> Result<Range<usize>> {
+        let start = self.parser.pos;
+        self.parser.skip_tag(tag)?;
+        Ok(start..self.parser.pos)
+    }
+
+    fn garbage(&mut self, end: usize) -> Event<'a> {
+        let start = self.parser.pos;
+        self.parser.pos = end;
+        Event::Garbage {
+            text: &self.parser.input[start..end],
+            span: start..end,
+        }
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Event<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(end) = self.resync.take() {
+            return Some(Ok(self.garbage(end)));
+        }
+        let input = self.parser.input;
+        let pos = self.parser.pos;
+        if pos >= input.len() {
+            return None;
+        }
+
+        if self.after_eof {
+            return Some(Ok(self.garbage(input.len())));
+        }
+        if !input[pos..].starts_with('<') {
+            let end = self.parser.find_next_tag_start().unwrap_or(input.len());
+            return Some(Ok(self.garbage(end)));
+        }
+
+        if self.in_header && self.parser.check_tag("EOH") {
+            self.in_header = false;
+            return Some(self.marker("EOH").map(Event::Eoh));
+        }
+        if !self.in_header && self.parser.check_tag("EOR") {
+            return Some(self.marker("EOR").map(Event::Eor));
+        }
+        if !self.in_header && self.parser.check_tag("EOF") {
+            self.after_eof = true;
+            return Some(self.marker("EOF").map(Event::Eof));
+        }
+
+        match self.parser.parse_field() {
+            Ok(field) if self.in_header => Some(Ok(Event::HeaderField(field))),
+            Ok(field) => Some(Ok(Event::RecordField(field))),
+            Err(e) => {
+                // Resynchronize on the next '<', yielding what's skipped
+                self.parser.pos = pos + 1;
+                self.resync = Some(self.parser.find_next_tag_start().unwrap_or(input.len()));
+                self.parser.pos = pos;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AdifError;
+
+    #[test]
+    fn test_events() {
+        let input = "Log\n<ADIF_VER:5>3.1.6<EOH>\n<CALL:4>W1AW<eor><EOF>junk";
+        let events: Vec<Event> = Tokens::new(input).map(|e| e.unwrap()).collect();
+
+        assert_eq!(events.len(), 8);
+        assert!(matches!(events[0], Event::Garbage { text: "Log\n", .. }));
+        assert!(matches!(&events[1], Event::HeaderField(f) if f.name == "ADIF_VER"));
+        assert_eq!(events[2], Event::Eoh(21..26));
+        assert!(matches!(events[3], Event::Garbage { text: "\n", .. }));
+        assert!(matches!(&events[4], Event::RecordField(f) if f.value == "W1AW"));
+        assert_eq!(events[5], Event::Eor(39..44));
+        assert_eq!(events[6], Event::Eof(44..49));
+        assert!(matches!(events[7], Event::Garbage { text: "junk", .. }));
+    }
+
+    #[test]
+    fn test_spans_cover_input() {
+        let input = "hdr <PROGRAMID:3>Foo <EOH> <CALL:4>W1AW <EOR>\r\n<CALL:4>K1AB<EOR>";
+        let mut end = 0;
+        for event in Tokens::new(input) {
+            let span = event.unwrap().span();
+            assert_eq!(span.start, end);
+            end = span.end;
+        }
+        assert_eq!(end, input.len());
+    }
+
+    #[test]
+    fn test_no_header() {
+        let input = "<CALL:4>W1AW<EOR>";
+        let events: Vec<Event> = Tokens::new(input).map(|e| e.unwrap()).collect();
+
+        assert!(matches!(events[0], Event::RecordField(_)));
+        assert!(matches!(events[1], Event::Eor(_)));
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        let input = "<CALL:x>W1AW<MODE:2>CW<EOR>";
+        let events: Vec<Result<Event>> = Tokens::new(input).collect();
+
+        assert!(matches!(
+            events[0],
+            Err(AdifError::InvalidDataSpecifier { .. })
+        ));
+        assert!(matches!(
+            events[1],
+            Ok(Event::Garbage {
+                text: "<CALL:x>W1AW",
+                ..
+            })
+        ));
+        assert!(matches!(&events[2], Ok(Event::RecordField(f)) if f.name == "MODE"));
+        assert!(matches!(events[3], Ok(Event::Eor(_))));
+        assert_eq!(events.len(), 4);
+    }
+
+    #[test]
+    fn test_spans_cover_malformed_input() {
+        let input = "<PROGRAMID:x>Foo<EOH><CALL:4>W1AW<:3 oops<MODE>\n<BAND:9>20m<EOR>";
+        let mut covered = String::new();
+        for event in Tokens::new(input).flatten() {
+            covered.push_str(&input[event.span()]);
+        }
+        assert_eq!(covered, input);
+    }
+}
@@ -1,8 +1,18 @@
+use crate::field_name::FieldName;
+use crate::options::DuplicateFields;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Write;
+use core::ops::Range;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::fmt::Write;
 
 /// ADIF data type indicators
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataType {
     /// Boolean (Y/N)
     Boolean,
@@ -65,23 +75,54 @@ impl DataType {
 }
 
 /// A single ADIF field with name, optional type, and value
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Fields compare equal by name, data type and value; the source text
+/// kept in fidelity mode is ignored.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Field {
     /// Field name (case-insensitive, stored uppercase)
-    pub name: String,
+    pub name: FieldName,
     /// Data type indicator (if specified)
     pub data_type: DataType,
     /// Field value
     pub value: String,
+    /// Original source text (only retained in fidelity mode)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub raw: Option<Box<RawField>>,
+}
+
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.data_type == other.data_type && self.value == other.value
+    }
+}
+
+impl Eq for Field {}
+
+/// Original source text of a parsed field, retained in fidelity mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawField {
+    /// Byte range of the data specifier and value in the input
+    pub span: Range<usize>,
+    /// Text between the previous tag and this field (whitespace, comments)
+    pub leading: String,
+    /// The data specifier exactly as written, e.g. `<call:4:s>`
+    pub specifier: String,
 }
 
 impl Field {
     /// Create a new field
     pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
         Self {
-            name: name.into().to_uppercase(),
+            name: FieldName::from(name.into().to_ascii_uppercase()),
             data_type: DataType::Unspecified,
             value: value.into(),
+            raw: None,
         }
     }
 
@@ -89,8 +130,8 @@ impl Field {
     pub fn to_adi_string(&self) -> String {
         let len = self.value.len();
         match self.data_type.to_char() {
-            Some(t) => format!("<{}:{}:{}>{}",  self.name, len, t, self.value),
-            None => format!("<{}:{}>{}",  self.name, len, self.value),
+            Some(t) => format!("<{}:{}:{}>{}", self.name, len, t, self.value),
+            None => format!("<{}:{}>{}", self.name, len, self.value),
         }
     }
 
@@ -101,15 +142,17 @@ impl Field {
         value: impl Into<String>,
     ) -> Self {
         Self {
-            name: name.into().to_uppercase(),
+            name: FieldName::from(name.into().to_ascii_uppercase()),
             data_type,
             value: value.into(),
+            raw: None,
         }
     }
 }
 
 /// ADIF file header containing metadata
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdifHeader {
     /// ADIF version
     pub adif_version: Option<String>,
@@ -123,13 +166,37 @@ pub struct AdifHeader {
     pub fields: Vec<Field>,
     /// Any text before the first tag (comments, etc.)
     pub preamble: String,
+    /// Text after the last header field up to and including `<EOH>`, as
+    /// written (only retained in fidelity mode)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub terminator: Option<String>,
 }
 
 impl AdifHeader {
+    /// Add a field to the header, updating the well-known header values
+    pub fn add_field(&mut self, field: Field) {
+        self.sync(&field);
+        self.fields.push(field);
+    }
+
+    /// Update the well-known header value a field holds, if any
+    pub(crate) fn sync(&mut self, field: &Field) {
+        let value = Some(field.value.clone());
+        match field.name.to_ascii_uppercase().as_str() {
+            "ADIF_VER" => self.adif_version = value,
+            "PROGRAMID" => self.program_id = value,
+            "PROGRAMVERSION" => self.program_version = value,
+            "CREATED_TIMESTAMP" => self.created_timestamp = value,
+            _ => {}
+        }
+    }
+
     /// Get a header field by name (case-insensitive)
     pub fn get(&self, name: &str) -> Option<&Field> {
-        let name_upper = name.to_uppercase();
-        self.fields.iter().find(|f| f.name == name_upper)
+        find_field(&self.fields, name)
     }
 
     /// Get a header field value by name (case-insensitive)
@@ -140,22 +207,96 @@ impl AdifHeader {
 
 /// A single QSO (contact) record
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Record {
     /// Fields in this record
     pub fields: Vec<Field>,
+    /// Text after the last field up to and including `<EOR>`, as written
+    /// (only retained in fidelity mode)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub terminator: Option<String>,
 }
 
 impl Record {
     /// Create a new empty record
     pub fn new() -> Self {
-        Self { fields: Vec::new() }
+        Self::default()
     }
 
-    /// Add a field to the record
+    /// Add a field to the record, keeping any earlier field with the same
+    /// name
     pub fn add_field(&mut self, field: Field) {
         self.fields.push(field);
     }
 
+    /// Add a field to the record, resolving a repeated name according to
+    /// `policy`
+    ///
+    /// Under [`DuplicateFields::Error`] a field that repeats one already in
+    /// the record is not added but handed back in `Err`.
+    pub fn add_field_with(
+        &mut self,
+        field: Field,
+        policy: DuplicateFields,
+    ) -> core::result::Result<(), Field> {
+        policy.add(&mut self.fields, field, |f| &f.name)
+    }
+
+    /// Set a field's value, replacing the first occurrence of the field or
+    /// adding it at the end
+    ///
+    /// A replaced field keeps its data type indicator but loses any source
+    /// text retained in fidelity mode.
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        match self
+            .fields
+            .iter_mut()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+        {
+            Some(field) => {
+                field.value = value.into();
+                field.raw = None;
+            }
+            None => self.add_field(Field::new(name, value)),
+        }
+    }
+
+    /// Remove a field (case-insensitive), returning its first occurrence
+    ///
+    /// Any repeats of the field are removed too.
+    pub fn remove(&mut self, name: &str) -> Option<Field> {
+        let index = self
+            .fields
+            .iter()
+            .position(|f| f.name.eq_ignore_ascii_case(name))?;
+        let field = self.fields.remove(index);
+        self.fields.retain(|f| !f.name.eq_ignore_ascii_case(name));
+        Some(field)
+    }
+
+    /// Rename a field (case-insensitive), keeping its place and value,
+    /// and return whether it was renamed
+    ///
+    /// Every occurrence of the field is renamed. Nothing changes if the
+    /// record doesn't have the field, or already has one named `new`.
+    pub fn rename(&mut self, old: &str, new: &str) -> bool {
+        if !old.eq_ignore_ascii_case(new) && self.get(new).is_some() {
+            return false;
+        }
+        let mut renamed = false;
+        for field in &mut self.fields {
+            if field.name.eq_ignore_ascii_case(old) {
+                field.name = FieldName::from(new.to_ascii_uppercase());
+                field.raw = None;
+                renamed = true;
+            }
+        }
+        renamed
+    }
+
     /// Serialize this record to ADI format (one line, terminated by `<EOR>`).
     pub fn to_adi_string(&self) -> String {
         let mut s = String::new();
@@ -167,9 +308,14 @@ impl Record {
     }
 
     /// Get a field by name (case-insensitive)
+    ///
+    /// If the record repeats the field, the first occurrence is returned.
+    /// Parsing keeps every occurrence unless
+    /// [`ParserOptions::duplicate_fields`](crate::ParserOptions::duplicate_fields)
+    /// says otherwise, and [`add_field_with`](Self::add_field_with) applies
+    /// the same policies when building records.
     pub fn get(&self, name: &str) -> Option<&Field> {
-        let name_upper = name.to_uppercase();
-        self.fields.iter().find(|f| f.name == name_upper)
+        find_field(&self.fields, name)
     }
 
     /// Get a field value by name (case-insensitive)
@@ -178,10 +324,11 @@ impl Record {
     }
 
     /// Convert to a HashMap for easier access
+    #[cfg(feature = "std")]
     pub fn to_map(&self) -> HashMap<String, String> {
         self.fields
             .iter()
-            .map(|f| (f.name.clone(), f.value.clone()))
+            .map(|f| (f.name.to_string(), f.value.clone()))
             .collect()
     }
 
@@ -224,15 +371,105 @@ impl Record {
     pub fn rst_rcvd(&self) -> Option<&str> {
         self.get_value("RST_RCVD")
     }
+
+    /// Get the call sign used on the air by the logging station
+    pub fn station_callsign(&self) -> Option<&str> {
+        self.get_value("STATION_CALLSIGN")
+    }
+
+    /// Get the call sign of the logging operator
+    pub fn operator(&self) -> Option<&str> {
+        self.get_value("OPERATOR")
+    }
+
+    /// Get the logging station's call sign: STATION_CALLSIGN, or else
+    /// OPERATOR if it is missing or empty
+    pub fn my_callsign(&self) -> Option<&str> {
+        ["STATION_CALLSIGN", "OPERATOR"]
+            .into_iter()
+            .filter_map(|name| self.get_value(name))
+            .find(|value| !value.trim().is_empty())
+    }
+
+    /// Get the logging station's name
+    pub fn my_name(&self) -> Option<&str> {
+        self.get_value("MY_NAME")
+    }
+
+    /// Get the logging station's rig
+    pub fn my_rig(&self) -> Option<&str> {
+        self.get_value("MY_RIG")
+    }
+
+    /// Get the logging station's antenna
+    pub fn my_antenna(&self) -> Option<&str> {
+        self.get_value("MY_ANTENNA")
+    }
+
+    /// Get the logging station's city
+    pub fn my_city(&self) -> Option<&str> {
+        self.get_value("MY_CITY")
+    }
+
+    /// Get the logging station's state or province code
+    pub fn my_state(&self) -> Option<&str> {
+        self.get_value("MY_STATE")
+    }
+
+    /// Get the logging station's county
+    pub fn my_cnty(&self) -> Option<&str> {
+        self.get_value("MY_CNTY")
+    }
+
+    /// Get the logging station's DXCC entity name
+    pub fn my_country(&self) -> Option<&str> {
+        self.get_value("MY_COUNTRY")
+    }
+
+    /// Get the logging station's IOTA designator
+    pub fn my_iota(&self) -> Option<&str> {
+        self.get_value("MY_IOTA")
+    }
+
+    /// Get the logging station's POTA references
+    pub fn my_pota_ref(&self) -> Option<&str> {
+        self.get_value("MY_POTA_REF")
+    }
+
+    /// Get the logging station's SOTA summit reference
+    pub fn my_sota_ref(&self) -> Option<&str> {
+        self.get_value("MY_SOTA_REF")
+    }
+
+    /// Get the logging station's WWFF reference
+    pub fn my_wwff_ref(&self) -> Option<&str> {
+        self.get_value("MY_WWFF_REF")
+    }
+}
+
+/// Find a field by name (case-insensitive), comparing known names by variant
+fn find_field<'a>(fields: &'a [Field], name: &str) -> Option<&'a Field> {
+    match FieldName::known(name) {
+        Some(known) => fields.iter().find(|f| f.name.is_same_field(&known)),
+        None => fields.iter().find(|f| f.name.eq_ignore_ascii_case(name)),
+    }
 }
 
 /// A complete ADIF file with header and records
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdifFile {
     /// File header (may be empty if no header present)
     pub header: AdifHeader,
     /// QSO records
     pub records: Vec<Record>,
+    /// Text after the last record, such as `<EOF>` or trailing newlines
+    /// (only retained in fidelity mode)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub trailer: Option<String>,
 }
 
 impl AdifFile {
@@ -256,28 +493,45 @@ impl AdifFile {
         self.records.iter()
     }
 
-    /// Serialize the entire file to ADI format.
-    pub fn to_adi_string(&self) -> String {
-        let mut s = String::new();
+    /// Add a record at the end
+    pub fn push(&mut self, record: Record) {
+        self.records.push(record);
+    }
 
-        // Header
-        if !self.header.preamble.is_empty() {
-            s.push_str(&self.header.preamble);
-            s.push('\n');
-        }
-        for field in &self.header.fields {
-            let _ = write!(s, "{}", field.to_adi_string());
-        }
-        if !self.header.fields.is_empty() || !self.header.preamble.is_empty() {
-            s.push_str("<EOH>\n");
-        }
+    /// Insert a record at `index`, shifting the records after it
+    ///
+    /// Panics if `index` is greater than the number of records.
+    pub fn insert(&mut self, index: usize, record: Record) {
+        self.records.insert(index, record);
+    }
 
-        // Records
-        for record in &self.records {
-            s.push_str(&record.to_adi_string());
-        }
+    /// Remove and return the record at `index`, shifting the records after
+    /// it
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Record {
+        self.records.remove(index)
+    }
 
-        s
+    /// Keep only the records for which `keep` returns true, in order
+    pub fn retain(&mut self, keep: impl FnMut(&Record) -> bool) {
+        self.records.retain(keep);
+    }
+
+    /// Sort the records with a comparison function, keeping the order of
+    /// equal records
+    pub fn sort_by(&mut self, compare: impl FnMut(&Record, &Record) -> Ordering) {
+        self.records.sort_by(compare);
+    }
+
+    /// Keep the first `len` records, dropping the rest
+    pub fn truncate(&mut self, len: usize) {
+        self.records.truncate(len);
+    }
+
+    /// Serialize the entire file to ADI format.
+    pub fn to_adi_string(&self) -> String {
+        crate::writer::write_adi(self)
     }
 }
 
@@ -307,6 +561,111 @@ mod tests {
         assert!(s.trim_end().ends_with("<EOR>"));
     }
 
+    #[test]
+    fn record_add_field_with() {
+        let mut rec = Record::new();
+        rec.add_field(Field::new("CALL", "K1ABC"));
+        rec.add_field(Field::new("BAND", "20m"));
+
+        assert!(
+            rec.add_field_with(Field::new("call", "W1AW"), DuplicateFields::KeepFirst)
+                .is_ok()
+        );
+        assert_eq!(rec.call(), Some("K1ABC"));
+        let rejected = rec.add_field_with(Field::new("CALL", "W1AW"), DuplicateFields::Error);
+        assert_eq!(rejected.unwrap_err().value, "W1AW");
+        assert!(
+            rec.add_field_with(Field::new("CALL", "W1AW"), DuplicateFields::KeepLast)
+                .is_ok()
+        );
+        assert_eq!(rec.call(), Some("W1AW"));
+        assert_eq!(rec.fields[0].name, "BAND");
+        assert!(
+            rec.add_field_with(Field::new("CALL", "N0CALL"), DuplicateFields::KeepAll)
+                .is_ok()
+        );
+        assert_eq!(rec.fields.len(), 3);
+    }
+
+    #[test]
+    fn record_set_remove_rename() {
+        let mut record = crate::parse_adi("<CALL:4>W1AW<NAME:3>Bob<NAME:3>Tom<EOR>")
+            .unwrap()
+            .records
+            .remove(0);
+        record.set("call", "K1AB");
+        record.set("band", "20m");
+        assert_eq!(
+            record.to_adi_string(),
+            "<CALL:4>K1AB<NAME:3>Bob<NAME:3>Tom<BAND:3>20m<EOR>\n"
+        );
+
+        assert!(record.rename("band", "BAND_RX"));
+        assert!(!record.rename("MODE", "SUBMODE"));
+        assert!(!record.rename("CALL", "band_rx"));
+        assert_eq!(record.call(), Some("K1AB"));
+        assert_eq!(record.remove("name").unwrap().value, "Bob");
+        assert!(record.remove("NAME").is_none());
+        assert_eq!(record.to_adi_string(), "<CALL:4>K1AB<BAND_RX:3>20m<EOR>\n");
+    }
+
+    #[test]
+    fn file_container_methods() {
+        let record = |call: &str| {
+            let mut record = Record::new();
+            record.set("CALL", call);
+            record
+        };
+        let calls = |file: &AdifFile| -> Vec<String> {
+            file.iter()
+                .filter_map(|r| r.call())
+                .map(String::from)
+                .collect()
+        };
+
+        let mut file = AdifFile::new();
+        file.push(record("W1AW"));
+        file.push(record("K1AB"));
+        file.insert(1, record("N0CA"));
+        file.push(record("DL1ABC"));
+        assert_eq!(calls(&file), ["W1AW", "N0CA", "K1AB", "DL1ABC"]);
+
+        assert_eq!(file.remove(0).call(), Some("W1AW"));
+        file.sort_by(|a, b| a.call().cmp(&b.call()));
+        assert_eq!(calls(&file), ["DL1ABC", "K1AB", "N0CA"]);
+        file.retain(|r| r.call() != Some("K1AB"));
+        assert_eq!(calls(&file), ["DL1ABC", "N0CA"]);
+        file.truncate(1);
+        assert_eq!(calls(&file), ["DL1ABC"]);
+    }
+
+    #[test]
+    fn field_eq_ignores_raw() {
+        let options = crate::ParserOptions {
+            preserve_raw: true,
+            ..Default::default()
+        };
+        let file = crate::parse_adi_with_options(" <call:4>W1AW<EOR>", &options).unwrap();
+        assert!(file.records[0].fields[0].raw.is_some());
+        assert_eq!(file.records[0].fields[0], Field::new("CALL", "W1AW"));
+    }
+
+    #[test]
+    fn record_my_accessors() {
+        let rec = crate::parse_adi(
+            "<STATION_CALLSIGN:0><OPERATOR:4>W1AW<MY_RIG:7>IC-7300<MY_POTA_REF:6>K-0001<EOR>",
+        )
+        .unwrap()
+        .records
+        .remove(0);
+        assert_eq!(rec.station_callsign(), Some(""));
+        assert_eq!(rec.my_callsign(), Some("W1AW"));
+        assert_eq!(rec.my_rig(), Some("IC-7300"));
+        assert_eq!(rec.my_pota_ref(), Some("K-0001"));
+        assert_eq!(rec.my_antenna(), None);
+        assert_eq!(Record::new().my_callsign(), None);
+    }
+
     #[test]
     fn roundtrip_parse_write_parse() {
         let original = "<CALL:5>W1AW1<QSO_DATE:8>20240115<BAND:3>20m<MODE:2>CW<EOR>\n\
@@ -344,4 +703,21 @@ mod tests {
         assert_eq!(reparsed.records.len(), 1);
         assert_eq!(reparsed.records[0].call(), Some("K3LR"));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let input = "Log<ADIF_VER:5>3.1.6<EOH><CALL:4>W1AW<FREQ:6:N>14.074<APP_X_Y:1>z<EOR>";
+        let file = crate::parse_adi(input).unwrap();
+
+        let json = serde_json::to_string(&file).unwrap();
+        assert!(json.contains(r#"{"name":"CALL","data_type":"Unspecified","value":"W1AW"}"#));
+        assert!(!json.contains("terminator"));
+
+        let back: AdifFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.header.fields, file.header.fields);
+        assert_eq!(back.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(back.records[0].fields, file.records[0].fields);
+        assert!(back.records[0].fields[0].name.is_known());
+    }
 }
@@ -0,0 +1,2163 @@
+//! Validation of logs against the fields defined by the ADIF specification
+
+use crate::app::split_app_name;
+use crate::arrl_section::ArrlSection;
+use crate::band::Band;
+use crate::callsign::Callsign;
+use crate::continent::Continent;
+use crate::coordinate::Coordinate;
+use crate::date::{AdifDate, AdifTime};
+use crate::dxcc::Dxcc;
+use crate::encoding::json_string;
+use crate::enums::{AntPath, DownloadStatus, MorseKeyType, QsoComplete, Region, UploadStatus};
+use crate::frequency::Frequency;
+use crate::from_field::FromField;
+use crate::grid::GridSquare;
+use crate::mode::{Mode, Submode};
+use crate::options::{IntlText, UnknownFields, is_intl_field};
+use crate::parser::find_tag;
+use crate::pota::is_park_ref;
+use crate::prop_mode::PropMode;
+use crate::qsl::{QslSentStatus, QslStatus, QslVia};
+use crate::sota::is_summit_ref;
+use crate::subdivision::Subdivision;
+use crate::types::{AdifFile, DataType, Field, Record};
+use crate::version::AdifVersion;
+use crate::wwff::is_wwff_ref;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// The data type of a field defined by the specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FieldType {
+    /// `AwardList`, a comma-separated list of award names (deprecated)
+    AwardList,
+    /// `Boolean`, `Y` or `N`
+    Boolean,
+    /// `CreditList`, a comma-separated list of credits
+    CreditList,
+    /// `Date`, `YYYYMMDD`
+    Date,
+    /// A value from one of the specification's enumerations
+    Enumeration(Enumeration),
+    /// `GridSquare`, a 2 to 8 character Maidenhead locator
+    GridSquare,
+    /// `GridSquareExt`, characters 9 and 10 of a locator
+    GridSquareExt,
+    /// `GridSquareList`, a comma-separated list of locators
+    GridSquareList,
+    /// `Integer`, digits with an optional minus sign
+    Integer,
+    /// `IntlMultilineString`, Unicode text over several lines
+    IntlMultilineString,
+    /// `IntlString`, a line of Unicode text
+    IntlString,
+    /// `IOTARefNo`, such as `NA-001`
+    IotaRef,
+    /// `Location`, `XDDD MM.MMM`
+    Location,
+    /// `MultilineString`, ASCII text over several lines
+    MultilineString,
+    /// `Number`, a decimal with an optional minus sign
+    Number,
+    /// `PositiveInteger`, digits with no sign
+    PositiveInteger,
+    /// `POTARefList`, a comma-separated list of park references
+    PotaRefList,
+    /// `SecondaryAdministrativeSubdivisionListAlt`, a semicolon-separated
+    /// list of alternative county names
+    SecondarySubdivisionListAlt,
+    /// `SecondarySubdivisionList`, a colon-separated list of counties
+    SecondarySubdivisionList,
+    /// `SOTARef`, such as `W2/WE-003`
+    SotaRef,
+    /// `SponsoredAwardList`, a comma-separated list of awards
+    SponsoredAwardList,
+    /// `String`, a line of ASCII text
+    String,
+    /// `Time`, `HHMMSS` or `HHMM`
+    Time,
+    /// `WWFFRef`, such as `KFF-0001`
+    WwffRef,
+}
+
+impl FieldType {
+    /// Get the type's name in the specification
+    pub fn name(self) -> &'static str {
+        match self {
+            FieldType::AwardList => "AwardList",
+            FieldType::Boolean => "Boolean",
+            FieldType::CreditList => "CreditList",
+            FieldType::Date => "Date",
+            FieldType::Enumeration(_) => "Enumeration",
+            FieldType::GridSquare => "GridSquare",
+            FieldType::GridSquareExt => "GridSquareExt",
+            FieldType::GridSquareList => "GridSquareList",
+            FieldType::Integer => "Integer",
+            FieldType::IntlMultilineString => "IntlMultilineString",
+            FieldType::IntlString => "IntlString",
+            FieldType::IotaRef => "IOTARefNo",
+            FieldType::Location => "Location",
+            FieldType::MultilineString => "MultilineString",
+            FieldType::Number => "Number",
+            FieldType::PositiveInteger => "PositiveInteger",
+            FieldType::PotaRefList => "POTARefList",
+            FieldType::SecondarySubdivisionListAlt => "SecondaryAdministrativeSubdivisionListAlt",
+            FieldType::SecondarySubdivisionList => "SecondarySubdivisionList",
+            FieldType::SotaRef => "SOTARef",
+            FieldType::SponsoredAwardList => "SponsoredAwardList",
+            FieldType::String => "String",
+            FieldType::Time => "Time",
+            FieldType::WwffRef => "WWFFRef",
+        }
+    }
+
+    /// Get the [`DataType`] for values of this type: integers and DXCC
+    /// entity codes are numbers, and types other than dates, times,
+    /// numbers, booleans and locations are unspecified
+    pub(crate) fn data_type(self) -> DataType {
+        match self {
+            FieldType::Boolean => DataType::Boolean,
+            FieldType::Date => DataType::Date,
+            FieldType::Time => DataType::Time,
+            FieldType::Integer
+            | FieldType::Number
+            | FieldType::PositiveInteger
+            | FieldType::Enumeration(Enumeration::Dxcc) => DataType::Number,
+            FieldType::Location => DataType::Location,
+            _ => DataType::Unspecified,
+        }
+    }
+
+    /// Check a value's format in a version of the specification, or give
+    /// the rule it breaks and why
+    ///
+    /// Types whose format is not checked accept any value.
+    fn check(self, value: &str, version: AdifVersion) -> core::result::Result<(), Problem> {
+        let valid = match self {
+            FieldType::Boolean => bool::from_field(value).is_ok(),
+            FieldType::Date => {
+                return AdifDate::from_field(value).map(drop).map_err(data_type);
+            }
+            FieldType::Enumeration(enumeration) => return enumeration.check(value, version),
+            FieldType::GridSquare => is_locator(value),
+            // Characters 9 and 10 follow on from a full 8 character locator
+            FieldType::GridSquareExt => {
+                value.len() == 2 && GridSquare::from_field(&format!("AA00AA00{}", value)).is_ok()
+            }
+            FieldType::GridSquareList => {
+                let bad = value.split(',').map(str::trim).find(|g| !is_locator(g));
+                if let Some(grid) = bad {
+                    return Err(data_type(format!(
+                        "'{}' in '{}' is not a valid GridSquare",
+                        grid, value
+                    )));
+                }
+                true
+            }
+            FieldType::Integer => is_integer(value.strip_prefix('-').unwrap_or(value)),
+            FieldType::IotaRef => is_iota_ref(value),
+            FieldType::Location => {
+                return Coordinate::from_field(value).map(drop).map_err(data_type);
+            }
+            FieldType::Number => is_number(value),
+            FieldType::PositiveInteger => is_integer(value) && value.bytes().any(|b| b != b'0'),
+            FieldType::PotaRefList => {
+                let bad = value.split(',').map(str::trim).find(|p| !is_park_ref(p));
+                if let Some(park) = bad {
+                    return Err(data_type(format!(
+                        "'{}' in '{}' is not a valid POTARef",
+                        park, value
+                    )));
+                }
+                true
+            }
+            FieldType::SotaRef => is_summit_ref(value),
+            FieldType::Time => {
+                return AdifTime::from_field(value).map(drop).map_err(data_type);
+            }
+            FieldType::WwffRef => is_wwff_ref(value),
+            _ => true,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(data_type(format!(
+                "'{}' is not a valid {}",
+                value,
+                self.name()
+            )))
+        }
+    }
+
+    /// Check whether this is a text type that may span several lines
+    fn is_multiline(self) -> bool {
+        matches!(
+            self,
+            FieldType::MultilineString | FieldType::IntlMultilineString
+        )
+    }
+
+    /// Check whether a value of this type may contain a character
+    ///
+    /// Non-ASCII text is left to [`ValidationOptions::intl_text`].
+    fn allows_char(self, c: char) -> bool {
+        match c {
+            '\r' | '\n' => self.is_multiline(),
+            '\u{FFFD}' => false,
+            c => !c.is_control(),
+        }
+    }
+
+    /// Check that a value has no characters its type doesn't allow, such
+    /// as control characters and U+FFFD from undecodable bytes
+    fn check_charset(self, value: &str) -> core::result::Result<(), Problem> {
+        let Some(c) = value.chars().find(|&c| !self.allows_char(c)) else {
+            return Ok(());
+        };
+        let message = match c {
+            '\u{FFFD}' => format!(
+                "U+FFFD (an undecodable byte) isn't allowed in a {}",
+                self.name()
+            ),
+            '\r' | '\n' => format!("a line break isn't allowed in a {}", self.name()),
+            c => format!(
+                "control character U+{:04X} isn't allowed in a {}",
+                u32::from(c),
+                self.name()
+            ),
+        };
+        Err((Severity::Error, "charset", message))
+    }
+
+    /// Remove characters a value of this type doesn't allow, putting a
+    /// space in place of tabs and line breaks in a single line
+    fn scrub(self, value: &str) -> String {
+        let mut s = String::with_capacity(value.len());
+        let mut prev = None;
+        for c in value.chars() {
+            if self.allows_char(c) {
+                s.push(c);
+            } else if matches!(c, '\t' | '\r') || (c == '\n' && prev != Some('\r')) {
+                s.push(' ');
+            }
+            prev = Some(c);
+        }
+        s
+    }
+}
+
+/// A problem with a value: its severity, rule and message
+type Problem = (Severity, &'static str, String);
+
+fn data_type(message: String) -> Problem {
+    (Severity::Error, "data-type", message)
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldType::Enumeration(enumeration) => write!(f, "Enumeration ({})", enumeration),
+            other => f.write_str(other.name()),
+        }
+    }
+}
+
+/// An enumeration in the specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Enumeration {
+    /// Antenna Path, see [`AntPath`]
+    AntPath,
+    /// ARRL Section, see [`ArrlSection`]
+    ArrlSection,
+    /// Band, see [`Band`]
+    Band,
+    /// Continent, see [`Continent`]
+    Continent,
+    /// DARC DOK, not checked
+    DarcDok,
+    /// DXCC Entity Code, see [`Dxcc`]
+    Dxcc,
+    /// Mode, see [`Mode`]
+    Mode,
+    /// Morse Key Type, see [`MorseKeyType`]
+    MorseKeyType,
+    /// Primary Administrative Subdivision, which depends on the DXCC
+    /// entity, so [`validate`] checks it against the record's DXCC
+    /// instead
+    PrimarySubdivision,
+    /// Propagation Mode, see [`PropMode`]
+    PropMode,
+    /// QSL Received, see [`QslStatus`]
+    QslRcvd,
+    /// QSL Sent, see [`QslSentStatus`]
+    QslSent,
+    /// QSL Via, see [`QslVia`]
+    QslVia,
+    /// QSO Complete, see [`QsoComplete`]
+    QsoComplete,
+    /// QSO Download Status, see [`DownloadStatus`]
+    QsoDownloadStatus,
+    /// QSO Upload Status, see [`UploadStatus`]
+    QsoUploadStatus,
+    /// Region, see [`Region`]
+    Region,
+    /// Secondary Administrative Subdivision, not checked
+    SecondarySubdivision,
+    /// Submode, see [`Submode`]
+    Submode,
+}
+
+impl Enumeration {
+    /// Get the enumeration's name in the specification
+    pub fn name(self) -> &'static str {
+        match self {
+            Enumeration::AntPath => "Ant_Path",
+            Enumeration::ArrlSection => "ARRL_Section",
+            Enumeration::Band => "Band",
+            Enumeration::Continent => "Continent",
+            Enumeration::DarcDok => "DARC_DOK",
+            Enumeration::Dxcc => "DXCC_Entity_Code",
+            Enumeration::Mode => "Mode",
+            Enumeration::MorseKeyType => "Morse_Key_Type",
+            Enumeration::PrimarySubdivision => "Primary_Administrative_Subdivision",
+            Enumeration::PropMode => "Propagation_Mode",
+            Enumeration::QslRcvd => "QSL_Rcvd",
+            Enumeration::QslSent => "QSL_Sent",
+            Enumeration::QslVia => "QSL_Via",
+            Enumeration::QsoComplete => "QSO_Complete",
+            Enumeration::QsoDownloadStatus => "QSO_Download_Status",
+            Enumeration::QsoUploadStatus => "QSO_Upload_Status",
+            Enumeration::Region => "Region",
+            Enumeration::SecondarySubdivision => "Secondary_Administrative_Subdivision",
+            Enumeration::Submode => "Submode",
+        }
+    }
+
+    /// Check whether a value is in the enumeration (case-insensitive)
+    ///
+    /// Enumerations without an embedded table accept any value.
+    pub fn contains(self, value: &str) -> bool {
+        fn is<T: FromField>(value: &str) -> bool {
+            T::from_field(value).is_ok()
+        }
+        match self {
+            Enumeration::AntPath => is::<AntPath>(value),
+            Enumeration::ArrlSection => is::<ArrlSection>(value),
+            Enumeration::Band => is::<Band>(value),
+            Enumeration::Continent => is::<Continent>(value),
+            Enumeration::Dxcc => is::<Dxcc>(value),
+            Enumeration::Mode => is::<Mode>(value),
+            Enumeration::MorseKeyType => is::<MorseKeyType>(value),
+            Enumeration::PropMode => is::<PropMode>(value),
+            Enumeration::QslRcvd => is::<QslStatus>(value),
+            Enumeration::QslSent => is::<QslSentStatus>(value),
+            Enumeration::QslVia => is::<QslVia>(value),
+            Enumeration::QsoComplete => is::<QsoComplete>(value),
+            Enumeration::QsoDownloadStatus => is::<DownloadStatus>(value),
+            Enumeration::QsoUploadStatus => is::<UploadStatus>(value),
+            Enumeration::Region => is::<Region>(value),
+            Enumeration::Submode => is::<Submode>(value),
+            Enumeration::DarcDok
+            | Enumeration::PrimarySubdivision
+            | Enumeration::SecondarySubdivision => true,
+        }
+    }
+}
+
+impl Enumeration {
+    /// Check a value is in the enumeration as of a version of the
+    /// specification
+    fn check(self, value: &str, version: AdifVersion) -> core::result::Result<(), Problem> {
+        if self == Enumeration::Mode
+            && !self.contains(value)
+            && let Ok(submode) = Submode::from_field(value)
+        {
+            // Before 3.0.0 there were no submodes, and what are now
+            // submodes were modes
+            if version < AdifVersion::V3_0_0 {
+                return Ok(());
+            }
+            return Err((
+                Severity::Warning,
+                "deprecated-mode",
+                format!(
+                    "'{}' is a submode, import-only as a mode; use MODE {} with SUBMODE {}",
+                    value,
+                    submode.mode(),
+                    submode
+                ),
+            ));
+        }
+        if !self.contains(value) {
+            return Err((
+                Severity::Error,
+                "enumeration",
+                format!("'{}' is not in the {} enumeration", value, self.name()),
+            ));
+        }
+        match VALUES_SINCE
+            .iter()
+            .find(|(e, v, _)| *e == self && v.eq_ignore_ascii_case(value))
+        {
+            Some(&(_, _, since)) if since > version => Err((
+                Severity::Error,
+                "version",
+                format!(
+                    "'{}' was added to the {} enumeration in ADIF {}",
+                    value,
+                    self.name(),
+                    since
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Some enumeration values added after ADIF 2.2.7, with the version that
+/// added them; values not listed are taken to be in every version
+const VALUES_SINCE: &[(Enumeration, &str, AdifVersion)] = &[
+    (Enumeration::Band, "8m", AdifVersion::V3_1_3),
+    (Enumeration::Band, "5m", AdifVersion::V3_1_3),
+    (Enumeration::Mode, "FT8", AdifVersion::V3_0_6),
+];
+
+impl fmt::Display for Enumeration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A field defined by the specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpec {
+    name: &'static str,
+    field_type: FieldType,
+    since: AdifVersion,
+    import_only: Option<AdifVersion>,
+    header: bool,
+}
+
+impl FieldSpec {
+    /// Look up a field by name (case-insensitive)
+    pub fn find(name: &str) -> Option<FieldSpec> {
+        FIELDS
+            .binary_search_by(|spec| {
+                spec.name
+                    .bytes()
+                    .cmp(name.bytes().map(|b| b.to_ascii_uppercase()))
+            })
+            .ok()
+            .map(|i| FIELDS[i])
+    }
+
+    /// Iterate over all fields, in alphabetical order
+    pub fn all() -> impl Iterator<Item = FieldSpec> {
+        FIELDS.iter().copied()
+    }
+
+    /// Get the field's name
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+
+    /// Get the field's data type
+    pub fn field_type(self) -> FieldType {
+        self.field_type
+    }
+
+    /// Check whether this is a header field rather than a QSO field
+    pub fn is_header(self) -> bool {
+        self.header
+    }
+
+    /// Get the version of the specification that added the field, or
+    /// 2.2.7 for fields at least that old
+    pub fn since(self) -> AdifVersion {
+        self.since
+    }
+
+    /// Get the version from which the field may only be imported, not
+    /// exported, if it is deprecated
+    pub fn import_only_since(self) -> Option<AdifVersion> {
+        self.import_only
+    }
+
+    /// Check whether a version of the specification defines the field
+    pub fn is_defined_in(self, version: AdifVersion) -> bool {
+        self.since <= version
+    }
+}
+
+/// Define the field table, sorted by name for binary search; header fields
+/// are marked `header`
+macro_rules! fields {
+    ($(
+        $($header:ident)? $name:literal => $type:ident $(($enumeration:ident))?, $since:ident
+            $(, import_only $import_only:ident)?;
+    )*) => {
+        const FIELDS: &[FieldSpec] = &[$(
+            FieldSpec {
+                name: $name,
+                field_type: fields!(@type $type $(($enumeration))?),
+                since: AdifVersion::$since,
+                import_only: fields!(@import_only $($import_only)?),
+                header: fields!(@header $($header)?),
+            },
+        )*];
+    };
+    (@type Enumeration($enumeration:ident)) => {
+        FieldType::Enumeration(Enumeration::$enumeration)
+    };
+    (@type $type:ident) => {
+        FieldType::$type
+    };
+    (@import_only $version:ident) => {
+        Some(AdifVersion::$version)
+    };
+    (@import_only) => {
+        None
+    };
+    (@header header) => {
+        true
+    };
+    (@header) => {
+        false
+    };
+}
+
+fields! {
+    "ADDRESS" => MultilineString, V2_2_7;
+    "ADDRESS_INTL" => IntlMultilineString, V3_0_0;
+    header "ADIF_VER" => String, V2_2_7;
+    "AGE" => Number, V2_2_7;
+    "ALTITUDE" => Number, V3_1_3;
+    "ANT_AZ" => Number, V2_2_7;
+    "ANT_EL" => Number, V2_2_7;
+    "ANT_PATH" => Enumeration(AntPath), V2_2_7;
+    "ARRL_SECT" => Enumeration(ArrlSection), V2_2_7;
+    "AWARD" => AwardList, V2_2_7, import_only V3_0_0;
+    "AWARD_GRANTED" => SponsoredAwardList, V3_0_0;
+    "AWARD_SUBMITTED" => SponsoredAwardList, V3_0_0;
+    "A_INDEX" => Number, V2_2_7;
+    "BAND" => Enumeration(Band), V2_2_7;
+    "BAND_RX" => Enumeration(Band), V2_2_7;
+    "CALL" => String, V2_2_7;
+    "CHECK" => String, V2_2_7;
+    "CLASS" => String, V2_2_7;
+    "CLUBLOG_QSO_UPLOAD_DATE" => Date, V3_0_4;
+    "CLUBLOG_QSO_UPLOAD_STATUS" => Enumeration(QsoUploadStatus), V3_0_4;
+    "CNTY" => Enumeration(SecondarySubdivision), V2_2_7;
+    "CNTY_ALT" => SecondarySubdivisionListAlt, V3_1_5;
+    "COMMENT" => String, V2_2_7;
+    "COMMENT_INTL" => IntlString, V3_0_0;
+    "CONT" => Enumeration(Continent), V2_2_7;
+    "CONTACTED_OP" => String, V2_2_7;
+    "CONTEST_ID" => String, V2_2_7;
+    "COUNTRY" => String, V2_2_7;
+    "COUNTRY_INTL" => IntlString, V3_0_0;
+    "CQZ" => PositiveInteger, V2_2_7;
+    header "CREATED_TIMESTAMP" => String, V3_0_0;
+    "CREDIT_GRANTED" => CreditList, V2_2_7;
+    "CREDIT_SUBMITTED" => CreditList, V2_2_7;
+    "DARC_DOK" => Enumeration(DarcDok), V3_0_6;
+    "DCL_QSLRDATE" => Date, V3_1_2;
+    "DCL_QSLSDATE" => Date, V3_1_2;
+    "DCL_QSL_RCVD" => Enumeration(QslRcvd), V3_1_2;
+    "DCL_QSL_SENT" => Enumeration(QslSent), V3_1_2;
+    "DISTANCE" => Number, V2_2_7;
+    "DXCC" => Enumeration(Dxcc), V2_2_7;
+    "EMAIL" => String, V2_2_7;
+    "EQSL_AG" => Boolean, V3_1_4;
+    "EQSL_QSLRDATE" => Date, V2_2_7;
+    "EQSL_QSLSDATE" => Date, V2_2_7;
+    "EQSL_QSL_RCVD" => Enumeration(QslRcvd), V2_2_7;
+    "EQSL_QSL_SENT" => Enumeration(QslSent), V2_2_7;
+    "EQ_CALL" => String, V2_2_7;
+    "FISTS" => PositiveInteger, V3_0_0;
+    "FISTS_CC" => PositiveInteger, V3_0_0;
+    "FORCE_INIT" => Boolean, V2_2_7;
+    "FREQ" => Number, V2_2_7;
+    "FREQ_RX" => Number, V2_2_7;
+    "GRIDSQUARE" => GridSquare, V2_2_7;
+    "GRIDSQUARE_EXT" => GridSquareExt, V3_1_3;
+    "GUEST_OP" => String, V2_2_7, import_only V3_0_0;
+    "HAMLOGEU_QSO_UPLOAD_DATE" => Date, V3_1_3;
+    "HAMLOGEU_QSO_UPLOAD_STATUS" => Enumeration(QsoUploadStatus), V3_1_3;
+    "HAMQTH_QSO_UPLOAD_DATE" => Date, V3_1_3;
+    "HAMQTH_QSO_UPLOAD_STATUS" => Enumeration(QsoUploadStatus), V3_1_3;
+    "HRDLOG_QSO_UPLOAD_DATE" => Date, V3_0_4;
+    "HRDLOG_QSO_UPLOAD_STATUS" => Enumeration(QsoUploadStatus), V3_0_4;
+    "IOTA" => IotaRef, V2_2_7;
+    "IOTA_ISLAND_ID" => PositiveInteger, V2_2_7;
+    "ITUZ" => PositiveInteger, V2_2_7;
+    "K_INDEX" => Integer, V2_2_7;
+    "LAT" => Location, V2_2_7;
+    "LON" => Location, V2_2_7;
+    "LOTW_QSLRDATE" => Date, V2_2_7;
+    "LOTW_QSLSDATE" => Date, V2_2_7;
+    "LOTW_QSL_RCVD" => Enumeration(QslRcvd), V2_2_7;
+    "LOTW_QSL_SENT" => Enumeration(QslSent), V2_2_7;
+    "MAX_BURSTS" => Number, V2_2_7;
+    "MODE" => Enumeration(Mode), V2_2_7;
+    "MORSE_KEY_INFO" => String, V3_1_5;
+    "MORSE_KEY_TYPE" => Enumeration(MorseKeyType), V3_1_5;
+    "MS_SHOWER" => String, V2_2_7;
+    "MY_ALTITUDE" => Number, V3_1_3;
+    "MY_ANTENNA" => String, V3_0_0;
+    "MY_ANTENNA_INTL" => IntlString, V3_0_0;
+    "MY_ARRL_SECT" => Enumeration(ArrlSection), V3_1_4;
+    "MY_CITY" => String, V2_2_7;
+    "MY_CITY_INTL" => IntlString, V3_0_0;
+    "MY_CNTY" => Enumeration(SecondarySubdivision), V2_2_7;
+    "MY_CNTY_ALT" => SecondarySubdivisionListAlt, V3_1_5;
+    "MY_COUNTRY" => String, V2_2_7;
+    "MY_COUNTRY_INTL" => IntlString, V3_0_0;
+    "MY_CQ_ZONE" => PositiveInteger, V2_2_7;
+    "MY_DARC_DOK" => Enumeration(DarcDok), V3_0_6;
+    "MY_DXCC" => Enumeration(Dxcc), V2_2_7;
+    "MY_FISTS" => PositiveInteger, V3_0_0;
+    "MY_GRIDSQUARE" => GridSquare, V2_2_7;
+    "MY_GRIDSQUARE_EXT" => GridSquareExt, V3_1_3;
+    "MY_IOTA" => IotaRef, V2_2_7;
+    "MY_IOTA_ISLAND_ID" => PositiveInteger, V2_2_7;
+    "MY_ITU_ZONE" => PositiveInteger, V2_2_7;
+    "MY_LAT" => Location, V2_2_7;
+    "MY_LON" => Location, V2_2_7;
+    "MY_MORSE_KEY_INFO" => String, V3_1_5;
+    "MY_MORSE_KEY_TYPE" => Enumeration(MorseKeyType), V3_1_5;
+    "MY_NAME" => String, V2_2_7;
+    "MY_NAME_INTL" => IntlString, V3_0_0;
+    "MY_POSTAL_CODE" => String, V2_2_7;
+    "MY_POSTAL_CODE_INTL" => IntlString, V3_0_0;
+    "MY_POTA_REF" => PotaRefList, V3_1_4;
+    "MY_RIG" => String, V2_2_7;
+    "MY_RIG_INTL" => IntlString, V3_0_0;
+    "MY_SIG" => String, V2_2_7;
+    "MY_SIG_INFO" => String, V2_2_7;
+    "MY_SIG_INFO_INTL" => IntlString, V3_0_0;
+    "MY_SIG_INTL" => IntlString, V3_0_0;
+    "MY_SOTA_REF" => SotaRef, V3_0_0;
+    "MY_STATE" => Enumeration(PrimarySubdivision), V2_2_7;
+    "MY_STREET" => String, V2_2_7;
+    "MY_STREET_INTL" => IntlString, V3_0_0;
+    "MY_USACA_COUNTIES" => SecondarySubdivisionList, V2_2_7;
+    "MY_VUCC_GRIDS" => GridSquareList, V2_2_7;
+    "MY_WWFF_REF" => WwffRef, V3_1_0;
+    "NAME" => String, V2_2_7;
+    "NAME_INTL" => IntlString, V3_0_0;
+    "NOTES" => MultilineString, V2_2_7;
+    "NOTES_INTL" => IntlMultilineString, V3_0_0;
+    "NR_BURSTS" => Integer, V2_2_7;
+    "NR_PINGS" => Integer, V2_2_7;
+    "OPERATOR" => String, V2_2_7;
+    "OWNER_CALLSIGN" => String, V2_2_7;
+    "PFX" => String, V2_2_7;
+    "POTA_REF" => PotaRefList, V3_1_4;
+    "PRECEDENCE" => String, V2_2_7;
+    header "PROGRAMID" => String, V2_2_7;
+    header "PROGRAMVERSION" => String, V2_2_7;
+    "PROP_MODE" => Enumeration(PropMode), V2_2_7;
+    "PUBLIC_KEY" => String, V2_2_7;
+    "QRZCOM_QSO_DOWNLOAD_DATE" => Date, V3_1_5;
+    "QRZCOM_QSO_DOWNLOAD_STATUS" => Enumeration(QsoDownloadStatus), V3_1_5;
+    "QRZCOM_QSO_UPLOAD_DATE" => Date, V3_0_4;
+    "QRZCOM_QSO_UPLOAD_STATUS" => Enumeration(QsoUploadStatus), V3_0_4;
+    "QSLMSG" => MultilineString, V2_2_7;
+    "QSLMSG_INTL" => IntlMultilineString, V3_0_0;
+    "QSLMSG_RCVD" => MultilineString, V3_1_5;
+    "QSLRDATE" => Date, V2_2_7;
+    "QSLSDATE" => Date, V2_2_7;
+    "QSL_RCVD" => Enumeration(QslRcvd), V2_2_7;
+    "QSL_RCVD_VIA" => Enumeration(QslVia), V2_2_7;
+    "QSL_SENT" => Enumeration(QslSent), V2_2_7;
+    "QSL_SENT_VIA" => Enumeration(QslVia), V2_2_7;
+    "QSL_VIA" => String, V2_2_7;
+    "QSO_COMPLETE" => Enumeration(QsoComplete), V2_2_7;
+    "QSO_DATE" => Date, V2_2_7;
+    "QSO_DATE_OFF" => Date, V2_2_7;
+    "QSO_RANDOM" => Boolean, V2_2_7;
+    "QTH" => String, V2_2_7;
+    "QTH_INTL" => IntlString, V3_0_0;
+    "REGION" => Enumeration(Region), V3_0_0;
+    "RIG" => MultilineString, V2_2_7;
+    "RIG_INTL" => IntlMultilineString, V3_0_0;
+    "RST_RCVD" => String, V2_2_7;
+    "RST_SENT" => String, V2_2_7;
+    "RX_PWR" => Number, V2_2_7;
+    "SAT_MODE" => String, V2_2_7;
+    "SAT_NAME" => String, V2_2_7;
+    "SFI" => Integer, V2_2_7;
+    "SIG" => String, V2_2_7;
+    "SIG_INFO" => String, V2_2_7;
+    "SIG_INFO_INTL" => IntlString, V3_0_0;
+    "SIG_INTL" => IntlString, V3_0_0;
+    "SILENT_KEY" => Boolean, V3_1_0;
+    "SKCC" => String, V3_0_0;
+    "SOTA_REF" => SotaRef, V3_0_0;
+    "SRX" => Integer, V2_2_7;
+    "SRX_STRING" => String, V2_2_7;
+    "STATE" => Enumeration(PrimarySubdivision), V2_2_7;
+    "STATION_CALLSIGN" => String, V2_2_7;
+    "STX" => Integer, V2_2_7;
+    "STX_STRING" => String, V2_2_7;
+    "SUBMODE" => Enumeration(Submode), V3_0_0;
+    "SWL" => Boolean, V2_2_7;
+    "TEN_TEN" => PositiveInteger, V2_2_7;
+    "TIME_OFF" => Time, V2_2_7;
+    "TIME_ON" => Time, V2_2_7;
+    "TX_PWR" => Number, V2_2_7;
+    "UKSMG" => PositiveInteger, V3_0_0;
+    "USACA_COUNTIES" => SecondarySubdivisionList, V2_2_7;
+    "VE_PROV" => String, V2_2_7, import_only V3_0_0;
+    "VUCC_GRIDS" => GridSquareList, V2_2_7;
+    "WEB" => String, V2_2_7;
+    "WWFF_REF" => WwffRef, V3_1_0;
+}
+
+/// How serious a [`ValidationIssue`] is, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// Worth knowing, but not a problem
+    Info,
+    /// Allowed by the specification, but likely a mistake
+    Warning,
+    /// Breaks the specification
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A problem found by [`validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// Index of the record in the log, or `None` for the header
+    pub record: Option<usize>,
+    /// Name of the offending field
+    pub field: String,
+    /// How serious the problem is
+    pub severity: Severity,
+    /// Identifier of the check that found the problem, such as
+    /// `data-type`
+    pub rule: &'static str,
+    /// What is wrong
+    pub message: String,
+    /// Byte range of the field in the source, if it was parsed in fidelity
+    /// mode
+    pub span: Option<Range<usize>>,
+}
+
+impl ValidationIssue {
+    /// Create an issue about a field, taking its span from the field
+    pub(crate) fn new(
+        record: Option<usize>,
+        field: &Field,
+        severity: Severity,
+        rule: &'static str,
+        message: String,
+    ) -> Self {
+        ValidationIssue {
+            record,
+            field: field.name.to_string(),
+            severity,
+            rule,
+            message,
+            span: field.raw.as_ref().map(|raw| raw.span.clone()),
+        }
+    }
+
+    /// Create an issue for a custom [`Rule`] to report about a field
+    ///
+    /// The record and span are filled in when the rule runs.
+    pub fn custom(
+        rule: &'static str,
+        severity: Severity,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        ValidationIssue {
+            record: None,
+            field: field.into(),
+            severity,
+            rule,
+            message: message.into(),
+            span: None,
+        }
+    }
+
+    /// Serialize as part of [`ValidationReport::to_json`]
+    fn json(&self) -> String {
+        let record = self.record.map_or("null".to_string(), |r| r.to_string());
+        let span = self.span.as_ref().map_or("null".to_string(), |span| {
+            format!(r#"{{"start":{},"end":{}}}"#, span.start, span.end)
+        });
+        format!(
+            r#"{{"record":{},"field":{},"severity":"{}","rule":{},"message":{},"span":{}}}"#,
+            record,
+            json_string(&self.field),
+            self.severity,
+            json_string(self.rule),
+            json_string(&self.message),
+            span
+        )
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.record {
+            Some(record) => write!(f, "record {}: ", record)?,
+            None => f.write_str("header: ")?,
+        }
+        write!(
+            f,
+            "{}: {}: {} [{}]",
+            self.severity, self.field, self.message, self.rule
+        )
+    }
+}
+
+/// The problems found by [`validate`], in log order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Problems found, header first and then by record
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Check whether no errors were found; warnings and information are
+    /// allowed
+    pub fn is_valid(&self) -> bool {
+        !self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    /// Iterate over the issues at least as serious as `severity`
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(move |i| i.severity >= severity)
+    }
+
+    /// Iterate over the issues found in one record
+    pub fn for_record(&self, record: usize) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(move |i| i.record == Some(record))
+    }
+
+    /// Count the issues of a severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+
+    /// Serialize to JSON for tools that consume validation results
+    ///
+    /// The schema is stable: `{"valid": .., "errors": .., "warnings": ..,
+    /// "info": .., "issues": [..]}`, where each issue is `{"record": ..,
+    /// "field": .., "severity": .., "rule": .., "message": .., "span": ..}`.
+    /// `record` is the record's index, or `null` for the header; `severity`
+    /// is `"error"`, `"warning"` or `"info"`; `span` is `{"start": ..,
+    /// "end": ..}` in bytes, or `null` unless the log was parsed in
+    /// fidelity mode. New keys may be added, but these won't change.
+    pub fn to_json(&self) -> String {
+        let issues: Vec<String> = self.issues.iter().map(ValidationIssue::json).collect();
+        format!(
+            r#"{{"valid":{},"errors":{},"warnings":{},"info":{},"issues":[{}]}}"#,
+            self.is_valid(),
+            self.count(Severity::Error),
+            self.count(Severity::Warning),
+            self.count(Severity::Info),
+            issues.join(",")
+        )
+    }
+}
+
+/// A check of each record that runs alongside the built-in checks, such as
+/// a club's house rules
+///
+/// Closures taking a `&Record` and returning a `Vec<ValidationIssue>` are
+/// rules. Issues are best made with [`ValidationIssue::custom`].
+pub trait Rule: Send + Sync {
+    /// Check a record, returning what's wrong with it
+    fn check(&self, record: &Record) -> Vec<ValidationIssue>;
+}
+
+impl<F> Rule for F
+where
+    F: Fn(&Record) -> Vec<ValidationIssue> + Send + Sync,
+{
+    fn check(&self, record: &Record) -> Vec<ValidationIssue> {
+        self(record)
+    }
+}
+
+/// The custom rules in [`ValidationOptions::rules`], run in the order they
+/// were added
+#[derive(Clone, Default)]
+pub struct Rules(Vec<Arc<dyn Rule>>);
+
+impl Rules {
+    /// Create an empty set of rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule
+    pub fn add(&mut self, rule: impl Rule + 'static) {
+        self.0.push(Arc::new(rule));
+    }
+
+    /// Get the number of rules
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether there are no rules
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rules({})", self.0.len())
+    }
+}
+
+/// Rules are equal if they are the same rules, in the same order
+impl PartialEq for Rules {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for Rules {}
+
+/// Options for [`validate_with_options`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Version of the specification to check against. Fields it doesn't
+    /// define yet are errors, and fields it makes import-only are warnings.
+    /// Only a few enumeration values, such as the 8m and 5m bands, are
+    /// checked against the version; others are accepted in any version.
+    pub version: AdifVersion,
+    /// How to report fields the specification doesn't define
+    pub unknown_fields: UnknownFields,
+    /// How to report international text, for logs read from or bound for
+    /// ADI
+    pub intl_text: IntlText,
+    /// The current UTC date and time, after which QSOs can't have taken
+    /// place. Defaults to the system clock, or to `None` (no check) without
+    /// the `std` feature.
+    pub now: Option<(AdifDate, AdifTime)>,
+    /// Values to accept in an enumeration besides those the specification
+    /// defines, for logs that extend an enumeration, such as with a mode
+    /// that isn't in the specification yet. Compared ignoring case.
+    pub extra_values: Vec<(Enumeration, String)>,
+    /// Custom checks of each record, run after the built-in ones
+    pub rules: Rules,
+}
+
+impl ValidationOptions {
+    /// Check whether a value has been added to an enumeration
+    fn is_extra_value(&self, enumeration: Enumeration, value: &str) -> bool {
+        self.extra_values
+            .iter()
+            .any(|(e, v)| *e == enumeration && v.eq_ignore_ascii_case(value))
+    }
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        #[cfg(feature = "std")]
+        let now = AdifDate::from_system_time(std::time::SystemTime::now()).ok();
+        #[cfg(not(feature = "std"))]
+        let now = None;
+        Self {
+            version: AdifVersion::LATEST,
+            unknown_fields: UnknownFields::Allow,
+            intl_text: IntlText::Allow,
+            now,
+            extra_values: Vec::new(),
+            rules: Rules::new(),
+        }
+    }
+}
+
+/// Check a log against the fields defined by ADIF 3.1.6
+///
+/// Each field's value is checked against its data type, and each record's
+/// fields against each other. Values that break the specification are
+/// errors, and values that are probably mistakes are warnings.
+pub fn validate(file: &AdifFile) -> ValidationReport {
+    validate_with_options(file, &ValidationOptions::default())
+}
+
+/// Check a log as [`validate`] does, against a chosen version of the
+/// specification and with any custom rules
+pub fn validate_with_options(file: &AdifFile, options: &ValidationOptions) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let userdefs = userdef_names(&file.header.fields);
+    check_fields(&mut report, options, &userdefs, None, &file.header.fields);
+    for (i, record) in file.records.iter().enumerate() {
+        check_fields(&mut report, options, &userdefs, Some(i), &record.fields);
+        check_band_frequency(&mut report, i, record);
+        check_reports(&mut report, i, record);
+        check_callsigns(&mut report, i, record);
+        check_gridsquare_ext(&mut report, i, record);
+        check_dates(&mut report, options, i, record);
+        check_sig_info(&mut report, i, record);
+        check_subdivisions(&mut report, options, i, record);
+        check_consistency(&mut report, i, record);
+        for rule in &options.rules.0 {
+            for mut issue in rule.check(record) {
+                issue.record = Some(i);
+                if issue.span.is_none() {
+                    issue.span = record
+                        .get(&issue.field)
+                        .and_then(|f| f.raw.as_ref())
+                        .map(|raw| raw.span.clone());
+                }
+                report.issues.push(issue);
+            }
+        }
+    }
+    report
+}
+
+/// Check each field the specification defines against its data type
+///
+/// Numbers, dates, times, Booleans, locations and grid squares must be well
+/// formed, and values of enumerations with an embedded table (such as BAND,
+/// MODE and DXCC) must be in it or in
+/// [`ValidationOptions::extra_values`]. Text must not contain control
+/// characters, other than line breaks in multiline fields, nor U+FFFD from
+/// undecodable bytes (see [`scrub_characters`]). A value that looks like it
+/// swallowed the next tag is a warning. Empty values, and fields the
+/// specification doesn't define, aren't checked.
+fn check_fields(
+    report: &mut ValidationReport,
+    options: &ValidationOptions,
+    userdefs: &[String],
+    record: Option<usize>,
+    fields: &[Field],
+) {
+    let version = options.version;
+    for field in fields {
+        let mut issue = |severity, rule, message| {
+            report
+                .issues
+                .push(ValidationIssue::new(record, field, severity, rule, message));
+        };
+        let intl_severity = match options.intl_text {
+            IntlText::Allow => None,
+            IntlText::Warn => Some(Severity::Warning),
+            IntlText::Error => Some(Severity::Error),
+        };
+        if let Some(severity) = intl_severity {
+            if is_intl_field(&field.name, field.data_type) {
+                let message = String::from("international fields aren't allowed in ADI");
+                issue(severity, "intl-text", message);
+            } else if !field.value.is_ascii() {
+                let message = String::from("non-ASCII text isn't allowed in ADI");
+                issue(severity, "intl-text", message);
+            }
+        }
+        if let Some(tag) = find_tag(&field.value) {
+            let message = format!(
+                "'{}' looks like a tag swallowed by a wrong field length",
+                tag
+            );
+            issue(Severity::Warning, "swallowed-tag", message);
+        }
+        let Some(spec) = FieldSpec::find(&field.name) else {
+            let severity = match options.unknown_fields {
+                UnknownFields::Allow => continue,
+                UnknownFields::Warn => Severity::Warning,
+                UnknownFields::Error => Severity::Error,
+            };
+            if !is_known_field(&field.name, userdefs) {
+                let message = String::from("not defined by the specification");
+                issue(severity, "unknown-field", message);
+            }
+            continue;
+        };
+        if !spec.is_defined_in(version) {
+            issue(
+                Severity::Error,
+                "version",
+                format!("added in ADIF {}, after {}", spec.since, version),
+            );
+            continue;
+        }
+        if let Some(since) = spec.import_only
+            && since <= version
+        {
+            issue(
+                Severity::Warning,
+                "deprecated",
+                format!("import-only since ADIF {}", since),
+            );
+        }
+        if let Err((severity, rule, message)) = spec.field_type.check_charset(&field.value) {
+            issue(severity, rule, message);
+            continue;
+        }
+        let value = field.value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        if let FieldType::Enumeration(enumeration) = spec.field_type
+            && options.is_extra_value(enumeration, value)
+        {
+            continue;
+        }
+        if let Err((severity, rule, message)) = spec.field_type.check(value, version) {
+            issue(severity, rule, message);
+        }
+    }
+}
+
+/// Remove the characters that fail the `charset` rule of [`validate`]
+///
+/// Control characters and U+FFFD are removed from the fields the
+/// specification defines, except that tabs and line breaks become spaces
+/// in single line fields. Returns the number of fields changed.
+pub fn scrub_characters(file: &mut AdifFile) -> usize {
+    let mut changed = 0;
+    let headers = core::iter::once(&mut file.header.fields);
+    for fields in headers.chain(file.records.iter_mut().map(|r| &mut r.fields)) {
+        for field in fields {
+            let Some(spec) = FieldSpec::find(&field.name) else {
+                continue;
+            };
+            if field.value.chars().all(|c| spec.field_type.allows_char(c)) {
+                continue;
+            }
+            field.value = spec.field_type.scrub(&field.value);
+            changed += 1;
+        }
+    }
+    changed
+}
+
+/// Check that FREQ is in BAND and FREQ_RX is in BAND_RX
+///
+/// Values that don't parse are left to the data type check.
+fn check_band_frequency(report: &mut ValidationReport, index: usize, record: &Record) {
+    for (freq, band) in [("FREQ", "BAND"), ("FREQ_RX", "BAND_RX")] {
+        let Some(field) = record.get(freq) else {
+            continue;
+        };
+        let (Ok(frequency), Ok(band)) = (
+            field.value.parse::<Frequency>(),
+            record.get_as::<Band>(band),
+        ) else {
+            continue;
+        };
+        if band.contains(frequency) {
+            continue;
+        }
+        let message = match Band::from_frequency(frequency) {
+            Some(actual) => format!("{} MHz is in the {} band, not {}", frequency, actual, band),
+            None => format!("{} MHz is outside the {} band", frequency, band),
+        };
+        report.issues.push(ValidationIssue::new(
+            Some(index),
+            field,
+            Severity::Error,
+            "band-frequency",
+            message,
+        ));
+    }
+}
+
+/// The form of signal report a mode uses
+#[derive(Clone, Copy)]
+enum ReportStyle {
+    /// Readability and strength, such as `59`
+    Rs,
+    /// Readability, strength and tone, such as `599`, optionally followed
+    /// by a letter such as the `C` of `599C`
+    Rst,
+    /// Signal-to-noise ratio in dB, such as `-12` or `+05`
+    Db,
+}
+
+impl ReportStyle {
+    /// Get the report style of a submode, if it differs from its mode's
+    fn of_submode(submode: Submode) -> Option<Self> {
+        match submode {
+            Submode::Fst4
+            | Submode::Fst4W
+            | Submode::Ft4
+            | Submode::Js8
+            | Submode::Jtms
+            | Submode::Q65 => Some(ReportStyle::Db),
+            _ => None,
+        }
+    }
+
+    /// Get the report style of a mode, if it has a usual one
+    fn of_mode(mode: Mode) -> Option<Self> {
+        match mode {
+            Mode::Am | Mode::DigitalVoice | Mode::Fm | Mode::Ssb => Some(ReportStyle::Rs),
+            Mode::Cw | Mode::Psk | Mode::Rtty => Some(ReportStyle::Rst),
+            Mode::Fsk441
+            | Mode::Ft8
+            | Mode::Iscat
+            | Mode::Jt4
+            | Mode::Jt6m
+            | Mode::Jt9
+            | Mode::Jt44
+            | Mode::Jt65
+            | Mode::Msk144
+            | Mode::Qra64
+            | Mode::Wspr => Some(ReportStyle::Db),
+            _ => None,
+        }
+    }
+
+    /// Check whether a report has this style
+    fn matches(self, report: &str) -> bool {
+        let digits = |s: &str, ranges: &[(u8, u8)]| {
+            s.len() == ranges.len()
+                && s.bytes()
+                    .zip(ranges)
+                    .all(|(b, &(low, high))| (low..=high).contains(&b))
+        };
+        match self {
+            ReportStyle::Rs => digits(report, &[(b'1', b'5'), (b'1', b'9')]),
+            ReportStyle::Rst => {
+                let report = report
+                    .strip_suffix(|c: char| c.is_ascii_alphabetic())
+                    .unwrap_or(report);
+                digits(report, &[(b'1', b'5'), (b'1', b'9'), (b'1', b'9')])
+            }
+            ReportStyle::Db => {
+                let db = report.trim_start_matches(['+', '-']);
+                report.len() - db.len() <= 1 && (1..=2).contains(&db.len()) && is_integer(db)
+            }
+        }
+    }
+
+    /// Describe the style with an example
+    fn description(self) -> &'static str {
+        match self {
+            ReportStyle::Rs => "an RS report such as 59",
+            ReportStyle::Rst => "an RST report such as 599",
+            ReportStyle::Db => "a report in dB such as -12",
+        }
+    }
+}
+
+/// Check that RST_SENT and RST_RCVD have the usual form for the mode
+///
+/// Reports that don't suit the mode, such as `59` on CW, are warnings.
+/// Records whose mode has no usual form of report aren't checked.
+fn check_reports(report: &mut ValidationReport, index: usize, record: &Record) {
+    let submode = record.get_as::<Submode>("SUBMODE").ok();
+    let mode = record.get_as::<Mode>("MODE").ok();
+    let style = submode
+        .and_then(|s| Some((s.as_str(), ReportStyle::of_submode(s)?)))
+        .or_else(|| mode.and_then(|m| Some((m.as_str(), ReportStyle::of_mode(m)?))));
+    let Some((name, style)) = style else {
+        return;
+    };
+    for field in ["RST_SENT", "RST_RCVD"]
+        .into_iter()
+        .filter_map(|f| record.get(f))
+    {
+        let value = field.value.trim();
+        if value.is_empty() || style.matches(value) {
+            continue;
+        }
+        report.issues.push(ValidationIssue::new(
+            Some(index),
+            field,
+            Severity::Warning,
+            "rst",
+            format!("'{}' on {} should be {}", value, name, style.description()),
+        ));
+    }
+}
+
+/// Check that CALL, OPERATOR and STATION_CALLSIGN look like call signs
+///
+/// Call signs that aren't well formed are warnings.
+fn check_callsigns(report: &mut ValidationReport, index: usize, record: &Record) {
+    for field in ["CALL", "OPERATOR", "STATION_CALLSIGN"]
+        .into_iter()
+        .filter_map(|f| record.get(f))
+    {
+        let value = field.value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        if let Err(message) = Callsign::from_field(value) {
+            report.issues.push(ValidationIssue::new(
+                Some(index),
+                field,
+                Severity::Warning,
+                "callsign",
+                message,
+            ));
+        }
+    }
+}
+
+/// Check that GRIDSQUARE_EXT and MY_GRIDSQUARE_EXT only extend an 8
+/// character locator
+fn check_gridsquare_ext(report: &mut ValidationReport, index: usize, record: &Record) {
+    for (name, ext) in [
+        ("GRIDSQUARE", "GRIDSQUARE_EXT"),
+        ("MY_GRIDSQUARE", "MY_GRIDSQUARE_EXT"),
+    ] {
+        let Some(field) = record.get(ext).filter(|f| !f.value.trim().is_empty()) else {
+            continue;
+        };
+        let grid = record.get_value(name).unwrap_or_default().trim();
+        if grid.len() != 8 {
+            report.issues.push(ValidationIssue::new(
+                Some(index),
+                field,
+                Severity::Error,
+                "gridsquare-ext",
+                format!("needs an 8 character {}, not '{}'", name, grid),
+            ));
+        }
+    }
+}
+
+/// Check that a QSO doesn't start or end in the future, and doesn't end
+/// before it starts
+///
+/// Without QSO_DATE_OFF, a TIME_OFF before TIME_ON is taken to be on the
+/// next day. Values that don't parse are left to the data type check.
+fn check_dates(
+    report: &mut ValidationReport,
+    options: &ValidationOptions,
+    index: usize,
+    record: &Record,
+) {
+    let mut issue = |name: &str, message: String| {
+        if let Some(field) = record.get(name) {
+            report.issues.push(ValidationIssue::new(
+                Some(index),
+                field,
+                Severity::Error,
+                "date-time",
+                message,
+            ));
+        }
+    };
+    let date = record.get_as::<AdifDate>("QSO_DATE").ok();
+    let time_on = record.get_as::<AdifTime>("TIME_ON").ok();
+    let date_off = record.get_as::<AdifDate>("QSO_DATE_OFF").ok();
+    let time_off = record.get_as::<AdifTime>("TIME_OFF").ok();
+
+    if let Some((today, now)) = options.now {
+        for (name, date, time) in [
+            ("QSO_DATE", date, time_on),
+            ("QSO_DATE_OFF", date_off, time_off),
+        ] {
+            let Some(date) = date else {
+                continue;
+            };
+            if date > today || (date == today && time.is_some_and(|time| time > now)) {
+                issue(name, format!("{} is in the future", date));
+            }
+        }
+    }
+
+    let (Some(date), Some(date_off)) = (date, date_off) else {
+        return;
+    };
+    if date_off < date {
+        issue(
+            "QSO_DATE_OFF",
+            format!("ends on {}, before it starts on {}", date_off, date),
+        );
+    } else if let (Some(time_on), Some(time_off)) = (time_on, time_off)
+        && date_off == date
+        && time_off < time_on
+    {
+        issue(
+            "TIME_OFF",
+            format!("ends at {}, before it starts at {}", time_off, time_on),
+        );
+    }
+}
+
+/// Check that SIG_INFO and MY_SIG_INFO are valid references for the
+/// award programs named by SIG and MY_SIG that have a reference format
+fn check_sig_info(report: &mut ValidationReport, index: usize, record: &Record) {
+    for (sig, info) in [("SIG", "SIG_INFO"), ("MY_SIG", "MY_SIG_INFO")] {
+        let Some(sig) = record.get_value(sig).map(str::trim) else {
+            continue;
+        };
+        let reference = match sig.to_ascii_uppercase().as_str() {
+            "IOTA" => FieldType::IotaRef,
+            "POTA" => FieldType::PotaRefList,
+            "SOTA" => FieldType::SotaRef,
+            "WWFF" => FieldType::WwffRef,
+            _ => continue,
+        };
+        let Some(field) = record.get(info) else {
+            continue;
+        };
+        let value = field.value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        if let Err((severity, _, message)) = reference.check(value, AdifVersion::LATEST) {
+            report.issues.push(ValidationIssue::new(
+                Some(index),
+                field,
+                severity,
+                "sig-info",
+                message,
+            ));
+        }
+    }
+}
+
+/// Check STATE and MY_STATE against the subdivisions of the DXCC and
+/// MY_DXCC entities, for the entities whose subdivisions are known
+fn check_subdivisions(
+    report: &mut ValidationReport,
+    options: &ValidationOptions,
+    index: usize,
+    record: &Record,
+) {
+    for (name, dxcc) in [("STATE", "DXCC"), ("MY_STATE", "MY_DXCC")] {
+        let Some(field) = record.get(name) else {
+            continue;
+        };
+        let value = field.value.trim();
+        let Ok(dxcc) = record.get_as::<u16>(dxcc) else {
+            continue;
+        };
+        if value.is_empty()
+            || Subdivision::for_dxcc(dxcc).next().is_none()
+            || Subdivision::find(dxcc, value).is_some()
+            || options.is_extra_value(Enumeration::PrimarySubdivision, value)
+        {
+            continue;
+        }
+        let entity = Dxcc::from_code(dxcc).map_or("", Dxcc::name);
+        report.issues.push(ValidationIssue::new(
+            Some(index),
+            field,
+            Severity::Error,
+            "enumeration",
+            format!("'{}' is not a subdivision of {}", value, entity),
+        ));
+    }
+}
+
+/// Check that fields filled in from different sources agree: COUNTRY with
+/// DXCC, CQZ and ITUZ with DXCC for the entities whose zones are known, a
+/// SAT_NAME for satellite QSOs, and GRIDSQUARE with LAT and LON (and the
+/// same for the MY_ fields), down to the subsquare
+///
+/// Fields that disagree are warnings.
+fn check_consistency(report: &mut ValidationReport, index: usize, record: &Record) {
+    let mut issue = |field: &Field, message: String| {
+        report.issues.push(ValidationIssue::new(
+            Some(index),
+            field,
+            Severity::Warning,
+            "consistency",
+            message,
+        ));
+    };
+    for prefix in ["", "MY_"] {
+        let name = |name: &str| [prefix, name].concat();
+        let dxcc = record.get_as::<Dxcc>(&name("DXCC")).ok();
+        if let (Some(dxcc), Some(field)) = (dxcc, record.get(&name("COUNTRY")))
+            && let Some(country) = Dxcc::from_name(&field.value)
+            && country.name() != dxcc.name()
+        {
+            let message = format!(
+                "'{}' is not the country of DXCC {} ({})",
+                field.value.trim(),
+                dxcc.code(),
+                dxcc
+            );
+            issue(field, message);
+        }
+        let (cqz, ituz) = match prefix {
+            "" => ("CQZ", "ITUZ"),
+            _ => ("MY_CQ_ZONE", "MY_ITU_ZONE"),
+        };
+        let zones = [
+            ("CQ", cqz, 40, dxcc.and_then(Dxcc::cq_zones)),
+            ("ITU", ituz, 90, dxcc.and_then(Dxcc::itu_zones)),
+        ];
+        for (kind, zone, max, known) in zones {
+            let (Some(known), Some(field)) = (known, record.get(zone)) else {
+                continue;
+            };
+            // Zones out of range are left to the data type check
+            let Ok(value) = field.value.trim().parse::<u8>() else {
+                continue;
+            };
+            if (1..=max).contains(&value) && !known.contains(&value) {
+                let list: Vec<String> = known.iter().map(u8::to_string).collect();
+                let message = format!(
+                    "{} is in {} zones {}, not {}",
+                    dxcc.map_or("", Dxcc::name),
+                    kind,
+                    list.join(", "),
+                    value
+                );
+                issue(field, message);
+            }
+        }
+        let grid = match prefix {
+            "" => record.gridsquare(),
+            _ => record.my_gridsquare(),
+        };
+        let lat = record.get_as::<Coordinate>(&name("LAT"));
+        let lon = record.get_as::<Coordinate>(&name("LON"));
+        if let (Some(Ok(grid)), Ok(lat), Ok(lon)) = (grid, lat, lon) {
+            let precision = grid.precision().min(6);
+            let located = GridSquare::from_latlon(lat.degrees(), lon.degrees(), precision);
+            if let Some(located) = located
+                && !grid.as_str().starts_with(located.as_str())
+                && let Some(field) = record.get(&name("GRIDSQUARE"))
+            {
+                let message = format!("LAT and LON are in {}, not {}", located, grid);
+                issue(field, message);
+            }
+        }
+    }
+    if let Some(field) = record.get("PROP_MODE")
+        && field.value.trim().eq_ignore_ascii_case("SAT")
+        && record
+            .get_value("SAT_NAME")
+            .is_none_or(|s| s.trim().is_empty())
+    {
+        issue(
+            field,
+            String::from("SAT_NAME is missing for a satellite QSO"),
+        );
+    }
+}
+
+/// Get the names of the record fields defined by USERDEFn header fields,
+/// whose values are the name optionally followed by a comma and the
+/// allowed values
+pub(crate) fn userdef_names(header: &[Field]) -> Vec<String> {
+    header
+        .iter()
+        .filter(|f| is_userdef(&f.name))
+        .map(|f| {
+            let name = f.value.split_once(',').map_or(&*f.value, |(name, _)| name);
+            name.trim().to_string()
+        })
+        .collect()
+}
+
+/// Check whether a field is defined by the specification, is an `APP_`
+/// field, or is a USERDEFn header field or a field one of them names
+pub(crate) fn is_known_field(name: &str, userdefs: &[String]) -> bool {
+    FieldSpec::find(name).is_some()
+        || split_app_name(name).is_some()
+        || is_userdef(name)
+        || userdefs.iter().any(|u| u.eq_ignore_ascii_case(name))
+}
+
+/// Check for a `USERDEFn` header field name
+fn is_userdef(name: &str) -> bool {
+    name.get(..7)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("USERDEF"))
+        && is_integer(&name[7..])
+}
+
+/// Check for an IOTA reference: a continent, a hyphen and three digits,
+/// such as `EU-005`
+fn is_iota_ref(value: &str) -> bool {
+    value.split_once('-').is_some_and(|(continent, number)| {
+        Continent::from_field(continent).is_ok() && number.len() == 3 && is_integer(number)
+    })
+}
+
+/// Check for a locator of 2, 4, 6 or 8 characters, as GRIDSQUARE holds
+fn is_locator(value: &str) -> bool {
+    value.len() <= 8 && GridSquare::from_field(value).is_ok()
+}
+
+/// Check for digits only
+fn is_integer(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Check for an ADIF Number: an optional minus sign, digits, and an
+/// optional decimal point, with at least one digit
+pub(crate) fn is_number(value: &str) -> bool {
+    let value = value.strip_prefix('-').unwrap_or(value);
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    (!whole.is_empty() || !fraction.is_empty())
+        && [whole, fraction]
+            .iter()
+            .all(|part| part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_field_table() {
+        assert!(FIELDS.windows(2).all(|w| w[0].name < w[1].name));
+        assert_eq!(FIELDS.len(), 186);
+        let band = FieldSpec::find("band_rx").unwrap();
+        assert_eq!(band.field_type(), FieldType::Enumeration(Enumeration::Band));
+        assert_eq!(band.field_type().to_string(), "Enumeration (Band)");
+        assert!(FieldSpec::find("PROGRAMID").unwrap().is_header());
+        assert!(FieldSpec::find("APP_N1MM_ID").is_none());
+    }
+
+    impl FieldType {
+        fn check_latest(self, value: &str) -> core::result::Result<(), Problem> {
+            self.check(value, AdifVersion::LATEST)
+        }
+    }
+
+    #[test]
+    fn test_check_types() {
+        assert!(FieldType::Number.check_latest("-.5").is_ok());
+        assert!(FieldType::Number.check_latest("14.074").is_ok());
+        assert!(FieldType::Number.check_latest("1e6").is_err());
+        assert!(FieldType::Number.check_latest("-").is_err());
+        assert!(FieldType::Integer.check_latest("-3").is_ok());
+        assert!(FieldType::PositiveInteger.check_latest("0").is_err());
+        assert!(FieldType::PositiveInteger.check_latest("05").is_ok());
+        assert!(FieldType::Boolean.check_latest("y").is_ok());
+        assert!(FieldType::Location.check_latest("N040 30.000").is_ok());
+        assert!(FieldType::String.check_latest("anything").is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        let file = parse_adi(
+            "<ADIF_VER:5>3.1.6<EOH>\
+             <CALL:4>W1AW<QSO_DATE:8>20240230<TIME_ON:4>1260<BAND:3>20m<MODE:3>FT8\
+             <FREQ:6>14,074<CQZ:1>0<DXCC:3>291<APP_X_Y:3>bad<STATE:2>ZZ<NAME:0><EOR>\
+             <CALL:4>K1AB<BAND:3>21M<MODE:3>ft4<QSL_RCVD:1>X<SWL:3>Yes<EOR>",
+        )
+        .unwrap();
+        let report = validate(&file);
+        let issues: Vec<_> = report.issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            [
+                "record 0: error: QSO_DATE: 2024-02-30 is not a valid date [data-type]",
+                "record 0: error: TIME_ON: 12:60:00 is not a valid time [data-type]",
+                "record 0: error: FREQ: '14,074' is not a valid Number [data-type]",
+                "record 0: error: CQZ: '0' is not a valid PositiveInteger [data-type]",
+                "record 0: error: STATE: 'ZZ' is not a subdivision of UNITED STATES OF AMERICA \
+                 [enumeration]",
+                "record 1: error: BAND: '21M' is not in the Band enumeration [enumeration]",
+                "record 1: warning: MODE: 'ft4' is a submode, import-only as a mode; use MODE MFSK with SUBMODE FT4 [deprecated-mode]",
+                "record 1: error: QSL_RCVD: 'X' is not in the QSL_Rcvd enumeration [enumeration]",
+                "record 1: error: SWL: 'Yes' is not a valid Boolean [data-type]",
+            ]
+        );
+        assert!(!report.is_valid());
+        assert!(validate(&parse_adi("<CALL:4>W1AW<EOR>").unwrap()).is_valid());
+    }
+
+    #[test]
+    fn test_report() {
+        let options = crate::ParserOptions {
+            preserve_raw: true,
+            ..Default::default()
+        };
+        let file = crate::parse_adi_with_options(
+            "<CALL:4>W1AW <BAND:3>20M<EOR>\n<CALL:4>K1AB <BAND:2>2M<FREQ:1>x<EOR>",
+            &options,
+        )
+        .unwrap();
+        let report = validate(&file);
+        assert!(!report.is_valid());
+        assert_eq!(report.count(Severity::Error), 1);
+        assert_eq!(report.for_record(0).count(), 0);
+        let issue = report.for_record(1).next().unwrap();
+        assert_eq!(issue.field, "FREQ");
+        assert_eq!(issue.rule, "data-type");
+        assert_eq!(issue.span, Some(53..62));
+        assert_eq!(report.at_least(Severity::Warning).count(), 1);
+
+        let mut report = ValidationReport::default();
+        report.issues.push(ValidationIssue {
+            severity: Severity::Warning,
+            ..issue.clone()
+        });
+        assert!(report.is_valid());
+        assert_eq!(report.at_least(Severity::Error).count(), 0);
+        assert!(Severity::Info < Severity::Warning);
+    }
+
+    #[test]
+    fn test_validate_version() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<MODE:3>FT8<BAND:2>8m<POTA_REF:6>K-0001<GUEST_OP:4>K1AB<EOR>\
+             <CALL:4>W1AW<MODE:5>PSK31<SUBMODE:5>PSK31<EOR>",
+        )
+        .unwrap();
+        let issues = |version| {
+            let options = ValidationOptions {
+                version,
+                ..Default::default()
+            };
+            validate_with_options(&file, &options)
+                .issues
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            issues(AdifVersion::V2_2_7),
+            [
+                "record 0: error: MODE: 'FT8' was added to the Mode enumeration in ADIF 3.0.6 [version]",
+                "record 0: error: BAND: '8m' was added to the Band enumeration in ADIF 3.1.3 [version]",
+                "record 0: error: POTA_REF: added in ADIF 3.1.4, after 2.2.7 [version]",
+                "record 1: error: SUBMODE: added in ADIF 3.0.0, after 2.2.7 [version]",
+            ]
+        );
+        assert_eq!(
+            issues(AdifVersion::LATEST),
+            [
+                "record 0: warning: GUEST_OP: import-only since ADIF 3.0.0 [deprecated]",
+                "record 1: warning: MODE: 'PSK31' is a submode, import-only as a mode; use MODE PSK with SUBMODE PSK31 [deprecated-mode]",
+            ]
+        );
+        let spec = FieldSpec::find("POTA_REF").unwrap();
+        assert!(spec.is_defined_in(AdifVersion::V3_1_4));
+        assert!(!spec.is_defined_in(AdifVersion::V3_1_3));
+        assert_eq!(
+            FieldSpec::find("VE_PROV").unwrap().import_only_since(),
+            Some(AdifVersion::V3_0_0)
+        );
+    }
+
+    #[test]
+    fn test_band_frequency() {
+        let file = parse_adi(
+            "<FREQ:6>14.074<BAND:3>20m<FREQ_RX:6>14.074<BAND_RX:3>40m<EOR>\
+             <FREQ:6>27.185<BAND:3>10m<EOR>\
+             <FREQ:5>7.074<BAND:3>11m<EOR>\
+             <FREQ:5>7.074<EOR>",
+        )
+        .unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .filter(|i| i.rule == "band-frequency")
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 0: error: FREQ_RX: 14.074 MHz is in the 20m band, not 40m [band-frequency]",
+                "record 1: error: FREQ: 27.185 MHz is outside the 10m band [band-frequency]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports() {
+        let file = parse_adi(
+            "<MODE:2>CW<RST_SENT:3>599<RST_RCVD:2>59<EOR>\
+             <MODE:3>SSB<RST_SENT:2>57<RST_RCVD:3>5NN<EOR>\
+             <MODE:3>FT8<RST_SENT:3>-12<RST_RCVD:3>599<EOR>\
+             <MODE:4>MFSK<SUBMODE:3>FT4<RST_SENT:3>+05<RST_RCVD:1>3<EOR>\
+             <MODE:4>MFSK<SUBMODE:6>MFSK16<RST_SENT:2>59<EOR>\
+             <MODE:4>RTTY<RST_SENT:4>599C<RST_RCVD:3>-10<EOR>",
+        )
+        .unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .filter(|i| i.rule == "rst")
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 0: warning: RST_RCVD: '59' on CW should be an RST report such as 599 [rst]",
+                "record 1: warning: RST_RCVD: '5NN' on SSB should be an RS report such as 59 [rst]",
+                "record 2: warning: RST_RCVD: '599' on FT8 should be a report in dB such as -12 [rst]",
+                "record 5: warning: RST_RCVD: '-10' on RTTY should be an RST report such as 599 [rst]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_callsigns() {
+        let file = parse_adi(
+            "<CALL:7>DL/W1AW<OPERATOR:4>K1AB<STATION_CALLSIGN:8>W1AW/QRP<EOR>\
+             <CALL:4>W1WA<OPERATOR:3>599<STATION_CALLSIGN:0><EOR>\
+             <CALL:6>W1AW/4<STATION_CALLSIGN:5>W1 AW<EOR>",
+        )
+        .unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 1: warning: OPERATOR: '599' is not a call sign [callsign]",
+                "record 2: warning: STATION_CALLSIGN: 'W1 AW' is not a call sign [callsign]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gridsquares() {
+        let file = parse_adi(
+            "<GRIDSQUARE:8>JN58td25<GRIDSQUARE_EXT:2>jk<MY_GRIDSQUARE:4>fn31<EOR>\
+             <GRIDSQUARE:5>FN31p<MY_GRIDSQUARE:10>FN31pr00aa<EOR>\
+             <GRIDSQUARE:6>FN31pr<GRIDSQUARE_EXT:2>zz<EOR>\
+             <VUCC_GRIDS:9>FN31,FN41<MY_VUCC_GRIDS:13>FN31, FN41,ZZ<EOR>",
+        )
+        .unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 1: error: GRIDSQUARE: 'FN31p' is not a valid GridSquare [data-type]",
+                "record 1: error: MY_GRIDSQUARE: 'FN31pr00aa' is not a valid GridSquare [data-type]",
+                "record 2: error: GRIDSQUARE_EXT: 'zz' is not a valid GridSquareExt [data-type]",
+                "record 2: error: GRIDSQUARE_EXT: needs an 8 character GRIDSQUARE, not 'FN31pr' \
+                 [gridsquare-ext]",
+                "record 3: error: MY_VUCC_GRIDS: 'ZZ' in 'FN31, FN41,ZZ' is not a valid \
+                 GridSquare [data-type]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dates() {
+        let file = parse_adi(
+            "<QSO_DATE:8>20240115<TIME_ON:4>2350<TIME_OFF:4>0010<EOR>\
+             <QSO_DATE:8>20240115<TIME_ON:4>2350<QSO_DATE_OFF:8>20240116<TIME_OFF:4>0010<EOR>\
+             <QSO_DATE:8>20240115<TIME_ON:4>2350<QSO_DATE_OFF:8>20240115<TIME_OFF:4>0010<EOR>\
+             <QSO_DATE:8>20240115<QSO_DATE_OFF:8>20240114<EOR>\
+             <QSO_DATE:8>20240601<TIME_ON:4>1201<EOR>\
+             <QSO_DATE:8>20240601<TIME_ON:4>1200<QSO_DATE_OFF:8>20240602<EOR>\
+             <QSO_DATE:8>20240230<EOR>",
+        )
+        .unwrap();
+        let options = ValidationOptions {
+            now: Some((
+                AdifDate::new(2024, 6, 1).unwrap(),
+                AdifTime::from_hm(12, 0).unwrap(),
+            )),
+            ..Default::default()
+        };
+        let issues: Vec<_> = validate_with_options(&file, &options)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 2: error: TIME_OFF: ends at 0010, before it starts at 2350 [date-time]",
+                "record 3: error: QSO_DATE_OFF: ends on 20240114, before it starts on 20240115 \
+                 [date-time]",
+                "record 4: error: QSO_DATE: 20240601 is in the future [date-time]",
+                "record 5: error: QSO_DATE_OFF: 20240602 is in the future [date-time]",
+                "record 6: error: QSO_DATE: 2024-02-30 is not a valid date [data-type]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_references() {
+        assert!(is_iota_ref("EU-005"));
+        assert!(is_iota_ref("na-001"));
+        assert!(!is_iota_ref("XX-001"));
+        assert!(!is_iota_ref("EU-05"));
+        assert!(is_summit_ref("W7A/NM-001"));
+        assert!(is_summit_ref("G/LD-003"));
+        assert!(!is_summit_ref("W7A/NM-01"));
+        assert!(!is_summit_ref("W7A-NM-001"));
+
+        let file = parse_adi(
+            "<IOTA:6>EU-005<SOTA_REF:10>W7A/NM-001<POTA_REF:14>US-0001,K-1234<WWFF_REF:8>KFF-0001<EOR>\
+             <IOTA:5>EU005<SOTA_REF:6>W7A/NM<MY_POTA_REF:12>US-0001,K-12<WWFF_REF:7>KF-0001<EOR>\
+             <SIG:4>pota<SIG_INFO:13>US-0001@US-ME<MY_SIG:4>SOTA<MY_SIG_INFO:5>G/LD3<EOR>\
+             <SIG:4>DOK<SIG_INFO:3>A01<EOR>",
+        )
+        .unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 1: error: IOTA: 'EU005' is not a valid IOTARefNo [data-type]",
+                "record 1: error: SOTA_REF: 'W7A/NM' is not a valid SOTARef [data-type]",
+                "record 1: error: MY_POTA_REF: 'K-12' in 'US-0001,K-12' is not a valid POTARef \
+                 [data-type]",
+                "record 1: error: WWFF_REF: 'KF-0001' is not a valid WWFFRef [data-type]",
+                "record 2: error: MY_SIG_INFO: 'G/LD3' is not a valid SOTARef [sig-info]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enumerations() {
+        let file = parse_adi(
+            "<MODE:4>FT17<BAND:3>11m<CONT:2>XX<PROP_MODE:2>ES<EOR>\
+             <STATE:2>ct<DXCC:3>291<MY_STATE:2>ZZ<MY_DXCC:1>1<EOR>\
+             <STATE:2>ZZ<DXCC:3>223<EOR>",
+        )
+        .unwrap();
+        let issues = |options: &ValidationOptions| {
+            validate_with_options(&file, options)
+                .issues
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            issues(&ValidationOptions::default()),
+            [
+                "record 0: error: MODE: 'FT17' is not in the Mode enumeration [enumeration]",
+                "record 0: error: BAND: '11m' is not in the Band enumeration [enumeration]",
+                "record 0: error: CONT: 'XX' is not in the Continent enumeration [enumeration]",
+                "record 1: error: MY_STATE: 'ZZ' is not a subdivision of CANADA [enumeration]",
+            ]
+        );
+        let options = ValidationOptions {
+            extra_values: vec![
+                (Enumeration::Mode, "ft17".into()),
+                (Enumeration::Band, "11m".into()),
+                (Enumeration::PrimarySubdivision, "ZZ".into()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            issues(&options),
+            ["record 0: error: CONT: 'XX' is not in the Continent enumeration [enumeration]"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        let file = parse_adi(
+            "<USERDEF1:3:N>EPC<USERDEF2:19:E>SweaterSize,{S,M,L}<MYHEADER:1>x<EOH>\
+             <CALL:4>W1AW<EPC:3>123<SWEATERSIZE:1>M<APP_N1MM_ID:1>1<MY_FIELD:1>y<EOR>",
+        )
+        .unwrap();
+        assert!(validate(&file).is_valid());
+        let options = ValidationOptions {
+            unknown_fields: UnknownFields::Warn,
+            ..Default::default()
+        };
+        let issues: Vec<_> = validate_with_options(&file, &options)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "header: warning: MYHEADER: not defined by the specification [unknown-field]",
+                "record 0: warning: MY_FIELD: not defined by the specification [unknown-field]",
+            ]
+        );
+        let options = ValidationOptions {
+            unknown_fields: UnknownFields::Error,
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_with_options(&file, &options).count(Severity::Error),
+            2
+        );
+    }
+
+    #[test]
+    fn test_intl_text() {
+        let file = parse_adi("<CALL:4>W1AW<NAME:5>José<NAME_INTL:5>José<QTH:3>Rio<EOR>").unwrap();
+        assert!(validate(&file).is_valid());
+        let options = ValidationOptions {
+            intl_text: IntlText::Warn,
+            ..Default::default()
+        };
+        let issues: Vec<_> = validate_with_options(&file, &options)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 0: warning: NAME: non-ASCII text isn't allowed in ADI [intl-text]",
+                "record 0: warning: NAME_INTL: international fields aren't allowed in ADI \
+                 [intl-text]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_charset() {
+        let mut file = parse_adi(
+            "<CALL:4>W1AW<NAME:6>Jo\te\r\n<NOTES:10>Good\r\nQSO\x07             <COMMENT:5>ok\u{FFFD}<QTH:3>Rio<EOR>",
+        )
+        .unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 0: error: NAME: control character U+0009 isn't allowed in a String \
+                 [charset]",
+                "record 0: error: NOTES: control character U+0007 isn't allowed in a \
+                 MultilineString [charset]",
+                "record 0: error: COMMENT: U+FFFD (an undecodable byte) isn't allowed in a \
+                 String [charset]",
+            ]
+        );
+
+        assert_eq!(scrub_characters(&mut file), 3);
+        let record = &file.records[0];
+        assert_eq!(record.get_value("NAME"), Some("Jo e "));
+        assert_eq!(record.get_value("NOTES"), Some("Good\r\nQSO"));
+        assert_eq!(record.get_value("COMMENT"), Some("ok"));
+        assert!(validate(&file).is_valid());
+        assert_eq!(scrub_characters(&mut file), 0);
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<STATION_CALLSIGN:4>W1AW<EOR>\
+             <CALL:4>K1AB<STATION_CALLSIGN:4>N0CA<EOR>",
+        )
+        .unwrap();
+        let mut options = ValidationOptions::default();
+        options
+            .rules
+            .add(|record: &Record| match record.station_callsign() {
+                Some("W1AW") => Vec::new(),
+                _ => vec![ValidationIssue::custom(
+                    "club-call",
+                    Severity::Error,
+                    "STATION_CALLSIGN",
+                    "must be the club call W1AW",
+                )],
+            });
+        assert_eq!(options.rules.len(), 1);
+        assert_ne!(options, ValidationOptions::default());
+
+        let issues: Vec<_> = validate_with_options(&file, &options)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            ["record 1: error: STATION_CALLSIGN: must be the club call W1AW [club-call]"]
+        );
+    }
+
+    #[test]
+    fn test_consistency() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<DXCC:3>291<COUNTRY:6>Canada<CQZ:2>14<ITUZ:1>8<GRIDSQUARE:6>FN31pr\
+             <LAT:11>N041 42.840<LON:11>W072 43.620<EOR>\
+             <CALL:4>K1AB<DXCC:3>291<COUNTRY:24>United States of America<CQZ:1>5\
+             <MY_GRIDSQUARE:4>FN42<MY_LAT:11>N041 42.840<MY_LON:11>W072 43.620\
+             <PROP_MODE:3>SAT<EOR>",
+        )
+        .unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 0: warning: COUNTRY: 'Canada' is not the country of DXCC 291 \
+                 (UNITED STATES OF AMERICA) [consistency]",
+                "record 0: warning: CQZ: UNITED STATES OF AMERICA is in CQ zones 3, 4, 5, \
+                 not 14 [consistency]",
+                "record 1: warning: MY_GRIDSQUARE: LAT and LON are in FN31, not FN42 \
+                 [consistency]",
+                "record 1: warning: PROP_MODE: SAT_NAME is missing for a satellite QSO \
+                 [consistency]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_swallowed_tag() {
+        let file = parse_adi("<CALL:6>W1AW<B<AND:3>20m<COMMENT:2><3<EOR>").unwrap();
+        let issues: Vec<_> = validate(&file)
+            .issues
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "record 0: warning: CALL: '<B' looks like a tag swallowed by a wrong field \
+                 length [swallowed-tag]",
+                "record 0: warning: CALL: 'W1AW<B' is not a call sign [callsign]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_json() {
+        let options = crate::ParserOptions {
+            preserve_raw: true,
+            ..Default::default()
+        };
+        let input = "<CALL:4>W1AW<BAND:3>21m<EOR>";
+        let file = crate::parse_adi_with_options(input, &options).unwrap();
+        assert_eq!(
+            validate(&file).to_json(),
+            r#"{"valid":false,"errors":1,"warnings":0,"info":0,"issues":[{"record":0,"field":"BAND","severity":"error","rule":"enumeration","message":"'21m' is not in the Band enumeration","span":{"start":12,"end":23}}]}"#
+        );
+        let report = validate(&parse_adi("<CALL:4>W1AW<EOR>").unwrap());
+        assert_eq!(
+            report.to_json(),
+            r#"{"valid":true,"errors":0,"warnings":0,"info":0,"issues":[]}"#
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::{AdifError, Result};
+use crate::types::AdifHeader;
+use alloc::format;
+use core::fmt;
+use core::str::FromStr;
+
+/// A version of the ADIF specification, such as `3.1.6`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AdifVersion {
+    major: u8,
+    minor: u8,
+    patch: u8,
+}
+
+impl AdifVersion {
+    /// ADIF 2.2.7, the last version before ADX and submodes
+    pub const V2_2_7: AdifVersion = AdifVersion::new(2, 2, 7);
+    /// ADIF 3.0.0
+    pub const V3_0_0: AdifVersion = AdifVersion::new(3, 0, 0);
+    /// ADIF 3.0.4
+    pub const V3_0_4: AdifVersion = AdifVersion::new(3, 0, 4);
+    /// ADIF 3.0.6
+    pub const V3_0_6: AdifVersion = AdifVersion::new(3, 0, 6);
+    /// ADIF 3.1.0
+    pub const V3_1_0: AdifVersion = AdifVersion::new(3, 1, 0);
+    /// ADIF 3.1.2
+    pub const V3_1_2: AdifVersion = AdifVersion::new(3, 1, 2);
+    /// ADIF 3.1.3
+    pub const V3_1_3: AdifVersion = AdifVersion::new(3, 1, 3);
+    /// ADIF 3.1.4
+    pub const V3_1_4: AdifVersion = AdifVersion::new(3, 1, 4);
+    /// ADIF 3.1.5
+    pub const V3_1_5: AdifVersion = AdifVersion::new(3, 1, 5);
+    /// ADIF 3.1.6
+    pub const V3_1_6: AdifVersion = AdifVersion::new(3, 1, 6);
+    /// The latest version this library supports
+    pub const LATEST: AdifVersion = AdifVersion::V3_1_6;
+
+    /// Create a version from its parts
+    pub const fn new(major: u8, minor: u8, patch: u8) -> Self {
+        AdifVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Get the major version
+    pub fn major(self) -> u8 {
+        self.major
+    }
+
+    /// Get the minor version
+    pub fn minor(self) -> u8 {
+        self.minor
+    }
+
+    /// Get the patch version
+    pub fn patch(self) -> u8 {
+        self.patch
+    }
+}
+
+impl FromStr for AdifVersion {
+    type Err = AdifError;
+
+    /// Parse a version such as `3.1.6`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let invalid = || AdifError::InvalidField {
+            field: "AdifVersion".into(),
+            message: format!("'{}' is not an ADIF version", s),
+        };
+        let mut parts = s.split('.').map(|part| {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            part.parse::<u8>().map_err(|_| invalid())
+        });
+        let version = AdifVersion::new(
+            parts.next().ok_or_else(invalid)??,
+            parts.next().ok_or_else(invalid)??,
+            parts.next().ok_or_else(invalid)??,
+        );
+        match parts.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(version),
+        }
+    }
+}
+
+impl fmt::Display for AdifVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl AdifHeader {
+    /// Get the ADIF version the file claims to follow, or `None` if
+    /// ADIF_VER is missing or empty
+    pub fn version(&self) -> Option<Result<AdifVersion>> {
+        let version = self.adif_version.as_deref()?.trim();
+        (!version.is_empty()).then(|| version.parse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse_version() {
+        let version: AdifVersion = "3.1.6".parse().unwrap();
+        assert_eq!(version, AdifVersion::V3_1_6);
+        assert_eq!(version.to_string(), "3.1.6");
+        assert!(AdifVersion::V2_2_7 < AdifVersion::V3_0_0);
+        assert!("3.10.0".parse::<AdifVersion>().unwrap() > AdifVersion::LATEST);
+        for bad in ["3.1", "3.1.6.1", "3..6", "v3.1.6", ""] {
+            assert!(bad.parse::<AdifVersion>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_header_version() {
+        let file = parse_adi("<ADIF_VER:5>3.0.4<EOH><CALL:4>W1AW<EOR>").unwrap();
+        assert_eq!(file.header.version().unwrap().unwrap(), AdifVersion::V3_0_4);
+        assert!(
+            parse_adi("<CALL:4>W1AW<EOR>")
+                .unwrap()
+                .header
+                .version()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Something odd in ADI data that didn't stop it from parsing
+///
+/// Returned by [`parse_adi_with_warnings`](crate::parse_adi_with_warnings).
+/// Positions are byte offsets in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Text in the records outside any tag, which was skipped
+    StrayText { position: usize, text: String },
+    /// A tag without a length other than `<EOH>`, `<EOR>` or `<EOF>`,
+    /// which was kept as a field with an empty value
+    MarkerTag { position: usize, name: String },
+    /// A data specifier without a field name, which was skipped along
+    /// with its value
+    EmptyFieldName { position: usize },
+    /// A value containing what looks like the next tag, as left by a
+    /// declared length that is too long
+    SwallowedTag {
+        position: usize,
+        name: String,
+        tag: String,
+    },
+    /// A field the specification doesn't define, when
+    /// [`UnknownFields::Warn`](crate::UnknownFields::Warn) is set
+    UnknownField { position: usize, name: String },
+    /// International text, when [`IntlText::Warn`](crate::IntlText::Warn)
+    /// is set
+    IntlText { position: usize, name: String },
+}
+
+impl Warning {
+    /// Get the position in the input the warning is about
+    pub fn position(&self) -> usize {
+        match *self {
+            Warning::StrayText { position, .. }
+            | Warning::MarkerTag { position, .. }
+            | Warning::EmptyFieldName { position }
+            | Warning::SwallowedTag { position, .. }
+            | Warning::UnknownField { position, .. }
+            | Warning::IntlText { position, .. } => position,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "position {}: ", self.position())?;
+        match self {
+            Warning::StrayText { text, .. } => write!(f, "skipped text '{}'", text),
+            Warning::MarkerTag { name, .. } => write!(f, "unknown tag <{}>", name),
+            Warning::EmptyFieldName { .. } => f.write_str("skipped field without a name"),
+            Warning::SwallowedTag { name, tag, .. } => {
+                write!(f, "{} contains '{}', which looks like a tag", name, tag)
+            }
+            Warning::UnknownField { name, .. } => write!(f, "unknown field {}", name),
+            Warning::IntlText { name, .. } => {
+                write!(f, "international text in field {}", name)
+            }
+        }
+    }
+}
@@ -0,0 +1,606 @@
+use crate::error::{AdifError, Result};
+use crate::field_name::FieldName;
+use crate::options::{is_intl_field, is_intl_text};
+use crate::types::{AdifFile, AdifHeader, DataType, Field, RawField, Record};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+/// Preamble emitted when a file has header fields but no preamble text.
+///
+/// The ADIF spec treats a file whose first character is `<` as having no
+/// header, so some text must precede the first header field.
+const DEFAULT_PREAMBLE: &str = "Generated by adif_parser";
+
+/// How to write international text, which ADI doesn't allow
+///
+/// See [`IntlText`](crate::IntlText) for what counts as international text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IntlOutput {
+    /// Write it as is, in UTF-8
+    #[default]
+    Keep,
+    /// Fail with [`AdifError::InvalidField`]
+    Error,
+    /// Write `_INTL` fields as their ASCII counterparts (`NAME` for
+    /// `NAME_INTL`) unless there is one already, replace accented letters
+    /// and typographic punctuation with ASCII lookalikes, and drop other
+    /// non-ASCII characters
+    Transliterate,
+    /// Drop `_INTL` fields and non-ASCII characters
+    Strip,
+}
+
+impl IntlOutput {
+    /// Apply this to the fields of a record or header, giving the fields
+    /// to write instead if any change
+    fn apply(self, fields: &[Field]) -> Result<Option<Vec<Field>>> {
+        let intl = |f: &Field| is_intl_text(&f.name, f.data_type, &f.value);
+        if self == IntlOutput::Keep || !fields.iter().any(intl) {
+            return Ok(None);
+        }
+        let mut output = Vec::with_capacity(fields.len());
+        for field in fields {
+            if !intl(field) {
+                output.push(field.clone());
+                continue;
+            }
+            let name = match field.name.len().checked_sub(5) {
+                Some(end) if is_intl_field(&field.name, DataType::Unspecified) => {
+                    FieldName::from(&field.name[..end])
+                }
+                _ => field.name.clone(),
+            };
+            let value = match self {
+                IntlOutput::Error => {
+                    return Err(AdifError::InvalidField {
+                        field: field.name.to_string(),
+                        message: "international text can't be written to ADI".to_string(),
+                    });
+                }
+                IntlOutput::Strip if is_intl_field(&field.name, field.data_type) => continue,
+                IntlOutput::Strip => field.value.chars().filter(char::is_ascii).collect(),
+                _ if name != field.name && fields.iter().any(|f| f.name == name) => continue,
+                _ => transliterate(&field.value),
+            };
+            let data_type = match field.data_type {
+                DataType::IntlString => DataType::String,
+                DataType::IntlMultilineString => DataType::MultilineString,
+                other => other,
+            };
+            output.push(Field {
+                name,
+                data_type,
+                value,
+                raw: field.raw.clone(),
+            });
+        }
+        Ok(Some(output))
+    }
+}
+
+/// Replace non-ASCII characters with ASCII lookalikes, dropping those
+/// without one
+fn transliterate(value: &str) -> String {
+    let mut s = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii() {
+            s.push(c);
+            continue;
+        }
+        s.push_str(match c {
+            'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+            'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+            'Æ' => "AE",
+            'æ' => "ae",
+            'Ç' | 'Ć' | 'Č' => "C",
+            'ç' | 'ć' | 'č' => "c",
+            'Ð' | 'Ď' | 'Đ' => "D",
+            'ð' | 'ď' | 'đ' => "d",
+            'È'..='Ë' | 'Ē' | 'Ę' | 'Ě' => "E",
+            'è'..='ë' | 'ē' | 'ę' | 'ě' => "e",
+            'Ğ' => "G",
+            'ğ' => "g",
+            'Ì'..='Ï' | 'Ī' | 'İ' => "I",
+            'ì'..='ï' | 'ī' | 'ı' => "i",
+            'Ł' => "L",
+            'ł' => "l",
+            'Ñ' | 'Ń' | 'Ň' => "N",
+            'ñ' | 'ń' | 'ň' => "n",
+            'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ő' => "O",
+            'ò'..='ö' | 'ø' | 'ō' | 'ő' => "o",
+            'Œ' => "OE",
+            'œ' => "oe",
+            'Ř' => "R",
+            'ř' => "r",
+            'Ś' | 'Ş' | 'Š' => "S",
+            'ś' | 'ş' | 'š' => "s",
+            'ß' => "ss",
+            'Ţ' | 'Ť' => "T",
+            'ţ' | 'ť' => "t",
+            'Þ' => "TH",
+            'þ' => "th",
+            'Ù'..='Ü' | 'Ū' | 'Ů' | 'Ű' => "U",
+            'ù'..='ü' | 'ū' | 'ů' | 'ű' => "u",
+            'Ý' | 'Ÿ' => "Y",
+            'ý' | 'ÿ' => "y",
+            'Ź' | 'Ż' | 'Ž' => "Z",
+            'ź' | 'ż' | 'ž' => "z",
+            '‘' | '’' | '′' => "'",
+            '“' | '”' | '″' => "\"",
+            '–' | '—' => "-",
+            '…' => "...",
+            '\u{A0}' => " ",
+            _ => "",
+        });
+    }
+    s
+}
+
+/// How to write values containing `<`
+///
+/// Values are framed by their length, so ADI allows `<` in them, but
+/// programs that look for the next tag instead misread such values.
+/// [`validate`](crate::validate) warns about values that look like they
+/// swallowed a tag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LessThan {
+    /// Write it as is
+    #[default]
+    Keep,
+    /// Fail with [`AdifError::InvalidField`]
+    Error,
+    /// Write another character in its place
+    Substitute(char),
+}
+
+impl LessThan {
+    /// Apply this to the fields of a record or header, giving the fields
+    /// to write instead if any change
+    fn apply(self, fields: &[Field]) -> Result<Option<Vec<Field>>> {
+        let has_less_than = |f: &Field| f.value.contains('<');
+        if self == LessThan::Keep || !fields.iter().any(has_less_than) {
+            return Ok(None);
+        }
+        let mut output = fields.to_vec();
+        for field in output.iter_mut().filter(|f| has_less_than(f)) {
+            match self {
+                LessThan::Substitute(c) => {
+                    field.value = field.value.replace('<', c.encode_utf8(&mut [0; 4]));
+                }
+                _ => {
+                    return Err(AdifError::InvalidField {
+                        field: field.name.to_string(),
+                        message: format!("'<' in '{}' is misread by some programs", field.value),
+                    });
+                }
+            }
+        }
+        Ok(Some(output))
+    }
+}
+
+/// Options controlling how ADI is written
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// What to do with international text
+    pub intl_text: IntlOutput,
+    /// What to do with `<` in values
+    pub less_than: LessThan,
+}
+
+impl WriteOptions {
+    /// Apply these to the fields of a record or header, giving the fields
+    /// to write instead if any change
+    fn apply(&self, fields: &[Field]) -> Result<Option<Vec<Field>>> {
+        let intl = self.intl_text.apply(fields)?;
+        let less_than = self.less_than.apply(intl.as_deref().unwrap_or(fields))?;
+        Ok(less_than.or(intl))
+    }
+
+    fn header<'h>(&self, header: &'h AdifHeader) -> Result<Cow<'h, AdifHeader>> {
+        Ok(match self.apply(&header.fields)? {
+            Some(fields) => Cow::Owned(AdifHeader {
+                fields,
+                ..header.clone()
+            }),
+            None => Cow::Borrowed(header),
+        })
+    }
+
+    fn record<'r>(&self, record: &'r Record) -> Result<Cow<'r, Record>> {
+        Ok(match self.apply(&record.fields)? {
+            Some(fields) => Cow::Owned(Record {
+                fields,
+                terminator: record.terminator.clone(),
+            }),
+            None => Cow::Borrowed(record),
+        })
+    }
+}
+
+/// Serialize an AdifFile to an ADI format string
+///
+/// Files parsed in fidelity mode (see [`ParserOptions::preserve_raw`]) are
+/// reproduced byte-for-byte, except for fields that have since changed.
+///
+/// [`ParserOptions::preserve_raw`]: crate::ParserOptions::preserve_raw
+pub fn write_adi(file: &AdifFile) -> String {
+    match write_adi_with_options(file, &WriteOptions::default()) {
+        Ok(s) => s,
+        Err(_) => unreachable!("writing with the default options can't fail"),
+    }
+}
+
+/// Serialize an AdifFile to an ADI format string with custom options
+///
+/// Fails only if international text or `<` in values are errors and there
+/// are some.
+pub fn write_adi_with_options(file: &AdifFile, options: &WriteOptions) -> Result<String> {
+    let mut s = String::new();
+    let header = &file.header;
+
+    if !header.fields.is_empty() || !header.preamble.is_empty() || header.terminator.is_some() {
+        let header = options.header(header)?;
+        push_header(&mut s, &header);
+    }
+    for record in &file.records {
+        let record = options.record(record)?;
+        push_record(&mut s, &record);
+    }
+    if let Some(trailer) = &file.trailer {
+        s.push_str(trailer);
+    }
+    Ok(s)
+}
+
+/// Append a header, terminated by `<EOH>`
+fn push_header(s: &mut String, header: &AdifHeader) {
+    // Reproduce a header parsed in fidelity mode exactly
+    if let Some(terminator) = &header.terminator {
+        s.push_str(&header.preamble);
+        for field in &header.fields {
+            s.push_str(&preserved_field(field));
+        }
+        s.push_str(terminator);
+        return;
+    }
+
+    let preamble = if header.preamble.is_empty() {
+        DEFAULT_PREAMBLE
+    } else {
+        header.preamble.as_str()
+    };
+    s.push_str(preamble);
+    if !preamble.ends_with('\n') {
+        s.push('\n');
+    }
+    for field in &header.fields {
+        s.push_str(&field.to_adi_string());
+        s.push('\n');
+    }
+    s.push_str("<EOH>\n");
+}
+
+/// Append a record, terminated by `<EOR>`
+fn push_record(s: &mut String, record: &Record) {
+    match &record.terminator {
+        Some(terminator) => {
+            for field in &record.fields {
+                s.push_str(&preserved_field(field));
+            }
+            s.push_str(terminator);
+        }
+        None => s.push_str(&record.to_adi_string()),
+    }
+}
+
+/// Streaming ADI writer that emits a header and records incrementally
+#[cfg(feature = "std")]
+pub struct AdiWriter<W: Write> {
+    writer: W,
+    options: WriteOptions,
+    records_written: usize,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> AdiWriter<W> {
+    /// Create a writer over an output sink
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, WriteOptions::default())
+    }
+
+    /// Create a writer using the given options
+    pub fn with_options(writer: W, options: WriteOptions) -> Self {
+        Self {
+            writer,
+            options,
+            records_written: 0,
+        }
+    }
+
+    /// Write the file header, terminated by `<EOH>`.
+    ///
+    /// Must be called before any records are written.
+    pub fn write_header(&mut self, header: &AdifHeader) -> Result<()> {
+        if self.records_written > 0 {
+            return Err(AdifError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "header must be written before records",
+            )));
+        }
+
+        let mut s = String::new();
+        let header = self.options.header(header)?;
+        push_header(&mut s, &header);
+        self.writer.write_all(s.as_bytes())?;
+        Ok(())
+    }
+
+    /// Write a single record, terminated by `<EOR>`
+    pub fn write_record(&mut self, record: &Record) -> Result<()> {
+        let mut s = String::new();
+        let record = self.options.record(record)?;
+        push_record(&mut s, &record);
+        self.writer.write_all(s.as_bytes())?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Get the number of records written so far
+    pub fn records_written(&self) -> usize {
+        self.records_written
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Unwrap this writer, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Serialize a field using its original source text where possible.
+///
+/// The original data specifier is kept as long as it still describes the
+/// field; otherwise a fresh one is generated after the original leading text.
+fn preserved_field(field: &Field) -> String {
+    match &field.raw {
+        Some(raw) if specifier_matches(raw, field) => {
+            format!("{}{}{}", raw.leading, raw.specifier, field.value)
+        }
+        Some(raw) => format!("{}{}", raw.leading, field.to_adi_string()),
+        None => field.to_adi_string(),
+    }
+}
+
+/// Check whether a raw data specifier still matches a field's name, length and type
+fn specifier_matches(raw: &RawField, field: &Field) -> bool {
+    let Some(inner) = raw
+        .specifier
+        .strip_prefix('<')
+        .and_then(|s| s.strip_suffix('>'))
+    else {
+        return false;
+    };
+
+    let mut parts = inner.splitn(3, ':');
+    let name = parts.next().unwrap_or_default();
+    let length = match parts.next() {
+        Some(length) => length.parse::<usize>().ok(),
+        None => Some(0),
+    };
+    let data_type = match parts.next() {
+        Some(t) => t
+            .chars()
+            .next()
+            .and_then(DataType::from_char)
+            .unwrap_or(DataType::Unspecified),
+        None => DataType::Unspecified,
+    };
+
+    name.eq_ignore_ascii_case(&field.name)
+        && length == Some(field.value.len())
+        && data_type == field.data_type
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use crate::types::{DataType, Field};
+
+    #[test]
+    fn test_write_records_only() {
+        let mut file = AdifFile::new();
+        let mut rec = Record::new();
+        rec.add_field(Field::new("CALL", "W1AW"));
+        file.records.push(rec);
+
+        assert_eq!(write_adi(&file), "<CALL:4>W1AW<EOR>\n");
+    }
+
+    #[test]
+    fn test_write_header_without_preamble() {
+        let mut file = AdifFile::new();
+        file.header.fields.push(Field::new("ADIF_VER", "3.1.6"));
+
+        let adi = write_adi(&file);
+        assert!(!adi.starts_with('<'));
+        assert!(adi.contains("<ADIF_VER:5>3.1.6\n<EOH>\n"));
+
+        let reparsed = parse_adi(&adi).unwrap();
+        assert_eq!(reparsed.header.adif_version, Some("3.1.6".to_string()));
+    }
+
+    #[test]
+    fn test_write_type_indicators() {
+        let mut file = AdifFile::new();
+        let mut rec = Record::new();
+        rec.add_field(Field::with_type("FREQ", DataType::Number, "7.030"));
+        rec.add_field(Field::with_type("QSO_DATE", DataType::Date, "20240115"));
+        file.records.push(rec);
+
+        let adi = write_adi(&file);
+        assert_eq!(adi, "<FREQ:5:N>7.030<QSO_DATE:8:D>20240115<EOR>\n");
+
+        let reparsed = parse_adi(&adi).unwrap();
+        let freq = reparsed.records[0].get("FREQ").unwrap();
+        assert_eq!(freq.data_type, DataType::Number);
+    }
+
+    #[test]
+    fn test_write_multibyte_length() {
+        let mut file = AdifFile::new();
+        let mut rec = Record::new();
+        rec.add_field(Field::new("NAME", "José"));
+        file.records.push(rec);
+
+        let reparsed = parse_adi(&write_adi(&file)).unwrap();
+        assert_eq!(reparsed.records[0].get_value("NAME"), Some("José"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_streaming_writer() {
+        let mut header = AdifHeader::default();
+        header.add_field(Field::new("ADIF_VER", "3.1.6"));
+
+        let mut writer = AdiWriter::new(Vec::new());
+        writer.write_header(&header).unwrap();
+        for call in ["W1AW", "K1ABC"] {
+            let mut rec = Record::new();
+            rec.add_field(Field::new("CALL", call));
+            writer.write_record(&rec).unwrap();
+        }
+        assert_eq!(writer.records_written(), 2);
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        let parsed = parse_adi(&output).unwrap();
+        assert_eq!(parsed.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(parsed.records.len(), 2);
+        assert_eq!(parsed.records[1].call(), Some("K1ABC"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_header_after_records() {
+        let mut writer = AdiWriter::new(Vec::new());
+        writer.write_record(&Record::new()).unwrap();
+        assert!(writer.write_header(&AdifHeader::default()).is_err());
+    }
+
+    #[test]
+    fn test_write_intl_text() {
+        let input = "<NAME:4>Jose<NAME_INTL:5>José<QTH_INTL:16>Kraków, Łódź\
+                     <COMMENT:20>“73” – Müller<EOR>\n";
+        let file = parse_adi(input).unwrap();
+        let write = |intl_text| {
+            write_adi_with_options(
+                &file,
+                &WriteOptions {
+                    intl_text,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(write(IntlOutput::Keep).unwrap(), input);
+        let err = write(IntlOutput::Error).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid NAME_INTL: international text can't be written to ADI"
+        );
+        assert_eq!(
+            write(IntlOutput::Transliterate).unwrap(),
+            "<NAME:4>Jose<QTH:12>Krakow, Lodz<COMMENT:13>\"73\" - Muller<EOR>\n"
+        );
+        assert_eq!(
+            write(IntlOutput::Strip).unwrap(),
+            "<NAME:4>Jose<COMMENT:9>73  Mller<EOR>\n"
+        );
+    }
+
+    #[test]
+    fn test_write_less_than() {
+        let file = parse_adi("<CALL:4>W1AW<COMMENT:6><3 73!<EOR>\n").unwrap();
+        let write = |less_than| {
+            let options = WriteOptions {
+                less_than,
+                ..Default::default()
+            };
+            write_adi_with_options(&file, &options)
+        };
+
+        assert_eq!(write(LessThan::Keep).unwrap(), write_adi(&file));
+        assert!(matches!(
+            write(LessThan::Error),
+            Err(AdifError::InvalidField { field, .. }) if field == "COMMENT"
+        ));
+        assert_eq!(
+            write(LessThan::Substitute('(')).unwrap(),
+            "<CALL:4>W1AW<COMMENT:6>(3 73!<EOR>\n"
+        );
+    }
+
+    fn parse_fidelity(input: &str) -> AdifFile {
+        let options = crate::ParserOptions {
+            preserve_raw: true,
+            ..Default::default()
+        };
+        crate::parse_adi_with_options(input, &options).unwrap()
+    }
+
+    #[test]
+    fn test_fidelity_roundtrip() {
+        let inputs = [
+            "Exported by Foo\r\n<adif_ver:5>3.1.6  <programid:3>Foo\r\n<eoh>\r\n\r\n\
+             <call:4>W1AW <Band:3:e>20m\r\n<eor>\r\n\
+             // a comment\r\n<CALL:4>K1AB<eor><EOR>\r\n<call:4>N0CA<EOF>\r\n",
+            "<CALL:4>W1AW<EOR>\n<CALL:4>K1AB",
+            "<ADIF_VER:5>3.1.6<EOH>",
+            "",
+        ];
+        for input in inputs {
+            assert_eq!(write_adi(&parse_fidelity(input)), input);
+        }
+    }
+
+    #[test]
+    fn test_fidelity_lenient_keeps_broken_text() {
+        let input = "<CALL:4>W1AW<BAND:x>20m <MODE:2>CW<EOR>\n";
+        let options = crate::ParserOptions {
+            preserve_raw: true,
+            lenient: true,
+            ..Default::default()
+        };
+        let file = crate::parse_adi_with_options(input, &options).unwrap();
+        assert_eq!(file.records[0].band(), None);
+        assert_eq!(write_adi(&file), input);
+    }
+
+    #[test]
+    fn test_fidelity_modified_field() {
+        let input = "hdr\n<adif_ver:5>3.1.6\n<eoh>\n<call:4>W1AW  <name:3>Bob <eor>\n";
+        let mut file = parse_fidelity(input);
+        file.records[0].fields[0].value = "K1AB".to_string();
+        file.records[0].fields[1].value = "Robert".to_string();
+
+        let mut rec = Record::new();
+        rec.add_field(Field::new("CALL", "N0CALL"));
+        file.records.push(rec);
+
+        assert_eq!(
+            write_adi(&file),
+            "hdr\n<adif_ver:5>3.1.6\n<eoh>\n<call:4>K1AB  <NAME:6>Robert <eor>\n\
+             <CALL:6>N0CALL<EOR>\n"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Decoding of the WSJT-X UDP "QSO Logged" message
+
+use crate::error::{AdifError, Result};
+use crate::frequency::Frequency;
+use crate::mode::Submode;
+use crate::types::{Field, Record};
+use std::net::{ToSocketAddrs, UdpSocket};
+
+/// First four bytes of every WSJT-X message
+const MAGIC: u32 = 0xADBC_CBDA;
+
+/// Message type of "QSO Logged"
+const QSO_LOGGED: u32 = 5;
+
+/// Largest datagram WSJT-X sends
+const MAX_DATAGRAM: usize = 65_536;
+
+/// Decode a WSJT-X UDP datagram into a record
+///
+/// Returns `None` for messages other than "QSO Logged" (such as
+/// heartbeats, status and decodes). The record holds the contact's CALL,
+/// GRIDSQUARE, FREQ, MODE (and SUBMODE, for modes such as FT4), reports,
+/// TX_PWR, COMMENT, NAME, start and end date and time, OPERATOR,
+/// STATION_CALLSIGN, MY_GRIDSQUARE, exchanges (as STX_STRING and
+/// SRX_STRING) and PROP_MODE; empty values are left out.
+pub fn decode_wsjtx(datagram: &[u8]) -> Result<Option<Record>> {
+    let mut d = Decoder {
+        input: datagram,
+        position: 0,
+    };
+    if d.u32()? != MAGIC {
+        return Err(d.error("not a WSJT-X message"));
+    }
+    let _schema = d.u32()?;
+    if d.u32()? != QSO_LOGGED {
+        return Ok(None);
+    }
+    let _id = d.utf8()?;
+
+    let mut record = Record::new();
+    let mut add = |name: &str, value: String| {
+        if !value.is_empty() {
+            record.add_field(Field::new(name, value));
+        }
+    };
+    let (date_off, time_off) = d.date_time()?;
+    let call = d.utf8()?;
+    let grid = d.utf8()?;
+    let freq = d.u64()?;
+    let mode = d.utf8()?;
+    let rst_sent = d.utf8()?;
+    let rst_rcvd = d.utf8()?;
+    let tx_pwr = d.utf8()?;
+    let comment = d.utf8()?;
+    let name = d.utf8()?;
+    let (date_on, time_on) = d.date_time()?;
+    let operator = d.utf8()?;
+    let station_callsign = d.utf8()?;
+    let my_grid = d.utf8()?;
+    // Fields added in later WSJT-X versions
+    let stx = d.optional_utf8()?;
+    let srx = d.optional_utf8()?;
+    let prop_mode = d.optional_utf8()?;
+
+    add("CALL", call);
+    add("GRIDSQUARE", grid);
+    add("QSO_DATE", date_on);
+    add("TIME_ON", time_on);
+    add("QSO_DATE_OFF", date_off);
+    add("TIME_OFF", time_off);
+    if freq > 0 {
+        add("FREQ", Frequency::from_hz(freq).to_string());
+    }
+    match mode.parse::<Submode>() {
+        Ok(submode) => {
+            add("MODE", submode.mode().to_string());
+            add("SUBMODE", mode);
+        }
+        Err(_) => add("MODE", mode),
+    }
+    add("RST_SENT", rst_sent);
+    add("RST_RCVD", rst_rcvd);
+    add("TX_PWR", tx_pwr);
+    add("COMMENT", comment);
+    add("NAME", name);
+    add("OPERATOR", operator);
+    add("STATION_CALLSIGN", station_callsign);
+    add("MY_GRIDSQUARE", my_grid);
+    add("STX_STRING", stx);
+    add("SRX_STRING", srx);
+    add("PROP_MODE", prop_mode);
+    Ok(Some(record))
+}
+
+/// Reader for Qt `QDataStream` values, which are big-endian
+struct Decoder<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Decoder<'_> {
+    fn error(&self, message: &str) -> AdifError {
+        AdifError::ParseError {
+            position: self.position,
+            message: message.into(),
+        }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self
+            .input
+            .get(self.position..self.position + N)
+            .ok_or(AdifError::UnexpectedEof(self.input.len()))?;
+        self.position += N;
+        Ok(bytes.try_into().expect("slice has N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take()?))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take()?))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_be_bytes(self.take()?))
+    }
+
+    /// A `QByteArray` of UTF-8; null arrays are empty
+    fn utf8(&mut self) -> Result<String> {
+        let len = self.u32()?;
+        if len == u32::MAX {
+            return Ok(String::new());
+        }
+        let bytes = self
+            .input
+            .get(self.position..self.position + len as usize)
+            .ok_or(AdifError::UnexpectedEof(self.input.len()))?;
+        let s = core::str::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8"))?;
+        self.position += len as usize;
+        Ok(s.trim().to_string())
+    }
+
+    /// A UTF-8 value that older versions don't send
+    fn optional_utf8(&mut self) -> Result<String> {
+        if self.position == self.input.len() {
+            return Ok(String::new());
+        }
+        self.utf8()
+    }
+
+    /// A `QDateTime` in UTC, as an ADIF date and time
+    fn date_time(&mut self) -> Result<(String, String)> {
+        let julian_day = self.i64()?;
+        let ms = self.u32()?;
+        let offset = match self.u8()? {
+            1 => 0,
+            2 => i64::from(self.i32()?),
+            _ => return Err(self.error("date and time are not in UTC")),
+        };
+        if !(0..=5_373_484).contains(&julian_day) || ms >= 86_400_000 {
+            return Err(self.error("invalid date and time"));
+        }
+        let seconds = julian_day * 86_400 + i64::from(ms / 1000) - offset;
+        let (day, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+        let (y, m, d) = civil_from_julian_day(day);
+        Ok((
+            format!("{:04}{:02}{:02}", y, m, d),
+            format!("{:02}{:02}{:02}", time / 3600, time / 60 % 60, time % 60),
+        ))
+    }
+}
+
+/// Convert a Julian day number to a Gregorian date
+fn civil_from_julian_day(julian_day: i64) -> (i64, i64, i64) {
+    let a = julian_day + 32_044;
+    let b = (4 * a + 3) / 146_097;
+    let c = a - 146_097 * b / 4;
+    let d = (4 * c + 3) / 1461;
+    let e = c - 1461 * d / 4;
+    let m = (5 * e + 2) / 153;
+    let day = e - (153 * m + 2) / 5 + 1;
+    let month = m + 3 - 12 * (m / 10);
+    let year = 100 * b + d - 4800 + m / 10;
+    (year, month, day)
+}
+
+/// Listener for QSOs logged by WSJT-X (or JTDX, MSHV) over UDP
+///
+/// WSJT-X sends to UDP port 2237 by default; bind to that port, or to a
+/// multicast group WSJT-X is configured to use.
+#[derive(Debug)]
+pub struct WsjtxListener {
+    socket: UdpSocket,
+    buf: Vec<u8>,
+}
+
+impl WsjtxListener {
+    /// Listen on a local address, such as `0.0.0.0:2237`
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<Self> {
+        Ok(Self::from_socket(UdpSocket::bind(addr)?))
+    }
+
+    /// Listen on an already configured socket
+    pub fn from_socket(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            buf: vec![0; MAX_DATAGRAM],
+        }
+    }
+
+    /// Get the underlying socket, e.g. to set a read timeout
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Wait for the next logged QSO, skipping other messages
+    pub fn recv(&mut self) -> Result<Record> {
+        loop {
+            let len = self.socket.recv(&mut self.buf)?;
+            if let Some(record) = decode_wsjtx(&self.buf[..len])? {
+                return Ok(record);
+            }
+        }
+    }
+}
+
+impl Iterator for WsjtxListener {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.recv())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf8(out: &mut Vec<u8>, s: &str) {
+        out.extend((s.len() as u32).to_be_bytes());
+        out.extend(s.as_bytes());
+    }
+
+    fn date_time(out: &mut Vec<u8>, julian_day: i64, ms: u32) {
+        out.extend(julian_day.to_be_bytes());
+        out.extend(ms.to_be_bytes());
+        out.push(1);
+    }
+
+    fn qso_logged(mode: &str, extended: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(MAGIC.to_be_bytes());
+        out.extend(3u32.to_be_bytes());
+        out.extend(QSO_LOGGED.to_be_bytes());
+        utf8(&mut out, "WSJT-X");
+        // 2024-06-01 12:01:15
+        date_time(&mut out, 2_460_463, 43_275_000);
+        utf8(&mut out, "K1ABC");
+        utf8(&mut out, "FN42");
+        out.extend(14_074_000u64.to_be_bytes());
+        utf8(&mut out, mode);
+        utf8(&mut out, "-10");
+        utf8(&mut out, "-12");
+        utf8(&mut out, "");
+        utf8(&mut out, "");
+        out.extend(u32::MAX.to_be_bytes());
+        date_time(&mut out, 2_460_463, 43_200_000);
+        utf8(&mut out, "");
+        utf8(&mut out, "W1AW");
+        utf8(&mut out, "FN31");
+        if extended {
+            utf8(&mut out, "FN31");
+            utf8(&mut out, "FN42");
+            utf8(&mut out, "");
+        }
+        out
+    }
+
+    #[test]
+    fn test_decode_wsjtx() {
+        let record = decode_wsjtx(&qso_logged("FT8", true)).unwrap().unwrap();
+        assert_eq!(
+            record.to_adi_string(),
+            "<CALL:5>K1ABC<GRIDSQUARE:4>FN42<QSO_DATE:8>20240601<TIME_ON:6>120000\
+             <QSO_DATE_OFF:8>20240601<TIME_OFF:6>120115<FREQ:6>14.074<MODE:3>FT8\
+             <RST_SENT:3>-10<RST_RCVD:3>-12<STATION_CALLSIGN:4>W1AW\
+             <MY_GRIDSQUARE:4>FN31<STX_STRING:4>FN31<SRX_STRING:4>FN42<EOR>\n"
+        );
+
+        let record = decode_wsjtx(&qso_logged("FT4", false)).unwrap().unwrap();
+        assert_eq!(record.get_value("MODE"), Some("MFSK"));
+        assert_eq!(record.get_value("SUBMODE"), Some("FT4"));
+        assert_eq!(record.get_value("STX_STRING"), None);
+    }
+
+    #[test]
+    fn test_decode_wsjtx_other_messages() {
+        let mut heartbeat = Vec::new();
+        heartbeat.extend(MAGIC.to_be_bytes());
+        heartbeat.extend(3u32.to_be_bytes());
+        heartbeat.extend(0u32.to_be_bytes());
+        assert!(decode_wsjtx(&heartbeat).unwrap().is_none());
+
+        assert!(matches!(
+            decode_wsjtx(b"hello world!"),
+            Err(AdifError::ParseError { .. })
+        ));
+        let truncated = qso_logged("FT8", false);
+        assert!(matches!(
+            decode_wsjtx(&truncated[..40]),
+            Err(AdifError::UnexpectedEof(_))
+        ));
+    }
+
+    #[test]
+    fn test_civil_from_julian_day() {
+        assert_eq!(civil_from_julian_day(2_451_545), (2000, 1, 1));
+        assert_eq!(civil_from_julian_day(2_440_588), (1970, 1, 1));
+    }
+
+    #[test]
+    fn test_listener() {
+        let mut listener = WsjtxListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.socket().local_addr().unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender.send_to(b"junk", addr).ok();
+        let mut status = Vec::new();
+        status.extend(MAGIC.to_be_bytes());
+        status.extend(3u32.to_be_bytes());
+        status.extend(1u32.to_be_bytes());
+        sender.send_to(&status, addr).unwrap();
+        sender.send_to(&qso_logged("FT8", true), addr).unwrap();
+        assert!(listener.recv().is_err());
+        assert_eq!(listener.recv().unwrap().call(), Some("K1ABC"));
+    }
+}
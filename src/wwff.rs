@@ -0,0 +1,143 @@
+use crate::profile::{ProfileExport, check_date, required, station_callsign, subset, value};
+use crate::types::{AdifFile, Record};
+use alloc::format;
+use alloc::string::String;
+
+/// Fields kept in a WWFF upload
+const FIELDS: &[&str] = &[
+    "STATION_CALLSIGN",
+    "OPERATOR",
+    "CALL",
+    "QSO_DATE",
+    "TIME_ON",
+    "BAND",
+    "FREQ",
+    "MODE",
+    "SUBMODE",
+    "RST_SENT",
+    "RST_RCVD",
+    "MY_SIG",
+    "MY_SIG_INFO",
+    "MY_WWFF_REF",
+    "SIG",
+    "SIG_INFO",
+    "WWFF_REF",
+];
+
+/// Export WWFF activations, one file per station, reference and UTC day
+///
+/// The activated reference comes from `MY_WWFF_REF`, or `MY_SIG_INFO`
+/// when `MY_SIG` is `WWFF`, and is written to both along with
+/// `MY_SIG=WWFF`. `OPERATOR` defaults to the station callsign. Files are
+/// named `CALL@REFERENCE YYYYMMDD.adi`, with `/` in the callsign replaced
+/// by `-`.
+pub(crate) fn export(file: &AdifFile) -> ProfileExport {
+    let mut export = ProfileExport::default();
+    for (index, record) in file.records.iter().enumerate() {
+        let (call, operator, date, reference) = match check(record) {
+            Ok(checked) => checked,
+            Err(reason) => {
+                export.reject(index, reason);
+                continue;
+            }
+        };
+        let mut qso = record.clone();
+        qso.set("STATION_CALLSIGN", &call);
+        qso.set("OPERATOR", &operator);
+        qso.set("MY_SIG", "WWFF");
+        qso.set("MY_SIG_INFO", &reference);
+        qso.set("MY_WWFF_REF", &reference);
+        let qso = subset(&qso, FIELDS);
+        let name = format!("{}@{} {}.adi", call.replace('/', "-"), reference, date);
+        export.file_mut(name, file).records.push(qso);
+    }
+    export
+}
+
+/// Check a record for the fields WWFF requires, returning the station
+/// callsign, operator, date and activated reference
+fn check(record: &Record) -> core::result::Result<(String, String, &str, String), String> {
+    let call = station_callsign(record)
+        .ok_or("missing STATION_CALLSIGN")?
+        .to_ascii_uppercase();
+    let operator = value(record, "OPERATOR").map_or_else(|| call.clone(), str::to_ascii_uppercase);
+    let reference = value(record, "MY_WWFF_REF")
+        .or_else(|| {
+            value(record, "MY_SIG")
+                .filter(|sig| sig.eq_ignore_ascii_case("WWFF"))
+                .and_then(|_| value(record, "MY_SIG_INFO"))
+        })
+        .ok_or("missing MY_WWFF_REF or MY_SIG_INFO")?;
+    if !is_wwff_ref(reference) {
+        return Err(format!("invalid WWFF reference '{}'", reference));
+    }
+    for name in ["CALL", "TIME_ON", "BAND", "MODE"] {
+        required(record, name)?;
+    }
+    let date = required(record, "QSO_DATE")?;
+    check_date(date)?;
+    Ok((call, operator, date, reference.to_ascii_uppercase()))
+}
+
+/// Check a WWFF reference: a program prefix ending in `FF`, a hyphen and
+/// four digits, such as `ONFF-0001` or `KFF-1234`
+pub(crate) fn is_wwff_ref(reference: &str) -> bool {
+    let Some((prefix, number)) = reference.split_once('-') else {
+        return false;
+    };
+    (3..=6).contains(&prefix.len())
+        && prefix.bytes().all(|b| b.is_ascii_alphanumeric())
+        && prefix[prefix.len() - 2..].eq_ignore_ascii_case("FF")
+        && number.len() == 4
+        && number.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_adi;
+    use crate::profile::ExportProfile;
+
+    #[test]
+    fn test_export_wwff() {
+        let file = parse_adi(
+            "<STATION_CALLSIGN:8>ON4ABC/P<CALL:4>W1AW<QSO_DATE:8>20240601<TIME_ON:4>1200\
+             <BAND:3>20m<MODE:3>SSB<MY_SIG:4>wwff<MY_SIG_INFO:9>onff-0001<EOR>\
+             <STATION_CALLSIGN:8>ON4ABC/P<OPERATOR:5>ON4XY<CALL:4>K1AB<QSO_DATE:8>20240601\
+             <TIME_ON:4>1210<BAND:3>40m<MODE:2>CW<MY_WWFF_REF:9>ONFF-0001<EOR>\
+             <STATION_CALLSIGN:6>ON4ABC<CALL:4>K2AB<QSO_DATE:8>20240601<TIME_ON:4>1220\
+             <BAND:3>40m<MODE:2>CW<MY_SIG:4>POTA<MY_SIG_INFO:7>BE-0001<EOR>\
+             <STATION_CALLSIGN:6>ON4ABC<CALL:4>K3AB<QSO_DATE:8>20240601<TIME_ON:4>1230\
+             <BAND:3>40m<MODE:2>CW<MY_WWFF_REF:7>ON-0001<EOR>",
+        )
+        .unwrap();
+        let export = ExportProfile::Wwff.export(&file);
+        assert_eq!(export.files.len(), 1);
+        assert_eq!(export.files[0].name, "ON4ABC-P@ONFF-0001 20240601.adi");
+        let records = &export.files[0].file.records;
+        assert_eq!(
+            records[0].to_adi_string(),
+            "<STATION_CALLSIGN:8>ON4ABC/P<OPERATOR:8>ON4ABC/P<CALL:4>W1AW\
+             <QSO_DATE:8>20240601<TIME_ON:4>1200<BAND:3>20m<MODE:3>SSB\
+             <MY_SIG:4>WWFF<MY_SIG_INFO:9>ONFF-0001<MY_WWFF_REF:9>ONFF-0001<EOR>\n"
+        );
+        assert_eq!(records[1].get_value("OPERATOR"), Some("ON4XY"));
+        let reasons: Vec<_> = export.rejected.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            reasons,
+            [
+                "record 2: missing MY_WWFF_REF or MY_SIG_INFO",
+                "record 3: invalid WWFF reference 'ON-0001'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_wwff_ref() {
+        assert!(is_wwff_ref("KFF-1234"));
+        assert!(is_wwff_ref("VKFF-0123"));
+        assert!(!is_wwff_ref("FF-1234"));
+        assert!(!is_wwff_ref("ONFF-123"));
+        assert!(!is_wwff_ref("ONXX-0001"));
+    }
+}
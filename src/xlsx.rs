@@ -0,0 +1,106 @@
+use crate::columns::{ColumnType, parse_bool, parse_number, record_columns};
+use crate::date::{AdifDate, AdifTime};
+use crate::error::Result;
+use crate::from_field::FromField;
+use crate::types::AdifFile;
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use std::io::Write;
+use std::path::Path;
+
+impl AdifFile {
+    /// Write the records to an Excel (`.xlsx`) workbook
+    ///
+    /// The worksheet has a bold, frozen header row naming one column per
+    /// field found in the records, then one row per record. Dates, times,
+    /// numbers and booleans are written as typed cells, based on each
+    /// field's type in the ADIF specification; a column with a value that
+    /// doesn't parse is written as text.
+    pub fn write_xlsx<W: Write + Send>(&self, writer: W) -> Result<()> {
+        self.workbook()?.save_to_writer(writer)?;
+        Ok(())
+    }
+
+    /// Write the records to an Excel workbook file (see
+    /// [`AdifFile::write_xlsx`])
+    pub fn to_xlsx(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.workbook()?.save(path.as_ref())?;
+        Ok(())
+    }
+
+    fn workbook(&self) -> Result<Workbook> {
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet();
+        sheet.set_name("QSOs")?;
+
+        let bold = Format::new().set_bold();
+        let date = Format::new().set_num_format("yyyy-mm-dd");
+        let time = Format::new().set_num_format("hh:mm:ss");
+
+        let columns = record_columns(&self.records);
+        for (col, (name, _)) in (0u16..).zip(&columns) {
+            sheet.write_string_with_format(0, col, name, &bold)?;
+        }
+        sheet.set_freeze_panes(1, 0)?;
+
+        for (row, record) in (1u32..).zip(&self.records) {
+            for (col, (name, column_type)) in (0u16..).zip(&columns) {
+                let Some(value) = record.get_value(name).filter(|v| !v.is_empty()) else {
+                    continue;
+                };
+                match column_type {
+                    ColumnType::Date => {
+                        let datetime = AdifDate::from_field(value).ok().and_then(|d| {
+                            ExcelDateTime::from_ymd(d.year(), d.month(), d.day()).ok()
+                        });
+                        match datetime {
+                            Some(datetime) => {
+                                sheet.write_datetime_with_format(row, col, &datetime, &date)?
+                            }
+                            // Before Excel's epoch
+                            None => sheet.write_string(row, col, value)?,
+                        };
+                    }
+                    ColumnType::Time => {
+                        let (h, m, s) = AdifTime::from_field(value)
+                            .map_or((0, 0, 0), |t| (t.hour(), t.minute(), t.second()));
+                        let datetime = ExcelDateTime::from_hms(u16::from(h), m, s)?;
+                        sheet.write_datetime_with_format(row, col, &datetime, &time)?;
+                    }
+                    ColumnType::Number => {
+                        sheet.write_number(row, col, parse_number(value).unwrap_or_default())?;
+                    }
+                    ColumnType::Boolean => {
+                        sheet.write_boolean(row, col, parse_bool(value).unwrap_or_default())?;
+                    }
+                    ColumnType::Text => {
+                        sheet.write_string(row, col, value)?;
+                    }
+                }
+            }
+        }
+        sheet.autofit();
+        Ok(workbook)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_adi;
+
+    #[test]
+    fn test_write_xlsx() {
+        let file = parse_adi(
+            "<CALL:4>W1AW<QSO_DATE:8>20240115<TIME_ON:4>1423<FREQ:6>14.074<SWL:1>N<EOR>\
+             <CALL:4>K1AB<QSO_DATE:8>18990101<NOTES:2>hi<EOR>",
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        file.write_xlsx(&mut out).unwrap();
+        assert!(out.starts_with(b"PK"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.xlsx");
+        file.to_xlsx(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap().len(), out.len());
+    }
+}
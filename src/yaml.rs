@@ -0,0 +1,67 @@
+use crate::error::Result;
+use crate::types::AdifFile;
+use serde_json::Value;
+
+impl AdifFile {
+    /// Serialize to YAML, with the same structure as
+    /// [`AdifFile::to_json_value`]
+    pub fn to_yaml(&self) -> String {
+        serde_yaml_ng::to_string(&self.to_json_value()).expect("JSON values always serialize")
+    }
+
+    /// Parse a YAML document in the format produced by [`AdifFile::to_yaml`]
+    ///
+    /// Number and boolean values are accepted and stored as text; `null`
+    /// values are skipped, and each value in a list becomes a field.
+    pub fn from_yaml(input: &str) -> Result<AdifFile> {
+        let value: Value = serde_yaml_ng::from_str(input)?;
+        AdifFile::from_json_value(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AdifError;
+    use crate::parse_adi;
+
+    #[test]
+    fn test_to_yaml() {
+        let file = parse_adi("<ADIF_VER:5>3.1.6<EOH><CALL:4>W1AW<FREQ:6>14.074<EOR>").unwrap();
+        assert_eq!(
+            file.to_yaml(),
+            "header:\n  ADIF_VER: 3.1.6\nrecords:\n- CALL: W1AW\n  FREQ: '14.074'\n"
+        );
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let file =
+            parse_adi("Log\n<ADIF_VER:5>3.1.6<EOH><CALL:4>W1AW<EOR><CALL:4>K1AB<EOR>").unwrap();
+        let back = AdifFile::from_yaml(&file.to_yaml()).unwrap();
+
+        assert_eq!(back.header.adif_version, Some("3.1.6".to_string()));
+        assert_eq!(back.header.preamble, "Log\n");
+        assert_eq!(back.len(), 2);
+        assert_eq!(back.records[1].fields, file.records[1].fields);
+    }
+
+    #[test]
+    fn test_from_yaml() {
+        let yaml = "records:\n  - call: W1AW\n    freq: 14.074\n    swl: false\n    notes: ~\n";
+        let record = &AdifFile::from_yaml(yaml).unwrap().records[0];
+        assert_eq!(record.call(), Some("W1AW"));
+        assert_eq!(record.freq(), Some("14.074"));
+        assert_eq!(record.get_value("SWL"), Some("N"));
+        assert_eq!(record.get("NOTES"), None);
+
+        assert!(matches!(
+            AdifFile::from_yaml("records: [a"),
+            Err(AdifError::Yaml(_))
+        ));
+        assert!(matches!(
+            AdifFile::from_yaml("records: 5"),
+            Err(AdifError::ParseError { .. })
+        ));
+    }
+}